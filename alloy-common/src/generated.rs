@@ -0,0 +1,263 @@
+//! `.alloy/generated.json`: which files each definition's code generator
+//! produced, so cleanup and regeneration know exactly what belongs to it.
+//!
+//! Keys and paths are relative to the project root with `/` separators, and
+//! entries are sorted, so the pretty-printed file diffs and merges cleanly.
+//! Files several definitions write into (tags, the mod initializer) are
+//! marked `shared` and carry no hash. The IDE and the MCP server both
+//! maintain this file through this module.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+pub const MANIFEST_PATH: &str = ".alloy/generated.json";
+
+/// Bump when the manifest's shape changes.
+const MANIFEST_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GeneratedManifest {
+    pub version: u32,
+    /// Keyed by the `.block.json` path
+    #[serde(default)]
+    pub blocks: BTreeMap<String, GeneratedEntry>,
+}
+
+impl Default for GeneratedManifest {
+    fn default() -> Self {
+        Self {
+            version: MANIFEST_VERSION,
+            blocks: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GeneratedEntry {
+    pub files: Vec<GeneratedFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratedFile {
+    pub path: String,
+    pub file_type: String,
+    /// SHA-256 of the content as generated; absent for shared files
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    /// Other definitions write into this file too, so it is never removed
+    /// with this one
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub shared: bool,
+}
+
+impl GeneratedManifest {
+    /// Read the project's manifest; a missing file is an empty manifest.
+    pub fn load(project: &Path) -> Result<Self, String> {
+        let path = project.join(MANIFEST_PATH);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", MANIFEST_PATH, e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", MANIFEST_PATH, e))
+    }
+
+    pub fn save(&self, project: &Path) -> Result<(), String> {
+        let path = project.join(MANIFEST_PATH);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create .alloy: {}", e))?;
+        }
+        let mut json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize {}: {}", MANIFEST_PATH, e))?;
+        json.push('\n');
+        // Write then rename so an interrupted save never truncates the manifest
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)
+            .and_then(|_| std::fs::rename(&tmp, &path))
+            .map_err(|e| format!("Failed to write {}: {}", MANIFEST_PATH, e))
+    }
+
+    /// Follow a directory move from `from` to `to` (both project-relative):
+    /// every key and recorded path under `from` is moved along with it.
+    /// Returns how many keys and paths changed.
+    pub fn move_dir(&mut self, from: &str, to: &str) -> usize {
+        let moved = |path: &str| {
+            path.strip_prefix(from)
+                .filter(|rest| rest.starts_with('/'))
+                .map(|rest| format!("{}{}", to, rest))
+        };
+        let mut count = 0;
+        let blocks = std::mem::take(&mut self.blocks);
+        for (key, mut entry) in blocks {
+            for file in &mut entry.files {
+                if let Some(path) = moved(&file.path) {
+                    file.path = path;
+                    count += 1;
+                }
+            }
+            let key = match moved(&key) {
+                Some(key) => {
+                    count += 1;
+                    key
+                }
+                None => key,
+            };
+            self.blocks.insert(key, entry);
+        }
+        count
+    }
+
+    /// A generated file at `path` was rewritten from `old` to `new` by a tool
+    /// rather than by hand. Its recorded hash follows the new content, unless
+    /// it was already hand-edited. Returns whether a hash changed.
+    pub fn rehash(&mut self, path: &str, old: &str, new: &str) -> bool {
+        let old_hash = hash_bytes(old.as_bytes());
+        let mut changed = false;
+        for file in self.blocks.values_mut().flat_map(|e| &mut e.files) {
+            if file.path == path && file.sha256.as_ref() == Some(&old_hash) {
+                file.sha256 = Some(hash_bytes(new.as_bytes()));
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+/// Writes one definition's generated files. A file whose content no longer
+/// matches its recorded hash was edited by hand since the last generation;
+/// it is left alone unless `force` is set.
+pub struct GeneratedWriter<'a> {
+    project: &'a Path,
+    previous: GeneratedEntry,
+    force: bool,
+    files: Vec<GeneratedFile>,
+    /// Hand-edited files left in place
+    pub skipped_modified: Vec<String>,
+    /// Hand-edited files replaced because of `force`
+    pub overwritten_modified: Vec<String>,
+}
+
+impl<'a> GeneratedWriter<'a> {
+    pub fn new(project: &'a Path, previous: Option<&GeneratedEntry>, force: bool) -> Self {
+        Self {
+            project,
+            previous: previous.cloned().unwrap_or_default(),
+            force,
+            files: Vec::new(),
+            skipped_modified: Vec::new(),
+            overwritten_modified: Vec::new(),
+        }
+    }
+
+    /// Write `content` to `path` and record it. Returns `Ok(false)` when the
+    /// file was skipped as hand-edited.
+    pub fn write(&mut self, path: &Path, content: &str, file_type: &str) -> std::io::Result<bool> {
+        let rel = relative_path(self.project, path);
+        let previous = self.previous.files.iter().find(|f| f.path == rel && !f.shared);
+        let modified = previous
+            .and_then(|f| f.sha256.as_ref())
+            .is_some_and(|recorded| hash_file(path).is_some_and(|current| &current != recorded));
+
+        if modified && !self.force {
+            // Keep the old hash so the file still counts as modified next time
+            self.files.extend(previous.cloned());
+            self.skipped_modified.push(rel);
+            return Ok(false);
+        }
+        if modified {
+            self.overwritten_modified.push(rel.clone());
+        }
+
+        std::fs::write(path, content)?;
+        self.files.push(GeneratedFile {
+            path: rel,
+            file_type: file_type.to_string(),
+            sha256: Some(hash_bytes(content.as_bytes())),
+            shared: false,
+        });
+        Ok(true)
+    }
+
+    /// Record a file other definitions also write into; it is never hashed.
+    pub fn record_shared(&mut self, path: &Path, file_type: &str) {
+        let rel = relative_path(self.project, path);
+        if !self.files.iter().any(|f| f.path == rel) {
+            self.files.push(GeneratedFile {
+                path: rel,
+                file_type: file_type.to_string(),
+                sha256: None,
+                shared: true,
+            });
+        }
+    }
+
+    pub fn finish(self) -> GeneratedEntry {
+        GeneratedEntry { files: self.files }
+    }
+}
+
+/// `path` relative to `project` with `/` separators, or as given when it lies
+/// outside the project.
+pub fn relative_path(project: &Path, path: &Path) -> String {
+    path.strip_prefix(project)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+fn hash_bytes(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+fn hash_file(path: &Path) -> Option<String> {
+    std::fs::read(path).ok().map(|bytes| hash_bytes(&bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest() -> GeneratedManifest {
+        let file = |path: &str, content: &str| GeneratedFile {
+            path: path.to_string(),
+            file_type: "model".to_string(),
+            sha256: Some(hash_bytes(content.as_bytes())),
+            shared: false,
+        };
+        let mut manifest = GeneratedManifest::default();
+        manifest.blocks.insert(
+            "src/main/resources/assets/oldmod/blocks/ruby.block.json".to_string(),
+            GeneratedEntry {
+                files: vec![
+                    file("src/main/resources/assets/oldmod/models/block/ruby.json", "{}"),
+                    file("src/main/resources/assets/oldmod_extra/ruby.json", "{}"),
+                ],
+            },
+        );
+        manifest
+    }
+
+    #[test]
+    fn move_dir_moves_keys_and_paths_under_the_directory() {
+        let mut manifest = manifest();
+        let moved =
+            manifest.move_dir("src/main/resources/assets/oldmod", "src/main/resources/assets/newmod");
+        assert_eq!(moved, 2);
+        let entry = &manifest.blocks["src/main/resources/assets/newmod/blocks/ruby.block.json"];
+        assert_eq!(entry.files[0].path, "src/main/resources/assets/newmod/models/block/ruby.json");
+        // A sibling that only shares the prefix is not inside the directory
+        assert_eq!(entry.files[1].path, "src/main/resources/assets/oldmod_extra/ruby.json");
+    }
+
+    #[test]
+    fn rehash_keeps_hand_edited_files_marked_modified() {
+        let mut manifest = manifest();
+        let path = "src/main/resources/assets/oldmod/models/block/ruby.json";
+        assert!(!manifest.rehash(path, "{\"edited\":true}", "{\"new\":true}"));
+        assert!(manifest.rehash(path, "{}", "{\"new\":true}"));
+        let entry = manifest.blocks.values().next().unwrap();
+        assert_eq!(entry.files[0].sha256, Some(hash_bytes(b"{\"new\":true}")));
+    }
+}
@@ -0,0 +1,322 @@
+//! Parsing of git's machine-readable output, shared by the IDE's git
+//! commands and the MCP git tools.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+/// Commits the current branch is ahead of and behind its upstream, or
+/// `None` when it has no tracking branch and the counts are meaningless.
+pub fn ahead_behind(repo: &Path) -> Option<(u32, u32)> {
+    // Fails with "no upstream configured" when nothing is tracked
+    let output = Command::new("git")
+        .args(["rev-list", "--left-right", "--count", "HEAD...@{u}"])
+        .current_dir(repo)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    let (ahead, behind) = text.trim().split_once('\t')?;
+    Some((ahead.parse().unwrap_or(0), behind.parse().unwrap_or(0)))
+}
+
+/// Extracts the path from a porcelain v1 entry. Renames and copies are reported
+/// as `old -> new`, so only the new path is kept.
+pub fn porcelain_path(x: char, raw: &str) -> String {
+    let raw = match x {
+        'R' | 'C' => raw.rsplit_once(" -> ").map(|(_, new)| new).unwrap_or(raw),
+        _ => raw,
+    };
+    unquote_git_path(raw)
+}
+
+/// Decodes a filename that git wrapped in double quotes with C-style escapes
+/// (core.quotepath). Octal escapes are raw bytes, so UTF-8 names arrive split
+/// across several of them.
+fn unquote_git_path(raw: &str) -> String {
+    let inner = match raw
+        .strip_prefix('"')
+        .and_then(|r| r.strip_suffix('"'))
+    {
+        Some(inner) => inner,
+        None => return raw.to_string(),
+    };
+
+    let mut bytes: Vec<u8> = Vec::with_capacity(inner.len());
+    let mut input = inner.bytes().peekable();
+    while let Some(b) = input.next() {
+        if b != b'\\' {
+            bytes.push(b);
+            continue;
+        }
+        match input.next() {
+            Some(b'a') => bytes.push(0x07),
+            Some(b'b') => bytes.push(0x08),
+            Some(b't') => bytes.push(b'\t'),
+            Some(b'n') => bytes.push(b'\n'),
+            Some(b'v') => bytes.push(0x0b),
+            Some(b'f') => bytes.push(0x0c),
+            Some(b'r') => bytes.push(b'\r'),
+            Some(d @ b'0'..=b'7') => {
+                let mut value = (d - b'0') as u32;
+                for _ in 0..2 {
+                    match input.peek() {
+                        Some(&n @ b'0'..=b'7') => {
+                            value = value * 8 + (n - b'0') as u32;
+                            input.next();
+                        }
+                        _ => break,
+                    }
+                }
+                bytes.push(value as u8);
+            }
+            Some(other) => bytes.push(other),
+            None => bytes.push(b'\\'),
+        }
+    }
+
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// One source line of `git blame --porcelain`, with its commit's metadata.
+#[derive(Debug, Clone, Serialize)]
+pub struct BlameLine {
+    pub hash: String,
+    pub author: String,
+    pub date: String,
+    pub summary: String,
+    pub content: String,
+}
+
+/// Parses `git blame --porcelain` output into one entry per source line.
+///
+/// Author/summary headers are only emitted the first time a commit appears,
+/// so metadata is kept per hash and looked up for every content line.
+pub fn parse_blame_porcelain(stdout: &str) -> Vec<BlameLine> {
+    let mut result: Vec<BlameLine> = Vec::new();
+    // hash -> (author, date, summary)
+    let mut commits: HashMap<String, (String, String, String)> = HashMap::new();
+    let mut current_hash = String::new();
+    let mut current_time: i64 = 0;
+
+    for line in stdout.lines() {
+        if let Some(content) = line.strip_prefix('\t') {
+            // This is the actual source line — finalize this blame entry
+            let (author, date, summary) =
+                commits.get(&current_hash).cloned().unwrap_or_default();
+            result.push(BlameLine {
+                hash: current_hash.clone(),
+                author,
+                date,
+                summary,
+                content: content.to_string(),
+            });
+            continue;
+        }
+
+        if is_blame_header(line) {
+            current_hash = line[..40].to_string();
+            commits.entry(current_hash.clone()).or_default();
+            continue;
+        }
+
+        let Some(entry) = commits.get_mut(&current_hash) else {
+            continue;
+        };
+        if let Some(rest) = line.strip_prefix("author ") {
+            entry.0 = rest.to_string();
+        } else if let Some(rest) = line.strip_prefix("author-time ") {
+            // The offset arrives on the following author-tz line
+            current_time = rest.parse::<i64>().unwrap_or(0);
+            entry.1 = chrono_lite_format(current_time, "+0000");
+        } else if let Some(rest) = line.strip_prefix("author-tz ") {
+            entry.1 = chrono_lite_format(current_time, rest);
+        } else if let Some(rest) = line.strip_prefix("summary ") {
+            entry.2 = rest.to_string();
+        }
+    }
+
+    result
+}
+
+/// A porcelain header is "<40-hex hash> <orig line> <final line> [<group size>]".
+fn is_blame_header(line: &str) -> bool {
+    let mut parts = line.split(' ');
+    let hash_ok = parts
+        .next()
+        .is_some_and(|h| h.len() == 40 && h.bytes().all(|b| b.is_ascii_hexdigit()));
+    let rest: Vec<&str> = parts.collect();
+    hash_ok
+        && (2..=3).contains(&rest.len())
+        && rest.iter().all(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Formats a Unix timestamp as "YYYY-MM-DD HH:MM:SS+HHMM" in the given git
+/// timezone offset (e.g. "-0500"), without a chrono dependency.
+pub fn chrono_lite_format(timestamp: i64, tz: &str) -> String {
+    let offset_secs = parse_tz_offset(tz);
+    let local = timestamp + offset_secs;
+
+    // Euclidean division keeps pre-1970 timestamps on the right day
+    let days = local.div_euclid(86400);
+    let secs_of_day = local.rem_euclid(86400);
+
+    // Civil-from-days over 400-year eras, which works for negative days too
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+
+    let sign = if offset_secs < 0 { '-' } else { '+' };
+    let offset_mins = offset_secs.abs() / 60;
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}{}{:02}{:02}",
+        y,
+        m,
+        d,
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60,
+        sign,
+        offset_mins / 60,
+        offset_mins % 60
+    )
+}
+
+/// Parses git's "+HHMM"/"-HHMM" timezone notation into an offset in seconds.
+fn parse_tz_offset(tz: &str) -> i64 {
+    let (sign, digits) = match tz.as_bytes().first() {
+        Some(b'-') => (-1, &tz[1..]),
+        Some(b'+') => (1, &tz[1..]),
+        _ => (1, tz),
+    };
+    if digits.len() != 4 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return 0;
+    }
+    let hours: i64 = digits[..2].parse().unwrap_or(0);
+    let mins: i64 = digits[2..].parse().unwrap_or(0);
+    sign * (hours * 3600 + mins * 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(["-c", "commit.gpgsign=false"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("git not found")
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn ahead_behind_without_upstream() {
+        let dir = tempfile::tempdir().unwrap();
+        git(dir.path(), &["init", "-q", "-b", "main"]);
+        git(dir.path(), &["commit", "-q", "--allow-empty", "-m", "first"]);
+
+        assert_eq!(ahead_behind(dir.path()), None);
+    }
+
+    #[test]
+    fn ahead_behind_with_tracking_branch() {
+        let remote = tempfile::tempdir().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        git(remote.path(), &["init", "-q", "--bare"]);
+        git(dir.path(), &["init", "-q", "-b", "main"]);
+        git(dir.path(), &["commit", "-q", "--allow-empty", "-m", "first"]);
+        git(dir.path(), &["remote", "add", "origin", &remote.path().to_string_lossy()]);
+        git(dir.path(), &["push", "-q", "-u", "origin", "main"]);
+        assert_eq!(ahead_behind(dir.path()), Some((0, 0)));
+
+        git(dir.path(), &["commit", "-q", "--allow-empty", "-m", "second"]);
+        assert_eq!(ahead_behind(dir.path()), Some((1, 0)));
+
+        git(dir.path(), &["push", "-q"]);
+        git(dir.path(), &["reset", "-q", "--hard", "HEAD~1"]);
+        assert_eq!(ahead_behind(dir.path()), Some((0, 1)));
+    }
+
+    #[test]
+    fn porcelain_path_keeps_the_new_side_of_a_rename() {
+        assert_eq!(porcelain_path('R', "src/old.rs -> src/new.rs"), "src/new.rs");
+        assert_eq!(porcelain_path('C', "a.txt -> b.txt"), "b.txt");
+        // Only renames and copies have two sides
+        assert_eq!(porcelain_path('M', "odd -> name.txt"), "odd -> name.txt");
+    }
+
+    #[test]
+    fn porcelain_path_unquotes_escaped_names() {
+        assert_eq!(porcelain_path('?', "\"with space.txt\""), "with space.txt");
+        assert_eq!(porcelain_path('M', "\"back\\\\slash\""), "back\\slash");
+        assert_eq!(porcelain_path('M', "\"a\\tb\\\"c\""), "a\tb\"c");
+        // UTF-8 names arrive as octal-escaped bytes
+        assert_eq!(porcelain_path('A', "\"caf\\303\\251.txt\""), "café.txt");
+        assert_eq!(
+            porcelain_path('R', "\"old name\" -> \"new\\303\\251 name\""),
+            "newé name"
+        );
+    }
+
+    #[test]
+    fn chrono_lite_format_epoch() {
+        assert_eq!(chrono_lite_format(0, "+0000"), "1970-01-01 00:00:00+0000");
+        assert_eq!(chrono_lite_format(-1, "+0000"), "1969-12-31 23:59:59+0000");
+        assert_eq!(chrono_lite_format(0, "-0500"), "1969-12-31 19:00:00-0500");
+    }
+
+    #[test]
+    fn chrono_lite_format_leap_days() {
+        assert_eq!(chrono_lite_format(1709208000, "+0000"), "2024-02-29 12:00:00+0000");
+        assert_eq!(chrono_lite_format(951782400, "+0000"), "2000-02-29 00:00:00+0000");
+        // 2100 is not a leap year, so February 28 is followed by March 1
+        assert_eq!(chrono_lite_format(4107542400 - 1, "+0000"), "2100-02-28 23:59:59+0000");
+    }
+
+    #[test]
+    fn chrono_lite_format_applies_the_offset() {
+        assert_eq!(chrono_lite_format(1700000000, "+0530"), "2023-11-15 03:43:20+0530");
+        // Unparseable offsets fall back to UTC
+        assert_eq!(chrono_lite_format(1700000000, "bogus"), "2023-11-14 22:13:20+0000");
+    }
+
+    #[test]
+    fn parse_blame_porcelain_reuses_metadata_of_repeated_commits() {
+        // Captured from a two-commit repository; line 3 belongs to the first
+        // commit again, so git only repeats its header line
+        let lines = parse_blame_porcelain(include_str!("../tests/fixtures/blame.porcelain"));
+        let first = "9df36d836232886d9e4e64f8de4b5229c6ca4332";
+        let second = "455c0c69202110dbf0a90323259c2e4fad10ec60";
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].hash, first);
+        assert_eq!(lines[0].author, "Ada Lovelace");
+        assert_eq!(lines[0].date, "2024-02-29 13:00:00+0100");
+        assert_eq!(lines[0].summary, "Add main");
+        assert_eq!(lines[0].content, "fn main() {");
+
+        assert_eq!(lines[1].hash, second);
+        assert_eq!(lines[1].author, "Grace Hopper");
+        assert_eq!(lines[1].date, "2023-11-14 17:13:20-0500");
+        assert_eq!(lines[1].summary, "Print a greeting");
+        assert_eq!(lines[1].content, "    println!(\"hi\");");
+
+        assert_eq!(lines[2].hash, first);
+        assert_eq!(lines[2].author, "Ada Lovelace");
+        assert_eq!(lines[2].date, "2024-02-29 13:00:00+0100");
+        assert_eq!(lines[2].summary, "Add main");
+        assert_eq!(lines[2].content, "}");
+    }
+}
@@ -0,0 +1,8 @@
+//! Rules for running a project's Gradle wrapper with caller-supplied
+//! arguments.
+
+/// Characters rejected in user-supplied Gradle arguments. The wrapper is run
+/// directly, but gradlew.bat is still interpreted by cmd on Windows.
+pub const SHELL_METACHARACTERS: &[char] = &[
+    '&', '|', ';', '<', '>', '$', '`', '(', ')', '^', '%', '!', '"', '\'', '\n', '\r',
+];
@@ -0,0 +1,124 @@
+//! MCP over Streamable HTTP, for the IDE (`ALLOY_MCP_HTTP`) and alloy-mcp
+//! (`--http`).
+//!
+//! rmcp's `StreamableHttpService` does the protocol work at [`ENDPOINT`]:
+//! every `initialize` request starts a new session with its own handler from
+//! `new_handler`. This module only adds the Origin and Host check in front of it.
+
+use axum::extract::{Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
+use rmcp::transport::streamable_http_server::{StreamableHttpServerConfig, StreamableHttpService};
+use rmcp::ServerHandler;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+/// Path of the MCP endpoint.
+pub const ENDPOINT: &str = "/mcp";
+/// Address used when `--http` is given without one.
+pub const DEFAULT_ADDR: &str = "127.0.0.1:8765";
+
+/// Serve MCP over HTTP on `addr` until the listener fails.
+pub async fn run_http_server<S, F>(addr: SocketAddr, new_handler: F) -> std::io::Result<()>
+where
+    S: ServerHandler,
+    F: Fn() -> S + Send + Sync + 'static,
+{
+    let service = StreamableHttpService::new(
+        move || Ok(new_handler()),
+        Arc::new(LocalSessionManager::default()),
+        StreamableHttpServerConfig::default(),
+    );
+    let listener = TcpListener::bind(addr).await?;
+    let bound = listener.local_addr()?;
+    let router = axum::Router::new()
+        .route_service(ENDPOINT, service)
+        .layer(middleware::from_fn_with_state(bound.ip(), check_origin));
+
+    eprintln!("MCP server listening on http://{}{}", bound, ENDPOINT);
+    axum::serve(listener, router).await
+}
+
+async fn check_origin(State(bound): State<IpAddr>, request: Request, next: Next) -> Response {
+    // Body isn't Sync, so the borrow of the headers must end before the await
+    let allowed = {
+        let header = |name| request.headers().get(name).and_then(|v| v.to_str().ok());
+        request_allowed(header(header::ORIGIN), header(header::HOST), bound)
+    };
+    if !allowed {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    next.run(request).await
+}
+
+/// Refuse requests a web page could send through DNS rebinding. A rebound
+/// browser names the attacker's domain in both Origin and Host, so each must
+/// be a loopback name or the address the server is bound to. Non-browser
+/// clients send no Origin.
+pub fn request_allowed(origin: Option<&str>, host: Option<&str>, bound: IpAddr) -> bool {
+    let origin_ok = origin.is_none_or(|o| {
+        let authority = o.split("://").nth(1).unwrap_or(o);
+        allowed_authority(authority, bound)
+    });
+    origin_ok && host.is_none_or(|h| allowed_authority(h, bound))
+}
+
+/// Whether a `host[:port]` authority names this machine's loopback interface
+/// or `bound`, the address the listener is bound to.
+fn allowed_authority(authority: &str, bound: IpAddr) -> bool {
+    let hostname = match authority.strip_prefix('[') {
+        Some(v6) => v6.split(']').next().unwrap_or(""),
+        None => authority.split(':').next().unwrap_or(""),
+    };
+    matches!(hostname, "localhost" | "127.0.0.1" | "::1")
+        || (!bound.is_unspecified() && hostname.parse::<IpAddr>() == Ok(bound))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOOPBACK: IpAddr = IpAddr::V4(std::net::Ipv4Addr::LOCALHOST);
+
+    #[test]
+    fn allows_clients_without_an_origin() {
+        assert!(request_allowed(None, Some("127.0.0.1:8765"), LOOPBACK));
+        assert!(request_allowed(None, None, LOOPBACK));
+    }
+
+    #[test]
+    fn allows_loopback_origins() {
+        assert!(request_allowed(Some("http://localhost:3000"), Some("127.0.0.1:8765"), LOOPBACK));
+        assert!(request_allowed(Some("http://127.0.0.1"), Some("localhost:8765"), LOOPBACK));
+        assert!(request_allowed(Some("http://[::1]:8765"), Some("[::1]:8765"), LOOPBACK));
+    }
+
+    #[test]
+    fn allows_the_bound_address() {
+        let lan: IpAddr = "192.168.1.20".parse().unwrap();
+        assert!(request_allowed(Some("http://192.168.1.20:8765"), Some("192.168.1.20:8765"), lan));
+        // Binding to every interface doesn't make every address trusted
+        let any: IpAddr = "0.0.0.0".parse().unwrap();
+        assert!(!request_allowed(None, Some("0.0.0.0:8765"), any));
+    }
+
+    #[test]
+    fn refuses_other_sites() {
+        assert!(!request_allowed(Some("https://evil.example"), Some("127.0.0.1:8765"), LOOPBACK));
+        assert!(!request_allowed(Some("http://localhost.evil.example"), None, LOOPBACK));
+        assert!(!request_allowed(Some("null"), Some("127.0.0.1:8765"), LOOPBACK));
+    }
+
+    #[test]
+    fn refuses_dns_rebinding() {
+        // After rebinding, the page is same-origin with the attacker's name
+        let attacker = "evil.example:8765";
+        let origin = format!("http://{}", attacker);
+        assert!(!request_allowed(Some(&origin), Some(attacker), LOOPBACK));
+        // Same-origin GETs may omit Origin, so Host alone must be refused too
+        assert!(!request_allowed(None, Some(attacker), LOOPBACK));
+    }
+}
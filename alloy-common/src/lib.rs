@@ -0,0 +1,10 @@
+//! Code the Alloy IDE and the standalone MCP server (`alloy-mcp`) both need,
+//! kept here so the two cannot drift apart.
+
+pub mod generated;
+pub mod git;
+pub mod gradle;
+pub mod http;
+pub mod mod_id;
+pub mod redact;
+pub mod scaffold;
@@ -0,0 +1,59 @@
+//! Mod id rules, shared by project creation, renaming and block definitions
+//! so every path accepts the same ids.
+
+/// Validate a mod id the way Fabric does: lowercase a-z0-9_ only, starting
+/// with a letter. Anything else ends up in namespaced ids and asset paths the
+/// game can't resolve.
+pub fn is_valid_mod_id(id: &str) -> bool {
+    id.starts_with(|c: char| c.is_ascii_lowercase())
+        && id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// The nearest valid mod id to `id`: lowercased, other characters replaced
+/// by underscores, and prefixed with `mod_` if it doesn't start with a letter.
+pub fn sanitize_mod_id(id: &str) -> String {
+    let replaced: String = id
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_lowercase() || c.is_ascii_digit() { c } else { '_' })
+        .collect();
+    let mut sanitized = replaced
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+    if !sanitized.starts_with(|c: char| c.is_ascii_lowercase()) {
+        sanitized.insert_str(0, "mod_");
+    }
+    sanitized.trim_end_matches('_').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_fabric_style_ids() {
+        assert!(is_valid_mod_id("examplemod"));
+        assert!(is_valid_mod_id("my_mod_2"));
+    }
+
+    #[test]
+    fn rejects_ids_fabric_would_reject() {
+        for id in ["", "2fast", "_mod", "My_Mod", "my-mod", "my.mod", "my mod"] {
+            assert!(!is_valid_mod_id(id), "{:?} should be invalid", id);
+        }
+    }
+
+    #[test]
+    fn sanitized_ids_are_valid() {
+        assert_eq!(sanitize_mod_id("My-Cool.Mod"), "my_cool_mod");
+        assert_eq!(sanitize_mod_id("2fast"), "mod_2fast");
+        assert_eq!(sanitize_mod_id("--weird__id--"), "weird_id");
+        for id in ["My-Cool.Mod", "2fast", "--weird__id--", "ÄÖÜ", ""] {
+            assert!(is_valid_mod_id(&sanitize_mod_id(id)), "{:?}", id);
+        }
+    }
+}
@@ -0,0 +1,507 @@
+//! Project scaffolds: an Alloy mod, a Fabric or Quilt mod built with Loom,
+//! or a modpack. The IDE's `create_project` and the MCP server's
+//! `project_create` both generate from these templates, so a project made
+//! through either looks the same.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// What a scaffold is generated from.
+pub struct ScaffoldArgs {
+    pub name: String,
+    pub environment: String,
+    pub mod_id: Option<String>,
+    pub package_name: Option<String>,
+    pub minecraft_version: Option<String>,
+}
+
+/// Files written by a scaffold.
+#[derive(Default)]
+pub struct Scaffold {
+    pub created_files: Vec<String>,
+    pub main_class: Option<String>,
+}
+
+impl Scaffold {
+    fn write(&mut self, dir: &Path, relative: &str, contents: &str) -> Result<PathBuf, String> {
+        let path = dir.join(relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        fs::write(&path, contents).map_err(|e| format!("Failed to write {}: {}", relative, e))?;
+        self.created_files.push(path.to_string_lossy().to_string());
+        Ok(path)
+    }
+}
+
+const DEFAULT_MINECRAFT_VERSION: &str = "1.21.11";
+const GRADLE_VERSION: &str = "8.14.3";
+const FABRIC_LOOM_VERSION: &str = "1.11-SNAPSHOT";
+pub const FABRIC_LOADER_VERSION: &str = "0.17.2";
+const QUILT_LOOM_VERSION: &str = "1.10.+";
+pub const QUILT_LOADER_VERSION: &str = "0.29.1";
+
+pub fn scaffold_mod_project(dir: &Path, args: &ScaffoldArgs) -> Result<Scaffold, String> {
+    let mut scaffold = Scaffold::default();
+    let mod_id = args.mod_id.as_deref().unwrap_or("mymod");
+    let pkg = args.package_name.as_deref().unwrap_or("com.example");
+    let pkg_path = pkg.replace('.', "/");
+    let class_name = to_pascal_case(&args.name);
+
+    // alloy.mod.json
+    let mod_json = format!(
+        r#"{{
+  "id": "{}",
+  "name": "{}",
+  "version": "1.0.0",
+  "entrypoint": "{}.{}.{}",
+  "environment": "{}"
+}}"#,
+        mod_id, args.name, pkg, mod_id, class_name, args.environment
+    );
+    scaffold.write(dir, "alloy.mod.json", &mod_json)?;
+
+    // build.gradle.kts
+    let build_gradle = format!(
+        r#"plugins {{
+    java
+}}
+
+group = "{}.{}"
+version = "1.0.0"
+
+repositories {{
+    mavenCentral()
+}}
+
+java {{
+    sourceCompatibility = JavaVersion.VERSION_21
+    targetCompatibility = JavaVersion.VERSION_21
+}}
+
+tasks.withType<JavaCompile> {{
+    options.release.set(21)
+    options.encoding = "UTF-8"
+}}
+
+dependencies {{
+    // Alloy API — provided at runtime by the loader
+    compileOnly(files("libs/alloy-api.jar"))
+}}
+"#,
+        pkg, mod_id
+    );
+    scaffold.write(dir, "build.gradle.kts", &build_gradle)?;
+
+    // settings.gradle.kts
+    let settings = format!("rootProject.name = \"{}\"", mod_id);
+    scaffold.write(dir, "settings.gradle.kts", &settings)?;
+
+    // .gitignore
+    let gitignore = "build/\n.gradle/\nlibs/\nout/\n*.class\n.idea/\n*.iml\n";
+    scaffold.write(dir, ".gitignore", gitignore)?;
+
+    // Source directories
+    let src_dir = dir.join("src/main/java").join(&pkg_path).join(mod_id);
+    fs::create_dir_all(&src_dir)
+        .map_err(|e| format!("Failed to create source directory: {}", e))?;
+
+    // Resources directory
+    let res_dir = dir.join("src/main/resources/assets").join(mod_id).join("textures");
+    fs::create_dir_all(&res_dir)
+        .map_err(|e| format!("Failed to create resource directory: {}", e))?;
+
+    // Main class
+    let entry_class = match args.environment.as_str() {
+        "server" => format!(
+            r#"package {pkg}.{mod_id};
+
+import net.alloymc.loader.api.ModInitializer;
+import net.alloymc.api.AlloyAPI;
+import net.alloymc.api.event.EventHandler;
+import net.alloymc.api.event.Listener;
+import net.alloymc.api.event.player.PlayerJoinEvent;
+
+public class {class_name} implements ModInitializer, Listener {{
+
+    @Override
+    public void onInitialize() {{
+        AlloyAPI.getEventBus().register(this);
+    }}
+
+    @EventHandler
+    public void onPlayerJoin(PlayerJoinEvent event) {{
+        event.getPlayer().sendMessage("Hello from {name}!");
+    }}
+}}
+"#,
+            pkg = pkg,
+            mod_id = mod_id,
+            class_name = class_name,
+            name = args.name
+        ),
+        "client" => format!(
+            r#"package {pkg}.{mod_id};
+
+import net.alloymc.loader.api.ModInitializer;
+
+public class {class_name} implements ModInitializer {{
+
+    @Override
+    public void onInitialize() {{
+        // Client-side initialization
+        // Register screens, renderers, HUD elements, etc.
+    }}
+}}
+"#,
+            pkg = pkg,
+            mod_id = mod_id,
+            class_name = class_name
+        ),
+        _ => format!(
+            r#"package {pkg}.{mod_id};
+
+import net.alloymc.loader.api.ModInitializer;
+import net.alloymc.api.AlloyAPI;
+import net.alloymc.api.event.EventHandler;
+import net.alloymc.api.event.Listener;
+import net.alloymc.api.event.player.PlayerJoinEvent;
+
+public class {class_name} implements ModInitializer, Listener {{
+
+    @Override
+    public void onInitialize() {{
+        AlloyAPI.getEventBus().register(this);
+    }}
+
+    @EventHandler
+    public void onPlayerJoin(PlayerJoinEvent event) {{
+        event.getPlayer().sendMessage("Hello from {name}!");
+    }}
+}}
+"#,
+            pkg = pkg,
+            mod_id = mod_id,
+            class_name = class_name,
+            name = args.name
+        ),
+    };
+
+    let entry_path = src_dir.join(format!("{}.java", class_name));
+    fs::write(&entry_path, entry_class)
+        .map_err(|e| format!("Failed to write entry class: {}", e))?;
+    let entry_path = entry_path.to_string_lossy().to_string();
+    scaffold.created_files.push(entry_path.clone());
+    scaffold.main_class = Some(entry_path);
+
+    // libs/ directory (placeholder)
+    fs::create_dir_all(dir.join("libs"))
+        .map_err(|e| format!("Failed to create libs directory: {}", e))?;
+
+    Ok(scaffold)
+}
+
+/// Scaffold a Fabric or Quilt mod built with Loom.
+pub fn scaffold_loom_project(dir: &Path, args: &ScaffoldArgs, loader: &str) -> Result<Scaffold, String> {
+    let mut scaffold = Scaffold::default();
+    let mod_id = args.mod_id.as_deref().unwrap_or("mymod");
+    let pkg = args.package_name.as_deref().unwrap_or("com.example");
+    let pkg_path = pkg.replace('.', "/");
+    let class_name = to_pascal_case(&args.name);
+    let mc_version = args
+        .minecraft_version
+        .as_deref()
+        .filter(|v| !v.is_empty())
+        .unwrap_or(DEFAULT_MINECRAFT_VERSION);
+    let java = java_release(mc_version);
+    let quilt = loader == "quilt";
+
+    // gradle.properties
+    let loader_version = if quilt { QUILT_LOADER_VERSION } else { FABRIC_LOADER_VERSION };
+    let properties = format!(
+        "org.gradle.jvmargs=-Xmx2G\n\nminecraft_version={}\nloader_version={}\n\nmod_version=1.0.0\nmaven_group={}\narchives_base_name={}\n",
+        mc_version, loader_version, pkg, mod_id
+    );
+    scaffold.write(dir, "gradle.properties", &properties)?;
+
+    // build.gradle
+    let (plugin, loader_dep, metadata_file) = if quilt {
+        (
+            format!("id 'org.quiltmc.loom' version '{}'", QUILT_LOOM_VERSION),
+            "modImplementation \"org.quiltmc:quilt-loader:${project.loader_version}\"",
+            "quilt.mod.json",
+        )
+    } else {
+        (
+            format!("id 'fabric-loom' version '{}'", FABRIC_LOOM_VERSION),
+            "modImplementation \"net.fabricmc:fabric-loader:${project.loader_version}\"",
+            "fabric.mod.json",
+        )
+    };
+    let build_gradle = format!(
+        r#"plugins {{
+    {plugin}
+    id 'maven-publish'
+}}
+
+version = project.mod_version
+group = project.maven_group
+
+base {{
+    archivesName = project.archives_base_name
+}}
+
+repositories {{
+    mavenCentral()
+}}
+
+dependencies {{
+    minecraft "com.mojang:minecraft:${{project.minecraft_version}}"
+    mappings loom.officialMojangMappings()
+    {loader_dep}
+}}
+
+processResources {{
+    inputs.property "version", project.version
+
+    filesMatching("{metadata_file}") {{
+        expand "version": project.version
+    }}
+}}
+
+tasks.withType(JavaCompile).configureEach {{
+    it.options.release = {java}
+    it.options.encoding = "UTF-8"
+}}
+
+java {{
+    withSourcesJar()
+    sourceCompatibility = JavaVersion.VERSION_{java}
+    targetCompatibility = JavaVersion.VERSION_{java}
+}}
+"#,
+        plugin = plugin,
+        loader_dep = loader_dep,
+        metadata_file = metadata_file,
+        java = java
+    );
+    scaffold.write(dir, "build.gradle", &build_gradle)?;
+
+    // settings.gradle
+    let plugin_repo = if quilt {
+        "        maven {\n            name = 'Quilt'\n            url = 'https://maven.quiltmc.org/repository/release'\n        }\n"
+    } else {
+        "        maven {\n            name = 'Fabric'\n            url = 'https://maven.fabricmc.net/'\n        }\n"
+    };
+    let settings = format!(
+        "pluginManagement {{\n    repositories {{\n{}        gradlePluginPortal()\n    }}\n}}\n\nrootProject.name = \"{}\"\n",
+        plugin_repo, mod_id
+    );
+    scaffold.write(dir, "settings.gradle", &settings)?;
+
+    // Gradle wrapper
+    let wrapper = format!(
+        "distributionBase=GRADLE_USER_HOME\ndistributionPath=wrapper/dists\ndistributionUrl=https\\://services.gradle.org/distributions/gradle-{}-bin.zip\nnetworkTimeout=10000\nvalidateDistributionUrl=true\nzipStoreBase=GRADLE_USER_HOME\nzipStorePath=wrapper/dists\n",
+        GRADLE_VERSION
+    );
+    scaffold.write(dir, "gradle/wrapper/gradle-wrapper.properties", &wrapper)?;
+    generate_gradle_wrapper(dir, &mut scaffold);
+
+    // .gitignore
+    let gitignore = "build/\n.gradle/\nrun/\nout/\n*.class\n.idea/\n*.iml\n";
+    scaffold.write(dir, ".gitignore", gitignore)?;
+
+    // Mod metadata
+    let entry = format!("{}.{}.{}", pkg, mod_id, class_name);
+    let mixins = format!("{}.mixins.json", mod_id);
+    let metadata = if quilt {
+        serde_json::json!({
+            "schema_version": 1,
+            "quilt_loader": {
+                "group": pkg,
+                "id": mod_id,
+                "version": "${version}",
+                "metadata": {
+                    "name": args.name,
+                    "description": "",
+                    "contributors": {},
+                },
+                "intermediate_mappings": "net.fabricmc:intermediary",
+                "entrypoints": { "main": entry },
+                "depends": [
+                    { "id": "quilt_loader", "versions": format!(">={}", QUILT_LOADER_VERSION) },
+                    { "id": "minecraft", "versions": format!("~{}", mc_version) },
+                ],
+            },
+            "mixin": mixins,
+            "minecraft": { "environment": fabric_environment(&args.environment) },
+        })
+    } else {
+        serde_json::json!({
+            "schemaVersion": 1,
+            "id": mod_id,
+            "version": "${version}",
+            "name": args.name,
+            "description": "",
+            "authors": [],
+            "license": "All-Rights-Reserved",
+            "environment": fabric_environment(&args.environment),
+            "entrypoints": { "main": [entry] },
+            "mixins": [mixins],
+            "depends": {
+                "fabricloader": format!(">={}", FABRIC_LOADER_VERSION),
+                "minecraft": format!("~{}", mc_version),
+                "java": format!(">={}", java),
+            },
+        })
+    };
+    let metadata = serde_json::to_string_pretty(&metadata).map_err(|e| e.to_string())?;
+    scaffold.write(dir, &format!("src/main/resources/{}", metadata_file), &metadata)?;
+
+    // Mixins config
+    let mixins_json = serde_json::json!({
+        "required": true,
+        "package": format!("{}.{}.mixin", pkg, mod_id),
+        "compatibilityLevel": format!("JAVA_{}", java),
+        "mixins": [],
+        "client": [],
+        "injectors": { "defaultRequire": 1 },
+    });
+    let mixins_json = serde_json::to_string_pretty(&mixins_json).map_err(|e| e.to_string())?;
+    scaffold.write(dir, &format!("src/main/resources/{}", mixins), &mixins_json)?;
+
+    fs::create_dir_all(dir.join("src/main/resources/assets").join(mod_id).join("textures"))
+        .map_err(|e| format!("Failed to create resource directory: {}", e))?;
+    fs::create_dir_all(dir.join("src/main/java").join(&pkg_path).join(mod_id).join("mixin"))
+        .map_err(|e| format!("Failed to create source directory: {}", e))?;
+
+    // Main class
+    let entry_class = format!(
+        r#"package {pkg}.{mod_id};
+
+import net.fabricmc.api.ModInitializer;
+import org.slf4j.Logger;
+import org.slf4j.LoggerFactory;
+
+public class {class_name} implements ModInitializer {{
+    public static final String MOD_ID = "{mod_id}";
+    public static final Logger LOGGER = LoggerFactory.getLogger(MOD_ID);
+
+    @Override
+    public void onInitialize() {{
+        LOGGER.info("Hello from {name}!");
+    }}
+}}
+"#,
+        pkg = pkg,
+        mod_id = mod_id,
+        class_name = class_name,
+        name = args.name
+    );
+    let entry_path = scaffold.write(
+        dir,
+        &format!("src/main/java/{}/{}/{}.java", pkg_path, mod_id, class_name),
+        &entry_class,
+    )?;
+    scaffold.main_class = Some(entry_path.to_string_lossy().to_string());
+
+    Ok(scaffold)
+}
+
+/// Java release a Minecraft version compiles against.
+fn java_release(mc_version: &str) -> u32 {
+    let mut parts = mc_version.split('.').map(|p| p.parse::<u32>().unwrap_or(0));
+    let (_, minor, patch) = (
+        parts.next().unwrap_or(1),
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+    );
+    match (minor, patch) {
+        (m, _) if m >= 21 => 21,
+        (20, p) if p >= 5 => 21,
+        (m, _) if m >= 18 => 17,
+        (17, _) => 16,
+        _ => 8,
+    }
+}
+
+/// Map an Alloy environment to the loader metadata's value.
+fn fabric_environment(environment: &str) -> &'static str {
+    match environment {
+        "client" => "client",
+        "server" => "server",
+        _ => "*",
+    }
+}
+
+/// Best effort: the wrapper jar and scripts can only come from a local Gradle
+/// install. Without one, the properties file is enough for IDE imports and
+/// `gradle wrapper` can be run later.
+fn generate_gradle_wrapper(dir: &Path, scaffold: &mut Scaffold) {
+    let status = std::process::Command::new("gradle")
+        .args(["wrapper", "--gradle-version", GRADLE_VERSION, "--offline"])
+        .current_dir(dir)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status();
+    if !matches!(status, Ok(s) if s.success()) {
+        return;
+    }
+    for file in ["gradlew", "gradlew.bat", "gradle/wrapper/gradle-wrapper.jar"] {
+        let path = dir.join(file);
+        if path.exists() {
+            scaffold.created_files.push(path.to_string_lossy().to_string());
+        }
+    }
+}
+
+pub fn scaffold_modpack_project(dir: &Path, args: &ScaffoldArgs) -> Result<Scaffold, String> {
+    let mut scaffold = Scaffold::default();
+    let pack_toml = format!(
+        r#"[pack]
+name = "{}"
+version = "1.0.0"
+description = ""
+authors = []
+
+[alloy]
+version = "0.1.0"
+minecraft = "1.21.11"
+
+[mods]
+# Add mods here:
+# my-mod = {{ path = "../my-mod" }}
+# some-mod = {{ git = "https://github.com/user/some-mod.git", tag = "v1.0.0" }}
+"#,
+        args.name
+    );
+    scaffold.write(dir, "alloy.pack.toml", &pack_toml)?;
+
+    // mods/ directory for local JAR imports
+    fs::create_dir_all(dir.join("mods"))
+        .map_err(|e| format!("Failed to create mods directory: {}", e))?;
+
+    // config/ directory for mod configs
+    fs::create_dir_all(dir.join("config"))
+        .map_err(|e| format!("Failed to create config directory: {}", e))?;
+
+    // .gitignore
+    let gitignore = "build/\n*.alloypack\n";
+    scaffold.write(dir, ".gitignore", gitignore)?;
+
+    Ok(scaffold)
+}
+
+fn to_pascal_case(s: &str) -> String {
+    s.split(['-', '_', ' '])
+        .filter(|w| !w.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(c) => c.to_uppercase().to_string() + &chars.as_str().to_lowercase(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
@@ -1,9 +1,14 @@
+use alloy_common::redact::Redactor;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tauri::{AppHandle, State};
 
 use crate::mcp::claude::ClaudeClient;
-use crate::mcp::types::{AiConfig, ChatMessage};
-use crate::state::AppState;
+use crate::mcp::permissions::{self, PendingConfirmation};
+use crate::mcp::tools::ToolRegistry;
+use crate::commands::lsp::Diagnostic;
+use crate::mcp::types::{AiConfig, ChatMessage, ToolResult};
+use crate::state::{AppState, SelectionRange};
 
 #[tauri::command]
 pub async fn ai_send_message(
@@ -18,6 +23,15 @@ pub async fn ai_send_message(
         .await
 }
 
+#[tauri::command]
+pub async fn ai_cancel(state: State<'_, Arc<AppState>>) -> Result<(), String> {
+    state
+        .ai_generation
+        .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    state.ai_cancel.notify_waiters();
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn ai_get_history(state: State<'_, Arc<AppState>>) -> Result<Vec<ChatMessage>, String> {
     let history = state.chat_history.lock().map_err(|e| e.to_string())?;
@@ -33,6 +47,7 @@ pub async fn ai_clear_history(state: State<'_, Arc<AppState>>) -> Result<(), Str
 
 #[tauri::command]
 pub async fn ai_set_config(config: AiConfig, state: State<'_, Arc<AppState>>) -> Result<(), String> {
+    Redactor::new(&config.redact_patterns)?;
     let mut ai_config = state.ai_config.lock().map_err(|e| e.to_string())?;
     *ai_config = config;
     Ok(())
@@ -48,6 +63,10 @@ pub async fn ai_get_config(state: State<'_, Arc<AppState>>) -> Result<AiConfig,
 pub async fn ai_update_editor_state(
     open_files: Vec<String>,
     selection: Option<String>,
+    buffers: Option<HashMap<String, String>>,
+    active_file: Option<String>,
+    selection_range: Option<SelectionRange>,
+    diagnostics: Option<Vec<Diagnostic>>,
     state: State<'_, Arc<AppState>>,
 ) -> Result<(), String> {
     if let Ok(mut files) = state.open_editor_files.lock() {
@@ -56,6 +75,18 @@ pub async fn ai_update_editor_state(
     if let Ok(mut sel) = state.editor_selection.lock() {
         *sel = selection;
     }
+    if let Ok(mut current) = state.editor_buffers.lock() {
+        *current = buffers.unwrap_or_default();
+    }
+    if let Ok(mut file) = state.active_editor_file.lock() {
+        *file = active_file;
+    }
+    if let Ok(mut range) = state.editor_selection_range.lock() {
+        *range = selection_range;
+    }
+    if let Ok(mut current) = state.active_file_diagnostics.lock() {
+        *current = diagnostics.unwrap_or_default();
+    }
     Ok(())
 }
 
@@ -66,3 +97,28 @@ pub async fn ai_get_pending_actions(state: State<'_, Arc<AppState>>) -> Result<V
     actions.clear();
     Ok(result)
 }
+
+#[tauri::command]
+pub async fn ai_list_confirmations(
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<PendingConfirmation>, String> {
+    let confirmations = state.pending_confirmations.lock().map_err(|e| e.to_string())?;
+    Ok(confirmations.clone())
+}
+
+/// Approve or deny a gated tool call. Approved calls run immediately and
+/// their result is returned.
+#[tauri::command]
+pub async fn ai_resolve_confirmation(
+    id: String,
+    approved: bool,
+    state: State<'_, Arc<AppState>>,
+) -> Result<ToolResult, String> {
+    let pending = permissions::take_confirmation(&id, &state)
+        .ok_or_else(|| format!("No pending confirmation: {}", id))?;
+
+    if !approved {
+        return Ok(ToolResult::error(format!("{} was denied by the user", pending.tool)));
+    }
+    Ok(ToolRegistry::execute_approved(&pending.tool, pending.input, &state).await)
+}
@@ -16,6 +16,10 @@ pub struct ImportAssetArgs {
     pub asset_name: String,
     /// Whether to generate registration code
     pub generate_code: bool,
+    /// Resize the texture to this many pixels square (nearest-neighbor)
+    /// before writing it. Animation strips keep their frame count.
+    #[serde(default)]
+    pub target_size: Option<u32>,
 }
 
 #[derive(Debug, Serialize)]
@@ -26,6 +30,14 @@ pub struct ImportResult {
     pub registration_code: Option<String>,
     /// The destination texture path
     pub texture_path: String,
+    /// Dimensions of the source image
+    pub original_width: u32,
+    pub original_height: u32,
+    /// Dimensions of the written texture
+    pub final_width: u32,
+    pub final_height: u32,
+    /// Whether the texture was resized on import
+    pub resized: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -41,8 +53,22 @@ pub struct AssetInfo {
     pub file_size: u64,
     pub suggested_type: String,
     pub suggested_name: String,
+    /// At least one pixel is not fully opaque
+    pub has_alpha: bool,
+    pub is_square: bool,
+    /// Both dimensions are powers of two
+    pub is_power_of_two: bool,
+    /// Most common colors of the opaque pixels, most frequent first
+    pub dominant_colors: Vec<[u8; 3]>,
 }
 
+/// Palette size reported by analyze_image
+const DOMINANT_COLORS: usize = 5;
+
+/// Images are downsampled to at most this many pixels per side before
+/// clustering; textures this small are analyzed as-is.
+const PALETTE_SAMPLE_SIZE: u32 = 64;
+
 #[tauri::command]
 pub async fn analyze_image(path: String) -> Result<AssetInfo, String> {
     let file_path = Path::new(&path);
@@ -53,10 +79,20 @@ pub async fn analyze_image(path: String) -> Result<AssetInfo, String> {
     let metadata = fs::metadata(&path).map_err(|e| format!("Failed to read file: {}", e))?;
     let file_size = metadata.len();
 
-    // Read PNG header to get dimensions
+    // Decode for pixel analysis; fall back to the PNG header for dimensions
     let data = fs::read(&path).map_err(|e| format!("Failed to read file: {}", e))?;
-    let (width, height) = read_png_dimensions(&data)
-        .unwrap_or((0, 0));
+    let decoded = image::load_from_memory(&data).ok().map(|img| img.to_rgba8());
+    let (width, height) = match &decoded {
+        Some(img) => img.dimensions(),
+        None => read_png_dimensions(&data).unwrap_or((0, 0)),
+    };
+    let has_alpha = decoded
+        .as_ref()
+        .is_some_and(|img| img.pixels().any(|p| p[3] < 255));
+    let dominant_colors = decoded
+        .as_ref()
+        .map(|img| dominant_colors(img, DOMINANT_COLORS))
+        .unwrap_or_default();
 
     // Suggest asset type based on dimensions
     let suggested_type = if width == height && (width == 16 || width == 32 || width == 64 || width == 128) {
@@ -89,9 +125,79 @@ pub async fn analyze_image(path: String) -> Result<AssetInfo, String> {
         file_size,
         suggested_type,
         suggested_name,
+        has_alpha,
+        is_square: width == height && width > 0,
+        is_power_of_two: width.is_power_of_two() && height.is_power_of_two(),
+        dominant_colors,
     })
 }
 
+/// Median-cut palette of the image's mostly-opaque pixels, largest buckets
+/// first. Large images are downsampled first so this stays fast.
+fn dominant_colors(img: &image::RgbaImage, count: usize) -> Vec<[u8; 3]> {
+    let sample;
+    let img = if img.width() > PALETTE_SAMPLE_SIZE || img.height() > PALETTE_SAMPLE_SIZE {
+        sample = image::imageops::thumbnail(img, PALETTE_SAMPLE_SIZE, PALETTE_SAMPLE_SIZE);
+        &sample
+    } else {
+        img
+    };
+
+    let pixels: Vec<[u8; 3]> = img
+        .pixels()
+        .filter(|p| p[3] >= 128)
+        .map(|p| [p[0], p[1], p[2]])
+        .collect();
+    if pixels.is_empty() {
+        return Vec::new();
+    }
+
+    // Repeatedly split the bucket with the widest channel range at its median
+    let mut buckets: Vec<Vec<[u8; 3]>> = vec![pixels];
+    while buckets.len() < count {
+        let widest = buckets
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| b.len() > 1)
+            .map(|(i, b)| {
+                let (channel, range) = (0..3)
+                    .map(|c| {
+                        let min = b.iter().map(|p| p[c]).min().unwrap_or(0);
+                        let max = b.iter().map(|p| p[c]).max().unwrap_or(0);
+                        (c, max - min)
+                    })
+                    .max_by_key(|&(_, range)| range)
+                    .unwrap_or((0, 0));
+                (i, channel, range)
+            })
+            .filter(|&(_, _, range)| range > 0)
+            .max_by_key(|&(_, _, range)| range);
+        let Some((index, channel, _)) = widest else {
+            break;
+        };
+        let mut bucket = buckets.swap_remove(index);
+        bucket.sort_unstable_by_key(|p| p[channel]);
+        let upper = bucket.split_off(bucket.len() / 2);
+        buckets.push(bucket);
+        buckets.push(upper);
+    }
+
+    buckets.sort_by_key(|b| std::cmp::Reverse(b.len()));
+    buckets
+        .iter()
+        .map(|b| {
+            let mut sum = [0u64; 3];
+            for p in b {
+                for c in 0..3 {
+                    sum[c] += p[c] as u64;
+                }
+            }
+            let n = b.len() as u64;
+            [(sum[0] / n) as u8, (sum[1] / n) as u8, (sum[2] / n) as u8]
+        })
+        .collect()
+}
+
 #[tauri::command]
 pub async fn import_asset(args: ImportAssetArgs) -> Result<ImportResult, String> {
     let project = Path::new(&args.project_path);
@@ -104,6 +210,36 @@ pub async fn import_asset(args: ImportAssetArgs) -> Result<ImportResult, String>
         return Err("Project directory does not exist".to_string());
     }
 
+    // Decode up front so non-images never land in textures/
+    let source_bytes = fs::read(source).map_err(|e| format!("Failed to read source file: {}", e))?;
+    let source_image = image::load_from_memory_with_format(&source_bytes, image::ImageFormat::Png)
+        .map_err(|e| {
+            format!(
+                "{} is not a valid PNG image ({}). Minecraft textures must be PNG files.",
+                source.display(),
+                e
+            )
+        })?;
+    let (original_width, original_height) = (source_image.width(), source_image.height());
+
+    // A vertical strip of square frames is an animation: scale each frame
+    let (final_width, final_height) = match args.target_size {
+        Some(0) => return Err("target_size must be at least 1 pixel".to_string()),
+        Some(size) => {
+            let frames = if original_height > original_width
+                && original_width > 0
+                && original_height % original_width == 0
+            {
+                original_height / original_width
+            } else {
+                1
+            };
+            (size, size * frames)
+        }
+        None => (original_width, original_height),
+    };
+    let resized = (final_width, final_height) != (original_width, original_height);
+
     let mut created_files = Vec::new();
 
     // Determine texture destination path
@@ -126,8 +262,16 @@ pub async fn import_asset(args: ImportAssetArgs) -> Result<ImportResult, String>
         .map_err(|e| format!("Failed to create textures directory: {}", e))?;
 
     let dest_texture = textures_dir.join(format!("{}.png", args.asset_name));
-    fs::copy(source, &dest_texture)
-        .map_err(|e| format!("Failed to copy texture: {}", e))?;
+    if resized {
+        // Nearest-neighbor keeps pixel art crisp
+        source_image
+            .resize_exact(final_width, final_height, image::imageops::FilterType::Nearest)
+            .save_with_format(&dest_texture, image::ImageFormat::Png)
+            .map_err(|e| format!("Failed to write resized texture: {}", e))?;
+    } else {
+        fs::write(&dest_texture, &source_bytes)
+            .map_err(|e| format!("Failed to copy texture: {}", e))?;
+    }
 
     created_files.push(CreatedFile {
         path: dest_texture.to_string_lossy().to_string(),
@@ -308,6 +452,11 @@ public static final Item {upper} = Registry.register(
         created_files,
         registration_code,
         texture_path: dest_texture.to_string_lossy().to_string(),
+        original_width,
+        original_height,
+        final_width,
+        final_height,
+        resized,
     })
 }
 
@@ -2,6 +2,9 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
 
+use alloy_common::generated::{self, GeneratedManifest, GeneratedWriter};
+use alloy_common::mod_id::{is_valid_mod_id, sanitize_mod_id};
+
 // --- Vanilla Minecraft block IDs for conflict checking ---
 // ~900 vanilla block names sorted for binary search
 const VANILLA_BLOCKS: &[&str] = &[
@@ -325,6 +328,10 @@ pub struct BlockCreateResult {
 #[derive(Debug, Serialize)]
 pub struct BlockGenerateResult {
     pub created_files: Vec<CreatedFile>,
+    /// Hand-edited files left untouched; pass `force` to overwrite them
+    pub skipped_modified: Vec<String>,
+    /// Hand-edited files overwritten because `force` was set
+    pub overwritten_modified: Vec<String>,
     pub block_class_path: String,
     pub registration_snippet: String,
 }
@@ -428,6 +435,13 @@ pub async fn create_block_from_wizard(
     if !project.exists() {
         return Err("Project directory does not exist".to_string());
     }
+    if !is_valid_mod_id(&mod_id) {
+        return Err(format!(
+            "Invalid mod ID '{}': it must start with a lowercase letter and use only a-z, 0-9 and underscores. Try '{}'.",
+            mod_id,
+            sanitize_mod_id(&mod_id)
+        ));
+    }
 
     let mut created_files = Vec::new();
 
@@ -501,6 +515,7 @@ pub async fn create_block_from_wizard(
 pub async fn generate_block_code(
     project_path: String,
     block_json_path: String,
+    force: Option<bool>,
 ) -> Result<BlockGenerateResult, String> {
     let project = Path::new(&project_path);
     let block_path = Path::new(&block_json_path);
@@ -518,6 +533,11 @@ pub async fn generate_block_code(
     let class_name = to_pascal_case(&block.name);
     let mut created_files = Vec::new();
 
+    let block_key = generated::relative_path(project, block_path);
+    let mut manifest = GeneratedManifest::load(project)
+        .map_err(|e| format!("{}; fix or delete it and retry", e))?;
+    let mut writer = GeneratedWriter::new(project, manifest.blocks.get(&block_key), force.unwrap_or(false));
+
     // Determine package from alloy.mod.json or fallback
     let package_name = format!("com.{}", block.mod_id);
 
@@ -532,13 +552,16 @@ pub async fn generate_block_code(
         .map_err(|e| format!("Failed to create directory: {}", e))?;
 
     let java_path = java_dir.join(format!("{}.java", class_name));
-    fs::write(&java_path, &java_code)
+    let written = writer
+        .write(&java_path, &java_code, "java_class")
         .map_err(|e| format!("Failed to write Java file: {}", e))?;
 
-    created_files.push(CreatedFile {
-        path: java_path.to_string_lossy().to_string(),
-        file_type: "java_class".to_string(),
-    });
+    if written {
+        created_files.push(CreatedFile {
+            path: java_path.to_string_lossy().to_string(),
+            file_type: "java_class".to_string(),
+        });
+    }
 
     // 2. Generate Block model JSON
     let models_dir = project
@@ -550,13 +573,16 @@ pub async fn generate_block_code(
 
     let model_json = generate_block_model(&block);
     let model_path = models_dir.join(format!("{}.json", block.name));
-    fs::write(&model_path, &model_json)
+    let written = writer
+        .write(&model_path, &model_json, "block_model")
         .map_err(|e| format!("Failed to write block model: {}", e))?;
 
-    created_files.push(CreatedFile {
-        path: model_path.to_string_lossy().to_string(),
-        file_type: "block_model".to_string(),
-    });
+    if written {
+        created_files.push(CreatedFile {
+            path: model_path.to_string_lossy().to_string(),
+            file_type: "block_model".to_string(),
+        });
+    }
 
     // 3. Generate Blockstate JSON
     let blockstates_dir = project
@@ -575,13 +601,16 @@ pub async fn generate_block_code(
         block.mod_id, block.name
     );
     let blockstate_path = blockstates_dir.join(format!("{}.json", block.name));
-    fs::write(&blockstate_path, &blockstate_json)
+    let written = writer
+        .write(&blockstate_path, &blockstate_json, "blockstate")
         .map_err(|e| format!("Failed to write blockstate: {}", e))?;
 
-    created_files.push(CreatedFile {
-        path: blockstate_path.to_string_lossy().to_string(),
-        file_type: "blockstate".to_string(),
-    });
+    if written {
+        created_files.push(CreatedFile {
+            path: blockstate_path.to_string_lossy().to_string(),
+            file_type: "blockstate".to_string(),
+        });
+    }
 
     // 4. Generate Item model JSON
     let item_models_dir = project
@@ -598,25 +627,31 @@ pub async fn generate_block_code(
         block.mod_id, block.name
     );
     let item_model_path = item_models_dir.join(format!("{}.json", block.name));
-    fs::write(&item_model_path, &item_model_json)
+    let written = writer
+        .write(&item_model_path, &item_model_json, "item_model")
         .map_err(|e| format!("Failed to write item model: {}", e))?;
 
-    created_files.push(CreatedFile {
-        path: item_model_path.to_string_lossy().to_string(),
-        file_type: "item_model".to_string(),
-    });
+    if written {
+        created_files.push(CreatedFile {
+            path: item_model_path.to_string_lossy().to_string(),
+            file_type: "item_model".to_string(),
+        });
+    }
 
     // 5. Generate BlockEntity class if needed
     if block.has_block_entity {
         let be_code = generate_block_entity_class(&block, &class_name, &package_name);
         let be_path = java_dir.join(format!("{}BlockEntity.java", class_name));
-        fs::write(&be_path, &be_code)
+        let written = writer
+            .write(&be_path, &be_code, "java_class")
             .map_err(|e| format!("Failed to write BlockEntity: {}", e))?;
 
-        created_files.push(CreatedFile {
-            path: be_path.to_string_lossy().to_string(),
-            file_type: "java_class".to_string(),
-        });
+        if written {
+            created_files.push(CreatedFile {
+                path: be_path.to_string_lossy().to_string(),
+                file_type: "java_class".to_string(),
+            });
+        }
     }
 
     // 6. Registration snippet
@@ -640,8 +675,15 @@ public static final Item {upper}_ITEM = Registry.register(
         name = block.name,
     );
 
+    let skipped_modified = std::mem::take(&mut writer.skipped_modified);
+    let overwritten_modified = std::mem::take(&mut writer.overwritten_modified);
+    manifest.blocks.insert(block_key, writer.finish());
+    manifest.save(project)?;
+
     Ok(BlockGenerateResult {
         created_files,
+        skipped_modified,
+        overwritten_modified,
         block_class_path: java_path.to_string_lossy().to_string(),
         registration_snippet,
     })
@@ -678,6 +720,17 @@ pub async fn validate_block(
         });
     }
 
+    if !is_valid_mod_id(&block.mod_id) {
+        issues.push(BlockValidationIssue {
+            severity: "error".to_string(),
+            message: format!(
+                "Mod ID \"{}\" is invalid. Must start with a lowercase letter and use only a-z, 0-9, and underscores, or textures and models won't be found.",
+                block.mod_id
+            ),
+            suggestion: Some(format!("Change the mod ID to \"{}\"", sanitize_mod_id(&block.mod_id))),
+        });
+    }
+
     // Check vanilla conflict
     if VANILLA_BLOCKS.binary_search(&block.name.as_str()).is_ok() {
         issues.push(BlockValidationIssue {
@@ -790,6 +843,38 @@ pub async fn validate_block(
         }
     }
 
+    // Check the generated item model still points at an existing block model
+    let item_model_path = project
+        .join("src/main/resources/assets")
+        .join(&block.mod_id)
+        .join("models/item")
+        .join(format!("{}.json", block.name));
+    if let Ok(item_content) = fs::read_to_string(&item_model_path) {
+        let parent = serde_json::from_str::<serde_json::Value>(&item_content)
+            .ok()
+            .and_then(|v| v.get("parent").and_then(|p| p.as_str()).map(String::from));
+        if let Some(parent) = parent {
+            let (namespace, model) = parent.split_once(':').unwrap_or(("minecraft", &parent));
+            if namespace != "minecraft" && !model.starts_with("builtin/") {
+                let parent_path = project
+                    .join("src/main/resources/assets")
+                    .join(namespace)
+                    .join("models")
+                    .join(format!("{}.json", model));
+                if !parent_path.exists() {
+                    issues.push(BlockValidationIssue {
+                        severity: "error".to_string(),
+                        message: format!(
+                            "Item model references parent \"{}\", which does not exist. The item will render as a missing model.",
+                            parent
+                        ),
+                        suggestion: Some("Restore the block model or regenerate the block code.".to_string()),
+                    });
+                }
+            }
+        }
+    }
+
     // Check property ranges
     if block.properties.light_level > 15 {
         issues.push(BlockValidationIssue {
@@ -1,7 +1,21 @@
+use crate::state::{AppState, GradleDependencyCache, GradleTaskCache};
+use alloy_common::gradle::SHELL_METACHARACTERS;
 use serde::Serialize;
-use std::io::BufRead;
-use std::process::{Command, Stdio};
-use tauri::{AppHandle, Emitter};
+use std::collections::VecDeque;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tauri::{AppHandle, Emitter, State};
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+/// Most recent output lines kept in memory per build; older lines are still
+/// streamed to the UI but dropped from the returned output.
+const MAX_BUILD_OUTPUT_LINES: usize = 5000;
+
+/// Tags each build's `build_cancel` entry so a finished build only clears its own.
+static NEXT_BUILD_ID: AtomicU64 = AtomicU64::new(0);
 
 #[derive(Debug, Clone, Serialize)]
 pub struct BuildError {
@@ -19,6 +33,21 @@ pub struct BuildResult {
     pub output: String,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct BuildFinished {
+    pub success: bool,
+    pub exit_code: i32,
+    pub cancelled: bool,
+}
+
+/// Output of a finished Gradle invocation.
+pub struct GradleRun {
+    pub success: bool,
+    pub exit_code: i32,
+    pub cancelled: bool,
+    pub output: String,
+}
+
 /// Determine the Gradle wrapper path for the platform
 fn gradle_cmd(project_path: &str) -> (String, Vec<String>) {
     let wrapper = if cfg!(target_os = "windows") {
@@ -40,18 +69,32 @@ fn gradle_cmd(project_path: &str) -> (String, Vec<String>) {
     }
 }
 
+/// Upper bound on diagnostics kept from a single build.
+const MAX_BUILD_ERRORS: usize = 300;
+
 /// Parse Java compiler errors from Gradle output
-fn parse_build_errors(output: &str) -> Vec<BuildError> {
+pub(crate) fn parse_build_errors(output: &str) -> Vec<BuildError> {
     let mut errors = Vec::new();
+    let lines: Vec<&str> = output.lines().collect();
 
-    for line in output.lines() {
-        // Java compiler error format: /path/File.java:42: error: message
-        if let Some(captures) = parse_java_error(line) {
-            errors.push(captures);
+    for (i, line) in lines.iter().enumerate() {
+        if errors.len() >= MAX_BUILD_ERRORS {
+            break;
         }
-        // Gradle failure format
-        if line.contains("FAILURE:") || line.contains("BUILD FAILED") {
-            // Already captured via exit code
+
+        // Java compiler error format: /path/File.java:42: error: message
+        if let Some(mut error) = parse_java_error(line) {
+            // javac echoes the source line, then a line with a caret under the
+            // offending column
+            if let Some(caret_line) = lines
+                .iter()
+                .skip(i + 1)
+                .take(3)
+                .find(|l| l.trim() == "^")
+            {
+                error.column = caret_line.find('^').map(|c| c + 1).unwrap_or(0);
+            }
+            errors.push(error);
         }
     }
 
@@ -93,63 +136,208 @@ fn parse_java_error(line: &str) -> Option<BuildError> {
     })
 }
 
-#[tauri::command]
-pub async fn run_gradle_task(
-    project_path: String,
-    task: String,
-    app: AppHandle,
-) -> Result<BuildResult, String> {
-    let (cmd, mut base_args) = gradle_cmd(&project_path);
-    base_args.push(task.clone());
-    base_args.push("--console=plain".to_string());
+/// Build the Gradle argument list: the task first, then `args` in order, so
+/// `build` with `["--rerun-tasks"]` runs `gradlew build --rerun-tasks`.
+pub(crate) fn gradle_task_args(
+    task: &str,
+    args: &[String],
+    offline: bool,
+) -> Result<Vec<String>, String> {
+    if let Some(bad) = std::iter::once(task)
+        .chain(args.iter().map(String::as_str))
+        .find(|a| a.contains(SHELL_METACHARACTERS))
+    {
+        return Err(format!("Gradle argument contains shell metacharacters: {}", bad));
+    }
+
+    let mut full = vec![task.to_string()];
+    full.extend(args.iter().cloned());
+    if offline && !args.iter().any(|a| a == "--offline") {
+        full.push("--offline".to_string());
+    }
+    Ok(full)
+}
+
+/// Kill the wrapper and everything it started. On Unix the build runs in its
+/// own process group; the Gradle daemon cancels its build once the client
+/// goes away.
+fn kill_process_tree(child: &mut tokio::process::Child) {
+    if let Some(pid) = child.id() {
+        let _ = if cfg!(target_os = "windows") {
+            std::process::Command::new("taskkill")
+                .args(["/T", "/F", "/PID", &pid.to_string()])
+                .output()
+        } else {
+            std::process::Command::new("kill")
+                .args(["-TERM", &format!("-{}", pid)])
+                .output()
+        };
+    }
+    let _ = child.start_kill();
+}
+
+/// Stop the running build, if any. Returns whether a build was running.
+pub(crate) fn cancel_build(state: &AppState) -> bool {
+    let sender = state.build_cancel.lock().ok().and_then(|mut slot| slot.take());
+    match sender {
+        Some((_, tx)) => tx.send(()).is_ok(),
+        None => false,
+    }
+}
 
-    let mut child = Command::new(&cmd)
-        .args(&base_args)
-        .current_dir(&project_path)
+/// Run a Gradle task, emitting each stdout/stderr line as a `build:output`
+/// event while it runs and a `build:finished` event with the exit code at
+/// the end. `task_args` come from [`gradle_task_args`]. The build can be
+/// stopped through [`cancel_build`].
+pub async fn stream_gradle(
+    project_path: &str,
+    task_args: &[String],
+    app: Option<&AppHandle>,
+    state: &AppState,
+) -> Result<GradleRun, String> {
+    let (cmd, mut args) = gradle_cmd(project_path);
+    args.extend(task_args.iter().cloned());
+    args.push("--console=plain".to_string());
+
+    let mut command = tokio::process::Command::new(&cmd);
+    command
+        .args(&args)
+        .current_dir(project_path)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    #[cfg(unix)]
+    command.process_group(0);
+
+    let mut child = command
         .spawn()
         .map_err(|e| format!("Failed to run gradle: {}", e))?;
 
+    let build_id = NEXT_BUILD_ID.fetch_add(1, Ordering::Relaxed);
+    let (cancel_tx, mut cancel_rx) = tokio::sync::oneshot::channel::<()>();
+    if let Ok(mut slot) = state.build_cancel.lock() {
+        *slot = Some((build_id, cancel_tx));
+    }
+
     let stdout = child.stdout.take().ok_or("No stdout")?;
     let stderr = child.stderr.take().ok_or("No stderr")?;
 
-    let app_out = app.clone();
-    let stdout_handle = std::thread::spawn(move || {
-        let reader = std::io::BufReader::new(stdout);
-        let mut output = String::new();
-        for line in reader.lines().flatten() {
-            let _ = app_out.emit("build:output", &line);
-            output.push_str(&line);
-            output.push('\n');
+    // Funnel both pipes into one channel so lines keep their arrival order
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+    let tx_err = tx.clone();
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(stdout).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if tx.send(line).is_err() {
+                break;
+            }
         }
-        output
     });
-
-    let app_err = app.clone();
-    let stderr_handle = std::thread::spawn(move || {
-        let reader = std::io::BufReader::new(stderr);
-        let mut output = String::new();
-        for line in reader.lines().flatten() {
-            let _ = app_err.emit("build:output", &line);
-            output.push_str(&line);
-            output.push('\n');
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(stderr).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if tx_err.send(line).is_err() {
+                break;
+            }
         }
-        output
     });
 
-    let status = child.wait().map_err(|e| e.to_string())?;
-    let stdout_output = stdout_handle.join().map_err(|_| "Thread error")?;
-    let stderr_output = stderr_handle.join().map_err(|_| "Thread error")?;
+    let mut buffer: VecDeque<String> = VecDeque::new();
+    let mut cancel_pending = true;
+    let mut cancelled = false;
+    loop {
+        tokio::select! {
+            line = rx.recv() => {
+                let Some(line) = line else { break };
+                if let Some(app) = app {
+                    let _ = app.emit("build:output", &line);
+                }
+                if buffer.len() == MAX_BUILD_OUTPUT_LINES {
+                    buffer.pop_front();
+                }
+                buffer.push_back(line);
+            }
+            // A dropped sender means a newer build replaced this one's slot
+            signal = &mut cancel_rx, if cancel_pending => {
+                cancel_pending = false;
+                if signal.is_ok() {
+                    kill_process_tree(&mut child);
+                    cancelled = true;
+                }
+            }
+        }
+    }
 
-    let full_output = format!("{}{}", stdout_output, stderr_output);
-    let errors = parse_build_errors(&full_output);
-    let success = status.success();
+    let status = child.wait().await.map_err(|e| e.to_string())?;
+    // The pipes can close before a replacement is noticed, so check the
+    // slot still holds this build's sender before clearing it
+    if let Ok(mut slot) = state.build_cancel.lock() {
+        if slot.as_ref().is_some_and(|(id, _)| *id == build_id) {
+            *slot = None;
+        }
+    }
+    let exit_code = status.code().unwrap_or(-1);
+    let success = status.success() && !cancelled;
+
+    if let Some(app) = app {
+        let finished = BuildFinished {
+            success,
+            exit_code,
+            cancelled,
+        };
+        let _ = app.emit("build:finished", &finished);
+    }
 
-    let result = BuildResult {
+    let mut output = String::new();
+    for line in buffer {
+        output.push_str(&line);
+        output.push('\n');
+    }
+
+    Ok(GradleRun {
         success,
-        errors: errors.clone(),
-        output: full_output,
+        exit_code,
+        cancelled,
+        output,
+    })
+}
+
+/// Replace the cached diagnostics that `build_get_errors` reports and, unless
+/// the run was cancelled, remember whether it succeeded. The diagnostics go
+/// to the model, so they are parsed from redacted output.
+pub(crate) fn store_build_errors(state: &AppState, run: &GradleRun) {
+    let errors = parse_build_errors(&state.redactor().redact(&run.output));
+    if let Ok(mut cached) = state.build_errors.lock() {
+        *cached = errors
+            .iter()
+            .filter_map(|e| serde_json::to_value(e).ok())
+            .collect();
+    }
+    if !run.cancelled {
+        if let Ok(mut last) = state.last_build_success.lock() {
+            *last = Some(run.success);
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn run_gradle_task(
+    project_path: String,
+    task: String,
+    args: Option<Vec<String>>,
+    offline: Option<bool>,
+    app: AppHandle,
+    state: State<'_, Arc<AppState>>,
+) -> Result<BuildResult, String> {
+    let task_args = gradle_task_args(&task, &args.unwrap_or_default(), offline.unwrap_or(false))?;
+    let run = stream_gradle(&project_path, &task_args, Some(&app), &state).await?;
+    let errors = parse_build_errors(&run.output);
+    store_build_errors(&state, &run);
+
+    let result = BuildResult {
+        success: run.success,
+        errors,
+        output: run.output,
     };
 
     let _ = app.emit("build:done", &result);
@@ -157,6 +345,11 @@ pub async fn run_gradle_task(
     Ok(result)
 }
 
+#[tauri::command]
+pub async fn cancel_gradle_task(state: State<'_, Arc<AppState>>) -> Result<bool, String> {
+    Ok(cancel_build(&state))
+}
+
 /// Validate mod environment constraints by scanning source files
 #[tauri::command]
 pub async fn validate_environment(project_path: String) -> Result<Vec<BuildError>, String> {
@@ -374,28 +567,300 @@ pub async fn validate_environment(project_path: String) -> Result<Vec<BuildError
     Ok(violations)
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct GradleTaskList {
+    pub tasks: Vec<String>,
+    /// Whether the list came from the cache rather than a fresh `gradlew tasks`
+    pub cached: bool,
+}
+
+/// Files whose changes can add or remove tasks; a cached task list is only
+/// reused while none of them has changed.
+const GRADLE_BUILD_FILES: &[&str] = &[
+    "build.gradle",
+    "build.gradle.kts",
+    "settings.gradle",
+    "settings.gradle.kts",
+    "gradle.properties",
+];
+
+/// Modification times of [`GRADLE_BUILD_FILES`], `None` for missing files.
+fn build_file_stamps(project_path: &str) -> Vec<Option<SystemTime>> {
+    GRADLE_BUILD_FILES
+        .iter()
+        .map(|name| {
+            std::fs::metadata(Path::new(project_path).join(name))
+                .and_then(|m| m.modified())
+                .ok()
+        })
+        .collect()
+}
+
+/// Parse task names from `gradlew tasks --all` output. Each group is a
+/// title, a dashed underline, then one `name - description` line per task.
+fn parse_gradle_tasks(output: &str) -> Vec<String> {
+    let mut tasks = Vec::new();
+    let mut in_group = false;
+    for line in output.lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            in_group = false;
+        } else if line.chars().all(|c| c == '-') {
+            in_group = true;
+        } else if in_group && !line.starts_with("Pattern:") {
+            let name = line.split(" - ").next().unwrap_or(line);
+            if !name.is_empty() && !name.contains(char::is_whitespace) {
+                tasks.push(name.to_string());
+            }
+        }
+    }
+    tasks.sort();
+    tasks.dedup();
+    tasks
+}
+
+/// Run a short Gradle report task with `--quiet` and return its stdout.
+async fn gradle_report(project_path: &str, report_args: &[&str]) -> Result<String, String> {
+    let (cmd, mut args) = gradle_cmd(project_path);
+    args.extend(report_args.iter().map(|a| a.to_string()));
+    args.extend(["--quiet", "--console=plain"].map(String::from));
+    let output = tokio::process::Command::new(&cmd)
+        .args(&args)
+        .current_dir(project_path)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run gradle: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("gradlew {} failed: {}", report_args.join(" "), stderr.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// List the project's Gradle tasks. `gradlew tasks` takes seconds, so the
+/// parsed list is cached per project until a build file changes or `refresh`
+/// is set.
 #[tauri::command]
-pub async fn list_gradle_tasks(project_path: String) -> Result<Vec<String>, String> {
-    // Return common Alloy mod tasks
+pub async fn list_gradle_tasks(
+    project_path: String,
+    refresh: Option<bool>,
+    state: State<'_, Arc<AppState>>,
+) -> Result<GradleTaskList, String> {
     let wrapper = if cfg!(target_os = "windows") {
         format!("{}\\gradlew.bat", project_path)
     } else {
         format!("{}/gradlew", project_path)
     };
+    if !Path::new(&wrapper).exists() {
+        return Err("No Gradle wrapper found in project".to_string());
+    }
 
-    let has_wrapper = std::path::Path::new(&wrapper).exists();
-
-    if has_wrapper {
-        Ok(vec![
-            "build".to_string(),
-            "clean".to_string(),
-            "setupWorkspace".to_string(),
-            "launchClient".to_string(),
-            "launchServer".to_string(),
-            "jar".to_string(),
-            "test".to_string(),
-        ])
-    } else {
-        Err("No Gradle wrapper found in project".to_string())
+    let stamps = build_file_stamps(&project_path);
+    if !refresh.unwrap_or(false) {
+        if let Ok(cache) = state.gradle_tasks.lock() {
+            if let Some(entry) = cache.get(&project_path) {
+                if entry.build_file_stamps == stamps {
+                    return Ok(GradleTaskList {
+                        tasks: entry.tasks.clone(),
+                        cached: true,
+                    });
+                }
+            }
+        }
+    }
+
+    let output = gradle_report(&project_path, &["tasks", "--all"]).await?;
+    let tasks = parse_gradle_tasks(&output);
+    if let Ok(mut cache) = state.gradle_tasks.lock() {
+        cache.insert(
+            project_path,
+            GradleTaskCache {
+                tasks: tasks.clone(),
+                build_file_stamps: stamps,
+            },
+        );
+    }
+
+    Ok(GradleTaskList { tasks, cached: false })
+}
+
+/// One node of a resolved dependency tree. Project dependencies have an
+/// empty `group` and the project path (`:core`) as `name`.
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyNode {
+    pub group: String,
+    pub name: String,
+    /// Version Gradle resolved, after any conflict resolution
+    pub version: String,
+    /// Version that was asked for, when Gradle resolved a different one
+    pub requested: Option<String>,
+    /// Gradle already listed this node's children elsewhere in the tree (`(*)`)
+    pub omitted: bool,
+    pub children: Vec<DependencyNode>,
+}
+
+/// A dependency Gradle resolved to a version other than the one requested.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DependencyConflict {
+    pub group: String,
+    pub name: String,
+    pub requested: String,
+    pub resolved: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyReport {
+    pub configuration: String,
+    pub dependencies: Vec<DependencyNode>,
+    pub conflicts: Vec<DependencyConflict>,
+    /// Whether the report came from the cache rather than a fresh Gradle run
+    pub cached: bool,
+}
+
+/// Parse one tree line such as `|    +--- org.ow2.asm:asm:9.5 -> 9.6 (*)`
+/// into its depth and node.
+fn parse_dependency_line(line: &str) -> Option<(usize, DependencyNode)> {
+    let marker = line.find("+--- ").or_else(|| line.find("\\--- "))?;
+    let depth = marker / 5;
+    let mut rest = line[marker + 5..].trim();
+
+    let mut omitted = false;
+    // Trailing markers: (*) omitted, (c) constraint, (n) not resolved
+    while let Some(stripped) = ["(*)", "(c)", "(n)"]
+        .iter()
+        .find_map(|m| rest.strip_suffix(m))
+    {
+        omitted |= rest.ends_with("(*)");
+        rest = stripped.trim_end();
+    }
+
+    let (coordinate, resolved) = match rest.split_once(" -> ") {
+        Some((c, r)) => (c.trim(), Some(r.trim())),
+        None => (rest, None),
+    };
+
+    let node = |group: &str, name: &str, version: &str, requested: Option<String>| DependencyNode {
+        group: group.to_string(),
+        name: name.to_string(),
+        version: version.to_string(),
+        requested,
+        omitted,
+        children: Vec::new(),
+    };
+
+    if let Some(project) = coordinate.strip_prefix("project ") {
+        return Some((depth, node("", project, "", None)));
     }
+
+    let mut parts = coordinate.splitn(3, ':');
+    let group = parts.next()?;
+    let name = parts.next()?;
+    let declared = parts.next();
+    let node = match (declared, resolved) {
+        (Some(declared), Some(resolved)) => {
+            node(group, name, resolved, Some(declared.to_string()))
+        }
+        (None, Some(resolved)) => node(group, name, resolved, None),
+        (Some(declared), None) => node(group, name, declared, None),
+        (None, None) => node(group, name, "", None),
+    };
+    Some((depth, node))
+}
+
+/// Build the dependency tree from `gradlew dependencies` output, collecting
+/// every `requested -> resolved` upgrade as a conflict.
+fn parse_dependency_tree(output: &str) -> (Vec<DependencyNode>, Vec<DependencyConflict>) {
+    let mut roots = Vec::new();
+    let mut conflicts = Vec::new();
+    // Ancestors of the line being parsed, outermost first
+    let mut stack: Vec<DependencyNode> = Vec::new();
+
+    fn attach(stack: &mut Vec<DependencyNode>, roots: &mut Vec<DependencyNode>) {
+        if let Some(node) = stack.pop() {
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(node),
+                None => roots.push(node),
+            }
+        }
+    }
+
+    for line in output.lines() {
+        let Some((depth, node)) = parse_dependency_line(line) else {
+            continue;
+        };
+        if let Some(requested) = &node.requested {
+            let conflict = DependencyConflict {
+                group: node.group.clone(),
+                name: node.name.clone(),
+                requested: requested.clone(),
+                resolved: node.version.clone(),
+            };
+            if !conflicts.contains(&conflict) {
+                conflicts.push(conflict);
+            }
+        }
+        while stack.len() > depth {
+            attach(&mut stack, &mut roots);
+        }
+        stack.push(node);
+    }
+    while !stack.is_empty() {
+        attach(&mut stack, &mut roots);
+    }
+
+    (roots, conflicts)
+}
+
+/// Resolve a configuration's dependency tree (default `runtimeClasspath`).
+/// Resolution is slow, so reports are cached like [`list_gradle_tasks`].
+#[tauri::command]
+pub async fn build_list_dependencies(
+    project_path: String,
+    configuration: Option<String>,
+    refresh: Option<bool>,
+    state: State<'_, Arc<AppState>>,
+) -> Result<DependencyReport, String> {
+    let configuration = configuration.unwrap_or_else(|| "runtimeClasspath".to_string());
+    if configuration.contains(SHELL_METACHARACTERS) {
+        return Err(format!("Gradle argument contains shell metacharacters: {}", configuration));
+    }
+
+    let key = (project_path.clone(), configuration.clone());
+    let stamps = build_file_stamps(&project_path);
+    if !refresh.unwrap_or(false) {
+        if let Ok(cache) = state.gradle_dependencies.lock() {
+            if let Some(entry) = cache.get(&key) {
+                if entry.build_file_stamps == stamps {
+                    return Ok(DependencyReport {
+                        configuration,
+                        dependencies: entry.dependencies.clone(),
+                        conflicts: entry.conflicts.clone(),
+                        cached: true,
+                    });
+                }
+            }
+        }
+    }
+
+    let output = gradle_report(&project_path, &["dependencies", "--configuration", &configuration]).await?;
+    let (dependencies, conflicts) = parse_dependency_tree(&output);
+    if let Ok(mut cache) = state.gradle_dependencies.lock() {
+        cache.insert(
+            key,
+            GradleDependencyCache {
+                dependencies: dependencies.clone(),
+                conflicts: conflicts.clone(),
+                build_file_stamps: stamps,
+            },
+        );
+    }
+
+    Ok(DependencyReport {
+        configuration,
+        dependencies,
+        conflicts,
+        cached: false,
+    })
 }
@@ -1,3 +1,4 @@
+use alloy_common::git::{ahead_behind, parse_blame_porcelain, porcelain_path, BlameLine};
 use serde::Serialize;
 use std::path::{Path, PathBuf};
 
@@ -116,6 +117,12 @@ pub struct SearchResult {
     pub name: String,
     pub line_number: usize,
     pub line_content: String,
+    /// Char offset of the first match in the line
+    pub match_start: usize,
+    /// Char offset just past the first match
+    pub match_end: usize,
+    /// How many matches the line has
+    pub match_count: usize,
 }
 
 #[tauri::command]
@@ -143,9 +150,9 @@ pub async fn search_files(query: String, search_path: String) -> Result<Vec<Sear
                 continue;
             }
 
-            if path.is_dir() {
+            if is_walkable_dir(&entry) {
                 walk_and_search(&path, query, results)?;
-            } else {
+            } else if path.is_file() {
                 // Only search text files by extension
                 let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
                 let searchable = matches!(
@@ -160,12 +167,17 @@ pub async fn search_files(query: String, search_path: String) -> Result<Vec<Sear
                     let reader = std::io::BufReader::new(file);
                     for (i, line) in reader.lines().enumerate() {
                         if let Ok(line) = line {
-                            if line.to_lowercase().contains(query) {
+                            let lower = line.to_lowercase();
+                            if let Some(idx) = lower.find(query) {
+                                let match_start = lower[..idx].chars().count();
                                 results.push(SearchResult {
                                     path: path.to_string_lossy().to_string(),
                                     name: name.clone(),
                                     line_number: i + 1,
                                     line_content: line.chars().take(200).collect(),
+                                    match_start,
+                                    match_end: match_start + query.chars().count(),
+                                    match_count: lower.matches(query).count(),
                                 });
                                 if results.len() >= 500 { return Ok(()); }
                             }
@@ -185,6 +197,77 @@ pub async fn search_files(query: String, search_path: String) -> Result<Vec<Sear
 pub struct ReplaceResult {
     pub files_changed: usize,
     pub replacements: usize,
+    /// Match count for each affected file
+    pub file_counts: Vec<ReplaceFileCount>,
+    /// Sample of lines that change; only filled in for a dry run
+    pub previews: Vec<ReplacePreview>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReplaceFileCount {
+    pub path: String,
+    pub replacements: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReplacePreview {
+    pub path: String,
+    /// 1-based line number
+    pub line: usize,
+    pub before: String,
+    pub after: String,
+}
+
+/// Most preview lines a dry run returns in total, and per file.
+const MAX_REPLACE_PREVIEWS: usize = 200;
+const MAX_REPLACE_PREVIEWS_PER_FILE: usize = 10;
+
+/// Text files the search and replace commands look inside.
+fn is_searchable_file(path: &Path) -> bool {
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    matches!(
+        ext,
+        "java" | "json" | "toml" | "xml" | "yml" | "yaml" | "properties"
+        | "md" | "txt" | "gradle" | "kts" | "cfg" | "conf" | "ts" | "tsx"
+        | "js" | "jsx" | "css" | "html" | "rs" | "py" | "sh" | "bat"
+    )
+}
+
+/// Names the project walkers never descend into or list: hidden entries and
+/// build output.
+fn is_skipped_name(name: &str) -> bool {
+    name.starts_with('.')
+        || name == "node_modules"
+        || name == "target"
+        || name == "build"
+        || name == "dist"
+}
+
+/// Whether a walker should descend into `entry`. Symlinked directories are
+/// never followed, as with walkdir's default, so a link cycle can't make a
+/// walk recurse forever.
+pub(crate) fn is_walkable_dir(entry: &std::fs::DirEntry) -> bool {
+    entry.file_type().is_ok_and(|t| t.is_dir())
+}
+
+/// Every file under `root` the project walkers should see, sorted. Inside a
+/// git repo this honours .gitignore on top of the usual skips; elsewhere, or
+/// with `include_ignored`, only the usual skips apply. Symlinks aren't
+/// followed, so a link cycle can't make the walk recurse forever.
+pub(crate) fn project_files(root: &Path, include_ignored: bool) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = ignore::WalkBuilder::new(root)
+        .follow_links(false)
+        .standard_filters(!include_ignored)
+        // Hidden entries are left to is_skipped_name, which applies either way
+        .hidden(false)
+        .filter_entry(|e| e.depth() == 0 || !is_skipped_name(&e.file_name().to_string_lossy()))
+        .build()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_some_and(|t| t.is_file()))
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+    files
 }
 
 #[tauri::command]
@@ -194,133 +277,105 @@ pub async fn replace_in_files(
     search_path: String,
     case_sensitive: bool,
     regex_mode: bool,
+    dry_run: Option<bool>,
+    include_ignored: Option<bool>,
 ) -> Result<ReplaceResult, String> {
-    let mut files_changed = 0usize;
-    let mut replacements = 0usize;
-
-    let compiled_regex = if regex_mode {
-        match regex::RegexBuilder::new(&query)
-            .case_insensitive(!case_sensitive)
-            .build()
-        {
-            Ok(r) => Some(r),
-            Err(e) => return Err(format!("Invalid regex: {}", e)),
+    let dry_run = dry_run.unwrap_or(false);
+
+    // Plain-text queries are escaped and their replacement inserted verbatim,
+    // so one regex handles every mode
+    let pattern = if regex_mode { query.clone() } else { regex::escape(&query) };
+    let re = regex::RegexBuilder::new(&pattern)
+        .case_insensitive(!case_sensitive)
+        .build()
+        .map_err(|e| format!("Invalid regex: {}", e))?;
+    let replace = |text: &str| -> String {
+        if regex_mode {
+            re.replace_all(text, replacement.as_str()).into_owned()
+        } else {
+            re.replace_all(text, regex::NoExpand(&replacement)).into_owned()
         }
-    } else {
-        None
     };
 
-    fn walk_replace(
-        dir: &Path,
-        query: &str,
-        replacement: &str,
-        case_sensitive: bool,
-        compiled_regex: &Option<regex::Regex>,
-        files_changed: &mut usize,
-        replacements: &mut usize,
-    ) -> Result<(), String> {
-        let read_dir = match std::fs::read_dir(dir) {
-            Ok(rd) => rd,
-            Err(_) => return Ok(()),
-        };
+    let files = project_files(Path::new(&search_path), include_ignored.unwrap_or(false));
 
-        for entry in read_dir.flatten() {
-            let path = entry.path();
-            let name = entry.file_name().to_string_lossy().to_string();
+    let mut result = ReplaceResult {
+        files_changed: 0,
+        replacements: 0,
+        file_counts: Vec::new(),
+        previews: Vec::new(),
+    };
 
-            if name.starts_with('.')
-                || name == "node_modules"
-                || name == "target"
-                || name == "build"
-                || name == "dist"
-            {
-                continue;
-            }
+    for path in files.into_iter().filter(|p| is_searchable_file(p)) {
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let count = re.find_iter(&content).count();
+        if count == 0 {
+            continue;
+        }
+        let new_content = replace(&content);
+        if new_content == content {
+            continue;
+        }
 
-            if path.is_dir() {
-                walk_replace(
-                    &path,
-                    query,
-                    replacement,
-                    case_sensitive,
-                    compiled_regex,
-                    files_changed,
-                    replacements,
-                )?;
-            } else {
-                let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-                let searchable = matches!(
-                    ext,
-                    "java" | "json" | "toml" | "xml" | "yml" | "yaml" | "properties"
-                    | "md" | "txt" | "gradle" | "kts" | "cfg" | "conf" | "ts" | "tsx"
-                    | "js" | "jsx" | "css" | "html" | "rs" | "py" | "sh" | "bat"
-                );
-                if !searchable {
-                    continue;
+        if dry_run {
+            let per_file = result.previews.len() + MAX_REPLACE_PREVIEWS_PER_FILE;
+            for (i, line) in content.lines().enumerate() {
+                if result.previews.len() >= MAX_REPLACE_PREVIEWS.min(per_file) {
+                    break;
                 }
-
-                if let Ok(content) = std::fs::read_to_string(&path) {
-                    let new_content = if let Some(re) = compiled_regex {
-                        let result = re.replace_all(&content, replacement).to_string();
-                        if result == content {
-                            continue;
-                        }
-                        let count = re.find_iter(&content).count();
-                        *replacements += count;
-                        result
-                    } else if case_sensitive {
-                        let count = content.matches(query).count();
-                        if count == 0 {
-                            continue;
-                        }
-                        *replacements += count;
-                        content.replace(query, replacement)
-                    } else {
-                        let lower_content = content.to_lowercase();
-                        let lower_query = query.to_lowercase();
-                        let count = lower_content.matches(&lower_query).count();
-                        if count == 0 {
-                            continue;
-                        }
-                        // Case-insensitive replace
-                        *replacements += count;
-                        let mut result = String::with_capacity(content.len());
-                        let mut last = 0;
-                        let lc = content.to_lowercase();
-                        let lq = query.to_lowercase();
-                        while let Some(pos) = lc[last..].find(&lq) {
-                            result.push_str(&content[last..last + pos]);
-                            result.push_str(replacement);
-                            last += pos + query.len();
-                        }
-                        result.push_str(&content[last..]);
-                        result
-                    };
-
-                    if let Err(e) = std::fs::write(&path, &new_content) {
-                        return Err(format!("Failed to write {}: {}", path.display(), e));
-                    }
-                    *files_changed += 1;
+                let after = replace(line);
+                if after != line {
+                    result.previews.push(ReplacePreview {
+                        path: path.to_string_lossy().to_string(),
+                        line: i + 1,
+                        before: line.to_string(),
+                        after,
+                    });
                 }
             }
+        } else if let Err(e) = std::fs::write(&path, &new_content) {
+            return Err(format!("Failed to write {}: {}", path.display(), e));
         }
-        Ok(())
+
+        result.files_changed += 1;
+        result.replacements += count;
+        result.file_counts.push(ReplaceFileCount {
+            path: path.to_string_lossy().to_string(),
+            replacements: count,
+        });
     }
 
-    walk_replace(
-        Path::new(&search_path),
-        &query,
-        &replacement,
-        case_sensitive,
-        &compiled_regex,
-        &mut files_changed,
-        &mut replacements,
-    )?;
+    Ok(result)
+}
 
-    Ok(ReplaceResult {
-        files_changed,
-        replacements,
-    })
+/// Build the regex `search_files_advanced` matches lines with. Plain-text
+/// queries are escaped first. With `whole_word` a regex query is wrapped in
+/// word boundaries; a plain-text query only gets a boundary on edges that are
+/// word characters, so `id` skips `width` while `.id` still matches `foo.id`.
+fn build_search_regex(
+    query: &str,
+    case_sensitive: bool,
+    regex_mode: bool,
+    whole_word: bool,
+) -> Result<regex::Regex, String> {
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    let pattern = match (regex_mode, whole_word) {
+        (true, true) => format!(r"\b(?:{})\b", query),
+        (true, false) => query.to_string(),
+        (false, true) => format!(
+            "{}{}{}",
+            if is_word(query.chars().next()) { r"\b" } else { "" },
+            regex::escape(query),
+            if is_word(query.chars().last()) { r"\b" } else { "" },
+        ),
+        (false, false) => regex::escape(query),
+    };
+    regex::RegexBuilder::new(&pattern)
+        .case_insensitive(!case_sensitive)
+        .build()
+        .map_err(|e| format!("Invalid regex: {}", e))
 }
 
 #[tauri::command]
@@ -329,100 +384,49 @@ pub async fn search_files_advanced(
     search_path: String,
     case_sensitive: bool,
     regex_mode: bool,
+    whole_word: Option<bool>,
+    include_ignored: Option<bool>,
 ) -> Result<Vec<SearchResult>, String> {
     use std::io::BufRead;
     let mut results = Vec::new();
 
-    let compiled_regex = if regex_mode {
-        match regex::RegexBuilder::new(&query)
-            .case_insensitive(!case_sensitive)
-            .build()
-        {
-            Ok(r) => Some(r),
-            Err(e) => return Err(format!("Invalid regex: {}", e)),
-        }
-    } else {
-        None
-    };
+    let re = build_search_regex(&query, case_sensitive, regex_mode, whole_word.unwrap_or(false))?;
 
-    fn walk_search(
-        dir: &Path,
-        query: &str,
-        case_sensitive: bool,
-        compiled_regex: &Option<regex::Regex>,
-        results: &mut Vec<SearchResult>,
-    ) -> Result<(), String> {
-        let read_dir = match std::fs::read_dir(dir) {
-            Ok(rd) => rd,
-            Err(_) => return Ok(()),
+    let files = project_files(Path::new(&search_path), include_ignored.unwrap_or(false));
+    for path in files.into_iter().filter(|p| is_searchable_file(p)) {
+        let Ok(file) = std::fs::File::open(&path) else {
+            continue;
         };
-
-        for entry in read_dir.flatten() {
-            let path = entry.path();
-            let name = entry.file_name().to_string_lossy().to_string();
-
-            if name.starts_with('.')
-                || name == "node_modules"
-                || name == "target"
-                || name == "build"
-                || name == "dist"
-            {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let reader = std::io::BufReader::new(file);
+        for (i, line) in reader.lines().enumerate() {
+            let Ok(line) = line else {
                 continue;
-            }
-
-            if path.is_dir() {
-                walk_search(&path, query, case_sensitive, compiled_regex, results)?;
-            } else {
-                let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-                let searchable = matches!(
-                    ext,
-                    "java" | "json" | "toml" | "xml" | "yml" | "yaml" | "properties"
-                    | "md" | "txt" | "gradle" | "kts" | "cfg" | "conf" | "ts" | "tsx"
-                    | "js" | "jsx" | "css" | "html" | "rs" | "py" | "sh" | "bat"
-                );
-                if !searchable {
-                    continue;
-                }
+            };
+            let mut matches = re.find_iter(&line);
+            let Some(first) = matches.next() else {
+                continue;
+            };
 
-                if let Ok(file) = std::fs::File::open(&path) {
-                    let reader = std::io::BufReader::new(file);
-                    for (i, line) in reader.lines().enumerate() {
-                        if let Ok(line) = line {
-                            let matches = if let Some(re) = compiled_regex {
-                                re.is_match(&line)
-                            } else if case_sensitive {
-                                line.contains(query)
-                            } else {
-                                line.to_lowercase().contains(&query.to_lowercase())
-                            };
-
-                            if matches {
-                                results.push(SearchResult {
-                                    path: path.to_string_lossy().to_string(),
-                                    name: name.clone(),
-                                    line_number: i + 1,
-                                    line_content: line.chars().take(200).collect(),
-                                });
-                                if results.len() >= 500 {
-                                    return Ok(());
-                                }
-                            }
-                        }
-                    }
-                }
+            let match_start = line[..first.start()].chars().count();
+            results.push(SearchResult {
+                path: path.to_string_lossy().to_string(),
+                name: name.clone(),
+                line_number: i + 1,
+                line_content: line.chars().take(200).collect(),
+                match_start,
+                match_end: match_start + first.as_str().chars().count(),
+                match_count: 1 + matches.count(),
+            });
+            if results.len() >= 500 {
+                return Ok(results);
             }
         }
-        Ok(())
     }
 
-    walk_search(
-        Path::new(&search_path),
-        &query,
-        case_sensitive,
-        &compiled_regex,
-        &mut results,
-    )?;
-
     Ok(results)
 }
 
@@ -433,51 +437,101 @@ pub struct QuickOpenEntry {
     pub relative_path: String,
 }
 
+/// Most files a project walk collects, so huge trees stay responsive.
+const MAX_PROJECT_FILES: usize = 5000;
+
+/// Quick-open entries for the files under `root`, capped at `MAX_PROJECT_FILES`.
+fn quick_open_entries(root: &Path, include_ignored: bool) -> Vec<QuickOpenEntry> {
+    project_files(root, include_ignored)
+        .into_iter()
+        .take(MAX_PROJECT_FILES)
+        .map(|path| QuickOpenEntry {
+            name: path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            relative_path: path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .to_string(),
+            path: path.to_string_lossy().to_string(),
+        })
+        .collect()
+}
+
 #[tauri::command]
-pub async fn list_all_files(root_path: String) -> Result<Vec<QuickOpenEntry>, String> {
+pub async fn list_all_files(
+    root_path: String,
+    include_ignored: Option<bool>,
+) -> Result<Vec<QuickOpenEntry>, String> {
     let root = PathBuf::from(&root_path);
-    let mut entries = Vec::new();
+    let mut entries = quick_open_entries(&root, include_ignored.unwrap_or(false));
+    entries.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    Ok(entries)
+}
 
-    fn walk(dir: &Path, root: &Path, entries: &mut Vec<QuickOpenEntry>) {
-        let read_dir = match std::fs::read_dir(dir) {
-            Ok(rd) => rd,
-            Err(_) => return,
-        };
-        for entry in read_dir.flatten() {
-            let name = entry.file_name().to_string_lossy().to_string();
-            if name.starts_with('.')
-                || name == "node_modules"
-                || name == "target"
-                || name == "build"
-                || name == "dist"
-                || name == ".gradle"
-            {
-                continue;
-            }
-            let path = entry.path();
-            if path.is_dir() {
-                walk(&path, root, entries);
-            } else {
-                let relative = path
-                    .strip_prefix(root)
-                    .unwrap_or(&path)
-                    .to_string_lossy()
-                    .to_string();
-                entries.push(QuickOpenEntry {
-                    name,
-                    path: path.to_string_lossy().to_string(),
-                    relative_path: relative,
-                });
-                if entries.len() >= 5000 {
-                    return;
-                }
+/// How well a file name matches a lowercase query; lower is better and
+/// `None` is no match. Exact names beat prefixes, prefixes beat substrings,
+/// and substrings beat fuzzy (in-order subsequence) matches.
+fn filename_match_rank(name: &str, query: &str) -> Option<(u8, usize)> {
+    let name = name.to_lowercase();
+    if name == query {
+        return Some((0, 0));
+    }
+    if name.starts_with(query) {
+        return Some((1, name.len()));
+    }
+    if let Some(pos) = name.find(query) {
+        return Some((2, pos));
+    }
+
+    // Fuzzy: every query char appears in order; fewer skipped chars ranks higher
+    let mut skipped = 0;
+    let mut chars = name.chars();
+    for q in query.chars() {
+        loop {
+            match chars.next() {
+                Some(c) if c == q => break,
+                Some(_) => skipped += 1,
+                None => return None,
             }
         }
     }
+    Some((3, skipped))
+}
 
-    walk(&root, &root, &mut entries);
-    entries.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-    Ok(entries)
+#[tauri::command]
+pub async fn search_filenames(
+    query: String,
+    search_path: String,
+    max_results: Option<usize>,
+    include_ignored: Option<bool>,
+) -> Result<Vec<QuickOpenEntry>, String> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let root = PathBuf::from(&search_path);
+    let entries = quick_open_entries(&root, include_ignored.unwrap_or(false));
+
+    let mut ranked: Vec<((u8, usize), QuickOpenEntry)> = entries
+        .into_iter()
+        .filter_map(|e| filename_match_rank(&e.name, &query).map(|rank| (rank, e)))
+        .collect();
+    ranked.sort_by(|(a_rank, a), (b_rank, b)| {
+        a_rank
+            .cmp(b_rank)
+            .then_with(|| a.relative_path.len().cmp(&b.relative_path.len()))
+            .then_with(|| a.relative_path.cmp(&b.relative_path))
+    });
+
+    Ok(ranked
+        .into_iter()
+        .take(max_results.unwrap_or(100))
+        .map(|(_, e)| e)
+        .collect())
 }
 
 #[tauri::command]
@@ -513,6 +567,8 @@ pub struct GitStatus {
     pub staged: Vec<GitFileStatus>,
     pub ahead: u32,
     pub behind: u32,
+    /// False when the branch has no tracking branch, so ahead/behind are meaningless.
+    pub has_upstream: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -539,20 +595,9 @@ pub async fn git_status(project_path: String) -> Result<GitStatus, String> {
     }
 
     // Get ahead/behind counts
-    let mut ahead: u32 = 0;
-    let mut behind: u32 = 0;
-    if let Ok(ab_output) = std::process::Command::new("git")
-        .args(["rev-list", "--left-right", "--count", &format!("HEAD...@{{u}}")])
-        .current_dir(&project_path)
-        .output()
-    {
-        let ab_text = String::from_utf8_lossy(&ab_output.stdout);
-        let parts: Vec<&str> = ab_text.trim().split('\t').collect();
-        if parts.len() == 2 {
-            ahead = parts[0].parse().unwrap_or(0);
-            behind = parts[1].parse().unwrap_or(0);
-        }
-    }
+    let upstream = ahead_behind(Path::new(&project_path));
+    let has_upstream = upstream.is_some();
+    let (ahead, behind) = upstream.unwrap_or((0, 0));
 
     // Get file statuses with porcelain v1 (XY format)
     let status_output = std::process::Command::new("git")
@@ -568,7 +613,7 @@ pub async fn git_status(project_path: String) -> Result<GitStatus, String> {
     for line in status_text.lines().filter(|l| !l.is_empty()) {
         let x = line.chars().next().unwrap_or(' ');
         let y = line.chars().nth(1).unwrap_or(' ');
-        let file_path = line.get(3..).unwrap_or("").to_string();
+        let file_path = porcelain_path(x, line.get(3..).unwrap_or(""));
 
         // Index (staged) status - first char
         if x != ' ' && x != '?' {
@@ -607,7 +652,14 @@ pub async fn git_status(project_path: String) -> Result<GitStatus, String> {
         }
     }
 
-    Ok(GitStatus { branch, files, staged, ahead, behind })
+    Ok(GitStatus {
+        branch,
+        files,
+        staged,
+        ahead,
+        behind,
+        has_upstream,
+    })
 }
 
 #[tauri::command]
@@ -654,30 +706,59 @@ pub async fn git_pull(project_path: String) -> Result<String, String> {
 }
 
 #[tauri::command]
-pub async fn git_diff(project_path: String, file_path: Option<String>) -> Result<String, String> {
-    let mut args = vec!["diff".to_string()];
-    if let Some(fp) = &file_path {
-        args.push("--".to_string());
-        args.push(fp.clone());
+pub async fn git_diff(
+    project_path: String,
+    file_path: Option<String>,
+    from_ref: Option<String>,
+    to_ref: Option<String>,
+    stat_only: Option<bool>,
+) -> Result<String, String> {
+    let from_ref = from_ref.filter(|r| !r.is_empty());
+    let to_ref = to_ref.filter(|r| !r.is_empty());
+    if let Some(r) = [&from_ref, &to_ref].into_iter().flatten().find(|r| r.starts_with('-')) {
+        return Err(format!("Invalid ref '{}'", r));
+    }
+
+    let mut revision_args: Vec<String> = Vec::new();
+    match (&from_ref, &to_ref) {
+        (Some(from), Some(to)) => revision_args.push(format!("{}..{}", from, to)),
+        // A single ref is compared against the working tree
+        (Some(from), None) => revision_args.push(from.clone()),
+        (None, Some(_)) => return Err("to_ref requires from_ref".to_string()),
+        (None, None) => {}
     }
 
+    let build_args = |extra: &[&str]| {
+        let mut args = vec!["diff".to_string()];
+        if stat_only.unwrap_or(false) {
+            args.push("--stat".to_string());
+        }
+        args.extend(extra.iter().map(|s| s.to_string()));
+        args.extend(revision_args.iter().cloned());
+        if let Some(fp) = &file_path {
+            args.push("--".to_string());
+            args.push(fp.clone());
+        }
+        args
+    };
+
     let output = std::process::Command::new("git")
-        .args(&args)
+        .args(build_args(&[]))
         .current_dir(&project_path)
         .output()
         .map_err(|e| format!("git diff failed: {}", e))?;
 
+    // Surface bad refs instead of returning an empty diff
+    if !output.status.success() && !revision_args.is_empty() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
     let diff_text = String::from_utf8_lossy(&output.stdout).to_string();
 
-    // If empty, try staged diff
-    if diff_text.is_empty() {
-        let mut staged_args = vec!["diff".to_string(), "--cached".to_string()];
-        if let Some(fp) = &file_path {
-            staged_args.push("--".to_string());
-            staged_args.push(fp.clone());
-        }
+    // If empty, try staged diff (only meaningful for the default working-tree diff)
+    if diff_text.is_empty() && revision_args.is_empty() {
         let staged_output = std::process::Command::new("git")
-            .args(&staged_args)
+            .args(build_args(&["--cached"]))
             .current_dir(&project_path)
             .output()
             .map_err(|e| format!("git diff --cached failed: {}", e))?;
@@ -730,9 +811,38 @@ pub async fn git_discard(project_path: String, file_path: String) -> Result<(),
 }
 
 #[tauri::command]
-pub async fn git_commit(project_path: String, message: String) -> Result<(), String> {
+pub async fn git_commit(
+    project_path: String,
+    message: Option<String>,
+    amend: Option<bool>,
+    author: Option<String>,
+    signoff: Option<bool>,
+) -> Result<String, String> {
+    let amend = amend.unwrap_or(false);
+    let message = message.filter(|m| !m.trim().is_empty());
+
+    let mut args = vec!["commit".to_string()];
+    match message {
+        Some(msg) => {
+            args.push("-m".to_string());
+            args.push(msg);
+        }
+        // Amending without a message keeps the previous one
+        None if amend => args.push("--no-edit".to_string()),
+        None => return Err("Commit message is required".to_string()),
+    }
+    if amend {
+        args.push("--amend".to_string());
+    }
+    if let Some(author) = author.filter(|a| !a.trim().is_empty()) {
+        args.push(format!("--author={}", author));
+    }
+    if signoff.unwrap_or(false) {
+        args.push("--signoff".to_string());
+    }
+
     let output = std::process::Command::new("git")
-        .args(["commit", "-m", &message])
+        .args(&args)
         .current_dir(&project_path)
         .output()
         .map_err(|e| format!("git commit failed: {}", e))?;
@@ -741,7 +851,13 @@ pub async fn git_commit(project_path: String, message: String) -> Result<(), Str
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
         return Err(stderr);
     }
-    Ok(())
+
+    let head = std::process::Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(&project_path)
+        .output()
+        .map_err(|e| format!("git rev-parse failed: {}", e))?;
+    Ok(String::from_utf8_lossy(&head.stdout).trim().to_string())
 }
 
 #[tauri::command]
@@ -759,105 +875,468 @@ pub async fn git_init(project_path: String) -> Result<String, String> {
     Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
 
-#[derive(serde::Serialize)]
-pub struct BlameLine {
-    pub hash: String,
-    pub author: String,
-    pub date: String,
-    pub summary: String,
+#[derive(Debug, Serialize)]
+pub struct GitResetResult {
+    /// False for a hard reset that was only previewed (no `confirm`)
+    pub applied: bool,
+    /// Tracked files a hard reset overwrites; empty for soft and mixed resets
+    pub affected_files: Vec<String>,
+    pub output: String,
 }
 
 #[tauri::command]
-pub async fn git_blame_file(
+pub async fn git_reset(
     project_path: String,
-    file_path: String,
-) -> Result<Vec<BlameLine>, String> {
+    mode: String,
+    r#ref: Option<String>,
+    confirm: Option<bool>,
+) -> Result<GitResetResult, String> {
+    if !matches!(mode.as_str(), "soft" | "mixed" | "hard") {
+        return Err(format!("Invalid reset mode '{}'. Use soft, mixed or hard.", mode));
+    }
+    let target = r#ref.filter(|r| !r.trim().is_empty()).unwrap_or_else(|| "HEAD".to_string());
+    if target.starts_with('-') {
+        return Err(format!("Invalid ref '{}'", target));
+    }
+
+    // A hard reset throws away working-tree changes, so without confirm only
+    // report which files it would overwrite
+    let mut affected_files = Vec::new();
+    if mode == "hard" {
+        let diff = std::process::Command::new("git")
+            .args(["diff", "--name-only", &target, "--"])
+            .current_dir(&project_path)
+            .output()
+            .map_err(|e| format!("git diff failed: {}", e))?;
+        if !diff.status.success() {
+            return Err(String::from_utf8_lossy(&diff.stderr).to_string());
+        }
+        affected_files = String::from_utf8_lossy(&diff.stdout)
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(String::from)
+            .collect();
+
+        if !confirm.unwrap_or(false) {
+            return Ok(GitResetResult {
+                applied: false,
+                affected_files,
+                output: "Dry run: pass confirm to discard these changes".to_string(),
+            });
+        }
+    }
+
     let output = std::process::Command::new("git")
-        .args(["blame", "--porcelain", &file_path])
+        .args(["reset", &format!("--{}", mode), &target])
         .current_dir(&project_path)
         .output()
-        .map_err(|e| format!("git blame failed: {}", e))?;
+        .map_err(|e| format!("git reset failed: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+    Ok(GitResetResult {
+        applied: true,
+        affected_files,
+        output: String::from_utf8_lossy(&output.stdout).to_string()
+            + &String::from_utf8_lossy(&output.stderr),
+    })
+}
+
+#[derive(Debug, Serialize)]
+pub struct GitBranch {
+    pub name: String,
+    pub current: bool,
+    pub upstream: Option<String>,
+    pub ahead: u32,
+    pub behind: u32,
+}
+
+#[tauri::command]
+pub async fn git_branch_list(project_path: String) -> Result<Vec<GitBranch>, String> {
+    let output = std::process::Command::new("git")
+        .args([
+            "for-each-ref",
+            "--format=%(HEAD)%00%(refname:short)%00%(upstream:short)%00%(upstream:track,nobracket)",
+            "refs/heads",
+        ])
+        .current_dir(&project_path)
+        .output()
+        .map_err(|e| format!("git for-each-ref failed: {}", e))?;
 
     if !output.status.success() {
         return Err(String::from_utf8_lossy(&output.stderr).to_string());
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    let mut result: Vec<BlameLine> = Vec::new();
-
-    let mut current_hash = String::new();
-    let mut current_author = String::new();
-    let mut current_date = String::new();
-    let mut current_summary = String::new();
-
-    for line in stdout.lines() {
-        if line.starts_with('\t') {
-            // This is the actual source line — finalize this blame entry
-            result.push(BlameLine {
-                hash: current_hash.clone(),
-                author: current_author.clone(),
-                date: current_date.clone(),
-                summary: current_summary.clone(),
-            });
-        } else if let Some(rest) = line.strip_prefix("author ") {
-            current_author = rest.to_string();
-        } else if let Some(rest) = line.strip_prefix("author-time ") {
-            // Convert Unix timestamp to ISO date
-            if let Ok(ts) = rest.parse::<i64>() {
-                let secs = ts;
-                // Simple UTC date formatting
-                let datetime = chrono_lite_format(secs);
-                current_date = datetime;
+    let mut branches: Vec<GitBranch> = Vec::new();
+
+    for line in stdout.lines().filter(|l| !l.is_empty()) {
+        let fields: Vec<&str> = line.split('\0').collect();
+        if fields.len() < 4 {
+            continue;
+        }
+
+        // Tracking info looks like "ahead 2, behind 1", "behind 3", or "gone"
+        let mut ahead: u32 = 0;
+        let mut behind: u32 = 0;
+        for segment in fields[3].split(", ") {
+            if let Some(n) = segment.strip_prefix("ahead ") {
+                ahead = n.trim().parse().unwrap_or(0);
+            } else if let Some(n) = segment.strip_prefix("behind ") {
+                behind = n.trim().parse().unwrap_or(0);
             }
-        } else if let Some(rest) = line.strip_prefix("summary ") {
-            current_summary = rest.to_string();
-        } else if line.len() >= 40 && line.chars().take(40).all(|c| c.is_ascii_hexdigit()) {
-            // This is a commit hash line (40 hex chars followed by line numbers)
-            current_hash = line[..40].to_string();
         }
+
+        branches.push(GitBranch {
+            name: fields[1].to_string(),
+            current: fields[0] == "*",
+            upstream: Some(fields[2]).filter(|u| !u.is_empty()).map(String::from),
+            ahead,
+            behind,
+        });
     }
 
-    Ok(result)
+    Ok(branches)
 }
 
-/// Simple Unix timestamp to "YYYY-MM-DD" formatting without chrono dependency.
-fn chrono_lite_format(timestamp: i64) -> String {
-    // Calculate date from Unix timestamp (UTC)
-    let secs_per_day: i64 = 86400;
-    let days = timestamp / secs_per_day;
+#[tauri::command]
+pub async fn git_create_branch(
+    project_path: String,
+    name: String,
+    start_point: Option<String>,
+) -> Result<(), String> {
+    if name.trim().is_empty() || name.starts_with('-') {
+        return Err(format!("Invalid branch name '{}'", name));
+    }
+    let mut args = vec!["branch".to_string(), name];
+    if let Some(start) = start_point.filter(|s| !s.is_empty()) {
+        if start.starts_with('-') {
+            return Err(format!("Invalid start point '{}'", start));
+        }
+        args.push(start);
+    }
 
-    // Days since 1970-01-01
-    let mut y = 1970i64;
-    let mut remaining = days;
+    let output = std::process::Command::new("git")
+        .args(&args)
+        .current_dir(&project_path)
+        .output()
+        .map_err(|e| format!("git branch failed: {}", e))?;
 
-    loop {
-        let days_in_year = if is_leap(y) { 366 } else { 365 };
-        if remaining < days_in_year {
-            break;
-        }
-        remaining -= days_in_year;
-        y += 1;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
     }
+    Ok(())
+}
 
-    let month_days = if is_leap(y) {
-        [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
-    } else {
-        [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
-    };
+#[tauri::command]
+pub async fn git_checkout_branch(project_path: String, name: String) -> Result<(), String> {
+    if name.trim().is_empty() || name.starts_with('-') {
+        return Err(format!("Invalid branch name '{}'", name));
+    }
+
+    // git refuses to switch when local changes would be overwritten; its stderr
+    // names the conflicting files, so pass it through untouched.
+    let output = std::process::Command::new("git")
+        .args(["checkout", &name])
+        .current_dir(&project_path)
+        .output()
+        .map_err(|e| format!("git checkout failed: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn git_stash_save(project_path: String, message: Option<String>) -> Result<String, String> {
+    let mut args = vec!["stash".to_string(), "push".to_string()];
+    if let Some(msg) = message.filter(|m| !m.is_empty()) {
+        args.push("-m".to_string());
+        args.push(msg);
+    }
+
+    let output = std::process::Command::new("git")
+        .args(&args)
+        .current_dir(&project_path)
+        .output()
+        .map_err(|e| format!("git stash failed: {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+
+    // A clean tree is not an error; report it so the caller knows nothing was saved
+    if stdout.contains("No local changes to save") || stderr.contains("No local changes to save") {
+        return Ok("No local changes to save".to_string());
+    }
+    if !output.status.success() {
+        return Err(stderr);
+    }
+    Ok(stdout)
+}
+
+#[derive(Debug, Serialize)]
+pub struct GitStash {
+    pub index: u32,
+    pub message: String,
+    pub branch: String,
+}
+
+#[tauri::command]
+pub async fn git_stash_list(project_path: String) -> Result<Vec<GitStash>, String> {
+    let output = std::process::Command::new("git")
+        .args(["stash", "list", "--format=%gd%x00%gs"])
+        .current_dir(&project_path)
+        .output()
+        .map_err(|e| format!("git stash list failed: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut stashes: Vec<GitStash> = Vec::new();
+
+    for line in stdout.lines().filter(|l| !l.is_empty()) {
+        let Some((selector, subject)) = line.split_once('\0') else {
+            continue;
+        };
+
+        // Selector is "stash@{N}"
+        let index = selector
+            .trim_start_matches("stash@{")
+            .trim_end_matches('}')
+            .parse()
+            .unwrap_or(0);
+
+        // Subject is "WIP on <branch>: <hash> <msg>" or "On <branch>: <msg>"
+        let subject = subject
+            .strip_prefix("WIP on ")
+            .or_else(|| subject.strip_prefix("On "))
+            .unwrap_or(subject);
+        let (branch, message) = subject.split_once(": ").unwrap_or(("", subject));
+
+        stashes.push(GitStash {
+            index,
+            message: message.to_string(),
+            branch: branch.to_string(),
+        });
+    }
+
+    Ok(stashes)
+}
+
+#[tauri::command]
+pub async fn git_stash_pop(project_path: String, index: Option<u32>) -> Result<String, String> {
+    let mut args = vec!["stash".to_string(), "pop".to_string()];
+    if let Some(i) = index {
+        args.push(format!("stash@{{{}}}", i));
+    }
+
+    let output = std::process::Command::new("git")
+        .args(&args)
+        .current_dir(&project_path)
+        .output()
+        .map_err(|e| format!("git stash pop failed: {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+    // Conflict details ("CONFLICT (content): ...") are printed on stdout
+    if !output.status.success() {
+        return Err(format!("{}{}", stdout, stderr).trim().to_string());
+    }
+    Ok(stdout.trim().to_string())
+}
+
+#[derive(Debug, Serialize)]
+pub struct GitTag {
+    pub name: String,
+    /// Commit the tag points to (peeled through annotated tags)
+    pub commit: String,
+    pub annotated: bool,
+    /// Subject of the tag message, for annotated tags
+    pub message: Option<String>,
+}
+
+#[tauri::command]
+pub async fn git_tag_list(project_path: String) -> Result<Vec<GitTag>, String> {
+    let output = std::process::Command::new("git")
+        .args([
+            "for-each-ref",
+            "--sort=-creatordate",
+            "--format=%(refname:short)%00%(objecttype)%00%(objectname)%00%(*objectname)%00%(contents:subject)",
+            "refs/tags",
+        ])
+        .current_dir(&project_path)
+        .output()
+        .map_err(|e| format!("git for-each-ref failed: {}", e))?;
 
-    let mut m = 0usize;
-    for (i, &md) in month_days.iter().enumerate() {
-        if remaining < md {
-            m = i;
-            break;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let tags = stdout
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\0').collect();
+            if fields.len() < 5 {
+                return None;
+            }
+            // Annotated tags are tag objects; `*objectname` is the commit behind them
+            let annotated = fields[1] == "tag";
+            Some(GitTag {
+                name: fields[0].to_string(),
+                commit: if annotated { fields[3] } else { fields[2] }.to_string(),
+                annotated,
+                message: Some(fields[4])
+                    .filter(|m| annotated && !m.is_empty())
+                    .map(String::from),
+            })
+        })
+        .collect();
+
+    Ok(tags)
+}
+
+#[tauri::command]
+pub async fn git_create_tag(
+    project_path: String,
+    name: String,
+    message: Option<String>,
+    target: Option<String>,
+    force: Option<bool>,
+    push: Option<bool>,
+) -> Result<String, String> {
+    if name.trim().is_empty() || name.starts_with('-') {
+        return Err(format!("Invalid tag name '{}'", name));
+    }
+    let force = force.unwrap_or(false);
+
+    let mut args = vec!["tag".to_string()];
+    if let Some(msg) = message.filter(|m| !m.trim().is_empty()) {
+        args.push("-a".to_string());
+        args.push("-m".to_string());
+        args.push(msg);
+    }
+    // Without -f git refuses an existing name with "tag '<name>' already exists"
+    if force {
+        args.push("-f".to_string());
+    }
+    args.push(name.clone());
+    if let Some(target) = target.filter(|t| !t.trim().is_empty()) {
+        if target.starts_with('-') {
+            return Err(format!("Invalid target '{}'", target));
         }
-        remaining -= md;
+        args.push(target);
+    }
+
+    let output = std::process::Command::new("git")
+        .args(&args)
+        .current_dir(&project_path)
+        .output()
+        .map_err(|e| format!("git tag failed: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
     }
 
-    let d = remaining + 1;
-    format!("{:04}-{:02}-{:02}", y, m + 1, d)
+    if !push.unwrap_or(false) {
+        return Ok(format!("Created tag {}", name));
+    }
+
+    let mut push_args = vec!["push".to_string(), "origin".to_string()];
+    if force {
+        push_args.push("--force".to_string());
+    }
+    push_args.push(format!("refs/tags/{}", name));
+
+    let pushed = std::process::Command::new("git")
+        .args(&push_args)
+        .current_dir(&project_path)
+        .output()
+        .map_err(|e| format!("git push failed: {}", e))?;
+
+    if !pushed.status.success() {
+        return Err(format!(
+            "Created tag {} locally, but pushing it failed:\n{}",
+            name,
+            String::from_utf8_lossy(&pushed.stderr)
+        ));
+    }
+    Ok(format!("Created and pushed tag {}", name))
 }
 
-fn is_leap(y: i64) -> bool {
-    (y % 4 == 0 && y % 100 != 0) || y % 400 == 0
+#[tauri::command]
+pub async fn git_delete_tag(project_path: String, name: String) -> Result<(), String> {
+    if name.starts_with('-') {
+        return Err(format!("Invalid tag name '{}'", name));
+    }
+
+    let output = std::process::Command::new("git")
+        .args(["tag", "-d", &name])
+        .current_dir(&project_path)
+        .output()
+        .map_err(|e| format!("git tag failed: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn git_blame_file(
+    project_path: String,
+    file_path: String,
+) -> Result<Vec<BlameLine>, String> {
+    let output = std::process::Command::new("git")
+        .args(["blame", "--porcelain", &file_path])
+        .current_dir(&project_path)
+        .output()
+        .map_err(|e| format!("git blame failed: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(parse_blame_porcelain(&stdout))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `a/a -> ../a` points a directory at itself; the walkers must list its
+    /// contents once instead of recursing through the link.
+    #[cfg(unix)]
+    fn symlink_cycle() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a");
+        std::fs::create_dir(&a).unwrap();
+        std::fs::write(a.join("notes.txt"), "needle\n").unwrap();
+        std::os::unix::fs::symlink("../a", a.join("a")).unwrap();
+        dir
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn project_files_does_not_follow_a_symlink_cycle() {
+        let dir = symlink_cycle();
+        let files = project_files(dir.path(), true);
+        assert_eq!(files, vec![dir.path().join("a").join("notes.txt")]);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn search_files_does_not_follow_a_symlink_cycle() {
+        let dir = symlink_cycle();
+        let root = dir.path().to_string_lossy().into_owned();
+        let results = search_files("needle".to_string(), root).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "notes.txt");
+    }
 }
@@ -654,9 +654,9 @@ pub async fn export_modpack(project_path: String, output_path: String) -> Result
                 let entry = entry.map_err(|e| format!("Dir entry error: {}", e))?;
                 let path = entry.path();
                 let name = path.strip_prefix(base).unwrap_or(&path).to_string_lossy();
-                if path.is_dir() {
+                if crate::commands::filesystem::is_walkable_dir(&entry) {
                     add_dir_to_zip(zip, base, &path, prefix, options)?;
-                } else {
+                } else if path.is_file() {
                     let data = std::fs::read(&path)
                         .map_err(|e| format!("Failed to read {}: {}", name, e))?;
                     zip.start_file(format!("{}/{}", prefix, name), options)
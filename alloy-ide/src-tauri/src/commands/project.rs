@@ -1,5 +1,10 @@
 use crate::state::{AppState, ProjectInfo, ProjectType, RecentProject};
-use serde::Deserialize;
+use alloy_common::mod_id::is_valid_mod_id;
+use alloy_common::scaffold::{
+    scaffold_loom_project, scaffold_mod_project, scaffold_modpack_project, ScaffoldArgs,
+    FABRIC_LOADER_VERSION, QUILT_LOADER_VERSION,
+};
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -23,16 +28,10 @@ fn write_current_project_file(info: &ProjectInfo) {
     }
 }
 
-#[tauri::command]
-pub async fn open_project(path: String, state: State<'_, Arc<AppState>>) -> Result<ProjectInfo, String> {
-    let project_path = Path::new(&path);
-    if !project_path.exists() {
-        return Err(format!("Path does not exist: {}", path));
-    }
-    if !project_path.is_dir() {
-        return Err(format!("Path is not a directory: {}", path));
-    }
-
+/// Classify the project at `path`. The Alloy manifests take priority; other
+/// loaders are recognised by [`detect_loader`].
+pub(crate) fn detect_project(path: &str) -> ProjectInfo {
+    let project_path = Path::new(path);
     let name = project_path
         .file_name()
         .and_then(|n| n.to_str())
@@ -42,7 +41,7 @@ pub async fn open_project(path: String, state: State<'_, Arc<AppState>>) -> Resu
     let mod_json = project_path.join("alloy.mod.json");
     let pack_toml = project_path.join("alloy.pack.toml");
 
-    let (project_type, environment) = if mod_json.exists() {
+    let (project_type, environment, loader, loader_version) = if mod_json.exists() {
         let env = match fs::read_to_string(&mod_json) {
             Ok(content) => {
                 serde_json::from_str::<serde_json::Value>(&content)
@@ -51,19 +50,115 @@ pub async fn open_project(path: String, state: State<'_, Arc<AppState>>) -> Resu
             }
             Err(_) => None,
         };
-        (ProjectType::Mod, env)
+        (ProjectType::Mod, env, Some("alloy".to_string()), None)
     } else if pack_toml.exists() {
-        (ProjectType::Modpack, None)
+        (ProjectType::Modpack, None, None, None)
+    } else if let Some(detected) = detect_loader(project_path) {
+        (
+            ProjectType::Mod,
+            detected.environment,
+            Some(detected.loader.to_string()),
+            detected.version,
+        )
     } else {
-        (ProjectType::Unknown, None)
+        (ProjectType::Unknown, None, None, None)
     };
 
-    let info = ProjectInfo {
-        name: name.clone(),
-        path: path.clone(),
-        project_type: project_type.clone(),
+    ProjectInfo {
+        name,
+        path: path.to_string(),
+        project_type,
         environment,
+        loader,
+        loader_version,
+    }
+}
+
+/// A mod loader recognised from a project's metadata or build script.
+pub(crate) struct DetectedLoader {
+    pub loader: &'static str,
+    pub version: Option<String>,
+    pub environment: Option<String>,
+}
+
+/// Identify Fabric, Quilt, Forge and NeoForge projects that have no Alloy
+/// manifest. Loader metadata under `src/main/resources` wins over the loader
+/// plugin applied in the build script.
+pub(crate) fn detect_loader(project_dir: &Path) -> Option<DetectedLoader> {
+    let resources = project_dir.join("src/main/resources");
+    let build_script = ["build.gradle", "build.gradle.kts"]
+        .iter()
+        .find_map(|f| std::fs::read_to_string(project_dir.join(f)).ok())
+        .unwrap_or_default();
+
+    let read_json = |file: &str| {
+        std::fs::read_to_string(resources.join(file))
+            .ok()
+            .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+    };
+    let environment = |value: Option<&serde_json::Value>| {
+        value
+            .and_then(|e| e.as_str())
+            .map(|e| if e == "*" { "both" } else { e }.to_string())
+    };
+
+    let (loader, environment) = if let Some(quilt) = read_json("quilt.mod.json") {
+        ("quilt", environment(quilt.pointer("/minecraft/environment")))
+    } else if let Some(fabric) = read_json("fabric.mod.json") {
+        ("fabric", environment(fabric.get("environment")))
+    } else if resources.join("META-INF/neoforge.mods.toml").exists() {
+        ("neoforge", None)
+    } else if resources.join("META-INF/mods.toml").exists() {
+        // NeoForge for 1.20.1 still used the Forge file name
+        let loader = if build_script.contains("net.neoforged") { "neoforge" } else { "forge" };
+        (loader, None)
+    } else if build_script.contains("org.quiltmc.loom") {
+        ("quilt", None)
+    } else if build_script.contains("fabric-loom") {
+        ("fabric", None)
+    } else if build_script.contains("net.neoforged") {
+        ("neoforge", None)
+    } else if build_script.contains("net.minecraftforge.gradle") {
+        ("forge", None)
+    } else {
+        return None;
+    };
+
+    // Loader version as the usual templates name it in gradle.properties
+    let version_key = match loader {
+        "forge" => "forge_version",
+        "neoforge" => "neo_version",
+        _ => "loader_version",
     };
+    let version = std::fs::read_to_string(project_dir.join("gradle.properties"))
+        .ok()
+        .and_then(|props| {
+            props.lines().find_map(|line| {
+                let (key, value) = line.split_once('=')?;
+                (key.trim() == version_key).then(|| value.trim().to_string())
+            })
+        });
+
+    Some(DetectedLoader {
+        loader,
+        version,
+        environment,
+    })
+}
+
+#[tauri::command]
+pub async fn open_project(path: String, state: State<'_, Arc<AppState>>) -> Result<ProjectInfo, String> {
+    let project_path = Path::new(&path);
+    if !project_path.exists() {
+        return Err(format!("Path does not exist: {}", path));
+    }
+    if !project_path.is_dir() {
+        return Err(format!("Path is not a directory: {}", path));
+    }
+
+    let info = detect_project(&path);
+    let name = info.name.clone();
+    let project_type = info.project_type.clone();
 
     // Update current project
     {
@@ -75,26 +170,7 @@ pub async fn open_project(path: String, state: State<'_, Arc<AppState>>) -> Resu
     write_current_project_file(&info);
 
     // Add to recent projects
-    {
-        let mut recents = state.recent_projects.lock().map_err(|e| e.to_string())?;
-        recents.retain(|r| r.path != path);
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
-        recents.insert(
-            0,
-            RecentProject {
-                name,
-                path,
-                project_type,
-                last_opened: now,
-            },
-        );
-        if recents.len() > 20 {
-            recents.truncate(20);
-        }
-    }
+    remember_project(&state, name, path, project_type)?;
 
     Ok(info)
 }
@@ -105,6 +181,123 @@ pub async fn get_recent_projects(state: State<'_, Arc<AppState>>) -> Result<Vec<
     Ok(recents.clone())
 }
 
+/// Drop a project from the recent list. Returns the updated list.
+#[tauri::command]
+pub async fn remove_recent_project(
+    path: String,
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<RecentProject>, String> {
+    let mut recents = state.recent_projects.lock().map_err(|e| e.to_string())?;
+    recents.retain(|r| r.path != path);
+    save_recent_projects(&recents);
+    Ok(recents.clone())
+}
+
+const MAX_RECENT_PROJECTS: usize = 20;
+
+/// ~/.alloy-ide/recent-projects.json, shared with the standalone MCP server.
+fn recent_projects_file() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".alloy-ide/recent-projects.json"))
+}
+
+/// Load the persisted recent list, skipping projects that no longer exist.
+pub fn load_recent_projects() -> Vec<RecentProject> {
+    let Some(file) = recent_projects_file() else {
+        return Vec::new();
+    };
+    let Ok(content) = fs::read_to_string(&file) else {
+        return Vec::new();
+    };
+    let entries: Vec<serde_json::Value> = serde_json::from_str(&content).unwrap_or_default();
+
+    let mut recents: Vec<RecentProject> = entries
+        .into_iter()
+        .filter_map(|entry| {
+            let path = entry.get("path")?.as_str()?.to_string();
+            if !Path::new(&path).is_dir() {
+                return None;
+            }
+            let name = entry
+                .get("name")
+                .and_then(|n| n.as_str())
+                .map(String::from)
+                .unwrap_or_else(|| {
+                    Path::new(&path)
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_default()
+                });
+            let project_type = entry
+                .get("project_type")
+                .cloned()
+                .and_then(|t| serde_json::from_value(t).ok())
+                .unwrap_or(ProjectType::Unknown);
+            // The MCP server writes `opened_at: "unix:<secs>"` instead
+            let last_opened = entry
+                .get("last_opened")
+                .and_then(|t| t.as_u64())
+                .or_else(|| {
+                    entry
+                        .get("opened_at")
+                        .and_then(|t| t.as_str())
+                        .and_then(|t| t.strip_prefix("unix:"))
+                        .and_then(|t| t.parse().ok())
+                })
+                .unwrap_or(0);
+            Some(RecentProject {
+                name,
+                path,
+                project_type,
+                last_opened,
+            })
+        })
+        .collect();
+
+    recents.truncate(MAX_RECENT_PROJECTS);
+    recents
+}
+
+fn save_recent_projects(recents: &[RecentProject]) {
+    let Some(file) = recent_projects_file() else {
+        return;
+    };
+    if let Some(dir) = file.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(recents) {
+        let _ = fs::write(&file, json);
+    }
+}
+
+/// Move a project to the front of the recent list and persist it.
+fn remember_project(
+    state: &AppState,
+    name: String,
+    path: String,
+    project_type: ProjectType,
+) -> Result<(), String> {
+    let mut recents = state.recent_projects.lock().map_err(|e| e.to_string())?;
+    recents.retain(|r| r.path != path);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    recents.insert(
+        0,
+        RecentProject {
+            name,
+            path,
+            project_type,
+            last_opened: now,
+        },
+    );
+    recents.truncate(MAX_RECENT_PROJECTS);
+    save_recent_projects(&recents);
+    Ok(())
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CreateProjectArgs {
     pub name: String,
@@ -112,26 +305,75 @@ pub struct CreateProjectArgs {
     pub project_type: String,
     pub environment: String,
     pub mod_id: Option<String>,
+    #[serde(alias = "package")]
     pub package_name: Option<String>,
+    /// "alloy" (default), "fabric", or "quilt"
+    #[serde(default)]
+    pub loader: Option<String>,
+    #[serde(default)]
+    pub minecraft_version: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreateProjectResult {
+    #[serde(flatten)]
+    pub project: ProjectInfo,
+    /// Every file the scaffold wrote
+    pub created_files: Vec<String>,
+    /// The mod's entry class, for the UI to open
+    pub main_class: Option<String>,
 }
 
 #[tauri::command]
-pub async fn create_project(args: CreateProjectArgs, state: State<'_, Arc<AppState>>) -> Result<ProjectInfo, String> {
+pub async fn create_project(
+    args: CreateProjectArgs,
+    state: State<'_, Arc<AppState>>,
+) -> Result<CreateProjectResult, String> {
     let project_dir = Path::new(&args.path).join(&args.name);
     if project_dir.exists() {
         return Err(format!("Directory already exists: {}", project_dir.display()));
     }
+    if let Some(mod_id) = args.mod_id.as_deref() {
+        if !is_valid_mod_id(mod_id) {
+            return Err(format!(
+                "Invalid mod ID '{}': use lowercase letters, digits, and underscores, \
+                 starting with a letter",
+                mod_id
+            ));
+        }
+    }
 
     fs::create_dir_all(&project_dir).map_err(|e| format!("Failed to create directory: {}", e))?;
 
-    match args.project_type.as_str() {
-        "mod" => scaffold_mod_project(&project_dir, &args)?,
-        "modpack" => scaffold_modpack_project(&project_dir, &args)?,
-        _ => return Err(format!("Unknown project type: {}", args.project_type)),
-    }
+    let scaffold_args = ScaffoldArgs {
+        name: args.name.clone(),
+        environment: args.environment.clone(),
+        mod_id: args.mod_id.clone(),
+        package_name: args.package_name.clone(),
+        minecraft_version: args.minecraft_version.clone(),
+    };
+    let scaffold = match (args.project_type.as_str(), args.loader.as_deref().unwrap_or("alloy")) {
+        ("mod", "alloy") => scaffold_mod_project(&project_dir, &scaffold_args),
+        ("mod", loader @ ("fabric" | "quilt")) => {
+            scaffold_loom_project(&project_dir, &scaffold_args, loader)
+        }
+        ("mod", other) => Err(format!("Unknown loader: {}", other)),
+        ("modpack", _) => scaffold_modpack_project(&project_dir, &scaffold_args),
+        _ => Err(format!("Unknown project type: {}", args.project_type)),
+    };
+    let scaffold = match scaffold {
+        Ok(s) => s,
+        Err(e) => {
+            // Don't leave a half-written project behind to block a retry
+            let _ = fs::remove_dir_all(&project_dir);
+            return Err(e);
+        }
+    };
 
     // Open the newly created project
     let path_str = project_dir.to_string_lossy().to_string();
+    let loader = (args.project_type == "mod")
+        .then(|| args.loader.clone().unwrap_or_else(|| "alloy".to_string()));
     let info = ProjectInfo {
         name: args.name.clone(),
         path: path_str.clone(),
@@ -141,6 +383,12 @@ pub async fn create_project(args: CreateProjectArgs, state: State<'_, Arc<AppSta
             _ => ProjectType::Unknown,
         },
         environment: Some(args.environment.clone()),
+        loader_version: match loader.as_deref() {
+            Some("fabric") => Some(FABRIC_LOADER_VERSION.to_string()),
+            Some("quilt") => Some(QUILT_LOADER_VERSION.to_string()),
+            _ => None,
+        },
+        loader,
     };
 
     {
@@ -151,237 +399,11 @@ pub async fn create_project(args: CreateProjectArgs, state: State<'_, Arc<AppSta
     // Write shared file for MCP server sync
     write_current_project_file(&info);
 
-    {
-        let mut recents = state.recent_projects.lock().map_err(|e| e.to_string())?;
-        recents.retain(|r| r.path != path_str);
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
-        recents.insert(0, RecentProject {
-            name: args.name,
-            path: path_str,
-            project_type: match args.project_type.as_str() {
-                "mod" => ProjectType::Mod,
-                "modpack" => ProjectType::Modpack,
-                _ => ProjectType::Unknown,
-            },
-            last_opened: now,
-        });
-        if recents.len() > 20 {
-            recents.truncate(20);
-        }
-    }
-
-    Ok(info)
-}
-
-fn scaffold_mod_project(dir: &Path, args: &CreateProjectArgs) -> Result<(), String> {
-    let mod_id = args.mod_id.as_deref().unwrap_or("mymod");
-    let pkg = args.package_name.as_deref().unwrap_or("com.example");
-    let pkg_path = pkg.replace('.', "/");
-    let class_name = to_pascal_case(&args.name);
-
-    // alloy.mod.json
-    let mod_json = format!(
-        r#"{{
-  "id": "{}",
-  "name": "{}",
-  "version": "1.0.0",
-  "entrypoint": "{}.{}.{}",
-  "environment": "{}"
-}}"#,
-        mod_id, args.name, pkg, mod_id, class_name, args.environment
-    );
-    fs::write(dir.join("alloy.mod.json"), mod_json)
-        .map_err(|e| format!("Failed to write alloy.mod.json: {}", e))?;
-
-    // build.gradle.kts
-    let build_gradle = format!(
-        r#"plugins {{
-    java
-}}
-
-group = "{}.{}"
-version = "1.0.0"
-
-repositories {{
-    mavenCentral()
-}}
-
-java {{
-    sourceCompatibility = JavaVersion.VERSION_21
-    targetCompatibility = JavaVersion.VERSION_21
-}}
-
-tasks.withType<JavaCompile> {{
-    options.release.set(21)
-    options.encoding = "UTF-8"
-}}
-
-dependencies {{
-    // Alloy API — provided at runtime by the loader
-    compileOnly(files("libs/alloy-api.jar"))
-}}
-"#,
-        pkg, mod_id
-    );
-    fs::write(dir.join("build.gradle.kts"), build_gradle)
-        .map_err(|e| format!("Failed to write build.gradle.kts: {}", e))?;
-
-    // settings.gradle.kts
-    let settings = format!("rootProject.name = \"{}\"", mod_id);
-    fs::write(dir.join("settings.gradle.kts"), settings)
-        .map_err(|e| format!("Failed to write settings.gradle.kts: {}", e))?;
-
-    // .gitignore
-    let gitignore = "build/\n.gradle/\nlibs/\nout/\n*.class\n.idea/\n*.iml\n";
-    fs::write(dir.join(".gitignore"), gitignore)
-        .map_err(|e| format!("Failed to write .gitignore: {}", e))?;
-
-    // Source directories
-    let src_dir = dir.join("src/main/java").join(&pkg_path).join(mod_id);
-    fs::create_dir_all(&src_dir)
-        .map_err(|e| format!("Failed to create source directory: {}", e))?;
-
-    // Resources directory
-    let res_dir = dir.join("src/main/resources/assets").join(mod_id).join("textures");
-    fs::create_dir_all(&res_dir)
-        .map_err(|e| format!("Failed to create resource directory: {}", e))?;
-
-    // Main class
-    let entry_class = match args.environment.as_str() {
-        "server" => format!(
-            r#"package {pkg}.{mod_id};
-
-import net.alloymc.loader.api.ModInitializer;
-import net.alloymc.api.AlloyAPI;
-import net.alloymc.api.event.EventHandler;
-import net.alloymc.api.event.Listener;
-import net.alloymc.api.event.player.PlayerJoinEvent;
-
-public class {class_name} implements ModInitializer, Listener {{
-
-    @Override
-    public void onInitialize() {{
-        AlloyAPI.getEventBus().register(this);
-    }}
-
-    @EventHandler
-    public void onPlayerJoin(PlayerJoinEvent event) {{
-        event.getPlayer().sendMessage("Hello from {name}!");
-    }}
-}}
-"#,
-            pkg = pkg,
-            mod_id = mod_id,
-            class_name = class_name,
-            name = args.name
-        ),
-        "client" => format!(
-            r#"package {pkg}.{mod_id};
-
-import net.alloymc.loader.api.ModInitializer;
-
-public class {class_name} implements ModInitializer {{
-
-    @Override
-    public void onInitialize() {{
-        // Client-side initialization
-        // Register screens, renderers, HUD elements, etc.
-    }}
-}}
-"#,
-            pkg = pkg,
-            mod_id = mod_id,
-            class_name = class_name
-        ),
-        _ => format!(
-            r#"package {pkg}.{mod_id};
-
-import net.alloymc.loader.api.ModInitializer;
-import net.alloymc.api.AlloyAPI;
-import net.alloymc.api.event.EventHandler;
-import net.alloymc.api.event.Listener;
-import net.alloymc.api.event.player.PlayerJoinEvent;
-
-public class {class_name} implements ModInitializer, Listener {{
-
-    @Override
-    public void onInitialize() {{
-        AlloyAPI.getEventBus().register(this);
-    }}
-
-    @EventHandler
-    public void onPlayerJoin(PlayerJoinEvent event) {{
-        event.getPlayer().sendMessage("Hello from {name}!");
-    }}
-}}
-"#,
-            pkg = pkg,
-            mod_id = mod_id,
-            class_name = class_name,
-            name = args.name
-        ),
-    };
-
-    fs::write(src_dir.join(format!("{}.java", class_name)), entry_class)
-        .map_err(|e| format!("Failed to write entry class: {}", e))?;
+    remember_project(&state, args.name, path_str, info.project_type.clone())?;
 
-    // libs/ directory (placeholder)
-    fs::create_dir_all(dir.join("libs"))
-        .map_err(|e| format!("Failed to create libs directory: {}", e))?;
-
-    Ok(())
-}
-
-fn scaffold_modpack_project(dir: &Path, args: &CreateProjectArgs) -> Result<(), String> {
-    let pack_toml = format!(
-        r#"[pack]
-name = "{}"
-version = "1.0.0"
-description = ""
-authors = []
-
-[alloy]
-version = "0.1.0"
-minecraft = "1.21.11"
-
-[mods]
-# Add mods here:
-# my-mod = {{ path = "../my-mod" }}
-# some-mod = {{ git = "https://github.com/user/some-mod.git", tag = "v1.0.0" }}
-"#,
-        args.name
-    );
-    fs::write(dir.join("alloy.pack.toml"), pack_toml)
-        .map_err(|e| format!("Failed to write alloy.pack.toml: {}", e))?;
-
-    // mods/ directory for local JAR imports
-    fs::create_dir_all(dir.join("mods"))
-        .map_err(|e| format!("Failed to create mods directory: {}", e))?;
-
-    // config/ directory for mod configs
-    fs::create_dir_all(dir.join("config"))
-        .map_err(|e| format!("Failed to create config directory: {}", e))?;
-
-    // .gitignore
-    let gitignore = "build/\n*.alloypack\n";
-    fs::write(dir.join(".gitignore"), gitignore)
-        .map_err(|e| format!("Failed to write .gitignore: {}", e))?;
-
-    Ok(())
-}
-
-fn to_pascal_case(s: &str) -> String {
-    s.split(|c: char| c == '-' || c == '_' || c == ' ')
-        .filter(|w| !w.is_empty())
-        .map(|word| {
-            let mut chars = word.chars();
-            match chars.next() {
-                Some(c) => c.to_uppercase().to_string() + &chars.as_str().to_lowercase(),
-                None => String::new(),
-            }
-        })
-        .collect()
+    Ok(CreateProjectResult {
+        project: info,
+        created_files: scaffold.created_files,
+        main_class: scaffold.main_class,
+    })
 }
@@ -6,7 +6,8 @@ use tauri::{AppHandle, Emitter, State};
 
 pub struct PtyInstance {
     writer: Box<dyn Write + Send>,
-    _child: Box<dyn portable_pty::Child + Send + Sync>,
+    master: Box<dyn portable_pty::MasterPty + Send>,
+    child: Box<dyn portable_pty::Child + Send + Sync>,
 }
 
 pub struct TerminalState {
@@ -55,7 +56,8 @@ pub async fn terminal_create(
             id.clone(),
             PtyInstance {
                 writer,
-                _child: child,
+                master: pair.master,
+                child,
             },
         );
     }
@@ -64,12 +66,24 @@ pub async fn terminal_create(
     let event_id = id.clone();
     std::thread::spawn(move || {
         let mut buf = [0u8; 4096];
+        // Bytes of a multi-byte character split across reads
+        let mut pending: Vec<u8> = Vec::new();
         loop {
             match reader.read(&mut buf) {
                 Ok(0) => break,
                 Ok(n) => {
-                    let data = String::from_utf8_lossy(&buf[..n]).to_string();
-                    let _ = app.emit(&format!("terminal:data:{}", event_id), data);
+                    pending.extend_from_slice(&buf[..n]);
+                    let valid = match std::str::from_utf8(&pending) {
+                        Ok(_) => pending.len(),
+                        // An incomplete sequence at the end waits for the next read
+                        Err(e) if e.error_len().is_none() => e.valid_up_to(),
+                        Err(_) => pending.len(),
+                    };
+                    let data = String::from_utf8_lossy(&pending[..valid]).to_string();
+                    pending.drain(..valid);
+                    if !data.is_empty() {
+                        let _ = app.emit(&format!("terminal:data:{}", event_id), data);
+                    }
                 }
                 Err(_) => break,
             }
@@ -102,11 +116,16 @@ pub async fn terminal_resize(
     cols: u16,
     term_state: State<'_, Arc<TerminalState>>,
 ) -> Result<(), String> {
-    // portable-pty doesn't expose resize on the writer directly
-    // The resize is handled by the master PTY, but we'd need to keep a reference.
-    // For now, this is a no-op — we'll enhance later if needed.
-    let _ = (id, rows, cols, term_state);
-    Ok(())
+    let ptys = term_state.ptys.lock().map_err(|e| e.to_string())?;
+    let pty = ptys.get(&id).ok_or("Terminal not found")?;
+    pty.master
+        .resize(PtySize {
+            rows,
+            cols,
+            pixel_width: 0,
+            pixel_height: 0,
+        })
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -115,6 +134,11 @@ pub async fn terminal_destroy(
     term_state: State<'_, Arc<TerminalState>>,
 ) -> Result<(), String> {
     let mut ptys = term_state.ptys.lock().map_err(|e| e.to_string())?;
-    ptys.remove(&id);
+    if let Some(mut pty) = ptys.remove(&id) {
+        // Dropping the master alone leaves the shell running until it notices
+        // the hangup, so kill and reap it here
+        let _ = pty.child.kill();
+        let _ = pty.child.wait();
+    }
     Ok(())
 }
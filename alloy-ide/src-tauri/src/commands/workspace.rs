@@ -1,11 +1,39 @@
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+/// Bump when the shape of `WorkspaceState` changes; `migrate` upgrades older files.
+pub const WORKSPACE_STATE_VERSION: u32 = 2;
+
+/// An open editor tab and where the user was in it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EditorTab {
+    pub path: String,
+    /// 1-based cursor line
+    #[serde(default = "one")]
+    pub cursor_line: u32,
+    /// 1-based cursor column
+    #[serde(default = "one")]
+    pub cursor_column: u32,
+    /// Vertical scroll offset in pixels
+    #[serde(default)]
+    pub scroll_top: f64,
+}
+
+fn one() -> u32 {
+    1
+}
+
+/// Everything needed to reopen a project where the user left off.
+/// Missing fields fall back to defaults so older and newer files still load.
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct WorkspaceState {
-    /// Paths of open files in tab order
-    pub open_files: Vec<String>,
+    /// Schema version of this file
+    pub version: u32,
+    /// Open tabs in tab order
+    pub open_tabs: Vec<EditorTab>,
     /// Path of the active (focused) file
     pub active_file: Option<String>,
     /// Which sidebar panel is shown
@@ -20,19 +48,77 @@ pub struct WorkspaceState {
     pub bottom_panel_visible: bool,
     /// Bottom panel height in pixels
     pub bottom_panel_height: f64,
+    /// Directories expanded in the file tree
+    pub expanded_dirs: Vec<String>,
+}
+
+impl Default for WorkspaceState {
+    fn default() -> Self {
+        Self {
+            version: WORKSPACE_STATE_VERSION,
+            open_tabs: Vec::new(),
+            active_file: None,
+            sidebar_panel: "files".to_string(),
+            sidebar_visible: true,
+            sidebar_width: 260.0,
+            bottom_panel: "terminal".to_string(),
+            bottom_panel_visible: false,
+            bottom_panel_height: 200.0,
+            expanded_dirs: Vec::new(),
+        }
+    }
+}
+
+fn state_path(project_path: &str) -> PathBuf {
+    Path::new(project_path).join(".alloy/workspace.json")
+}
+
+/// Where workspace state lived before it moved under `.alloy/`.
+fn legacy_state_path(project_path: &str) -> PathBuf {
+    Path::new(project_path).join(".alloy-ide/workspace.json")
+}
+
+/// Upgrade an older workspace file in place.
+fn migrate(value: &mut Value) {
+    if !value.is_object() {
+        return;
+    }
+    let version = value.get("version").and_then(|v| v.as_u64()).unwrap_or(1);
+    if version < 2 {
+        // v1 stored tabs as bare paths under `open_files`
+        if let Some(files) = value.as_object_mut().and_then(|o| o.remove("open_files")) {
+            let tabs: Vec<Value> = files
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|f| f.as_str())
+                .map(|path| serde_json::json!({ "path": path }))
+                .collect();
+            value["open_tabs"] = Value::Array(tabs);
+        }
+    }
+    value["version"] = Value::from(WORKSPACE_STATE_VERSION);
 }
 
 #[tauri::command]
 pub async fn save_workspace_state(project_path: String, state: WorkspaceState) -> Result<(), String> {
-    let ide_dir = Path::new(&project_path).join(".alloy-ide");
-    fs::create_dir_all(&ide_dir)
-        .map_err(|e| format!("Failed to create .alloy-ide dir: {}", e))?;
+    let state_path = state_path(&project_path);
+    if let Some(dir) = state_path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create .alloy dir: {}", e))?;
+    }
 
-    let state_path = ide_dir.join("workspace.json");
+    let state = WorkspaceState {
+        version: WORKSPACE_STATE_VERSION,
+        ..state
+    };
     let json = serde_json::to_string_pretty(&state)
         .map_err(|e| format!("Failed to serialize state: {}", e))?;
 
-    fs::write(&state_path, json)
+    // Write then rename so a crash mid-save never leaves a truncated file
+    let tmp_path = state_path.with_extension("json.tmp");
+    fs::write(&tmp_path, json)
+        .map_err(|e| format!("Failed to write workspace state: {}", e))?;
+    fs::rename(&tmp_path, &state_path)
         .map_err(|e| format!("Failed to write workspace state: {}", e))?;
 
     Ok(())
@@ -40,16 +126,21 @@ pub async fn save_workspace_state(project_path: String, state: WorkspaceState) -
 
 #[tauri::command]
 pub async fn load_workspace_state(project_path: String) -> Result<Option<WorkspaceState>, String> {
-    let state_path = Path::new(&project_path).join(".alloy-ide/workspace.json");
-
-    if !state_path.exists() {
+    let state_path = [state_path(&project_path), legacy_state_path(&project_path)]
+        .into_iter()
+        .find(|p| p.exists());
+    let Some(state_path) = state_path else {
         return Ok(None);
-    }
+    };
 
     let content = fs::read_to_string(&state_path)
         .map_err(|e| format!("Failed to read workspace state: {}", e))?;
 
-    let state: WorkspaceState = serde_json::from_str(&content)
+    let mut value: Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse workspace state: {}", e))?;
+    migrate(&mut value);
+
+    let state: WorkspaceState = serde_json::from_value(value)
         .map_err(|e| format!("Failed to parse workspace state: {}", e))?;
 
     Ok(Some(state))
@@ -7,6 +7,9 @@ use std::sync::Arc;
 
 pub fn run() {
     let app_state = Arc::new(state::AppState::new());
+    if let Ok(mut recents) = app_state.recent_projects.lock() {
+        *recents = commands::project::load_recent_projects();
+    }
     let mcp_state = app_state.clone();
     let term_state = Arc::new(commands::terminal::TerminalState::new());
     let watcher_state = commands::watcher::WatcherState::new();
@@ -23,6 +26,7 @@ pub fn run() {
             // Project commands
             commands::project::open_project,
             commands::project::get_recent_projects,
+            commands::project::remove_recent_project,
             commands::project::create_project,
             // Asset commands
             commands::assets::analyze_image,
@@ -48,6 +52,7 @@ pub fn run() {
             commands::filesystem::rename_path,
             commands::filesystem::search_files,
             commands::filesystem::list_all_files,
+            commands::filesystem::search_filenames,
             commands::filesystem::git_status,
             commands::filesystem::git_diff,
             commands::filesystem::git_stage,
@@ -55,10 +60,20 @@ pub fn run() {
             commands::filesystem::git_discard,
             commands::filesystem::git_commit,
             commands::filesystem::git_init,
+            commands::filesystem::git_reset,
             commands::filesystem::git_show_file,
             commands::filesystem::git_blame_file,
             commands::filesystem::git_push,
             commands::filesystem::git_pull,
+            commands::filesystem::git_branch_list,
+            commands::filesystem::git_create_branch,
+            commands::filesystem::git_checkout_branch,
+            commands::filesystem::git_stash_save,
+            commands::filesystem::git_stash_list,
+            commands::filesystem::git_stash_pop,
+            commands::filesystem::git_tag_list,
+            commands::filesystem::git_create_tag,
+            commands::filesystem::git_delete_tag,
             commands::filesystem::replace_in_files,
             commands::filesystem::search_files_advanced,
             commands::filesystem::copy_file_to,
@@ -70,7 +85,9 @@ pub fn run() {
             commands::terminal::terminal_destroy,
             // Build commands
             commands::build::run_gradle_task,
+            commands::build::cancel_gradle_task,
             commands::build::list_gradle_tasks,
+            commands::build::build_list_dependencies,
             commands::build::validate_environment,
             // Modpack commands
             commands::modpack::load_modpack_manifest,
@@ -83,6 +100,9 @@ pub fn run() {
             commands::modpack::save_mod_config,
             // AI commands
             commands::ai::ai_send_message,
+            commands::ai::ai_cancel,
+            commands::ai::ai_list_confirmations,
+            commands::ai::ai_resolve_confirmation,
             commands::ai::ai_get_history,
             commands::ai::ai_clear_history,
             commands::ai::ai_set_config,
@@ -112,7 +132,11 @@ pub fn run() {
             commands::watcher::unwatch_file,
             commands::watcher::unwatch_all,
         ])
-        .setup(move |_app| {
+        .setup(move |app| {
+            if let Ok(mut handle) = mcp_state.app_handle.lock() {
+                *handle = Some(app.handle().clone());
+            }
+
             // Spawn MCP server on background task (only if ALLOY_MCP env var is set)
             let state = mcp_state;
             tauri::async_runtime::spawn(async move {
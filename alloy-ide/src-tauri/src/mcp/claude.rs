@@ -1,13 +1,14 @@
 use serde_json::{json, Value};
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tauri::{AppHandle, Emitter};
 
 use crate::state::AppState;
+use super::providers::{AiProvider, AiResponse, ConfiguredProvider, TurnContext, TurnOutcome};
 use super::tools::ToolRegistry;
 use super::types::{ChatMessage, ChatRole, ContentBlock, ToolCall, ToolCallStatus};
 
-const ANTHROPIC_API_URL: &str = "https://api.anthropic.com/v1/messages";
 const SYSTEM_PROMPT: &str = r#"You are the Alloy IDE AI assistant — a helpful coding companion embedded in a Minecraft modding IDE.
 
 You have access to tools that let you interact with the IDE: reading/writing files, opening editors, running builds, executing terminal commands, and more.
@@ -26,7 +27,8 @@ When helping users:
 - Generate code that follows Alloy conventions
 - Explain what you're doing when using tools"#;
 
-/// Claude API client that manages conversations and runs the agentic tool loop.
+/// Chat client that manages conversations and runs the agentic tool loop
+/// against whichever provider is configured.
 pub struct ClaudeClient {
     http: reqwest::Client,
 }
@@ -38,7 +40,7 @@ impl ClaudeClient {
         }
     }
 
-    /// Send a user message and run the agentic loop until Claude responds with end_turn.
+    /// Send a user message and run the agentic loop until the model responds with end_turn.
     pub async fn send_message(
         &self,
         user_message: &str,
@@ -50,10 +52,11 @@ impl ClaudeClient {
             cfg.clone()
         };
 
-        let api_key = config
-            .api_key
-            .as_ref()
-            .ok_or("No API key configured. Set your Anthropic API key first.")?;
+        let provider = ConfiguredProvider::from_config(&self.http, &config)?;
+
+        // Starting a new message aborts any request still in flight
+        let generation = state.ai_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        state.ai_cancel.notify_waiters();
 
         // Add user message to history
         let user_msg = ChatMessage {
@@ -67,92 +70,38 @@ impl ClaudeClient {
             history.push(user_msg);
         }
 
-        // Build tool definitions for the API
-        let tools: Vec<Value> = ToolRegistry::definitions()
-            .iter()
-            .map(|t| {
-                json!({
-                    "name": t.name,
-                    "description": t.description,
-                    "input_schema": t.input_schema,
-                })
-            })
-            .collect();
+        let tools = ToolRegistry::definitions();
+        let ctx = TurnContext {
+            state,
+            app_handle,
+            generation,
+        };
 
         // Agentic loop
+        let mut rounds: u32 = 0;
         loop {
-            let messages = {
+            let history = {
                 let history = state.chat_history.lock().map_err(|e| e.to_string())?;
-                build_api_messages(&history)
+                history.clone()
             };
 
-            let body = json!({
-                "model": config.model,
-                "max_tokens": config.max_tokens,
-                "system": SYSTEM_PROMPT,
-                "tools": tools,
-                "messages": messages,
-            });
-
-            let response = self
-                .http
-                .post(ANTHROPIC_API_URL)
-                .header("x-api-key", api_key)
-                .header("anthropic-version", "2023-06-01")
-                .header("content-type", "application/json")
-                .json(&body)
-                .send()
-                .await
-                .map_err(|e| format!("HTTP request failed: {}", e))?;
-
-            if !response.status().is_success() {
-                let status = response.status();
-                let body = response.text().await.unwrap_or_default();
-                let err = format!("API error ({}): {}", status, body);
-                let _ = app_handle.emit("ai:error", json!({ "error": &err }));
-                return Err(err);
-            }
+            // Re-read each round so the model sees edits made since the last one
+            let system = match editor_context(state) {
+                Some(context) => format!("{}\n\n{}", SYSTEM_PROMPT, context),
+                None => SYSTEM_PROMPT.to_string(),
+            };
 
-            let resp_body: Value = response
-                .json()
-                .await
-                .map_err(|e| format!("Failed to parse response: {}", e))?;
-
-            let stop_reason = resp_body
-                .get("stop_reason")
-                .and_then(|v| v.as_str())
-                .unwrap_or("end_turn");
-
-            let content_blocks = resp_body
-                .get("content")
-                .and_then(|v| v.as_array())
-                .cloned()
-                .unwrap_or_default();
-
-            // Collect text and tool_use blocks
-            let mut assistant_text = String::new();
-            let mut tool_uses: Vec<(String, String, Value)> = Vec::new();
-
-            for block in &content_blocks {
-                match block.get("type").and_then(|v| v.as_str()) {
-                    Some("text") => {
-                        if let Some(text) = block.get("text").and_then(|v| v.as_str()) {
-                            assistant_text.push_str(text);
-                            let _ = app_handle.emit(
-                                "ai:response-chunk",
-                                json!({ "text": text }),
-                            );
-                        }
-                    }
-                    Some("tool_use") => {
-                        let id = block.get("id").and_then(|v| v.as_str()).unwrap_or("").to_string();
-                        let name = block.get("name").and_then(|v| v.as_str()).unwrap_or("").to_string();
-                        let input = block.get("input").cloned().unwrap_or(Value::Null);
-                        tool_uses.push((id, name, input));
-                    }
-                    _ => {}
+            let turn = match provider.send(&system, &history, &tools, &ctx).await? {
+                TurnOutcome::Complete(turn) => turn,
+                TurnOutcome::Cancelled(partial) => {
+                    return Self::finish_cancelled(partial, state, app_handle)
                 }
-            }
+            };
+            let AiResponse {
+                text: assistant_text,
+                tool_uses,
+                stop_reason,
+            } = turn;
 
             if tool_uses.is_empty() {
                 // No tool calls — conversation turn is done
@@ -176,7 +125,37 @@ impl ClaudeClient {
                 return Ok(assistant_msg);
             }
 
-            // Process tool calls
+            // Guard against a model that never stops calling tools
+            if rounds >= config.max_tool_iterations {
+                let note = format!(
+                    "Stopped after {} rounds of tool calls without a final answer.",
+                    config.max_tool_iterations
+                );
+                let content = if assistant_text.is_empty() {
+                    note
+                } else {
+                    format!("{}\n\n{}", assistant_text, note)
+                };
+                let assistant_msg = ChatMessage {
+                    role: ChatRole::Assistant,
+                    content,
+                    tool_calls: vec![],
+                    timestamp: now(),
+                };
+                {
+                    let mut history = state.chat_history.lock().map_err(|e| e.to_string())?;
+                    history.push(assistant_msg.clone());
+                }
+                let _ = app_handle.emit(
+                    "ai:response-done",
+                    json!({ "message": assistant_msg }),
+                );
+                return Ok(assistant_msg);
+            }
+            rounds += 1;
+
+            // Process tool calls. Failures go back to the model as error
+            // results so it can recover, rather than ending the turn.
             let mut tool_call_records: Vec<ToolCall> = Vec::new();
             let mut tool_results_for_api: Vec<Value> = Vec::new();
 
@@ -212,6 +191,7 @@ impl ClaudeClient {
                 tc_done.result = Some(result_text.clone());
 
                 let _ = app_handle.emit("ai:tool-done", json!({ "tool_call": tc_done }));
+                state.queue_editor_action(json!({ "action": "tool_call", "tool_call": &tc_done }));
 
                 tool_call_records.push(tc_done);
 
@@ -246,6 +226,10 @@ impl ClaudeClient {
                 });
             }
 
+            if state.ai_generation.load(Ordering::SeqCst) != generation {
+                return Self::finish_cancelled(String::new(), state, app_handle);
+            }
+
             // If stop_reason is "end_turn", we're done even with tools
             if stop_reason == "end_turn" {
                 let final_msg = ChatMessage {
@@ -264,71 +248,77 @@ impl ClaudeClient {
             // Continue the loop — Claude wants to make more tool calls or generate more text
         }
     }
-}
 
-/// Build the messages array for the Claude API from chat history.
-fn build_api_messages(history: &[ChatMessage]) -> Vec<Value> {
-    let mut messages: Vec<Value> = Vec::new();
-
-    for msg in history {
-        match msg.role {
-            ChatRole::User => {
-                if msg.tool_calls.is_empty() {
-                    // Regular user message
-                    messages.push(json!({
-                        "role": "user",
-                        "content": msg.content,
-                    }));
-                } else {
-                    // Tool results (sent as user role per API spec)
-                    let content: Vec<Value> = msg
-                        .tool_calls
-                        .iter()
-                        .map(|tc| {
-                            json!({
-                                "type": "tool_result",
-                                "tool_use_id": tc.id,
-                                "content": tc.result.as_deref().unwrap_or(""),
-                                "is_error": tc.status == ToolCallStatus::Error,
-                            })
-                        })
-                        .collect();
-                    messages.push(json!({
-                        "role": "user",
-                        "content": content,
-                    }));
-                }
-            }
-            ChatRole::Assistant => {
-                let mut content: Vec<Value> = Vec::new();
-
-                if !msg.content.is_empty() {
-                    content.push(json!({
-                        "type": "text",
-                        "text": msg.content,
-                    }));
-                }
+    /// Record an aborted turn: partial text (if any) is kept in history so it
+    /// matches what the UI already showed, and the frontend is told to stop.
+    fn finish_cancelled(
+        partial_text: String,
+        state: &Arc<AppState>,
+        app_handle: &AppHandle,
+    ) -> Result<ChatMessage, String> {
+        let msg = ChatMessage {
+            role: ChatRole::Assistant,
+            content: partial_text,
+            tool_calls: vec![],
+            timestamp: now(),
+        };
+        if !msg.content.is_empty() {
+            let mut history = state.chat_history.lock().map_err(|e| e.to_string())?;
+            history.push(msg.clone());
+        }
+        let _ = app_handle.emit("ai:cancelled", json!({ "message": &msg }));
+        Ok(msg)
+    }
+}
 
-                for tc in &msg.tool_calls {
-                    content.push(json!({
-                        "type": "tool_use",
-                        "id": tc.id,
-                        "name": tc.name,
-                        "input": tc.input,
-                    }));
-                }
+/// Diagnostics listed in the editor context before the rest are summarised.
+const MAX_CONTEXT_DIAGNOSTICS: usize = 20;
+
+/// Describe the active file, selection and diagnostics for the system prompt,
+/// so requests like "fix the error on this line" need no tool call to locate.
+fn editor_context(state: &AppState) -> Option<String> {
+    let file = state.active_editor_file.lock().ok().and_then(|f| f.clone())?;
+    let mut context = format!("Current editor state:\n- Active file: {}", file);
+
+    if let Some(range) = state.editor_selection_range.lock().ok().and_then(|r| r.clone()) {
+        if (range.start_line, range.start_col) == (range.end_line, range.end_col) {
+            context.push_str(&format!(
+                "\n- Cursor: line {}, column {}",
+                range.start_line, range.start_col
+            ));
+        } else {
+            context.push_str(&format!(
+                "\n- Selection: line {}, column {} to line {}, column {}",
+                range.start_line, range.start_col, range.end_line, range.end_col
+            ));
+        }
+    }
 
-                if !content.is_empty() {
-                    messages.push(json!({
-                        "role": "assistant",
-                        "content": content,
-                    }));
-                }
-            }
+    let diagnostics = state
+        .active_file_diagnostics
+        .lock()
+        .map(|d| d.clone())
+        .unwrap_or_default();
+    if !diagnostics.is_empty() {
+        context.push_str("\n- Diagnostics in the active file:");
+        for d in diagnostics.iter().take(MAX_CONTEXT_DIAGNOSTICS) {
+            context.push_str(&format!(
+                "\n  - line {}, column {}: {}: {}",
+                d.line + 1,
+                d.character + 1,
+                d.severity,
+                d.message
+            ));
+        }
+        if diagnostics.len() > MAX_CONTEXT_DIAGNOSTICS {
+            context.push_str(&format!(
+                "\n  - ...and {} more",
+                diagnostics.len() - MAX_CONTEXT_DIAGNOSTICS
+            ));
         }
     }
 
-    messages
+    Some(context)
 }
 
 fn now() -> u64 {
@@ -1,4 +1,7 @@
 pub mod claude;
+pub mod patch;
+pub mod permissions;
+pub mod providers;
 pub mod server;
 pub mod tools;
 pub mod types;
@@ -0,0 +1,369 @@
+//! Unified diff application for the `editor_apply_diff` tool.
+//!
+//! Hunks are located by their context rather than trusting line numbers, so a
+//! patch still applies when the file has shifted since the diff was produced.
+
+use serde::Serialize;
+use std::path::Path;
+
+/// Context lines that may be dropped from each end of a hunk when it does
+/// not match as written, like `patch --fuzz=2`.
+const MAX_FUZZ: usize = 2;
+
+/// (tag, text) where tag is ' ', '-' or '+'
+type HunkLine = (char, String);
+
+#[derive(Debug, Clone)]
+struct Hunk {
+    header: String,
+    old_start: usize,
+    lines: Vec<HunkLine>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HunkReport {
+    pub index: usize,
+    pub header: String,
+    pub applied: bool,
+    /// Line (1-based) in the original content where the hunk matched
+    pub matched_at: Option<usize>,
+    pub fuzz: usize,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PatchOutcome {
+    #[serde(skip)]
+    pub content: Option<String>,
+    pub hunks: Vec<HunkReport>,
+}
+
+/// The hunks of one file in a patch, with the paths its headers name.
+#[derive(Debug, Default)]
+struct FilePatch {
+    old_path: Option<String>,
+    new_path: Option<String>,
+    hunks: Vec<Hunk>,
+}
+
+impl FilePatch {
+    /// Whether a header names `path`. Paths are compared by their trailing
+    /// components, since diffs are usually relative to the repository root.
+    fn touches(&self, path: &str) -> bool {
+        [&self.new_path, &self.old_path]
+            .into_iter()
+            .flatten()
+            .any(|p| p != "/dev/null" && Path::new(path).ends_with(p))
+    }
+}
+
+/// Path from a "--- "/"+++ " header: without git's a/ or b/ prefix, and
+/// without the timestamp diff -u appends after a tab.
+fn header_path(raw: &str, git_prefix: &str) -> String {
+    let raw = raw.split('\t').next().unwrap_or(raw).trim_end();
+    raw.strip_prefix(git_prefix).unwrap_or(raw).to_string()
+}
+
+/// Split a patch into per-file sections. A new file starts at "diff --git"
+/// or at a "--- " line directly followed by "+++ ", so the next file's
+/// headers are never mistaken for lines of the previous hunk.
+fn parse_patch(patch: &str) -> Result<Vec<FilePatch>, String> {
+    let mut files: Vec<FilePatch> = Vec::new();
+    let mut lines = patch.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            files.push(FilePatch {
+                new_path: rest.rsplit_once(" b/").map(|(_, new)| new.to_string()),
+                ..FilePatch::default()
+            });
+            continue;
+        }
+
+        if let Some(old) = line.strip_prefix("--- ") {
+            if let Some(new) = lines.peek().and_then(|l| l.strip_prefix("+++ ")) {
+                // "diff --git" already opened this file's section
+                if files.last().is_none_or(|f| !f.hunks.is_empty()) {
+                    files.push(FilePatch::default());
+                }
+                if let Some(file) = files.last_mut() {
+                    file.old_path = Some(header_path(old, "a/"));
+                    file.new_path = Some(header_path(new, "b/"));
+                }
+                lines.next();
+                continue;
+            }
+        }
+
+        if line.starts_with("@@") {
+            // "@@ -12,5 +12,6 @@ optional section"
+            let old_start = line
+                .split_whitespace()
+                .nth(1)
+                .and_then(|r| r.strip_prefix('-'))
+                .and_then(|r| r.split(',').next())
+                .and_then(|n| n.parse::<usize>().ok())
+                .ok_or_else(|| format!("Malformed hunk header: {}", line))?;
+            if files.is_empty() {
+                files.push(FilePatch::default());
+            }
+            if let Some(file) = files.last_mut() {
+                file.hunks.push(Hunk {
+                    header: line.to_string(),
+                    old_start,
+                    lines: Vec::new(),
+                });
+            }
+            continue;
+        }
+
+        let Some(hunk) = files.last_mut().and_then(|f| f.hunks.last_mut()) else {
+            // Other file headers ("index", mode lines) before the first hunk
+            continue;
+        };
+
+        if line.starts_with("\\") {
+            // "\ No newline at end of file"
+            continue;
+        }
+        match line.chars().next() {
+            Some(tag @ (' ' | '-' | '+')) => hunk.lines.push((tag, line[1..].to_string())),
+            // Some tools strip the space from blank context lines
+            None => hunk.lines.push((' ', String::new())),
+            _ => {}
+        }
+    }
+
+    Ok(files)
+}
+
+/// The hunks of `patch` that apply to `path`. A single-file patch applies
+/// whatever its headers say; a multi-file patch contributes only the
+/// sections that name `path`.
+fn parse_hunks(patch: &str, path: &str) -> Result<Vec<Hunk>, String> {
+    let files: Vec<FilePatch> = parse_patch(patch)?
+        .into_iter()
+        .filter(|f| !f.hunks.is_empty())
+        .collect();
+
+    let hunks: Vec<Hunk> = match files.len() {
+        0 => return Err("Patch contains no hunks".to_string()),
+        1 => files.into_iter().flat_map(|f| f.hunks).collect(),
+        n => {
+            let hunks: Vec<Hunk> = files
+                .into_iter()
+                .filter(|f| f.touches(path))
+                .flat_map(|f| f.hunks)
+                .collect();
+            if hunks.is_empty() {
+                return Err(format!("Patch changes {} files, none of them {}", n, path));
+            }
+            hunks
+        }
+    };
+    Ok(hunks)
+}
+
+/// Find `needle` in `haystack`, preferring the match closest to `expected`.
+fn find_block(
+    haystack: &[String],
+    needle: &[String],
+    expected: usize,
+    loose: bool,
+) -> Option<usize> {
+    if needle.is_empty() {
+        return Some(expected.min(haystack.len()));
+    }
+    if needle.len() > haystack.len() {
+        return None;
+    }
+
+    let eq = |a: &str, b: &str| {
+        if loose {
+            a.split_whitespace().eq(b.split_whitespace())
+        } else {
+            a == b
+        }
+    };
+    let matches_at = |start: usize| {
+        needle
+            .iter()
+            .zip(&haystack[start..start + needle.len()])
+            .all(|(n, h)| eq(n, h))
+    };
+
+    let last = haystack.len() - needle.len();
+    let expected = expected.min(last);
+    for distance in 0..=last {
+        if let Some(start) = expected.checked_sub(distance) {
+            if matches_at(start) {
+                return Some(start);
+            }
+        }
+        let start = expected + distance;
+        if distance > 0 && start <= last && matches_at(start) {
+            return Some(start);
+        }
+    }
+    None
+}
+
+/// Apply the part of a unified diff that targets `path` to `original`, the
+/// current content of that file. Either every hunk applies and `content`
+/// holds the result, or `content` is `None` and the reports say which hunks
+/// were rejected.
+pub fn apply_unified_diff(path: &str, original: &str, patch: &str) -> Result<PatchOutcome, String> {
+    let hunks = parse_hunks(patch, path)?;
+
+    let trailing_newline = original.ends_with('\n');
+    let mut lines: Vec<String> = original.lines().map(String::from).collect();
+    let mut reports: Vec<HunkReport> = Vec::new();
+    // Shift between original line numbers and the current buffer
+    let mut offset: isize = 0;
+    let mut all_applied = true;
+
+    for (index, hunk) in hunks.iter().enumerate() {
+        let leading = hunk.lines.iter().take_while(|(t, _)| *t == ' ').count();
+        let trailing = hunk.lines.iter().rev().take_while(|(t, _)| *t == ' ').count();
+
+        let mut placed: Option<(usize, usize, Vec<HunkLine>)> = None;
+        'fuzz: for fuzz in 0..=MAX_FUZZ {
+            let drop_front = fuzz.min(leading);
+            let drop_back = fuzz.min(trailing);
+            if drop_front + drop_back >= hunk.lines.len() {
+                break;
+            }
+            let body = &hunk.lines[drop_front..hunk.lines.len() - drop_back];
+            let old: Vec<String> = body
+                .iter()
+                .filter(|(t, _)| *t != '+')
+                .map(|(_, l)| l.clone())
+                .collect();
+
+            let expected = (hunk.old_start.saturating_sub(1) + drop_front) as isize + offset;
+            let expected = expected.max(0) as usize;
+            for loose in [false, true] {
+                if let Some(start) = find_block(&lines, &old, expected, loose) {
+                    placed = Some((start, fuzz, body.to_vec()));
+                    break 'fuzz;
+                }
+            }
+        }
+
+        match placed {
+            Some((start, fuzz, body)) => {
+                let old_len = body.iter().filter(|(t, _)| *t != '+').count();
+                let new: Vec<String> = body
+                    .iter()
+                    .filter(|(t, _)| *t != '-')
+                    .map(|(_, l)| l.clone())
+                    .collect();
+                let new_len = new.len();
+                let original_line = (start as isize - offset) as usize + 1;
+                lines.splice(start..start + old_len, new);
+                offset += new_len as isize - old_len as isize;
+                reports.push(HunkReport {
+                    index,
+                    header: hunk.header.clone(),
+                    applied: true,
+                    matched_at: Some(original_line),
+                    fuzz,
+                    reason: None,
+                });
+            }
+            None => {
+                all_applied = false;
+                reports.push(HunkReport {
+                    index,
+                    header: hunk.header.clone(),
+                    applied: false,
+                    matched_at: None,
+                    fuzz: 0,
+                    reason: Some("Context does not match the current content".to_string()),
+                });
+            }
+        }
+    }
+
+    let content = all_applied.then(|| {
+        let mut result = lines.join("\n");
+        if trailing_newline && !result.is_empty() {
+            result.push('\n');
+        }
+        result
+    });
+
+    Ok(PatchOutcome {
+        content,
+        hunks: reports,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TWO_FILES: &str = "\
+diff --git a/src/first.txt b/src/first.txt
+index 1111111..2222222 100644
+--- a/src/first.txt
++++ b/src/first.txt
+@@ -1,3 +1,3 @@
+ one
+-two
++TWO
+ three
+diff --git a/src/second.txt b/src/second.txt
+index 3333333..4444444 100644
+--- a/src/second.txt
++++ b/src/second.txt
+@@ -1,2 +1,2 @@
+-alpha
++ALPHA
+ beta
+";
+
+    #[test]
+    fn multi_file_patch_keeps_the_next_file_headers_out_of_the_hunk() {
+        let first = apply_unified_diff("/project/src/first.txt", "one\ntwo\nthree\n", TWO_FILES)
+            .unwrap();
+        assert_eq!(first.hunks.len(), 1);
+        assert_eq!(first.content.as_deref(), Some("one\nTWO\nthree\n"));
+
+        let second = apply_unified_diff("/project/src/second.txt", "alpha\nbeta\n", TWO_FILES)
+            .unwrap();
+        assert_eq!(second.hunks.len(), 1);
+        assert_eq!(second.content.as_deref(), Some("ALPHA\nbeta\n"));
+    }
+
+    #[test]
+    fn multi_file_patch_without_git_headers() {
+        let patch = "\
+--- first.txt\t2024-02-29 12:00:00.000000000 +0000
++++ first.txt\t2024-02-29 12:00:01.000000000 +0000
+@@ -1 +1 @@
+-old
++new
+--- second.txt\t2024-02-29 12:00:00.000000000 +0000
++++ second.txt\t2024-02-29 12:00:01.000000000 +0000
+@@ -1 +1 @@
+-left
++right
+";
+        let outcome = apply_unified_diff("second.txt", "left\n", patch).unwrap();
+        assert_eq!(outcome.content.as_deref(), Some("right\n"));
+    }
+
+    #[test]
+    fn multi_file_patch_must_name_the_path() {
+        let err = apply_unified_diff("other.txt", "one\n", TWO_FILES).unwrap_err();
+        assert!(err.contains("none of them other.txt"), "{}", err);
+    }
+
+    #[test]
+    fn single_file_patch_applies_whatever_its_headers_say() {
+        let patch = "--- a/old-name.txt\n+++ b/old-name.txt\n@@ -1 +1 @@\n-x\n+y\n";
+        let outcome = apply_unified_diff("renamed.txt", "x\n", patch).unwrap();
+        assert_eq!(outcome.content.as_deref(), Some("y\n"));
+    }
+}
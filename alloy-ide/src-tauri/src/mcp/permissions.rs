@@ -0,0 +1,97 @@
+//! Confirmation gating for tool calls.
+//!
+//! Every tool is classified by how much damage it can do. Depending on the
+//! configured `ApprovalPolicy`, risky calls are parked as pending
+//! confirmations instead of running, and only execute once the user approves
+//! them from the frontend.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::Emitter;
+
+use crate::state::AppState;
+use super::types::{ApprovalPolicy, ToolResult};
+
+static NEXT_CONFIRMATION_ID: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolRisk {
+    ReadOnly,
+    Mutating,
+    Destructive,
+}
+
+/// A gated tool call waiting on the user.
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingConfirmation {
+    pub id: String,
+    pub tool: String,
+    pub input: Value,
+    pub risk: ToolRisk,
+    pub created_at: u64,
+}
+
+/// How risky a tool is. Unknown tools are treated as destructive.
+pub fn classify(name: &str) -> ToolRisk {
+    match name {
+        "project_get_info" | "project_list_recent" | "fs_list_directory" | "fs_read_file"
+        | "fs_search" | "editor_get_content" | "editor_get_selection" | "editor_list_open"
+        | "build_get_errors" | "terminal_get_output" => ToolRisk::ReadOnly,
+        "project_open" | "fs_write_file" | "fs_create_file" | "editor_open_file"
+        | "editor_set_content" | "editor_insert_at" | "editor_apply_diff"
+        | "editor_close_file" | "build_run" | "build_cancel" => ToolRisk::Mutating,
+        // Deletes, renames over existing files, and arbitrary shell commands
+        _ => ToolRisk::Destructive,
+    }
+}
+
+impl ApprovalPolicy {
+    pub fn requires_confirmation(self, risk: ToolRisk) -> bool {
+        match self {
+            ApprovalPolicy::AutoApprove => false,
+            ApprovalPolicy::ConfirmDestructive => risk == ToolRisk::Destructive,
+            ApprovalPolicy::ConfirmWrites => risk != ToolRisk::ReadOnly,
+        }
+    }
+}
+
+/// Park a tool call until the user decides, and tell the caller it is pending.
+pub fn request_confirmation(name: &str, params: Value, state: &AppState) -> ToolResult {
+    let pending = PendingConfirmation {
+        id: format!("confirm_{}", NEXT_CONFIRMATION_ID.fetch_add(1, Ordering::Relaxed)),
+        tool: name.to_string(),
+        input: params,
+        risk: classify(name),
+        created_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+    };
+
+    if let Ok(mut confirmations) = state.pending_confirmations.lock() {
+        confirmations.push(pending.clone());
+    }
+    state.queue_editor_action(json!({ "action": "confirm_tool", "confirmation": &pending }));
+    if let Some(app) = state.app_handle.lock().ok().and_then(|h| h.clone()) {
+        let _ = app.emit("ai:confirmation-requested", json!({ "confirmation": &pending }));
+    }
+
+    ToolResult::json(&json!({
+        "status": "awaiting_confirmation",
+        "confirmation_id": pending.id,
+        "message": format!(
+            "{} needs the user's approval and has not run yet. It will run if they approve it; do not retry the call.",
+            name
+        ),
+    }))
+}
+
+/// Remove and return a pending confirmation by id.
+pub fn take_confirmation(id: &str, state: &AppState) -> Option<PendingConfirmation> {
+    let mut confirmations = state.pending_confirmations.lock().ok()?;
+    let index = confirmations.iter().position(|c| c.id == id)?;
+    Some(confirmations.remove(index))
+}
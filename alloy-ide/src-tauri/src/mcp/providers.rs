@@ -0,0 +1,676 @@
+//! Chat backends for the built-in assistant.
+//!
+//! Each provider translates the shared chat history and tool definitions into
+//! its own wire format and returns one assistant turn. The agentic loop in
+//! `claude.rs` runs tools and feeds results back the same way for all of them.
+
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter};
+
+use crate::state::AppState;
+use super::types::{AiConfig, AiProviderKind, ChatMessage, ChatRole, ToolCall, ToolCallStatus, ToolDefinition};
+
+const ANTHROPIC_API_URL: &str = "https://api.anthropic.com";
+const OPENAI_API_URL: &str = "https://api.openai.com/v1";
+const OLLAMA_API_URL: &str = "http://localhost:11434";
+
+/// Ollama does not assign tool call ids, so we hand out our own.
+static NEXT_CALL_ID: AtomicU64 = AtomicU64::new(1);
+
+/// One assistant turn, normalized across providers.
+pub(crate) struct AiResponse {
+    pub text: String,
+    /// (id, name, input)
+    pub tool_uses: Vec<(String, String, Value)>,
+    /// Anthropic vocabulary: "end_turn", "tool_use", "max_tokens"
+    pub stop_reason: String,
+}
+
+pub(crate) enum TurnOutcome {
+    Complete(AiResponse),
+    /// Aborted mid-request, with the text received so far
+    Cancelled(String),
+}
+
+/// What a provider needs to report progress and notice cancellation.
+pub(crate) struct TurnContext<'a> {
+    pub state: &'a Arc<AppState>,
+    pub app_handle: &'a AppHandle,
+    pub generation: u64,
+}
+
+impl TurnContext<'_> {
+    fn is_cancelled(&self) -> bool {
+        self.state.ai_generation.load(Ordering::SeqCst) != self.generation
+    }
+
+    fn emit_text(&self, text: &str) {
+        let _ = self.app_handle.emit("ai:response-chunk", json!({ "text": text }));
+    }
+
+    fn fail(&self, err: String) -> String {
+        let _ = self.app_handle.emit("ai:error", json!({ "error": &err }));
+        err
+    }
+
+    /// Drive `fut` to completion unless the message is cancelled first.
+    async fn until_cancelled<F: Future>(&self, fut: F) -> Option<F::Output> {
+        tokio::pin!(fut);
+        loop {
+            // Created before the generation check so a cancel in between still wakes us
+            let cancelled = self.state.ai_cancel.notified();
+            if self.is_cancelled() {
+                return None;
+            }
+            tokio::select! {
+                out = &mut fut => return Some(out),
+                _ = cancelled => continue,
+            }
+        }
+    }
+
+    /// Send `request`, turning transport failures and non-2xx statuses into errors.
+    async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Option<Result<reqwest::Response, String>> {
+        let response = match self.until_cancelled(request.send()).await? {
+            Ok(response) => response,
+            Err(e) => return Some(Err(self.fail(format!("HTTP request failed: {}", e)))),
+        };
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = self.until_cancelled(response.text()).await?.unwrap_or_default();
+            return Some(Err(self.fail(format!("API error ({}): {}", status, body))));
+        }
+        Some(Ok(response))
+    }
+}
+
+pub(crate) trait AiProvider {
+    async fn send(
+        &self,
+        system: &str,
+        history: &[ChatMessage],
+        tools: &[ToolDefinition],
+        ctx: &TurnContext<'_>,
+    ) -> Result<TurnOutcome, String>;
+}
+
+/// The provider selected in `AiConfig`.
+pub(crate) enum ConfiguredProvider {
+    Anthropic(AnthropicProvider),
+    OpenAi(OpenAiProvider),
+    Ollama(OllamaProvider),
+}
+
+impl ConfiguredProvider {
+    pub fn from_config(http: &reqwest::Client, config: &AiConfig) -> Result<Self, String> {
+        let base_url = |default: &str| {
+            config
+                .base_url
+                .as_deref()
+                .map(str::trim)
+                .filter(|u| !u.is_empty())
+                .unwrap_or(default)
+                .trim_end_matches('/')
+                .to_string()
+        };
+        let api_key = config.api_key.clone().filter(|k| !k.trim().is_empty());
+
+        Ok(match config.provider {
+            AiProviderKind::Anthropic => Self::Anthropic(AnthropicProvider {
+                http: http.clone(),
+                url: format!("{}/v1/messages", base_url(ANTHROPIC_API_URL)),
+                api_key: api_key.ok_or("No API key configured. Set your Anthropic API key first.")?,
+                model: config.model.clone(),
+                max_tokens: config.max_tokens,
+            }),
+            AiProviderKind::OpenAi => Self::OpenAi(OpenAiProvider {
+                http: http.clone(),
+                url: format!("{}/chat/completions", base_url(OPENAI_API_URL)),
+                api_key: api_key.ok_or("No API key configured. Set your OpenAI API key first.")?,
+                model: config.model.clone(),
+                max_tokens: config.max_tokens,
+            }),
+            AiProviderKind::Ollama => Self::Ollama(OllamaProvider {
+                http: http.clone(),
+                url: format!("{}/api/chat", base_url(OLLAMA_API_URL)),
+                api_key,
+                model: config.model.clone(),
+                max_tokens: config.max_tokens,
+            }),
+        })
+    }
+}
+
+impl AiProvider for ConfiguredProvider {
+    async fn send(
+        &self,
+        system: &str,
+        history: &[ChatMessage],
+        tools: &[ToolDefinition],
+        ctx: &TurnContext<'_>,
+    ) -> Result<TurnOutcome, String> {
+        match self {
+            Self::Anthropic(p) => p.send(system, history, tools, ctx).await,
+            Self::OpenAi(p) => p.send(system, history, tools, ctx).await,
+            Self::Ollama(p) => p.send(system, history, tools, ctx).await,
+        }
+    }
+}
+
+// ── Anthropic ──────────────────────────────────────────────────────────
+
+/// Anthropic Messages API, streamed over SSE.
+pub(crate) struct AnthropicProvider {
+    http: reqwest::Client,
+    url: String,
+    api_key: String,
+    model: String,
+    max_tokens: u32,
+}
+
+impl AiProvider for AnthropicProvider {
+    async fn send(
+        &self,
+        system: &str,
+        history: &[ChatMessage],
+        tools: &[ToolDefinition],
+        ctx: &TurnContext<'_>,
+    ) -> Result<TurnOutcome, String> {
+        let tools: Vec<Value> = tools
+            .iter()
+            .map(|t| {
+                json!({
+                    "name": t.name,
+                    "description": t.description,
+                    "input_schema": t.input_schema,
+                })
+            })
+            .collect();
+
+        let body = json!({
+            "model": self.model,
+            "max_tokens": self.max_tokens,
+            "system": system,
+            "tools": tools,
+            "messages": anthropic_messages(history),
+            "stream": true,
+        });
+
+        let request = self
+            .http
+            .post(&self.url)
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", "2023-06-01")
+            .header("content-type", "application/json")
+            .json(&body);
+        let Some(response) = ctx.send(request).await else {
+            return Ok(TurnOutcome::Cancelled(String::new()));
+        };
+        let mut response = response?;
+
+        let mut turn = AiResponse {
+            text: String::new(),
+            tool_uses: Vec::new(),
+            stop_reason: "end_turn".to_string(),
+        };
+        // Tool inputs arrive as JSON fragments, keyed by content block index
+        let mut pending_tools: BTreeMap<u64, (String, String, String)> = BTreeMap::new();
+        let mut buffer: Vec<u8> = Vec::new();
+
+        loop {
+            let Some(chunk) = ctx.until_cancelled(response.chunk()).await else {
+                return Ok(TurnOutcome::Cancelled(turn.text));
+            };
+            let chunk = chunk.map_err(|e| format!("Stream error: {}", e))?;
+            let Some(chunk) = chunk else { break };
+            buffer.extend_from_slice(&chunk);
+
+            // SSE events are separated by a blank line
+            while let Some(end) = buffer.windows(2).position(|w| w == b"\n\n") {
+                let event: Vec<u8> = buffer.drain(..end + 2).collect();
+                let event = String::from_utf8_lossy(&event);
+                let data: String = event
+                    .lines()
+                    .filter_map(|l| l.strip_prefix("data:"))
+                    .map(str::trim_start)
+                    .collect();
+                if data.is_empty() {
+                    continue;
+                }
+                let Ok(data) = serde_json::from_str::<Value>(&data) else {
+                    continue;
+                };
+
+                match data.get("type").and_then(|v| v.as_str()) {
+                    Some("content_block_start") => {
+                        let index = data.get("index").and_then(|v| v.as_u64()).unwrap_or(0);
+                        let block = data.get("content_block").cloned().unwrap_or(Value::Null);
+                        if block.get("type").and_then(|v| v.as_str()) == Some("tool_use") {
+                            let id = block.get("id").and_then(|v| v.as_str()).unwrap_or("").to_string();
+                            let name = block.get("name").and_then(|v| v.as_str()).unwrap_or("").to_string();
+                            pending_tools.insert(index, (id, name, String::new()));
+                        }
+                    }
+                    Some("content_block_delta") => {
+                        let index = data.get("index").and_then(|v| v.as_u64()).unwrap_or(0);
+                        let delta = data.get("delta").cloned().unwrap_or(Value::Null);
+                        match delta.get("type").and_then(|v| v.as_str()) {
+                            Some("text_delta") => {
+                                if let Some(text) = delta.get("text").and_then(|v| v.as_str()) {
+                                    turn.text.push_str(text);
+                                    ctx.emit_text(text);
+                                }
+                            }
+                            Some("input_json_delta") => {
+                                if let (Some(tool), Some(part)) = (
+                                    pending_tools.get_mut(&index),
+                                    delta.get("partial_json").and_then(|v| v.as_str()),
+                                ) {
+                                    tool.2.push_str(part);
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                    Some("message_delta") => {
+                        if let Some(reason) = data
+                            .get("delta")
+                            .and_then(|d| d.get("stop_reason"))
+                            .and_then(|v| v.as_str())
+                        {
+                            turn.stop_reason = reason.to_string();
+                        }
+                    }
+                    Some("error") => {
+                        return Err(ctx.fail(format!(
+                            "API error: {}",
+                            data.get("error").cloned().unwrap_or(Value::Null)
+                        )));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        for (id, name, input) in pending_tools.into_values() {
+            let input = if input.trim().is_empty() {
+                json!({})
+            } else {
+                serde_json::from_str(&input).unwrap_or(Value::Null)
+            };
+            turn.tool_uses.push((id, name, input));
+        }
+
+        Ok(TurnOutcome::Complete(turn))
+    }
+}
+
+/// Build the messages array for the Anthropic API from chat history.
+fn anthropic_messages(history: &[ChatMessage]) -> Vec<Value> {
+    let mut messages: Vec<Value> = Vec::new();
+
+    for msg in history {
+        match msg.role {
+            ChatRole::User => {
+                if msg.tool_calls.is_empty() {
+                    // Regular user message
+                    messages.push(json!({
+                        "role": "user",
+                        "content": msg.content,
+                    }));
+                } else {
+                    // Tool results (sent as user role per API spec)
+                    let content: Vec<Value> = msg
+                        .tool_calls
+                        .iter()
+                        .map(|tc| {
+                            json!({
+                                "type": "tool_result",
+                                "tool_use_id": tc.id,
+                                "content": tc.result.as_deref().unwrap_or(""),
+                                "is_error": tc.status == ToolCallStatus::Error,
+                            })
+                        })
+                        .collect();
+                    messages.push(json!({
+                        "role": "user",
+                        "content": content,
+                    }));
+                }
+            }
+            ChatRole::Assistant => {
+                let mut content: Vec<Value> = Vec::new();
+
+                if !msg.content.is_empty() {
+                    content.push(json!({
+                        "type": "text",
+                        "text": msg.content,
+                    }));
+                }
+
+                for tc in &msg.tool_calls {
+                    content.push(json!({
+                        "type": "tool_use",
+                        "id": tc.id,
+                        "name": tc.name,
+                        "input": tc.input,
+                    }));
+                }
+
+                if !content.is_empty() {
+                    messages.push(json!({
+                        "role": "assistant",
+                        "content": content,
+                    }));
+                }
+            }
+        }
+    }
+
+    messages
+}
+
+/// OpenAI and Ollama share the "function" tool shape.
+fn function_tools(tools: &[ToolDefinition]) -> Vec<Value> {
+    tools
+        .iter()
+        .map(|t| {
+            json!({
+                "type": "function",
+                "function": {
+                    "name": t.name,
+                    "description": t.description,
+                    "parameters": t.input_schema,
+                },
+            })
+        })
+        .collect()
+}
+
+/// Tool result content for APIs without an `is_error` flag.
+fn tool_result_text(tc: &ToolCall) -> String {
+    let result = tc.result.as_deref().unwrap_or("");
+    if tc.status == ToolCallStatus::Error {
+        format!("Error: {}", result)
+    } else {
+        result.to_string()
+    }
+}
+
+// ── OpenAI ─────────────────────────────────────────────────────────────
+
+/// OpenAI Chat Completions, or any server that implements it via `base_url`.
+pub(crate) struct OpenAiProvider {
+    http: reqwest::Client,
+    url: String,
+    api_key: String,
+    model: String,
+    max_tokens: u32,
+}
+
+impl AiProvider for OpenAiProvider {
+    async fn send(
+        &self,
+        system: &str,
+        history: &[ChatMessage],
+        tools: &[ToolDefinition],
+        ctx: &TurnContext<'_>,
+    ) -> Result<TurnOutcome, String> {
+        let mut body = json!({
+            "model": self.model,
+            "max_tokens": self.max_tokens,
+            "messages": openai_messages(system, history),
+        });
+        if !tools.is_empty() {
+            body["tools"] = Value::Array(function_tools(tools));
+        }
+
+        let request = self
+            .http
+            .post(&self.url)
+            .bearer_auth(&self.api_key)
+            .json(&body);
+        let Some(response) = ctx.send(request).await else {
+            return Ok(TurnOutcome::Cancelled(String::new()));
+        };
+        let Some(data) = ctx.until_cancelled(response?.json::<Value>()).await else {
+            return Ok(TurnOutcome::Cancelled(String::new()));
+        };
+        let data = data.map_err(|e| format!("Failed to parse response: {}", e))?;
+
+        let choice = data
+            .get("choices")
+            .and_then(|c| c.get(0))
+            .ok_or_else(|| ctx.fail(format!("API error: no choices in response: {}", data)))?;
+        let message = choice.get("message").cloned().unwrap_or(Value::Null);
+
+        let text = message
+            .get("content")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+        if !text.is_empty() {
+            ctx.emit_text(&text);
+        }
+
+        let tool_uses: Vec<(String, String, Value)> = message
+            .get("tool_calls")
+            .and_then(|v| v.as_array())
+            .map(|calls| {
+                calls
+                    .iter()
+                    .map(|call| {
+                        let id = call.get("id").and_then(|v| v.as_str()).unwrap_or("").to_string();
+                        let function = call.get("function").cloned().unwrap_or(Value::Null);
+                        let name = function.get("name").and_then(|v| v.as_str()).unwrap_or("").to_string();
+                        // Arguments are a JSON-encoded string
+                        let input = function
+                            .get("arguments")
+                            .and_then(|v| v.as_str())
+                            .filter(|a| !a.trim().is_empty())
+                            .map(|a| serde_json::from_str(a).unwrap_or(Value::Null))
+                            .unwrap_or_else(|| json!({}));
+                        (id, name, input)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let stop_reason = match choice.get("finish_reason").and_then(|v| v.as_str()) {
+            Some("tool_calls") => "tool_use",
+            Some("length") => "max_tokens",
+            _ if !tool_uses.is_empty() => "tool_use",
+            _ => "end_turn",
+        };
+
+        Ok(TurnOutcome::Complete(AiResponse {
+            text,
+            tool_uses,
+            stop_reason: stop_reason.to_string(),
+        }))
+    }
+}
+
+fn openai_messages(system: &str, history: &[ChatMessage]) -> Vec<Value> {
+    let mut messages = vec![json!({ "role": "system", "content": system })];
+
+    for msg in history {
+        match msg.role {
+            ChatRole::User if msg.tool_calls.is_empty() => {
+                messages.push(json!({ "role": "user", "content": msg.content }));
+            }
+            ChatRole::User => {
+                // Each tool result is its own message
+                for tc in &msg.tool_calls {
+                    messages.push(json!({
+                        "role": "tool",
+                        "tool_call_id": tc.id,
+                        "content": tool_result_text(tc),
+                    }));
+                }
+            }
+            ChatRole::Assistant => {
+                if msg.content.is_empty() && msg.tool_calls.is_empty() {
+                    continue;
+                }
+                let mut entry = json!({
+                    "role": "assistant",
+                    "content": if msg.content.is_empty() { Value::Null } else { json!(msg.content) },
+                });
+                if !msg.tool_calls.is_empty() {
+                    entry["tool_calls"] = msg
+                        .tool_calls
+                        .iter()
+                        .map(|tc| {
+                            json!({
+                                "id": tc.id,
+                                "type": "function",
+                                "function": {
+                                    "name": tc.name,
+                                    "arguments": tc.input.to_string(),
+                                },
+                            })
+                        })
+                        .collect();
+                }
+                messages.push(entry);
+            }
+        }
+    }
+
+    messages
+}
+
+// ── Ollama ─────────────────────────────────────────────────────────────
+
+/// A local Ollama server's native chat endpoint.
+pub(crate) struct OllamaProvider {
+    http: reqwest::Client,
+    url: String,
+    /// Only needed when Ollama sits behind an authenticating proxy
+    api_key: Option<String>,
+    model: String,
+    max_tokens: u32,
+}
+
+impl AiProvider for OllamaProvider {
+    async fn send(
+        &self,
+        system: &str,
+        history: &[ChatMessage],
+        tools: &[ToolDefinition],
+        ctx: &TurnContext<'_>,
+    ) -> Result<TurnOutcome, String> {
+        let mut body = json!({
+            "model": self.model,
+            "messages": ollama_messages(system, history),
+            "stream": false,
+            "options": { "num_predict": self.max_tokens },
+        });
+        if !tools.is_empty() {
+            body["tools"] = Value::Array(function_tools(tools));
+        }
+
+        let mut request = self.http.post(&self.url).json(&body);
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+        let Some(response) = ctx.send(request).await else {
+            return Ok(TurnOutcome::Cancelled(String::new()));
+        };
+        let Some(data) = ctx.until_cancelled(response?.json::<Value>()).await else {
+            return Ok(TurnOutcome::Cancelled(String::new()));
+        };
+        let data = data.map_err(|e| format!("Failed to parse response: {}", e))?;
+
+        if let Some(err) = data.get("error").and_then(|v| v.as_str()) {
+            return Err(ctx.fail(format!("API error: {}", err)));
+        }
+
+        let message = data.get("message").cloned().unwrap_or(Value::Null);
+        let text = message
+            .get("content")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+        if !text.is_empty() {
+            ctx.emit_text(&text);
+        }
+
+        let tool_uses: Vec<(String, String, Value)> = message
+            .get("tool_calls")
+            .and_then(|v| v.as_array())
+            .map(|calls| {
+                calls
+                    .iter()
+                    .map(|call| {
+                        let function = call.get("function").cloned().unwrap_or(Value::Null);
+                        let name = function.get("name").and_then(|v| v.as_str()).unwrap_or("").to_string();
+                        let input = function.get("arguments").cloned().unwrap_or_else(|| json!({}));
+                        let id = format!("ollama_{}", NEXT_CALL_ID.fetch_add(1, Ordering::Relaxed));
+                        (id, name, input)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let stop_reason = if !tool_uses.is_empty() {
+            "tool_use"
+        } else if data.get("done_reason").and_then(|v| v.as_str()) == Some("length") {
+            "max_tokens"
+        } else {
+            "end_turn"
+        };
+
+        Ok(TurnOutcome::Complete(AiResponse {
+            text,
+            tool_uses,
+            stop_reason: stop_reason.to_string(),
+        }))
+    }
+}
+
+fn ollama_messages(system: &str, history: &[ChatMessage]) -> Vec<Value> {
+    let mut messages = vec![json!({ "role": "system", "content": system })];
+
+    for msg in history {
+        match msg.role {
+            ChatRole::User if msg.tool_calls.is_empty() => {
+                messages.push(json!({ "role": "user", "content": msg.content }));
+            }
+            ChatRole::User => {
+                for tc in &msg.tool_calls {
+                    messages.push(json!({
+                        "role": "tool",
+                        "tool_name": tc.name,
+                        "content": tool_result_text(tc),
+                    }));
+                }
+            }
+            ChatRole::Assistant => {
+                if msg.content.is_empty() && msg.tool_calls.is_empty() {
+                    continue;
+                }
+                let tool_calls: Vec<Value> = msg
+                    .tool_calls
+                    .iter()
+                    .map(|tc| json!({ "function": { "name": tc.name, "arguments": tc.input } }))
+                    .collect();
+                messages.push(json!({
+                    "role": "assistant",
+                    "content": msg.content,
+                    "tool_calls": tool_calls,
+                }));
+            }
+        }
+    }
+
+    messages
+}
@@ -105,9 +105,31 @@ impl ServerHandler for AlloyMcpServer {
     }
 }
 
-/// Spawn the MCP server listening on stdio. Called from lib.rs on startup.
+/// Spawn the MCP server. Called from lib.rs on startup.
+///
+/// `ALLOY_MCP` serves stdio; `ALLOY_MCP_HTTP=<addr>` (e.g. 127.0.0.1:8765)
+/// serves Streamable HTTP at `/mcp`. Every HTTP session drives this IDE
+/// window, so they all share its state.
 pub async fn spawn_mcp_server(state: Arc<AppState>) {
-    // Only start if ALLOY_MCP env var is set (for external agent connections)
+    if let Ok(addr) = std::env::var("ALLOY_MCP_HTTP") {
+        match addr.parse() {
+            Ok(addr) => {
+                let http_state = state.clone();
+                tokio::spawn(async move {
+                    let served = alloy_common::http::run_http_server(addr, move || {
+                        AlloyMcpServer::new(http_state.clone())
+                    })
+                    .await;
+                    if let Err(e) = served {
+                        eprintln!("MCP HTTP server error: {}", e);
+                    }
+                });
+            }
+            Err(_) => eprintln!("Invalid ALLOY_MCP_HTTP address: {}", addr),
+        }
+    }
+
+    // Only serve stdio if ALLOY_MCP env var is set (for external agent connections)
     if std::env::var("ALLOY_MCP").is_err() {
         return;
     }
@@ -1,7 +1,43 @@
-use crate::state::AppState;
+use crate::state::{AppState, DEFAULT_TERMINAL_SESSION};
+use super::permissions;
 use super::types::{ToolDefinition, ToolResult};
 use serde_json::{json, Value};
 use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::Emitter;
+use tokio::io::AsyncReadExt;
+
+/// Default limit for `terminal_execute` so a hung command cannot wedge the server.
+const DEFAULT_COMMAND_TIMEOUT_MS: u64 = 120_000;
+
+/// Append everything read from a child pipe into a shared buffer.
+async fn collect_pipe(mut pipe: impl tokio::io::AsyncRead + Unpin, buf: Arc<Mutex<Vec<u8>>>) {
+    let mut chunk = [0u8; 4096];
+    while let Ok(n) = pipe.read(&mut chunk).await {
+        if n == 0 {
+            break;
+        }
+        if let Ok(mut b) = buf.lock() {
+            b.extend_from_slice(&chunk[..n]);
+        }
+    }
+}
+
+/// SIGKILL the process group led by `pid`, taking background jobs down with the shell.
+#[cfg(unix)]
+fn kill_process_group(pid: Option<u32>) {
+    if let Some(pid) = pid.and_then(|p| libc::pid_t::try_from(p).ok()) {
+        // SAFETY: killpg takes no pointers; a stale group id just fails with ESRCH
+        unsafe {
+            libc::killpg(pid, libc::SIGKILL);
+        }
+    }
+}
+
+/// Windows has no process groups here; `Child::kill` stops the shell itself.
+#[cfg(not(unix))]
+fn kill_process_group(_pid: Option<u32>) {}
 
 /// Central tool registry. Every IDE action is a tool callable by both
 /// the MCP server (external agents) and the Claude API client (built-in AI).
@@ -25,7 +61,9 @@ impl ToolRegistry {
             },
             ToolDefinition {
                 name: "project_get_info".into(),
-                description: "Get information about the currently open project".into(),
+                description: "Get information about the currently open project, plus a status \
+                    overview: git branch and dirty state (null outside a git repo), counts of \
+                    .block.json/.item.json/.gui.json definitions, and the last build's outcome".into(),
                 input_schema: json!({ "type": "object", "properties": {} }),
             },
             ToolDefinition {
@@ -165,9 +203,21 @@ impl ToolRegistry {
                     "required": ["path", "line", "column", "text"]
                 }),
             },
+            ToolDefinition {
+                name: "editor_apply_diff".into(),
+                description: "Apply a unified diff to a file, hunk by hunk with fuzzy context matching. If any hunk fails the file is left untouched and the rejected hunks are reported.".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "path": { "type": "string", "description": "Path of the file to patch" },
+                        "patch": { "type": "string", "description": "Unified diff (as produced by diff -u or git diff). Only the sections of a multi-file diff that name this path are applied." }
+                    },
+                    "required": ["path", "patch"]
+                }),
+            },
             ToolDefinition {
                 name: "editor_get_selection".into(),
-                description: "Get the currently selected text in the active editor".into(),
+                description: "Get the active file, the selection range and text in it, and the LSP diagnostics on the selected lines".into(),
                 input_schema: json!({ "type": "object", "properties": {} }),
             },
             ToolDefinition {
@@ -189,11 +239,13 @@ impl ToolRegistry {
             // — Build tools —
             ToolDefinition {
                 name: "build_run".into(),
-                description: "Run a Gradle build task".into(),
+                description: "Run a Gradle build task. The task is passed first, followed by args in order.".into(),
                 input_schema: json!({
                     "type": "object",
                     "properties": {
-                        "task": { "type": "string", "description": "Gradle task name (default: \"build\")" }
+                        "task": { "type": "string", "description": "Gradle task name (default: \"build\")" },
+                        "args": { "type": "array", "items": { "type": "string" }, "description": "Additional Gradle arguments, e.g. [\"--rerun-tasks\", \"-Pfoo=bar\"]" },
+                        "offline": { "type": "boolean", "description": "Run with --offline (default: false)" }
                     }
                 }),
             },
@@ -202,6 +254,11 @@ impl ToolRegistry {
                 description: "Get current build errors and warnings".into(),
                 input_schema: json!({ "type": "object", "properties": {} }),
             },
+            ToolDefinition {
+                name: "build_cancel".into(),
+                description: "Stop the currently running Gradle build".into(),
+                input_schema: json!({ "type": "object", "properties": {} }),
+            },
             // — Terminal tools —
             ToolDefinition {
                 name: "terminal_execute".into(),
@@ -210,24 +267,49 @@ impl ToolRegistry {
                     "type": "object",
                     "properties": {
                         "command": { "type": "string", "description": "Shell command to execute" },
-                        "cwd": { "type": "string", "description": "Working directory (defaults to project root)" }
+                        "cwd": { "type": "string", "description": "Working directory (defaults to project root)" },
+                        "timeout_ms": { "type": "integer", "description": "Kill the command after this many milliseconds (default: 120000)" },
+                        "session_id": { "type": "string", "description": "Terminal session to record the output under (default: \"default\")" }
                     },
                     "required": ["command"]
                 }),
             },
             ToolDefinition {
                 name: "terminal_get_output".into(),
-                description: "Get recent terminal output".into(),
-                input_schema: json!({ "type": "object", "properties": {} }),
+                description: "Get recent command history and output for one terminal session".into(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "session_id": { "type": "string", "description": "Terminal session to read (default: \"default\")" }
+                    }
+                }),
             },
         ]
     }
 
-    /// Execute a tool by name with JSON parameters.
+    /// Execute a tool, holding it for user confirmation if the approval
+    /// policy requires it.
     pub async fn execute(
         name: &str,
         params: Value,
         state: &AppState,
+    ) -> ToolResult {
+        let policy = state
+            .ai_config
+            .lock()
+            .map(|c| c.approval_policy)
+            .unwrap_or_default();
+        if policy.requires_confirmation(permissions::classify(name)) {
+            return permissions::request_confirmation(name, params, state);
+        }
+        Self::execute_approved(name, params, state).await
+    }
+
+    /// Execute a tool without consulting the approval policy.
+    pub async fn execute_approved(
+        name: &str,
+        params: Value,
+        state: &AppState,
     ) -> ToolResult {
         match name {
             "project_open" => Self::project_open(params, state).await,
@@ -241,16 +323,18 @@ impl ToolRegistry {
             "fs_rename" => Self::fs_rename(params).await,
             "fs_search" => Self::fs_search(params, state).await,
             "editor_open_file" => Self::editor_open_file(params, state).await,
-            "editor_get_content" => Self::editor_get_content(params).await,
-            "editor_set_content" => Self::editor_set_content(params).await,
-            "editor_insert_at" => Self::editor_insert_at(params).await,
+            "editor_get_content" => Self::editor_get_content(params, state).await,
+            "editor_set_content" => Self::editor_set_content(params, state).await,
+            "editor_insert_at" => Self::editor_insert_at(params, state).await,
+            "editor_apply_diff" => Self::editor_apply_diff(params, state).await,
             "editor_get_selection" => Self::editor_get_selection(state).await,
             "editor_list_open" => Self::editor_list_open(state).await,
             "editor_close_file" => Self::editor_close_file(params, state).await,
             "build_run" => Self::build_run(params, state).await,
             "build_get_errors" => Self::build_get_errors(state).await,
+            "build_cancel" => Self::build_cancel(state).await,
             "terminal_execute" => Self::terminal_execute(params, state).await,
-            "terminal_get_output" => Self::terminal_get_output(state).await,
+            "terminal_get_output" => Self::terminal_get_output(params, state).await,
             _ => ToolResult::error(format!("Unknown tool: {}", name)),
         }
     }
@@ -268,36 +352,7 @@ impl ToolRegistry {
             return ToolResult::error(format!("Not a valid directory: {}", path));
         }
 
-        let name = project_path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("Unknown")
-            .to_string();
-
-        let mod_json = project_path.join("alloy.mod.json");
-        let pack_toml = project_path.join("alloy.pack.toml");
-
-        let (project_type, environment) = if mod_json.exists() {
-            let env = std::fs::read_to_string(&mod_json)
-                .ok()
-                .and_then(|content| {
-                    serde_json::from_str::<Value>(&content)
-                        .ok()
-                        .and_then(|v| v.get("environment").and_then(|e| e.as_str()).map(String::from))
-                });
-            (crate::state::ProjectType::Mod, env)
-        } else if pack_toml.exists() {
-            (crate::state::ProjectType::Modpack, None)
-        } else {
-            (crate::state::ProjectType::Unknown, None)
-        };
-
-        let info = crate::state::ProjectInfo {
-            name,
-            path: path.to_string(),
-            project_type,
-            environment,
-        };
+        let info = crate::commands::project::detect_project(path);
 
         if let Ok(mut current) = state.current_project.lock() {
             *current = Some(info.clone());
@@ -307,11 +362,66 @@ impl ToolRegistry {
     }
 
     async fn project_get_info(state: &AppState) -> ToolResult {
-        let current = state.current_project.lock().ok();
-        match current.as_deref() {
-            Some(Some(info)) => ToolResult::json(info),
-            _ => ToolResult::text("No project is currently open"),
+        let info = match state.current_project.lock().ok().and_then(|c| c.clone()) {
+            Some(info) => info,
+            None => return ToolResult::text("No project is currently open"),
+        };
+
+        let root = Path::new(&info.path);
+        let (mut blocks, mut items, mut guis) = (0, 0, 0);
+        for file in crate::commands::filesystem::project_files(root, false) {
+            let name = file.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+            if name.ends_with(".block.json") {
+                blocks += 1;
+            } else if name.ends_with(".item.json") {
+                items += 1;
+            } else if name.ends_with(".gui.json") {
+                guis += 1;
+            }
         }
+
+        let (error_count, warning_count) = match state.build_errors.lock() {
+            Ok(errors) => errors.iter().fold((0, 0), |(e, w), err| {
+                match err.get("severity").and_then(|s| s.as_str()) {
+                    Some("warning") => (e, w + 1),
+                    _ => (e + 1, w),
+                }
+            }),
+            Err(_) => (0, 0),
+        };
+        let last_success = state.last_build_success.lock().ok().and_then(|l| *l);
+
+        let mut result = serde_json::to_value(&info).unwrap_or_else(|_| json!({}));
+        result["status"] = json!({
+            "git": Self::git_overview(root),
+            "definitions": { "blocks": blocks, "items": items, "guis": guis },
+            "last_build": {
+                "succeeded": last_success,
+                "errors": error_count,
+                "warnings": warning_count,
+            },
+        });
+        ToolResult::json(&result)
+    }
+
+    /// Current branch and whether the working tree has changes, or `None`
+    /// when the project isn't a git repository.
+    fn git_overview(root: &Path) -> Option<Value> {
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(root)
+                .output()
+                .ok()
+                .filter(|o| o.status.success())
+                .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+        };
+        let branch = git(&["rev-parse", "--abbrev-ref", "HEAD"])?;
+        let status = git(&["status", "--porcelain=v1"])?;
+        Some(json!({
+            "branch": branch.trim(),
+            "dirty": !status.trim().is_empty(),
+        }))
     }
 
     async fn project_list_recent(state: &AppState) -> ToolResult {
@@ -554,27 +664,59 @@ impl ToolRegistry {
         }
 
         // Store the request in pending_editor_actions for the frontend to pick up
-        if let Ok(mut actions) = state.pending_editor_actions.lock() {
-            actions.push(json!({ "action": "open", "path": path }));
-        }
+        state.queue_editor_action(json!({ "action": "open", "path": path }));
 
         ToolResult::text(format!("Opening {} in editor", path))
     }
 
-    async fn editor_get_content(params: Value) -> ToolResult {
+    /// Unsaved editor contents for `path`, if the user has pending edits.
+    fn editor_buffer(state: &AppState, path: &str) -> Option<String> {
+        state
+            .editor_buffers
+            .lock()
+            .ok()
+            .and_then(|buffers| buffers.get(path).cloned())
+    }
+
+    /// Apply new contents to a file with unsaved edits without touching disk,
+    /// and send them to the frontend so the open tab shows them. Without the
+    /// event the frontend's next editor state sync would put the old text back.
+    fn update_editor_buffer(state: &AppState, path: &str, content: &str) {
+        if let Ok(mut buffers) = state.editor_buffers.lock() {
+            buffers.insert(path.to_string(), content.to_string());
+        }
+        if let Some(app) = state.app_handle.lock().ok().and_then(|h| h.clone()) {
+            let _ = app.emit("editor:set-content", json!({ "path": path, "content": content }));
+        }
+    }
+
+    /// Tell the frontend a file was rewritten on disk so an open tab reloads
+    /// it instead of keeping a stale buffer the next save would write back.
+    fn notify_file_written(state: &AppState, path: &str) {
+        state.queue_editor_action(json!({ "action": "reload", "path": path }));
+        if let Some(app) = state.app_handle.lock().ok().and_then(|h| h.clone()) {
+            let _ = app.emit("editor:reload", json!({ "path": path }));
+        }
+    }
+
+    async fn editor_get_content(params: Value, state: &AppState) -> ToolResult {
         let path = match params.get("path").and_then(|v| v.as_str()) {
             Some(p) => p,
             None => return ToolResult::error("Missing required parameter: path"),
         };
 
-        // Read from disk as the source of truth
+        // The open editor is the source of truth; disk only when there are no unsaved edits
+        if let Some(content) = Self::editor_buffer(state, path) {
+            return ToolResult::text(content);
+        }
+
         match std::fs::read_to_string(path) {
             Ok(content) => ToolResult::text(content),
             Err(e) => ToolResult::error(format!("Failed to read {}: {}", path, e)),
         }
     }
 
-    async fn editor_set_content(params: Value) -> ToolResult {
+    async fn editor_set_content(params: Value, state: &AppState) -> ToolResult {
         let path = match params.get("path").and_then(|v| v.as_str()) {
             Some(p) => p,
             None => return ToolResult::error("Missing required parameter: path"),
@@ -584,13 +726,21 @@ impl ToolRegistry {
             None => return ToolResult::error("Missing required parameter: content"),
         };
 
+        if Self::editor_buffer(state, path).is_some() {
+            Self::update_editor_buffer(state, path, content);
+            return ToolResult::text(format!("Updated unsaved editor content of {}", path));
+        }
+
         match std::fs::write(path, content) {
-            Ok(()) => ToolResult::text(format!("Updated content of {}", path)),
+            Ok(()) => {
+                Self::notify_file_written(state, path);
+                ToolResult::text(format!("Updated content of {}", path))
+            }
             Err(e) => ToolResult::error(format!("Failed to write {}: {}", path, e)),
         }
     }
 
-    async fn editor_insert_at(params: Value) -> ToolResult {
+    async fn editor_insert_at(params: Value, state: &AppState) -> ToolResult {
         let path = match params.get("path").and_then(|v| v.as_str()) {
             Some(p) => p,
             None => return ToolResult::error("Missing required parameter: path"),
@@ -608,9 +758,13 @@ impl ToolRegistry {
             None => return ToolResult::error("Missing required parameter: text"),
         };
 
-        let content = match std::fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(e) => return ToolResult::error(format!("Failed to read {}: {}", path, e)),
+        let buffered = Self::editor_buffer(state, path);
+        let content = match &buffered {
+            Some(c) => c.clone(),
+            None => match std::fs::read_to_string(path) {
+                Ok(c) => c,
+                Err(e) => return ToolResult::error(format!("Failed to read {}: {}", path, e)),
+            },
         };
 
         let mut lines: Vec<String> = content.lines().map(String::from).collect();
@@ -644,21 +798,105 @@ impl ToolRegistry {
         }
 
         let result = lines.join("\n");
-        match std::fs::write(path, &result) {
-            Ok(()) => ToolResult::text(format!(
-                "Inserted text at line {}, column {} in {}",
+        if buffered.is_some() {
+            Self::update_editor_buffer(state, path, &result);
+            return ToolResult::text(format!(
+                "Inserted text at line {}, column {} in unsaved editor content of {}",
                 line, column, path
-            )),
+            ));
+        }
+
+        match std::fs::write(path, &result) {
+            Ok(()) => {
+                Self::notify_file_written(state, path);
+                ToolResult::text(format!(
+                    "Inserted text at line {}, column {} in {}",
+                    line, column, path
+                ))
+            }
             Err(e) => ToolResult::error(format!("Failed to write {}: {}", path, e)),
         }
     }
 
-    async fn editor_get_selection(state: &AppState) -> ToolResult {
-        let selection = state.editor_selection.lock().ok();
-        match selection.as_deref() {
-            Some(Some(sel)) => ToolResult::text(sel.clone()),
-            _ => ToolResult::text("No text currently selected"),
+    async fn editor_apply_diff(params: Value, state: &AppState) -> ToolResult {
+        let path = match params.get("path").and_then(|v| v.as_str()) {
+            Some(p) => p,
+            None => return ToolResult::error("Missing required parameter: path"),
+        };
+        let patch = match params.get("patch").and_then(|v| v.as_str()) {
+            Some(p) => p,
+            None => return ToolResult::error("Missing required parameter: patch"),
+        };
+
+        let buffered = Self::editor_buffer(state, path);
+        let content = match &buffered {
+            Some(c) => c.clone(),
+            None => match std::fs::read_to_string(path) {
+                Ok(c) => c,
+                Err(e) => return ToolResult::error(format!("Failed to read {}: {}", path, e)),
+            },
+        };
+
+        let outcome = match super::patch::apply_unified_diff(path, &content, patch) {
+            Ok(o) => o,
+            Err(e) => return ToolResult::error(e),
+        };
+
+        let Some(patched) = &outcome.content else {
+            let rejected = outcome.hunks.iter().filter(|h| !h.applied).count();
+            let report = json!({
+                "path": path,
+                "applied": false,
+                "rejected": rejected,
+                "hunks": outcome.hunks,
+            });
+            return ToolResult::error(serde_json::to_string_pretty(&report).unwrap_or_default());
+        };
+
+        if buffered.is_some() {
+            Self::update_editor_buffer(state, path, patched);
+        } else if let Err(e) = std::fs::write(path, patched) {
+            return ToolResult::error(format!("Failed to write {}: {}", path, e));
+        } else {
+            Self::notify_file_written(state, path);
         }
+
+        ToolResult::json(&json!({
+            "path": path,
+            "applied": true,
+            "hunks": outcome.hunks,
+        }))
+    }
+
+    async fn editor_get_selection(state: &AppState) -> ToolResult {
+        let text = state.editor_selection.lock().ok().and_then(|s| s.clone());
+        let range = state.editor_selection_range.lock().ok().and_then(|r| r.clone());
+        let Some(range) = range else {
+            return match text {
+                Some(sel) => ToolResult::text(sel),
+                None => ToolResult::text("No text currently selected"),
+            };
+        };
+
+        // LSP lines are 0-based
+        let diagnostics: Vec<Value> = state
+            .active_file_diagnostics
+            .lock()
+            .map(|diags| {
+                diags
+                    .iter()
+                    .filter(|d| d.line < range.end_line && d.end_line + 1 >= range.start_line)
+                    .filter_map(|d| serde_json::to_value(d).ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        ToolResult::json(&json!({
+            "path": state.active_editor_file.lock().ok().and_then(|f| f.clone()),
+            "range": range,
+            "text": text,
+            "diagnostics": diagnostics,
+        }))
     }
 
     async fn editor_list_open(state: &AppState) -> ToolResult {
@@ -675,9 +913,7 @@ impl ToolRegistry {
             None => return ToolResult::error("Missing required parameter: path"),
         };
 
-        if let Ok(mut actions) = state.pending_editor_actions.lock() {
-            actions.push(json!({ "action": "close", "path": path }));
-        }
+        state.queue_editor_action(json!({ "action": "close", "path": path }));
 
         ToolResult::text(format!("Closing {} in editor", path))
     }
@@ -698,37 +934,50 @@ impl ToolRegistry {
             }
         };
 
-        let gradlew = if cfg!(windows) {
-            "gradlew.bat"
-        } else {
-            "./gradlew"
+        let args: Vec<String> = params
+            .get("args")
+            .and_then(|v| v.as_array())
+            .map(|a| a.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+            .unwrap_or_default();
+        let offline = params.get("offline").and_then(|v| v.as_bool()).unwrap_or(false);
+
+        let task_args = match crate::commands::build::gradle_task_args(task, &args, offline) {
+            Ok(a) => a,
+            Err(e) => return ToolResult::error(e),
         };
 
-        let output = match tokio::process::Command::new(gradlew)
-            .arg(task)
-            .current_dir(&project_path)
-            .output()
-            .await
-        {
-            Ok(o) => o,
-            Err(e) => return ToolResult::error(format!("Failed to run gradle: {}", e)),
+        // Stream lines to the output panel while the tool call is in flight
+        let app = state.app_handle.lock().ok().and_then(|h| h.clone());
+
+        let run = match crate::commands::build::stream_gradle(&project_path, &task_args, app.as_ref(), state).await {
+            Ok(r) => r,
+            Err(e) => return ToolResult::error(e),
         };
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let stderr = String::from_utf8_lossy(&output.stderr);
+        crate::commands::build::store_build_errors(state, &run);
 
-        if output.status.success() {
-            ToolResult::text(format!("Build succeeded.\n{}", stdout))
+        let run = crate::commands::build::GradleRun {
+            output: state.redactor().redact(&run.output),
+            ..run
+        };
+        if run.cancelled {
+            ToolResult::text(format!("Build cancelled.\n{}", run.output))
+        } else if run.success {
+            ToolResult::text(format!("Build succeeded.\n{}", run.output))
         } else {
             ToolResult::error(format!(
-                "Build failed (exit {}).\nstdout:\n{}\nstderr:\n{}",
-                output.status.code().unwrap_or(-1),
-                stdout,
-                stderr
+                "Build failed (exit {}).\n{}",
+                run.exit_code, run.output
             ))
         }
     }
 
+    async fn build_cancel(state: &AppState) -> ToolResult {
+        ToolResult::json(&json!({
+            "was_running": crate::commands::build::cancel_build(state),
+        }))
+    }
+
     async fn build_get_errors(state: &AppState) -> ToolResult {
         let errors = state.build_errors.lock().ok();
         match errors.as_deref() {
@@ -756,35 +1005,104 @@ impl ToolRegistry {
             }
         };
 
+        let session_id = params
+            .get("session_id")
+            .and_then(|v| v.as_str())
+            .unwrap_or(DEFAULT_TERMINAL_SESSION);
+
+        let timeout_ms = params
+            .get("timeout_ms")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_COMMAND_TIMEOUT_MS);
+
         let shell = if cfg!(windows) { "cmd" } else { "sh" };
         let flag = if cfg!(windows) { "/C" } else { "-c" };
 
-        let output = match tokio::process::Command::new(shell)
-            .arg(flag)
+        let mut cmd = tokio::process::Command::new(shell);
+        cmd.arg(flag)
             .arg(command)
             .current_dir(&cwd)
-            .output()
-            .await
-        {
-            Ok(o) => o,
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true);
+        // Own process group, so a timeout can take down background jobs too
+        #[cfg(unix)]
+        cmd.process_group(0);
+
+        let mut child = match cmd.spawn() {
+            Ok(c) => c,
             Err(e) => return ToolResult::error(format!("Failed to execute command: {}", e)),
         };
+        // Taken now: id() is gone once the shell has been reaped
+        let pid = child.id();
+
+        // Collect output incrementally so a timeout can still report what was printed
+        let stdout_buf = Arc::new(Mutex::new(Vec::<u8>::new()));
+        let stderr_buf = Arc::new(Mutex::new(Vec::<u8>::new()));
+        let stdout_task = child
+            .stdout
+            .take()
+            .map(|pipe| tokio::spawn(collect_pipe(pipe, stdout_buf.clone())));
+        let stderr_task = child
+            .stderr
+            .take()
+            .map(|pipe| tokio::spawn(collect_pipe(pipe, stderr_buf.clone())));
+
+        let deadline = tokio::time::Instant::now() + Duration::from_millis(timeout_ms);
+        let waited = tokio::time::timeout_at(deadline, child.wait()).await;
+
+        let status = match waited {
+            Ok(Ok(status)) => Some(status),
+            Ok(Err(e)) => return ToolResult::error(format!("Failed to wait for command: {}", e)),
+            // kill() also waits on the child, so it is reaped rather than left a zombie
+            Err(_) => {
+                kill_process_group(pid);
+                let _ = child.kill().await;
+                None
+            }
+        };
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let exit_code = output.status.code().unwrap_or(-1);
-
-        // Store in terminal history
-        if let Ok(mut history) = state.terminal_output.lock() {
-            history.push(format!("$ {}\n{}{}", command, stdout, stderr));
-            // Keep last 50 commands
-            if history.len() > 50 {
-                let drain_to = history.len() - 50;
-                history.drain(..drain_to);
+        // A backgrounded job can hold the pipes open after the shell exits, so
+        // draining shares the same deadline and the group is killed past it
+        for task in [stdout_task, stderr_task].into_iter().flatten() {
+            let abort = task.abort_handle();
+            if tokio::time::timeout_at(deadline, task).await.is_err() {
+                kill_process_group(pid);
+                abort.abort();
             }
         }
 
-        if output.status.success() {
+        let read_buf = |buf: &Arc<Mutex<Vec<u8>>>| {
+            buf.lock()
+                .map(|b| String::from_utf8_lossy(&b).to_string())
+                .unwrap_or_default()
+        };
+        // Mask secrets before anything reaches the history or the model
+        let redactor = state.redactor();
+        let command = redactor.redact(command);
+        let stdout = redactor.redact(&read_buf(&stdout_buf));
+        let stderr = redactor.redact(&read_buf(&stderr_buf));
+
+        let Some(status) = status else {
+            state.record_terminal_output(
+                session_id,
+                format!("$ {}\n{}{}[timed out]", command, stdout, stderr),
+            );
+            let result = json!({
+                "timed_out": true,
+                "timeout_ms": timeout_ms,
+                "stdout": stdout,
+                "stderr": stderr,
+            });
+            return ToolResult::error(serde_json::to_string_pretty(&result).unwrap_or_default());
+        };
+        let exit_code = status.code().unwrap_or(-1);
+
+        // Store in the session's terminal history
+        state.record_terminal_output(session_id, format!("$ {}\n{}{}", command, stdout, stderr));
+
+        if status.success() {
             ToolResult::text(format!("{}{}", stdout, stderr))
         } else {
             ToolResult::error(format!(
@@ -794,13 +1112,31 @@ impl ToolRegistry {
         }
     }
 
-    async fn terminal_get_output(state: &AppState) -> ToolResult {
-        let history = state.terminal_output.lock().ok();
-        match history.as_deref() {
-            Some(lines) if !lines.is_empty() => {
-                ToolResult::text(lines.join("\n---\n"))
+    async fn terminal_get_output(params: Value, state: &AppState) -> ToolResult {
+        let session_id = params
+            .get("session_id")
+            .and_then(|v| v.as_str())
+            .unwrap_or(DEFAULT_TERMINAL_SESSION);
+
+        let sessions = match state.terminal_output.lock() {
+            Ok(s) => s,
+            Err(e) => return ToolResult::error(format!("Failed to read terminal output: {}", e)),
+        };
+        match sessions.get(session_id) {
+            Some(lines) if !lines.is_empty() => ToolResult::text(lines.join("\n---\n")),
+            _ => {
+                let mut known: Vec<&str> = sessions.keys().map(String::as_str).collect();
+                known.sort();
+                if known.is_empty() {
+                    ToolResult::text("No terminal output")
+                } else {
+                    ToolResult::text(format!(
+                        "No terminal output for session '{}'. Sessions with output: {}",
+                        session_id,
+                        known.join(", ")
+                    ))
+                }
             }
-            _ => ToolResult::text("No terminal output"),
         }
     }
 }
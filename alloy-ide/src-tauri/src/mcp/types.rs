@@ -86,20 +86,66 @@ pub struct ChatMessage {
     pub timestamp: u64,
 }
 
+/// Which backend the built-in assistant talks to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AiProviderKind {
+    #[default]
+    Anthropic,
+    OpenAi,
+    Ollama,
+}
+
+/// Which tool calls need the user's approval before they run.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalPolicy {
+    /// Run everything without asking
+    AutoApprove,
+    /// Auto-approve reads and edits, confirm deletes and shell commands
+    #[default]
+    ConfirmDestructive,
+    /// Auto-approve reads, confirm anything that changes state
+    ConfirmWrites,
+}
+
 /// AI configuration stored in app state.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AiConfig {
+    #[serde(default)]
+    pub provider: AiProviderKind,
     pub api_key: Option<String>,
     pub model: String,
     pub max_tokens: u32,
+    /// Overrides the provider's default endpoint (e.g. a remote Ollama host)
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// Most rounds of tool calls the assistant may make for one message
+    #[serde(default = "default_max_tool_iterations")]
+    pub max_tool_iterations: u32,
+    #[serde(default)]
+    pub approval_policy: ApprovalPolicy,
+    /// Extra regexes masked in terminal and build output, on top of the
+    /// built-in secret patterns
+    #[serde(default)]
+    pub redact_patterns: Vec<String>,
+}
+
+fn default_max_tool_iterations() -> u32 {
+    25
 }
 
 impl Default for AiConfig {
     fn default() -> Self {
         Self {
+            provider: AiProviderKind::Anthropic,
             api_key: None,
             model: "claude-sonnet-4-5-20250929".to_string(),
             max_tokens: 4096,
+            base_url: None,
+            max_tool_iterations: default_max_tool_iterations(),
+            approval_policy: ApprovalPolicy::default(),
+            redact_patterns: Vec::new(),
         }
     }
 }
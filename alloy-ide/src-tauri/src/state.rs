@@ -1,6 +1,13 @@
+use alloy_common::redact::Redactor;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
 use std::sync::Mutex;
+use std::time::SystemTime;
 
+use crate::commands::build::{DependencyConflict, DependencyNode};
+use crate::commands::lsp::Diagnostic;
+use crate::mcp::permissions::PendingConfirmation;
 use crate::mcp::types::{AiConfig, ChatMessage};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -9,6 +16,11 @@ pub struct ProjectInfo {
     pub path: String,
     pub project_type: ProjectType,
     pub environment: Option<String>,
+    /// "alloy", "fabric", "quilt", "forge" or "neoforge" for mods
+    #[serde(default)]
+    pub loader: Option<String>,
+    #[serde(default)]
+    pub loader_version: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -27,6 +39,40 @@ pub struct RecentProject {
     pub last_opened: u64,
 }
 
+/// The active editor's main selection, 1-based. Start equals end for a bare
+/// cursor.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SelectionRange {
+    pub start_line: u32,
+    pub start_col: u32,
+    pub end_line: u32,
+    pub end_col: u32,
+}
+
+/// Tasks parsed from `gradlew tasks` for one project, with the modification
+/// times of the build files they were read from.
+pub struct GradleTaskCache {
+    pub tasks: Vec<String>,
+    pub build_file_stamps: Vec<Option<SystemTime>>,
+}
+
+/// A resolved dependency tree from `gradlew dependencies`, stamped like
+/// [`GradleTaskCache`].
+pub struct GradleDependencyCache {
+    pub dependencies: Vec<DependencyNode>,
+    pub conflicts: Vec<DependencyConflict>,
+    pub build_file_stamps: Vec<Option<SystemTime>>,
+}
+
+/// Commands kept per terminal session by `terminal_execute`.
+const TERMINAL_HISTORY_LIMIT: usize = 50;
+
+/// Editor actions held until the frontend drains them with `ai_get_pending_actions`.
+const PENDING_EDITOR_ACTIONS_LIMIT: usize = 200;
+
+/// Terminal session used when a tool call gives no `session_id`.
+pub const DEFAULT_TERMINAL_SESSION: &str = "default";
+
 pub struct AppState {
     // Existing state
     pub current_project: Mutex<Option<ProjectInfo>>,
@@ -35,17 +81,44 @@ pub struct AppState {
     // Editor state (synced from frontend for tool access)
     pub open_editor_files: Mutex<Vec<String>>,
     pub editor_selection: Mutex<Option<String>>,
+    /// File shown in the focused editor
+    pub active_editor_file: Mutex<Option<String>>,
+    pub editor_selection_range: Mutex<Option<SelectionRange>>,
+    /// LSP diagnostics for `active_editor_file`
+    pub active_file_diagnostics: Mutex<Vec<Diagnostic>>,
+    /// Unsaved contents of open files, keyed by path. Takes precedence over disk.
+    pub editor_buffers: Mutex<HashMap<String, String>>,
+    /// Editor actions for the frontend; add them with `queue_editor_action`
     pub pending_editor_actions: Mutex<Vec<serde_json::Value>>,
 
+    // Handle for emitting events from tools that run outside a command
+    pub app_handle: Mutex<Option<tauri::AppHandle>>,
+
     // Build state
     pub build_errors: Mutex<Vec<serde_json::Value>>,
+    /// Whether the last finished build succeeded; `None` until one completes.
+    pub last_build_success: Mutex<Option<bool>>,
+    /// Signals the running Gradle build to stop, tagged with that build's id;
+    /// `None` when no build is running.
+    pub build_cancel: Mutex<Option<(u64, tokio::sync::oneshot::Sender<()>)>>,
+    /// `list_gradle_tasks` results, keyed by project path.
+    pub gradle_tasks: Mutex<HashMap<String, GradleTaskCache>>,
+    /// `build_list_dependencies` results, keyed by project path and configuration.
+    pub gradle_dependencies: Mutex<HashMap<(String, String), GradleDependencyCache>>,
 
     // Terminal state
-    pub terminal_output: Mutex<Vec<String>>,
+    /// `terminal_execute` history, keyed by terminal session id.
+    pub terminal_output: Mutex<HashMap<String, Vec<String>>>,
 
     // AI state
     pub ai_config: Mutex<AiConfig>,
     pub chat_history: Mutex<Vec<ChatMessage>>,
+    /// Bumped for every new message or stop request; a streaming response
+    /// whose generation no longer matches aborts.
+    pub ai_generation: AtomicU64,
+    pub ai_cancel: tokio::sync::Notify,
+    /// Gated tool calls waiting for the user to approve or deny them
+    pub pending_confirmations: Mutex<Vec<PendingConfirmation>>,
 }
 
 impl AppState {
@@ -55,11 +128,59 @@ impl AppState {
             recent_projects: Mutex::new(Vec::new()),
             open_editor_files: Mutex::new(Vec::new()),
             editor_selection: Mutex::new(None),
+            active_editor_file: Mutex::new(None),
+            editor_selection_range: Mutex::new(None),
+            active_file_diagnostics: Mutex::new(Vec::new()),
+            editor_buffers: Mutex::new(HashMap::new()),
             pending_editor_actions: Mutex::new(Vec::new()),
+            app_handle: Mutex::new(None),
             build_errors: Mutex::new(Vec::new()),
-            terminal_output: Mutex::new(Vec::new()),
+            last_build_success: Mutex::new(None),
+            build_cancel: Mutex::new(None),
+            gradle_tasks: Mutex::new(HashMap::new()),
+            gradle_dependencies: Mutex::new(HashMap::new()),
+            terminal_output: Mutex::new(HashMap::new()),
             ai_config: Mutex::new(AiConfig::default()),
             chat_history: Mutex::new(Vec::new()),
+            ai_generation: AtomicU64::new(0),
+            ai_cancel: tokio::sync::Notify::new(),
+            pending_confirmations: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Append a command's output to a terminal session's history, keeping
+    /// only the most recent `TERMINAL_HISTORY_LIMIT` entries.
+    pub fn record_terminal_output(&self, session_id: &str, entry: String) {
+        if let Ok(mut sessions) = self.terminal_output.lock() {
+            let history = sessions.entry(session_id.to_string()).or_default();
+            history.push(entry);
+            if history.len() > TERMINAL_HISTORY_LIMIT {
+                let drain_to = history.len() - TERMINAL_HISTORY_LIMIT;
+                history.drain(..drain_to);
+            }
+        }
+    }
+
+    /// Secret masking for command and build output, with the user's extra
+    /// patterns. `ai_set_config` rejects invalid ones, so the fallback to
+    /// the built-in rules only covers a poisoned lock.
+    pub fn redactor(&self) -> Redactor {
+        self.ai_config
+            .lock()
+            .ok()
+            .and_then(|c| Redactor::new(&c.redact_patterns).ok())
+            .unwrap_or_default()
+    }
+
+    /// Queue an action for the frontend to apply, dropping the oldest once
+    /// `PENDING_EDITOR_ACTIONS_LIMIT` are waiting so an idle UI can't grow it.
+    pub fn queue_editor_action(&self, action: serde_json::Value) {
+        if let Ok(mut actions) = self.pending_editor_actions.lock() {
+            actions.push(action);
+            if actions.len() > PENDING_EDITOR_ACTIONS_LIMIT {
+                let drain_to = actions.len() - PENDING_EDITOR_ACTIONS_LIMIT;
+                actions.drain(..drain_to);
+            }
         }
     }
 }
@@ -1,5 +1,5 @@
 import { useEffect, useRef } from "react";
-import { Trash2, Loader2 } from "lucide-react";
+import { Trash2, Loader2, Square, ShieldAlert } from "lucide-react";
 import { useStore, initAiListeners } from "../../lib/store";
 import ChatMessage from "./ChatMessage";
 import ChatInput from "./ChatInput";
@@ -12,6 +12,9 @@ export default function AiPanel() {
   const aiConfig = useStore((s) => s.aiConfig);
   const aiConfigLoaded = useStore((s) => s.aiConfigLoaded);
   const sendMessage = useStore((s) => s.sendMessage);
+  const cancelMessage = useStore((s) => s.cancelMessage);
+  const pendingConfirmations = useStore((s) => s.pendingConfirmations);
+  const resolveConfirmation = useStore((s) => s.resolveConfirmation);
   const clearChat = useStore((s) => s.clearChat);
   const loadAiConfig = useStore((s) => s.loadAiConfig);
   const messagesEndRef = useRef<HTMLDivElement>(null);
@@ -37,7 +40,8 @@ export default function AiPanel() {
     );
   }
 
-  if (!aiConfig?.api_key) {
+  // Ollama runs locally and needs no key
+  if (!aiConfig?.api_key && aiConfig?.provider !== "ollama") {
     return <AiSetup />;
   }
 
@@ -48,14 +52,26 @@ export default function AiPanel() {
         <span className="text-[11px] text-stone-400 font-medium uppercase tracking-wider">
           AI Chat
         </span>
-        <Tooltip content="Clear chat" side="bottom">
-          <button
-            onClick={clearChat}
-            className="flex h-6 w-6 items-center justify-center rounded text-stone-500 hover:text-stone-300 hover:bg-obsidian-800 transition-colors"
-          >
-            <Trash2 size={13} />
-          </button>
-        </Tooltip>
+        <div className="flex items-center gap-1">
+          {aiLoading && (
+            <Tooltip content="Stop generating" side="bottom">
+              <button
+                onClick={cancelMessage}
+                className="flex h-6 w-6 items-center justify-center rounded text-stone-500 hover:text-red-400 hover:bg-obsidian-800 transition-colors"
+              >
+                <Square size={12} />
+              </button>
+            </Tooltip>
+          )}
+          <Tooltip content="Clear chat" side="bottom">
+            <button
+              onClick={clearChat}
+              className="flex h-6 w-6 items-center justify-center rounded text-stone-500 hover:text-stone-300 hover:bg-obsidian-800 transition-colors"
+            >
+              <Trash2 size={13} />
+            </button>
+          </Tooltip>
+        </div>
       </div>
 
       {/* Messages */}
@@ -84,6 +100,37 @@ export default function AiPanel() {
         )}
       </div>
 
+      {/* Tool calls waiting for approval */}
+      {pendingConfirmations.length > 0 && (
+        <div className="border-t border-obsidian-600 px-3 py-2 flex flex-col gap-2">
+          {pendingConfirmations.map((c) => (
+            <div key={c.id} className="rounded-md border border-amber-500/30 bg-amber-500/5 px-2.5 py-2">
+              <div className="flex items-center gap-1.5 text-[11px] text-amber-400 font-medium">
+                <ShieldAlert size={12} />
+                Allow <code className="font-mono">{c.tool}</code>?
+              </div>
+              <pre className="mt-1 max-h-24 overflow-auto scrollbar-thin text-[10px] text-stone-400 font-mono whitespace-pre-wrap break-all">
+                {JSON.stringify(c.input, null, 2)}
+              </pre>
+              <div className="mt-2 flex gap-1.5">
+                <button
+                  onClick={() => resolveConfirmation(c.id, true)}
+                  className="flex-1 rounded py-1 text-[11px] bg-ember text-obsidian-950 hover:bg-ember-light transition-colors"
+                >
+                  Approve
+                </button>
+                <button
+                  onClick={() => resolveConfirmation(c.id, false)}
+                  className="flex-1 rounded py-1 text-[11px] border border-obsidian-600 text-stone-400 hover:text-stone-200 transition-colors"
+                >
+                  Deny
+                </button>
+              </div>
+            </div>
+          ))}
+        </div>
+      )}
+
       {/* Input */}
       <ChatInput onSend={sendMessage} disabled={aiLoading} />
     </div>
@@ -1,20 +1,39 @@
 import { useState } from "react";
 import { Key, Bot, Download, ChevronDown, ChevronRight } from "lucide-react";
 import { useStore } from "../../lib/store";
+import type { AiProvider } from "../../lib/types";
+
+const PROVIDERS: { id: AiProvider; label: string; model: string; placeholder: string }[] = [
+  { id: "anthropic", label: "Anthropic", model: "claude-sonnet-4-5-20250929", placeholder: "sk-ant-..." },
+  { id: "openai", label: "OpenAI", model: "gpt-4o", placeholder: "sk-..." },
+  { id: "ollama", label: "Ollama", model: "llama3.1", placeholder: "" },
+];
 
 export default function AiSetup() {
   const [showApiKey, setShowApiKey] = useState(false);
+  const [provider, setProvider] = useState<AiProvider>("anthropic");
   const [apiKey, setApiKey] = useState("");
+  const [model, setModel] = useState(PROVIDERS[0].model);
   const [saving, setSaving] = useState(false);
   const setAiConfig = useStore((s) => s.setAiConfig);
 
+  const selected = PROVIDERS.find((p) => p.id === provider) ?? PROVIDERS[0];
+  const needsKey = provider !== "ollama";
+  const canSave = (needsKey ? apiKey.trim() : model.trim()) !== "";
+
+  const handleProviderChange = (id: AiProvider) => {
+    setProvider(id);
+    setModel(PROVIDERS.find((p) => p.id === id)?.model ?? "");
+  };
+
   const handleSave = async () => {
-    if (!apiKey.trim()) return;
+    if (!canSave) return;
     setSaving(true);
     try {
       await setAiConfig({
-        api_key: apiKey.trim(),
-        model: "claude-sonnet-4-5-20250929",
+        provider,
+        api_key: apiKey.trim() || null,
+        model: model.trim() || selected.model,
         max_tokens: 4096,
       });
     } finally {
@@ -73,15 +92,47 @@ export default function AiSetup() {
 
         {showApiKey && (
           <div className="mt-3 flex flex-col gap-2.5">
+            <div className="flex gap-1">
+              {PROVIDERS.map((p) => (
+                <button
+                  key={p.id}
+                  onClick={() => handleProviderChange(p.id)}
+                  className={
+                    "flex-1 rounded-md py-1 text-[11px] transition-colors border " +
+                    (provider === p.id
+                      ? "border-ember/40 bg-ember/10 text-ember"
+                      : "border-obsidian-600 text-stone-500 hover:text-stone-300")
+                  }
+                >
+                  {p.label}
+                </button>
+              ))}
+            </div>
             <p className="text-stone-500 text-[10px] leading-relaxed">
-              Enter your Anthropic API key for a built-in AI assistant. Stored locally, never shared.
+              {needsKey
+                ? `Enter your ${selected.label} API key for a built-in AI assistant. Stored locally, never shared.`
+                : "Uses a model served by Ollama on this machine. No key needed."}
             </p>
+            {needsKey && (
+              <input
+                type="password"
+                value={apiKey}
+                onChange={(e) => setApiKey(e.target.value)}
+                onKeyDown={handleKeyDown}
+                placeholder={selected.placeholder}
+                className={
+                  "w-full rounded-md border border-obsidian-600 bg-obsidian-950 px-3 py-2 " +
+                  "text-[13px] text-stone-100 placeholder:text-stone-500 " +
+                  "focus:border-ember focus:outline-none focus:ring-1 focus:ring-ember/30"
+                }
+              />
+            )}
             <input
-              type="password"
-              value={apiKey}
-              onChange={(e) => setApiKey(e.target.value)}
+              type="text"
+              value={model}
+              onChange={(e) => setModel(e.target.value)}
               onKeyDown={handleKeyDown}
-              placeholder="sk-ant-..."
+              placeholder="Model"
               className={
                 "w-full rounded-md border border-obsidian-600 bg-obsidian-950 px-3 py-2 " +
                 "text-[13px] text-stone-100 placeholder:text-stone-500 " +
@@ -90,10 +141,10 @@ export default function AiSetup() {
             />
             <button
               onClick={handleSave}
-              disabled={!apiKey.trim() || saving}
+              disabled={!canSave || saving}
               className={
                 "w-full rounded-md py-2 text-[13px] font-medium transition-colors " +
-                (apiKey.trim() && !saving
+                (canSave && !saving
                   ? "bg-obsidian-700 text-stone-100 hover:bg-obsidian-600 border border-obsidian-600"
                   : "bg-obsidian-800 text-stone-500 cursor-not-allowed border border-obsidian-700")
               }
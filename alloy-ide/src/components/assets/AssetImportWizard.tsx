@@ -22,6 +22,10 @@ interface AssetInfo {
   file_size: number;
   suggested_type: string;
   suggested_name: string;
+  has_alpha: boolean;
+  is_square: boolean;
+  is_power_of_two: boolean;
+  dominant_colors: [number, number, number][];
 }
 
 interface CreatedFile {
@@ -33,8 +37,15 @@ interface ImportResult {
   created_files: CreatedFile[];
   registration_code: string | null;
   texture_path: string;
+  original_width: number;
+  original_height: number;
+  final_width: number;
+  final_height: number;
+  resized: boolean;
 }
 
+const RESIZE_OPTIONS = [16, 32, 64, 128];
+
 type AssetType = "block" | "item" | "gui_element" | "entity" | "particle";
 
 const assetTypes: { value: AssetType; label: string; desc: string; icon: React.ReactNode }[] = [
@@ -57,6 +68,7 @@ export default function AssetImportWizard({ sourcePath, onClose }: Props) {
   const [assetType, setAssetType] = useState<AssetType>("block");
   const [assetName, setAssetName] = useState("");
   const [generateCode, setGenerateCode] = useState(true);
+  const [targetSize, setTargetSize] = useState<number | null>(null);
   const [importing, setImporting] = useState(false);
   const [result, setResult] = useState<ImportResult | null>(null);
   const [codeCopied, setCodeCopied] = useState(false);
@@ -90,6 +102,7 @@ export default function AssetImportWizard({ sourcePath, onClose }: Props) {
           mod_id: modId,
           asset_name: assetName,
           generate_code: generateCode,
+          target_size: targetSize,
         },
       });
       setResult(res);
@@ -152,9 +165,28 @@ export default function AssetImportWizard({ sourcePath, onClose }: Props) {
               <div>{assetInfo.width} x {assetInfo.height} px</div>
               <div>{(assetInfo.file_size / 1024).toFixed(1)} KB</div>
               <div className="text-stone-600 font-mono">{fileName}</div>
+              {assetInfo.dominant_colors.length > 0 && (
+                <div className="flex gap-1 pt-1">
+                  {assetInfo.dominant_colors.map(([r, g, b], i) => (
+                    <span
+                      key={i}
+                      title={`rgb(${r}, ${g}, ${b})`}
+                      className="h-3 w-3 rounded-sm border border-obsidian-600"
+                      style={{ backgroundColor: `rgb(${r}, ${g}, ${b})` }}
+                    />
+                  ))}
+                </div>
+              )}
             </div>
           </div>
         )}
+        {assetInfo && textureWarnings(assetInfo, assetType).length > 0 && (
+          <div className="mx-5 mt-3 rounded-lg border border-amber-500/30 bg-amber-500/5 px-3 py-2 text-[11px] text-yellow-300 space-y-0.5">
+            {textureWarnings(assetInfo, assetType).map((w) => (
+              <div key={w}>{w}</div>
+            ))}
+          </div>
+        )}
 
         {/* Step content */}
         <div className="p-5">
@@ -214,6 +246,26 @@ export default function AssetImportWizard({ sourcePath, onClose }: Props) {
                 </div>
               </div>
 
+              {assetInfo && (
+                <div>
+                  <label className="block text-[11px] text-stone-400 uppercase tracking-wider font-semibold mb-1.5">
+                    Texture Size
+                  </label>
+                  <select
+                    value={targetSize ?? ""}
+                    onChange={(e) => setTargetSize(e.target.value ? Number(e.target.value) : null)}
+                    className="w-full rounded-lg bg-obsidian-900 border border-obsidian-600 px-3 py-2.5 text-sm text-stone-200 focus:outline-none focus:border-ember/50 focus:ring-1 focus:ring-ember/20 transition-colors"
+                  >
+                    <option value="">Keep original ({assetInfo.width} x {assetInfo.height})</option>
+                    {RESIZE_OPTIONS.filter((size) => size !== assetInfo.width).map((size) => (
+                      <option key={size} value={size}>
+                        Resize to {size} x {size}
+                      </option>
+                    ))}
+                  </select>
+                </div>
+              )}
+
               <label className="flex items-center gap-2 cursor-pointer">
                 <input
                   type="checkbox"
@@ -251,6 +303,11 @@ export default function AssetImportWizard({ sourcePath, onClose }: Props) {
                     {result.created_files.length} file{result.created_files.length !== 1 ? "s" : ""} created
                   </span>
                 </div>
+                <div className="mb-2 text-[10px] text-stone-500">
+                  {result.resized
+                    ? `Resized ${result.original_width} x ${result.original_height} → ${result.final_width} x ${result.final_height} px`
+                    : `Copied at ${result.final_width} x ${result.final_height} px`}
+                </div>
                 <div className="space-y-1">
                   {result.created_files.map((f, i) => (
                     <div key={i} className="flex items-center gap-2 text-[10px] text-stone-500 font-mono">
@@ -298,3 +355,21 @@ export default function AssetImportWizard({ sourcePath, onClose }: Props) {
     </div>
   );
 }
+
+/** Problems that would make the texture render wrongly in game, for the chosen asset type. */
+function textureWarnings(info: AssetInfo, assetType: AssetType): string[] {
+  const warnings: string[] = [];
+  if (assetType === "block" || assetType === "item") {
+    const isStrip = info.height > info.width && info.width > 0 && info.height % info.width === 0;
+    if (!info.is_square && !isStrip) {
+      warnings.push(`Not square (${info.width}x${info.height}): Minecraft will stretch or reject it.`);
+    }
+    if (!info.is_power_of_two && !isStrip) {
+      warnings.push("Dimensions are not powers of two (16, 32, 64...), which causes blurring on mipmaps.");
+    }
+    if (assetType === "block" && info.has_alpha) {
+      warnings.push("Has transparent pixels: the block must be non-opaque or they render black.");
+    }
+  }
+  return warnings;
+}
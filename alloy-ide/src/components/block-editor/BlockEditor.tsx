@@ -14,6 +14,7 @@ import {
 import { invoke } from "@tauri-apps/api/core";
 import { useStore } from "../../lib/store";
 import { showToast } from "../ui/Toast";
+import { showConfirm } from "../ui/ConfirmDialog";
 import type { BlockProject, BlockTextureMode, BlockTextures, BlockProperties } from "../../lib/types";
 import BlockPropertiesPanel from "./BlockPropertiesPanel";
 import TexturePanel from "./TexturePanel";
@@ -155,14 +156,35 @@ export default function BlockEditor({ path, content, onSave }: Props) {
       return;
     }
     try {
-      const result = await invoke<{
-        created_files: { path: string; file_type: string }[];
-        block_class_path: string;
-        registration_snippet: string;
-      }>("generate_block_code", {
-        projectPath: currentProject.path,
-        blockJsonPath: path,
-      });
+      const generate = (force: boolean) =>
+        invoke<{
+          created_files: { path: string; file_type: string }[];
+          skipped_modified: string[];
+          overwritten_modified: string[];
+          block_class_path: string;
+          registration_snippet: string;
+        }>("generate_block_code", {
+          projectPath: currentProject.path,
+          blockJsonPath: path,
+          force,
+        });
+      let result = await generate(false);
+      if (result.skipped_modified.length > 0) {
+        const overwrite = await showConfirm({
+          title: "Overwrite edited files?",
+          message:
+            `These generated files were edited by hand and were not exported: ` +
+            `${result.skipped_modified.join(", ")}. Overwrite them with freshly generated code?`,
+          confirmLabel: "Overwrite",
+          cancelLabel: "Keep edits",
+          variant: "danger",
+        });
+        if (overwrite) {
+          result = await generate(true);
+        } else {
+          showToast("warning", `Kept ${result.skipped_modified.length} hand-edited file(s)`);
+        }
+      }
       showToast("success", `Exported ${result.created_files.length} files`);
       const fileName = result.block_class_path.split("/").pop() || "Block.java";
       openFile(result.block_class_path, fileName);
@@ -1,6 +1,6 @@
 import { useRef, useEffect, useState, useCallback } from "react";
 import { EditorView, keymap } from "@codemirror/view";
-import { EditorState, EditorSelection } from "@codemirror/state";
+import { Annotation, EditorState, EditorSelection } from "@codemirror/state";
 import { basicSetup } from "codemirror";
 import { java } from "@codemirror/lang-java";
 import { json } from "@codemirror/lang-json";
@@ -28,6 +28,9 @@ import Minimap from "./Minimap";
 import { useStore } from "../../lib/store";
 import type { CursorPosition } from "../../lib/types";
 
+/** Marks a document replaced from outside the editor, which is not a user edit. */
+const externalContent = Annotation.define<boolean>();
+
 interface CodeEditorProps {
   path: string;
   content: string;
@@ -88,7 +91,8 @@ export default function CodeEditor({
     if (!containerRef.current) return;
 
     const updateListener = EditorView.updateListener.of((update) => {
-      if (update.docChanged) {
+      const external = update.transactions.some((tr) => tr.annotation(externalContent));
+      if (update.docChanged && !external) {
         onChange(update.state.doc.toString());
       }
       if (update.selectionSet || update.docChanged) {
@@ -103,6 +107,20 @@ export default function CodeEditor({
           selected: selected > 0 ? selected : undefined,
           cursors: cursorCount > 1 ? cursorCount : undefined,
         });
+        useStore.getState().setEditorViewState(path, {
+          line: line.number,
+          column: pos - line.from + 1,
+        });
+        const fromLine = update.state.doc.lineAt(sel.from);
+        const toLine = update.state.doc.lineAt(sel.to);
+        useStore.getState().setEditorSelection({
+          path,
+          start_line: fromLine.number,
+          start_col: sel.from - fromLine.from + 1,
+          end_line: toLine.number,
+          end_col: sel.to - toLine.from + 1,
+          text: selected > 0 ? update.state.sliceDoc(sel.from, sel.to) : null,
+        });
       }
       // Update visible range for minimap
       if (update.geometryChanged || update.viewportChanged) {
@@ -183,6 +201,23 @@ export default function CodeEditor({
 
     viewRef.current = view;
 
+    // Put the cursor and scroll back where they were when this file was last shown
+    const savedView = useStore.getState().editorViewStates[path];
+    let scrollFrame: number | undefined;
+    if (savedView) {
+      const doc = view.state.doc;
+      const line = doc.line(Math.min(Math.max(savedView.line, 1), doc.lines));
+      const column = Math.min(Math.max(savedView.column, 1) - 1, line.length);
+      view.dispatch({ selection: EditorSelection.cursor(line.from + column) });
+      scrollFrame = requestAnimationFrame(() => {
+        view.scrollDOM.scrollTop = savedView.scrollTop;
+      });
+    }
+    const handleScroll = () => {
+      useStore.getState().setEditorViewState(path, { scrollTop: view.scrollDOM.scrollTop });
+    };
+    view.scrollDOM.addEventListener("scroll", handleScroll, { passive: true });
+
     // Initial visible range
     const startLine = view.state.doc.lineAt(view.viewport.from).number;
     const endLine = view.state.doc.lineAt(view.viewport.to).number;
@@ -199,11 +234,25 @@ export default function CodeEditor({
 
     return () => {
       if (cleanupLsp) cleanupLsp();
+      if (scrollFrame !== undefined) cancelAnimationFrame(scrollFrame);
+      view.scrollDOM.removeEventListener("scroll", handleScroll);
       view.destroy();
     };
     // eslint-disable-next-line react-hooks/exhaustive-deps
   }, [path, editorSettings.fontSize, editorSettings.tabSize, editorSettings.wordWrap, editorSettings.indentGuides]);
 
+  // The tab's content was replaced from outside (an AI edit of the unsaved
+  // buffer, or a reload from disk). Typing keeps the two equal, so this
+  // only dispatches for those.
+  useEffect(() => {
+    const view = viewRef.current;
+    if (!view || view.state.doc.toString() === content) return;
+    view.dispatch({
+      changes: { from: 0, to: view.state.doc.length, insert: content },
+      annotations: externalContent.of(true),
+    });
+  }, [content]);
+
   // Handle go-to-line requests
   const pendingGoToLine = useStore((s) => s.pendingGoToLine);
   const clearGoToLine = useStore((s) => s.clearGoToLine);
@@ -18,6 +18,7 @@ import {
   onLspDiagnostics,
 } from "../../lib/lsp";
 import type { LspCompletionItem, LspDiagnostic, LspCodeAction } from "../../lib/lsp";
+import { useStore } from "../../lib/store";
 
 // Map LSP completion kinds to CodeMirror types
 function mapCompletionKind(kind: string): string {
@@ -391,6 +392,7 @@ export function setupLspDiagnostics(
 
     // Filter diagnostics for this file
     const fileDiags = diagnostics.filter((d) => d.path === filePath);
+    useStore.getState().setFileDiagnostics(filePath, fileDiags);
 
     const cmDiags: Diagnostic[] = fileDiags
       .map((d) => {
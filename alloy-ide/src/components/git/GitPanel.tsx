@@ -279,6 +279,9 @@ export default function GitPanel() {
           <span className="text-stone-200 font-medium">
             {gitStatus?.branch || "..."}
           </span>
+          {gitStatus && !gitStatus.has_upstream && (
+            <span className="text-[10px] text-stone-500">no upstream</span>
+          )}
           {gitStatus && (gitStatus.ahead > 0 || gitStatus.behind > 0) && (
             <span className="text-[10px] text-stone-500">
               {gitStatus.ahead > 0 && `↑${gitStatus.ahead}`}
@@ -36,6 +36,9 @@ import { lspRename } from "../../lib/lsp";
 import type { LspCodeAction } from "../../lib/lsp";
 import { invoke } from "@tauri-apps/api/core";
 
+/** How long the workspace must sit still before its state is written to disk. */
+const WORKSPACE_SAVE_DEBOUNCE_MS = 1000;
+
 interface ConfirmState {
   title: string;
   message: string;
@@ -76,20 +79,41 @@ export default function IdeLayout() {
     registerConfirmHandler((opts) => setConfirmDialog(opts));
   }, []);
 
-  // Save workspace state on window close and periodically
+  // Save workspace state on window close, and shortly after it settles
   useEffect(() => {
     const save = () => useStore.getState().saveWorkspaceState();
+    let timer: ReturnType<typeof setTimeout> | undefined;
+    const unsubscribe = useStore.subscribe((state, prev) => {
+      const changed =
+        state.openFiles.length !== prev.openFiles.length ||
+        state.openFiles.some((f, i) => f.path !== prev.openFiles[i]?.path) ||
+        state.activeFilePath !== prev.activeFilePath ||
+        state.editorViewStates !== prev.editorViewStates ||
+        state.expandedDirs !== prev.expandedDirs ||
+        state.sidebarPanel !== prev.sidebarPanel ||
+        state.sidebarVisible !== prev.sidebarVisible ||
+        state.sidebarWidth !== prev.sidebarWidth ||
+        state.bottomPanel !== prev.bottomPanel ||
+        state.bottomPanelVisible !== prev.bottomPanelVisible ||
+        state.bottomPanelHeight !== prev.bottomPanelHeight;
+      if (!changed) return;
+      clearTimeout(timer);
+      timer = setTimeout(save, WORKSPACE_SAVE_DEBOUNCE_MS);
+    });
     window.addEventListener("beforeunload", save);
-    const interval = setInterval(save, 30_000); // Auto-save every 30s
     return () => {
+      unsubscribe();
+      clearTimeout(timer);
       window.removeEventListener("beforeunload", save);
-      clearInterval(interval);
     };
   }, []);
 
   // Auto-save dirty files
   const editorSettings = useStore((s) => s.editorSettings);
   const openFiles = useStore((s) => s.openFiles);
+  const activeFilePath = useStore((s) => s.activeFilePath);
+  const editorSelection = useStore((s) => s.editorSelection);
+  const fileDiagnostics = useStore((s) => s.fileDiagnostics);
   useEffect(() => {
     if (!editorSettings.autoSave) return;
     const dirtyFiles = openFiles.filter((f) => f.dirty);
@@ -109,6 +133,33 @@ export default function IdeLayout() {
     return () => clearTimeout(timer);
   }, [editorSettings.autoSave, editorSettings.autoSaveDelay, openFiles]);
 
+  // Keep the backend's view of open files and unsaved buffers current so
+  // AI editor tools never read or overwrite stale disk content, and tell it
+  // where the user is and what the LSP reports for the active file
+  useEffect(() => {
+    const timer = setTimeout(() => {
+      const buffers: Record<string, string> = {};
+      for (const file of openFiles) {
+        if (file.dirty) buffers[file.path] = file.content;
+      }
+      const selection = editorSelection?.path === activeFilePath ? editorSelection : null;
+      invoke("ai_update_editor_state", {
+        openFiles: openFiles.map((f) => f.path),
+        selection: selection?.text ?? null,
+        buffers,
+        activeFile: activeFilePath,
+        selectionRange: selection && {
+          start_line: selection.start_line,
+          start_col: selection.start_col,
+          end_line: selection.end_line,
+          end_col: selection.end_col,
+        },
+        diagnostics: activeFilePath ? fileDiagnostics[activeFilePath] ?? [] : [],
+      }).catch(() => {});
+    }, 300);
+    return () => clearTimeout(timer);
+  }, [openFiles, activeFilePath, editorSelection, fileDiagnostics]);
+
   // LSP go-to-definition across files
   useEffect(() => {
     const handler = (e: Event) => {
@@ -71,6 +71,11 @@ export default function StatusBar() {
     ? { client: "text-blue-400", server: "text-green-400", both: "text-forge-gold" }[env]
     : "";
 
+  const loader = currentProject?.loader;
+  const loaderLabel = loader && loader !== "alloy"
+    ? { fabric: "Fabric", quilt: "Quilt", forge: "Forge", neoforge: "NeoForge" }[loader] + " "
+    : "";
+
   const hasApiKey = !!aiConfig?.api_key;
 
   return (
@@ -90,7 +95,7 @@ export default function StatusBar() {
         )}
         {currentProject && (
           <span className="text-stone-500">
-            {currentProject.project_type === "mod" ? "Mod" : currentProject.project_type === "modpack" ? "Modpack" : "Project"}
+            {currentProject.project_type === "mod" ? loaderLabel + "Mod" : currentProject.project_type === "modpack" ? "Modpack" : "Project"}
           </span>
         )}
         {/* Build status */}
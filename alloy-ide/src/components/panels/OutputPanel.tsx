@@ -1,6 +1,7 @@
 import { useEffect, useRef, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
-import { Bot, Trash2 } from "lucide-react";
+import { Bot, Square, Trash2 } from "lucide-react";
 import { useStore } from "../../lib/store";
 import { AnsiLine, stripAnsi } from "../../lib/ansi";
 
@@ -63,6 +64,16 @@ export default function OutputPanel() {
           )}
         </div>
         <div className="flex items-center gap-1">
+          {buildRunning && (
+            <button
+              onClick={() => invoke("cancel_gradle_task")}
+              className="flex items-center gap-1 rounded px-2 py-0.5 text-[10px] text-red-400 hover:bg-red-400/10 transition-colors"
+              title="Stop build"
+            >
+              <Square size={10} />
+              Stop
+            </button>
+          )}
           {hasError && aiConfig?.api_key && (
             <button
               onClick={handleExplainError}
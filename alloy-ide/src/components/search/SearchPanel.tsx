@@ -1,12 +1,34 @@
 import { useState, useRef, useCallback } from "react";
 import {
   Search, FileText, Loader2, Replace, ChevronDown, ChevronRight,
-  CaseSensitive, Regex, ArrowRightLeft,
+  CaseSensitive, Regex, WholeWord, ArrowRightLeft,
 } from "lucide-react";
 import { invoke } from "@tauri-apps/api/core";
 import { useStore } from "../../lib/store";
 import { showToast } from "../ui/Toast";
-import type { SearchResult } from "../../lib/types";
+import type { ReplaceResult, SearchResult } from "../../lib/types";
+
+/** How many preview lines the replace confirmation lists. */
+const CONFIRM_PREVIEW_LINES = 8;
+
+/**
+ * replace_in_files has no whole-word option, so whole-word replaces go through
+ * regex mode with the same boundaries search_files_advanced applies.
+ */
+function wholeWordReplaceArgs(query: string, replacement: string, regexMode: boolean) {
+  const isWord = (c: string | undefined) => c !== undefined && /[\p{L}\p{N}_]/u.test(c);
+  if (regexMode) {
+    return { query: `\\b(?:${query})\\b`, replacement, regexMode: true };
+  }
+  const chars = Array.from(query);
+  const escaped = query.replace(/[.*+?^${}()|[\]\\\-#&~]/g, "\\$&");
+  return {
+    query: (isWord(chars[0]) ? "\\b" : "") + escaped + (isWord(chars[chars.length - 1]) ? "\\b" : ""),
+    // Literal replacement: `$` must not expand capture groups
+    replacement: replacement.replace(/\$/g, "$$$$"),
+    regexMode: true,
+  };
+}
 
 export default function SearchPanel() {
   const searchResults = useStore((s) => s.searchResults);
@@ -19,6 +41,7 @@ export default function SearchPanel() {
   const [showReplace, setShowReplace] = useState(false);
   const [caseSensitive, setCaseSensitive] = useState(false);
   const [regexMode, setRegexMode] = useState(false);
+  const [wholeWord, setWholeWord] = useState(false);
   const [replacing, setReplacing] = useState(false);
   const [loading, setLoading] = useState(false);
   const debounceRef = useRef<ReturnType<typeof setTimeout>>(undefined);
@@ -37,6 +60,7 @@ export default function SearchPanel() {
           searchPath: currentProject.path,
           caseSensitive,
           regexMode,
+          wholeWord,
         });
         useStore.setState({ searchResults: results, searchLoading: false });
       } catch (err) {
@@ -47,7 +71,7 @@ export default function SearchPanel() {
       }
       setLoading(false);
     },
-    [currentProject, caseSensitive, regexMode],
+    [currentProject, caseSensitive, regexMode, wholeWord],
   );
 
   const handleChange = useCallback(
@@ -74,26 +98,53 @@ export default function SearchPanel() {
       setTimeout(() => doSearch(searchValue), 50);
     }
   };
+  const toggleWholeWord = () => {
+    const next = !wholeWord;
+    setWholeWord(next);
+    if (searchValue.trim().length >= 2) {
+      setTimeout(() => doSearch(searchValue), 50);
+    }
+  };
 
   const handleReplaceAll = async () => {
     if (!currentProject || !searchValue.trim()) return;
-    const confirmed = window.confirm(
-      `Replace all occurrences of "${searchValue}" with "${replaceValue}" across the project?`,
-    );
-    if (!confirmed) return;
+    const args = {
+      query: searchValue.trim(),
+      replacement: replaceValue,
+      searchPath: currentProject.path,
+      caseSensitive,
+      regexMode,
+      ...(wholeWord ? wholeWordReplaceArgs(searchValue.trim(), replaceValue, regexMode) : {}),
+    };
 
     setReplacing(true);
     try {
-      const result = await invoke<{ files_changed: number; replacements: number }>(
-        "replace_in_files",
-        {
-          query: searchValue.trim(),
-          replacement: replaceValue,
-          searchPath: currentProject.path,
-          caseSensitive,
-          regexMode,
-        },
+      // Preview first so the user sees what a broad pattern would really touch
+      const preview = await invoke<ReplaceResult>("replace_in_files", { ...args, dryRun: true });
+      if (preview.replacements === 0) {
+        showToast("info", "No matches to replace");
+        setReplacing(false);
+        return;
+      }
+      const root = currentProject.path.replace(/[\\/]+$/, "");
+      const sample = preview.previews
+        .slice(0, CONFIRM_PREVIEW_LINES)
+        .map((p) => {
+          const rel = p.path.startsWith(root) ? p.path.slice(root.length + 1) : p.path;
+          return `${rel}:${p.line}\n  - ${p.before.trim()}\n  + ${p.after.trim()}`;
+        })
+        .join("\n");
+      const more = preview.previews.length > CONFIRM_PREVIEW_LINES ? "\n..." : "";
+      const confirmed = window.confirm(
+        `Replace ${preview.replacements} occurrence(s) of "${searchValue}" with "${replaceValue}" ` +
+          `in ${preview.files_changed} file(s)?\n\n${sample}${more}`,
       );
+      if (!confirmed) {
+        setReplacing(false);
+        return;
+      }
+
+      const result = await invoke<ReplaceResult>("replace_in_files", args);
       showToast(
         "success",
         `Replaced ${result.replacements} occurrence(s) in ${result.files_changed} file(s)`,
@@ -200,6 +251,18 @@ export default function SearchPanel() {
           >
             <Regex size={14} />
           </button>
+          <button
+            onClick={toggleWholeWord}
+            className={
+              "rounded p-1 transition-colors shrink-0 " +
+              (wholeWord
+                ? "bg-ember/15 text-ember border border-ember/30"
+                : "text-stone-500 hover:text-stone-300 hover:bg-obsidian-800 border border-transparent")
+            }
+            title="Match Whole Word"
+          >
+            <WholeWord size={14} />
+          </button>
         </div>
 
         {/* Replace input */}
@@ -249,7 +312,6 @@ export default function SearchPanel() {
             name={name}
             results={results}
             onResultClick={(path, name, line) => handleResultClick(path, name, line)}
-            searchQuery={searchValue}
           />
         ))}
 
@@ -269,13 +331,11 @@ function FileResultGroup({
   name,
   results,
   onResultClick,
-  searchQuery,
 }: {
   filePath: string;
   name: string;
   results: SearchResult[];
   onResultClick: (path: string, name: string, lineNumber: number) => void;
-  searchQuery: string;
 }) {
   const [collapsed, setCollapsed] = useState(false);
   const project = useStore((s) => s.currentProject);
@@ -290,7 +350,9 @@ function FileResultGroup({
         {collapsed ? <ChevronRight size={12} /> : <ChevronDown size={12} />}
         <FileText size={12} className="shrink-0" />
         <span className="truncate font-medium text-left">{relativePath}</span>
-        <span className="text-stone-500 ml-auto shrink-0">{results.length}</span>
+        <span className="text-stone-500 ml-auto shrink-0">
+          {results.reduce((n, r) => n + r.match_count, 0)}
+        </span>
       </button>
       {!collapsed &&
         results.map((r, i) => (
@@ -302,29 +364,26 @@ function FileResultGroup({
             <span className="text-stone-500 shrink-0 w-8 text-right tabular-nums">
               {r.line_number}
             </span>
-            <HighlightedLine text={r.line_content.trim()} query={searchQuery} />
+            <HighlightedLine result={r} />
           </button>
         ))}
     </div>
   );
 }
 
-function HighlightedLine({ text, query }: { text: string; query: string }) {
-  if (!query.trim()) return <span className="text-stone-300 truncate">{text}</span>;
-
-  const lower = text.toLowerCase();
-  const lq = query.toLowerCase();
-  const parts: { text: string; match: boolean }[] = [];
-  let last = 0;
-
-  let idx = lower.indexOf(lq, last);
-  while (idx !== -1) {
-    if (idx > last) parts.push({ text: text.slice(last, idx), match: false });
-    parts.push({ text: text.slice(idx, idx + query.length), match: true });
-    last = idx + query.length;
-    idx = lower.indexOf(lq, last);
-  }
-  if (last < text.length) parts.push({ text: text.slice(last), match: false });
+function HighlightedLine({ result }: { result: SearchResult }) {
+  // Offsets are in chars, so index code points rather than UTF-16 units
+  const chars = Array.from(result.line_content);
+  const indent = chars.length - Array.from(result.line_content.trimStart()).length;
+  const textChars = chars.slice(indent);
+  // line_content is capped at 200 chars; a match past that has nothing to mark
+  const start = Math.max(result.match_start - indent, 0);
+  const end = Math.max(result.match_end - indent, start);
+  const parts: { text: string; match: boolean }[] = [
+    { text: textChars.slice(0, start).join(""), match: false },
+    { text: textChars.slice(start, end).join(""), match: true },
+    { text: textChars.slice(end).join(""), match: false },
+  ].filter((p) => p.text);
 
   return (
     <span className="text-stone-300 truncate">
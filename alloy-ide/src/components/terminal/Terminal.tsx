@@ -72,9 +72,11 @@ function TerminalInstance({ id, visible }: { id: string; visible: boolean }) {
     fitRef.current = fitAddon;
 
     const cwd = currentProject?.path ?? undefined;
-    invoke("terminal_create", { id, cwd }).catch((err) => {
-      term.writeln(`\x1b[31mFailed to create terminal: ${err}\x1b[0m`);
-    });
+    invoke("terminal_create", { id, cwd })
+      .then(() => invoke("terminal_resize", { id, rows: term.rows, cols: term.cols }))
+      .catch((err) => {
+        term.writeln(`\x1b[31mFailed to create terminal: ${err}\x1b[0m`);
+      });
 
     const unlistenData = listen<string>(`terminal:data:${id}`, (event) => {
       term.write(event.payload);
@@ -88,6 +90,10 @@ function TerminalInstance({ id, visible }: { id: string; visible: boolean }) {
       invoke("terminal_write", { id, data }).catch(() => {});
     });
 
+    const onResize = term.onResize(({ rows, cols }) => {
+      invoke("terminal_resize", { id, rows, cols }).catch(() => {});
+    });
+
     const observer = new ResizeObserver(() => {
       fitAddon.fit();
     });
@@ -95,6 +101,7 @@ function TerminalInstance({ id, visible }: { id: string; visible: boolean }) {
 
     return () => {
       onData.dispose();
+      onResize.dispose();
       observer.disconnect();
       unlistenData.then((fn) => fn());
       unlistenExit.then((fn) => fn());
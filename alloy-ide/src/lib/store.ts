@@ -12,15 +12,22 @@ import type {
   SplitDirection,
   BottomPanelTab,
   CursorPosition,
+  EditorViewState,
+  EditorSelectionRange,
   ChatMessage,
   AiConfig,
   ToolCall,
+  PendingConfirmation,
   SearchResult,
   BuildError,
   BuildResult,
   EditorSettings,
 } from "./types";
 import { lspStart, lspStop } from "./lsp";
+import type { LspDiagnostic } from "./lsp";
+
+/** Set while a saved workspace is being reopened, so partial state isn't saved over it. */
+let restoringWorkspace = false;
 
 function detectLanguage(path: string): string {
   const name = path.split("/").pop()?.toLowerCase() || "";
@@ -107,6 +114,10 @@ interface IdeStore {
   openFiles: OpenFile[];
   activeFilePath: string | null;
   cursorPosition: CursorPosition;
+  editorViewStates: Record<string, EditorViewState>;
+  editorSelection: EditorSelectionRange | null;
+  /** Latest LSP diagnostics for each open file */
+  fileDiagnostics: Record<string, LspDiagnostic[]>;
 
   // Panels
   sidebarPanel: SidebarPanel;
@@ -154,6 +165,7 @@ interface IdeStore {
   aiLoading: boolean;
   aiConfig: AiConfig | null;
   aiConfigLoaded: boolean;
+  pendingConfirmations: PendingConfirmation[];
 
   // LSP
   lspRunning: boolean;
@@ -165,6 +177,7 @@ interface IdeStore {
   openProject: (path: string) => Promise<void>;
   openFolderDialog: () => Promise<void>;
   loadRecentProjects: () => Promise<void>;
+  removeRecentProject: (path: string) => Promise<void>;
 
   // Actions — File tree
   loadFileTree: (path: string) => Promise<FileEntry[]>;
@@ -179,6 +192,9 @@ interface IdeStore {
   updateFileContent: (path: string, content: string) => void;
   saveFile: (path: string) => Promise<void>;
   setCursorPosition: (pos: CursorPosition) => void;
+  setEditorViewState: (path: string, viewState: Partial<EditorViewState>) => void;
+  setEditorSelection: (selection: EditorSelectionRange | null) => void;
+  setFileDiagnostics: (path: string, diagnostics: LspDiagnostic[]) => void;
 
   // Actions — Panels
   setSidebarPanel: (panel: SidebarPanel) => void;
@@ -237,6 +253,8 @@ interface IdeStore {
 
   // Actions — AI
   sendMessage: (message: string) => Promise<void>;
+  cancelMessage: () => Promise<void>;
+  resolveConfirmation: (id: string, approved: boolean) => Promise<void>;
   clearChat: () => Promise<void>;
   loadAiConfig: () => Promise<void>;
   setAiConfig: (config: AiConfig) => Promise<void>;
@@ -253,6 +271,9 @@ export const useStore = create<IdeStore>((set, get) => ({
   openFiles: [],
   activeFilePath: null,
   cursorPosition: { line: 1, column: 1 },
+  editorViewStates: {},
+  editorSelection: null,
+  fileDiagnostics: {},
   sidebarPanel: "files",
   sidebarVisible: true,
   sidebarWidth: 260,
@@ -305,6 +326,7 @@ export const useStore = create<IdeStore>((set, get) => ({
   aiLoading: false,
   aiConfig: null,
   aiConfigLoaded: false,
+  pendingConfirmations: [],
 
   // LSP initial state
   lspRunning: false,
@@ -327,8 +349,10 @@ export const useStore = create<IdeStore>((set, get) => ({
     set({
       currentProject: info,
       fileTree: entries,
+      expandedDirs: new Set<string>(),
       openFiles: [],
       activeFilePath: null,
+      editorViewStates: {},
       sidebarPanel: "files",
       sidebarVisible: true,
     });
@@ -358,6 +382,15 @@ export const useStore = create<IdeStore>((set, get) => ({
     }
   },
 
+  removeRecentProject: async (path: string) => {
+    try {
+      const recents = await invoke<RecentProject[]>("remove_recent_project", { path });
+      set({ recentProjects: recents });
+    } catch {
+      // ignore
+    }
+  },
+
   loadFileTree: async (dirPath: string) => {
     const { expandedDirs } = get();
     const entries = await invoke<FileEntry[]>("list_directory", { path: dirPath });
@@ -519,6 +552,20 @@ export const useStore = create<IdeStore>((set, get) => ({
     set({ cursorPosition: pos });
   },
 
+  setEditorSelection: (selection: EditorSelectionRange | null) => {
+    set({ editorSelection: selection });
+  },
+
+  setFileDiagnostics: (path: string, diagnostics: LspDiagnostic[]) => {
+    set({ fileDiagnostics: { ...get().fileDiagnostics, [path]: diagnostics } });
+  },
+
+  setEditorViewState: (path: string, viewState: Partial<EditorViewState>) => {
+    const { editorViewStates } = get();
+    const prev = editorViewStates[path] ?? { line: 1, column: 1, scrollTop: 0 };
+    set({ editorViewStates: { ...editorViewStates, [path]: { ...prev, ...viewState } } });
+  },
+
   setSidebarPanel: (panel: SidebarPanel) => {
     const { sidebarPanel, sidebarVisible } = get();
     if (sidebarPanel === panel && sidebarVisible) {
@@ -739,13 +786,21 @@ export const useStore = create<IdeStore>((set, get) => ({
   // Workspace persistence
 
   saveWorkspaceState: async () => {
-    const { currentProject, openFiles, activeFilePath, sidebarPanel, sidebarVisible, sidebarWidth, bottomPanel, bottomPanelVisible, bottomPanelHeight } = get();
-    if (!currentProject) return;
+    const { currentProject, openFiles, activeFilePath, editorViewStates, expandedDirs, sidebarPanel, sidebarVisible, sidebarWidth, bottomPanel, bottomPanelVisible, bottomPanelHeight } = get();
+    if (!currentProject || restoringWorkspace) return;
     try {
       await invoke("save_workspace_state", {
         projectPath: currentProject.path,
         state: {
-          open_files: openFiles.map((f) => f.path),
+          open_tabs: openFiles.map((f) => {
+            const view = editorViewStates[f.path];
+            return {
+              path: f.path,
+              cursor_line: view?.line ?? 1,
+              cursor_column: view?.column ?? 1,
+              scroll_top: view?.scrollTop ?? 0,
+            };
+          }),
           active_file: activeFilePath,
           sidebar_panel: sidebarPanel,
           sidebar_visible: sidebarVisible,
@@ -753,6 +808,7 @@ export const useStore = create<IdeStore>((set, get) => ({
           bottom_panel: bottomPanel,
           bottom_panel_visible: bottomPanelVisible,
           bottom_panel_height: bottomPanelHeight,
+          expanded_dirs: [...expandedDirs],
         },
       });
     } catch {
@@ -761,9 +817,11 @@ export const useStore = create<IdeStore>((set, get) => ({
   },
 
   restoreWorkspaceState: async (projectPath: string) => {
+    restoringWorkspace = true;
     try {
       const saved = await invoke<{
-        open_files: string[];
+        version: number;
+        open_tabs: { path: string; cursor_line: number; cursor_column: number; scroll_top: number }[];
         active_file: string | null;
         sidebar_panel: string;
         sidebar_visible: boolean;
@@ -771,11 +829,20 @@ export const useStore = create<IdeStore>((set, get) => ({
         bottom_panel: string;
         bottom_panel_visible: boolean;
         bottom_panel_height: number;
+        expanded_dirs: string[];
       } | null>("load_workspace_state", { projectPath });
 
       if (!saved) return;
 
-      // Restore panel state immediately
+      // Restore panel state and per-file positions before any editor mounts
+      const editorViewStates: Record<string, EditorViewState> = {};
+      for (const tab of saved.open_tabs) {
+        editorViewStates[tab.path] = {
+          line: tab.cursor_line,
+          column: tab.cursor_column,
+          scrollTop: tab.scroll_top,
+        };
+      }
       set({
         sidebarPanel: saved.sidebar_panel as SidebarPanel,
         sidebarVisible: saved.sidebar_visible,
@@ -783,14 +850,27 @@ export const useStore = create<IdeStore>((set, get) => ({
         bottomPanel: saved.bottom_panel as BottomPanelTab,
         bottomPanelVisible: saved.bottom_panel_visible,
         bottomPanelHeight: saved.bottom_panel_height,
+        editorViewStates,
       });
 
+      // Re-expand the file tree, parents before children
+      const dirs = [...saved.expanded_dirs].sort((a, b) => a.length - b.length);
+      for (const dir of dirs) {
+        if (!get().expandedDirs.has(dir)) {
+          try {
+            await get().toggleDirectory(dir);
+          } catch {
+            // Directory may have been removed — skip it
+          }
+        }
+      }
+
       // Re-open files (sequentially to preserve order)
-      for (const filePath of saved.open_files) {
-        const name = filePath.split("/").pop() || filePath;
+      for (const tab of saved.open_tabs) {
+        const name = tab.path.split("/").pop() || tab.path;
         try {
-          await get().openFile(filePath, name);
-          get().pinFile(filePath); // Restored tabs should be pinned, not preview
+          await get().openFile(tab.path, name);
+          get().pinFile(tab.path); // Restored tabs should be pinned, not preview
         } catch {
           // File may have been deleted — skip it
         }
@@ -802,6 +882,8 @@ export const useStore = create<IdeStore>((set, get) => ({
       }
     } catch {
       // Silent fail
+    } finally {
+      restoringWorkspace = false;
     }
   },
 
@@ -903,6 +985,28 @@ export const useStore = create<IdeStore>((set, get) => ({
     }
   },
 
+  cancelMessage: async () => {
+    await invoke("ai_cancel");
+  },
+
+  resolveConfirmation: async (id: string, approved: boolean) => {
+    set((s) => ({
+      pendingConfirmations: s.pendingConfirmations.filter((c) => c.id !== id),
+    }));
+    try {
+      const result = await invoke<{ is_error: boolean; content: { text: string }[] }>(
+        "ai_resolve_confirmation",
+        { id, approved },
+      );
+      if (approved) {
+        const text = result.content.map((c) => c.text).join("\n");
+        showToast(result.is_error ? "error" : "success", text.slice(0, 200));
+      }
+    } catch (err) {
+      showToast("error", String(err));
+    }
+  },
+
   clearChat: async () => {
     await invoke("ai_clear_history");
     set({ chatMessages: [] });
@@ -952,10 +1056,47 @@ export const useStore = create<IdeStore>((set, get) => ({
 // Set up Tauri event listeners for AI and file watcher events
 let listenersInitialized = false;
 
+type EditorAction =
+  | { action: "open" | "close" | "reload"; path: string }
+  | { action: "tool_call"; tool_call: ToolCall }
+  | { action: "confirm_tool"; confirmation: PendingConfirmation };
+
+// Apply editor actions queued by tools. Reloads and confirmations also
+// arrive as events, so only the queued-only actions need handling here.
+async function drainPendingActions() {
+  let actions: EditorAction[];
+  try {
+    actions = await invoke<EditorAction[]>("ai_get_pending_actions");
+  } catch {
+    return;
+  }
+  const store = useStore.getState();
+  for (const action of actions) {
+    switch (action.action) {
+      case "open":
+        await store
+          .openFile(action.path, action.path.split("/").pop() || action.path)
+          .catch(() => {});
+        break;
+      case "close":
+        if (useStore.getState().openFiles.some((f) => f.path === action.path)) {
+          store.closeFile(action.path);
+        }
+        break;
+      case "tool_call":
+        store.updateToolCall(action.tool_call);
+        break;
+    }
+  }
+}
+
 export function initAiListeners() {
   if (listenersInitialized) return;
   listenersInitialized = true;
 
+  // External MCP clients queue actions too, with no chat turn to hook into
+  setInterval(drainPendingActions, 1_000);
+
   // File watcher events
   listen<{ path: string; kind: string }>("file:changed", async (event) => {
     const { path, kind } = event.payload;
@@ -994,6 +1135,34 @@ export function initAiListeners() {
     }
   });
 
+  // An AI tool rewrote a file on disk; refresh its tab unless the user has
+  // edits. Edits to unsaved buffers arrive as editor:set-content instead.
+  listen<{ path: string }>("editor:reload", async (event) => {
+    const { path } = event.payload;
+    const file = useStore.getState().openFiles.find((f) => f.path === path);
+    if (!file || file.dirty) return;
+    try {
+      const content = await invoke<string>("read_file", { path });
+      useStore.setState({
+        openFiles: useStore.getState().openFiles.map((f) =>
+          f.path === path ? { ...f, content, externallyModified: false } : f,
+        ),
+      });
+    } catch {
+      // File may have been deleted
+    }
+  });
+
+  // An AI tool edited a tab's unsaved buffer; show the edit, still unsaved
+  listen<{ path: string; content: string }>("editor:set-content", (event) => {
+    const { path, content } = event.payload;
+    useStore.setState({
+      openFiles: useStore.getState().openFiles.map((f) =>
+        f.path === path ? { ...f, content, dirty: true } : f,
+      ),
+    });
+  });
+
   listen<{ text: string }>("ai:response-chunk", (event) => {
     // Streaming chunks — update or create the last assistant message
     const store = useStore.getState();
@@ -1029,6 +1198,17 @@ export function initAiListeners() {
     useStore.setState({ aiLoading: false });
   });
 
+  listen<{ message: ChatMessage }>("ai:cancelled", (_event) => {
+    useStore.setState({ aiLoading: false });
+  });
+
+  listen<{ confirmation: PendingConfirmation }>("ai:confirmation-requested", (event) => {
+    const store = useStore.getState();
+    useStore.setState({
+      pendingConfirmations: [...store.pendingConfirmations, event.payload.confirmation],
+    });
+  });
+
   listen<{ error: string }>("ai:error", (event) => {
     const errorMsg: ChatMessage = {
       role: "assistant",
@@ -2,11 +2,20 @@ export type ModEnvironment = "client" | "server" | "both";
 
 export type ProjectType = "mod" | "modpack" | "unknown";
 
+export type ModLoader = "alloy" | "fabric" | "quilt" | "forge" | "neoforge";
+
 export interface ProjectInfo {
   name: string;
   path: string;
   project_type: ProjectType;
   environment: ModEnvironment | null;
+  loader: ModLoader | null;
+  loader_version: string | null;
+}
+
+export interface CreateProjectResult extends ProjectInfo {
+  created_files: string[];
+  main_class: string | null;
 }
 
 export interface RecentProject {
@@ -49,6 +58,24 @@ export interface CursorPosition {
   cursors?: number;
 }
 
+/** Where the user last was in a file, kept per path across tab switches and sessions. */
+export interface EditorViewState {
+  line: number;
+  column: number;
+  scrollTop: number;
+}
+
+/** The active editor's main selection; 1-based, a bare cursor when start equals end. */
+export interface EditorSelectionRange {
+  path: string;
+  start_line: number;
+  start_col: number;
+  end_line: number;
+  end_col: number;
+  /** Selected text, or null for a bare cursor */
+  text: string | null;
+}
+
 // Search types
 
 export interface SearchResult {
@@ -56,6 +83,27 @@ export interface SearchResult {
   name: string;
   line_number: number;
   line_content: string;
+  /** Char offset of the first match in the line */
+  match_start: number;
+  /** Char offset just past the first match */
+  match_end: number;
+  /** How many matches the line has */
+  match_count: number;
+}
+
+export interface ReplacePreview {
+  path: string;
+  line: number;
+  before: string;
+  after: string;
+}
+
+export interface ReplaceResult {
+  files_changed: number;
+  replacements: number;
+  file_counts: { path: string; replacements: number }[];
+  /** Only filled in for a dry run */
+  previews: ReplacePreview[];
 }
 
 // Quick Open types
@@ -74,6 +122,7 @@ export interface GitStatus {
   staged: GitFileStatus[];
   ahead: number;
   behind: number;
+  has_upstream: boolean;
 }
 
 export interface GitFileStatus {
@@ -102,10 +151,28 @@ export interface ChatMessage {
   timestamp: number;
 }
 
+export type AiProvider = "anthropic" | "openai" | "ollama";
+
 export interface AiConfig {
+  provider?: AiProvider;
   api_key: string | null;
   model: string;
   max_tokens: number;
+  base_url?: string | null;
+  max_tool_iterations?: number;
+  approval_policy?: ApprovalPolicy;
+  /** Extra regexes masked in terminal and build output */
+  redact_patterns?: string[];
+}
+
+export type ApprovalPolicy = "auto_approve" | "confirm_destructive" | "confirm_writes";
+
+export interface PendingConfirmation {
+  id: string;
+  tool: string;
+  input: Record<string, unknown>;
+  risk: "read_only" | "mutating" | "destructive";
+  created_at: number;
 }
 
 // Build types
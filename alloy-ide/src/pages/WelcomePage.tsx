@@ -13,12 +13,13 @@ import {
   Package,
   ArrowLeft,
   Sparkles,
+  X,
 } from "lucide-react";
 import { open } from "@tauri-apps/plugin-dialog";
 import { invoke } from "@tauri-apps/api/core";
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import { useStore } from "../lib/store";
-import type { ProjectInfo, RecentProject } from "../lib/types";
+import type { CreateProjectResult, RecentProject } from "../lib/types";
 import GridOverlay from "../components/effects/GridOverlay";
 import EmberGlow from "../components/effects/EmberGlow";
 import alloyLogo from "../assets/alloy-logo.svg";
@@ -26,13 +27,23 @@ import alloyLogo from "../assets/alloy-logo.svg";
 type Screen = "home" | "new-project";
 type ProjectTypeChoice = "mod" | "modpack";
 type EnvChoice = "client" | "server" | "both";
+type LoaderChoice = "alloy" | "fabric" | "quilt";
+
+const LOADER_OPTIONS: { value: LoaderChoice; label: string }[] = [
+  { value: "alloy", label: "Alloy" },
+  { value: "fabric", label: "Fabric" },
+  { value: "quilt", label: "Quilt" },
+];
+const MINECRAFT_VERSIONS = ["1.21.11", "1.21.4", "1.21.1", "1.20.6", "1.20.1"];
 
 export default function WelcomePage() {
   const navigate = useNavigate();
   const appWindow = getCurrentWindow();
   const recentProjects = useStore((s) => s.recentProjects);
   const loadRecentProjects = useStore((s) => s.loadRecentProjects);
+  const removeRecentProject = useStore((s) => s.removeRecentProject);
   const openProject = useStore((s) => s.openProject);
+  const openFile = useStore((s) => s.openFile);
 
   const [screen, setScreen] = useState<Screen>("home");
 
@@ -42,6 +53,8 @@ export default function WelcomePage() {
   const [modId, setModId] = useState("");
   const [packageName, setPackageName] = useState("com.example");
   const [environment, setEnvironment] = useState<EnvChoice>("both");
+  const [loader, setLoader] = useState<LoaderChoice>("alloy");
+  const [minecraftVersion, setMinecraftVersion] = useState(MINECRAFT_VERSIONS[0]);
   const [projectLocation, setProjectLocation] = useState("");
   const [creating, setCreating] = useState(false);
   const [error, setError] = useState("");
@@ -95,7 +108,7 @@ export default function WelcomePage() {
     setError("");
 
     try {
-      await invoke<ProjectInfo>("create_project", {
+      const result = await invoke<CreateProjectResult>("create_project", {
         args: {
           name: projectName.trim(),
           path: projectLocation,
@@ -103,11 +116,16 @@ export default function WelcomePage() {
           environment,
           mod_id: modId.trim() || null,
           package_name: packageName.trim() || null,
+          loader,
+          minecraft_version: loader === "alloy" ? null : minecraftVersion,
         },
       });
       // Open the newly created project
-      const fullPath = projectLocation + "/" + projectName.trim();
-      await openProject(fullPath);
+      await openProject(result.path);
+      if (result.main_class) {
+        const mainName = result.main_class.split(/[\\/]/).pop() ?? result.main_class;
+        await openFile(result.main_class, mainName);
+      }
       navigate("/editor", { replace: true });
     } catch (err) {
       setError(String(err));
@@ -200,6 +218,7 @@ export default function WelcomePage() {
             onNewProject={() => setScreen("new-project")}
             onOpenFolder={handleOpenFolder}
             onOpenRecent={handleOpenRecent}
+            onRemoveRecent={removeRecentProject}
             formatTime={formatTime}
             projectTypeBadge={projectTypeBadge}
           />
@@ -215,6 +234,10 @@ export default function WelcomePage() {
             setPackageName={setPackageName}
             environment={environment}
             setEnvironment={setEnvironment}
+            loader={loader}
+            setLoader={setLoader}
+            minecraftVersion={minecraftVersion}
+            setMinecraftVersion={setMinecraftVersion}
             projectLocation={projectLocation}
             onPickLocation={handlePickLocation}
             creating={creating}
@@ -235,6 +258,7 @@ function HomeScreen({
   onNewProject,
   onOpenFolder,
   onOpenRecent,
+  onRemoveRecent,
   formatTime,
   projectTypeBadge,
 }: {
@@ -242,6 +266,7 @@ function HomeScreen({
   onNewProject: () => void;
   onOpenFolder: () => void;
   onOpenRecent: (path: string) => void;
+  onRemoveRecent: (path: string) => void;
   formatTime: (ts: number) => string;
   projectTypeBadge: (type: string) => React.ReactNode;
 }) {
@@ -331,6 +356,17 @@ function HomeScreen({
                   <span className="text-[10px] text-stone-600 shrink-0">
                     {formatTime(p.last_opened)}
                   </span>
+                  <span
+                    role="button"
+                    title="Remove from recent projects"
+                    onClick={(e) => {
+                      e.stopPropagation();
+                      onRemoveRecent(p.path);
+                    }}
+                    className="flex h-5 w-5 items-center justify-center rounded text-stone-700 opacity-0 group-hover:opacity-100 hover:text-stone-300 hover:bg-obsidian-700 shrink-0 transition-all"
+                  >
+                    <X size={11} />
+                  </span>
                   <ChevronRight size={12} className="text-stone-700 group-hover:text-stone-500 shrink-0 transition-colors" />
                 </button>
               ))}
@@ -361,6 +397,10 @@ function NewProjectScreen({
   setPackageName,
   environment,
   setEnvironment,
+  loader,
+  setLoader,
+  minecraftVersion,
+  setMinecraftVersion,
   projectLocation,
   onPickLocation,
   creating,
@@ -378,6 +418,10 @@ function NewProjectScreen({
   setPackageName: (s: string) => void;
   environment: EnvChoice;
   setEnvironment: (e: EnvChoice) => void;
+  loader: LoaderChoice;
+  setLoader: (l: LoaderChoice) => void;
+  minecraftVersion: string;
+  setMinecraftVersion: (v: string) => void;
   projectLocation: string;
   onPickLocation: () => void;
   creating: boolean;
@@ -489,6 +533,44 @@ function NewProjectScreen({
                 </div>
               </div>
 
+              {/* Loader + Minecraft version */}
+              <div className="flex gap-3">
+                <div className="flex-1">
+                  <label className="block text-[11px] text-stone-400 uppercase tracking-wider font-semibold mb-1.5">
+                    Loader
+                  </label>
+                  <select
+                    value={loader}
+                    onChange={(e) => setLoader(e.target.value as LoaderChoice)}
+                    className="w-full rounded-lg bg-obsidian-900 border border-obsidian-600 px-3 py-2.5 text-sm text-stone-200 focus:outline-none focus:border-ember/50 focus:ring-1 focus:ring-ember/20 transition-colors"
+                  >
+                    {LOADER_OPTIONS.map((opt) => (
+                      <option key={opt.value} value={opt.value}>
+                        {opt.label}
+                      </option>
+                    ))}
+                  </select>
+                </div>
+                {loader !== "alloy" && (
+                  <div className="flex-1">
+                    <label className="block text-[11px] text-stone-400 uppercase tracking-wider font-semibold mb-1.5">
+                      Minecraft Version
+                    </label>
+                    <select
+                      value={minecraftVersion}
+                      onChange={(e) => setMinecraftVersion(e.target.value)}
+                      className="w-full rounded-lg bg-obsidian-900 border border-obsidian-600 px-3 py-2.5 text-sm text-stone-200 font-mono focus:outline-none focus:border-ember/50 focus:ring-1 focus:ring-ember/20 transition-colors"
+                    >
+                      {MINECRAFT_VERSIONS.map((v) => (
+                        <option key={v} value={v}>
+                          {v}
+                        </option>
+                      ))}
+                    </select>
+                  </div>
+                )}
+              </div>
+
               {/* Environment selector */}
               <div>
                 <label className="block text-[11px] text-stone-400 uppercase tracking-wider font-semibold mb-1.5">
@@ -1,7 +1,7 @@
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use rand::RngCore;
 use reqwest::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::io::{Read, Write};
 use std::time::Duration;
@@ -9,6 +9,8 @@ use url::Url;
 
 const AUTH_URL: &str = "https://login.microsoftonline.com/consumers/oauth2/v2.0/authorize";
 const TOKEN_URL: &str = "https://login.microsoftonline.com/consumers/oauth2/v2.0/token";
+const DEVICE_CODE_URL: &str = "https://login.microsoftonline.com/consumers/oauth2/v2.0/devicecode";
+const DEVICE_CODE_GRANT: &str = "urn:ietf:params:oauth:grant-type:device_code";
 
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
@@ -18,6 +20,32 @@ pub struct MsTokenResponse {
     pub expires_in: u64,
 }
 
+/// Device authorization response. The user enters `user_code` at
+/// `verification_uri` while we poll the token endpoint with `device_code`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DeviceCodeResponse {
+    #[serde(skip_serializing)]
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub expires_in: u64,
+    #[serde(default = "default_poll_interval")]
+    pub interval: u64,
+    #[serde(default)]
+    pub message: String,
+}
+
+fn default_poll_interval() -> u64 {
+    5
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenErrorResponse {
+    error: String,
+    #[serde(default)]
+    error_description: String,
+}
+
 pub struct PkceChallenge {
     pub verifier: String,
     pub challenge: String,
@@ -180,6 +208,12 @@ pub async fn refresh_tokens(
 
     if !resp.status().is_success() {
         let body = resp.text().await.unwrap_or_default();
+        // A revoked or expired refresh token can only be fixed by signing in again
+        if let Ok(err) = serde_json::from_str::<TokenErrorResponse>(&body) {
+            if matches!(err.error.as_str(), "invalid_grant" | "interaction_required") {
+                return Err(super::tokens::relogin_required(&err.error_description));
+            }
+        }
         return Err(format!("Token refresh failed: {}", body));
     }
 
@@ -187,3 +221,80 @@ pub async fn refresh_tokens(
         .await
         .map_err(|e| format!("Failed to parse refresh response: {}", e))
 }
+
+/// Start a device-code sign-in for machines where a browser redirect is awkward.
+pub async fn request_device_code(
+    client: &Client,
+    client_id: &str,
+) -> Result<DeviceCodeResponse, String> {
+    let resp = client
+        .post(DEVICE_CODE_URL)
+        .form(&[
+            ("client_id", client_id),
+            ("scope", "XboxLive.signin offline_access"),
+        ])
+        .send()
+        .await
+        .map_err(|e| format!("Device code request failed: {}", e))?;
+
+    if !resp.status().is_success() {
+        let body = resp.text().await.unwrap_or_default();
+        return Err(format!("Device code request failed: {}", body));
+    }
+
+    resp.json::<DeviceCodeResponse>()
+        .await
+        .map_err(|e| format!("Failed to parse device code response: {}", e))
+}
+
+/// Poll the token endpoint until the user finishes signing in (RFC 8628 §3.5).
+pub async fn poll_device_code(
+    client: &Client,
+    client_id: &str,
+    device: &DeviceCodeResponse,
+) -> Result<MsTokenResponse, String> {
+    let deadline = std::time::Instant::now() + Duration::from_secs(device.expires_in);
+    let mut interval = device.interval.max(1);
+
+    loop {
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+        if std::time::Instant::now() >= deadline {
+            return Err("Sign-in code expired. Please try again.".to_string());
+        }
+
+        let resp = client
+            .post(TOKEN_URL)
+            .form(&[
+                ("client_id", client_id),
+                ("grant_type", DEVICE_CODE_GRANT),
+                ("device_code", device.device_code.as_str()),
+            ])
+            .send()
+            .await
+            .map_err(|e| format!("Token poll request failed: {}", e))?;
+
+        if resp.status().is_success() {
+            return resp
+                .json::<MsTokenResponse>()
+                .await
+                .map_err(|e| format!("Failed to parse token response: {}", e));
+        }
+
+        let body = resp.text().await.unwrap_or_default();
+        let err = serde_json::from_str::<TokenErrorResponse>(&body)
+            .map_err(|_| format!("Token poll failed: {}", body))?;
+        match err.error.as_str() {
+            "authorization_pending" => {}
+            // The server wants us to back off: add 5 seconds to the interval
+            "slow_down" => interval += 5,
+            "expired_token" => return Err("Sign-in code expired. Please try again.".to_string()),
+            "authorization_declined" => return Err("Sign-in was declined.".to_string()),
+            _ => {
+                return Err(format!(
+                    "Auth error: {}: {}",
+                    err.error, err.error_description
+                ))
+            }
+        }
+    }
+}
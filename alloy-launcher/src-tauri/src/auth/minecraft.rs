@@ -15,6 +15,18 @@ struct McAuthRequest {
 #[derive(Debug, Deserialize)]
 struct McAuthResponse {
     access_token: String,
+    /// Seconds until the token expires (currently 24 hours)
+    #[serde(default = "default_mc_expires_in")]
+    expires_in: u64,
+}
+
+fn default_mc_expires_in() -> u64 {
+    86_400
+}
+
+pub struct McToken {
+    pub access_token: String,
+    pub expires_in: u64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -34,7 +46,7 @@ pub async fn authenticate_minecraft(
     client: &Client,
     user_hash: &str,
     xsts_token: &str,
-) -> Result<String, String> {
+) -> Result<McToken, String> {
     let body = McAuthRequest {
         identity_token: format!("XBL3.0 x={};{}", user_hash, xsts_token),
     };
@@ -56,7 +68,10 @@ pub async fn authenticate_minecraft(
         .await
         .map_err(|e| format!("Failed to parse MC auth response: {}", e))?;
 
-    Ok(data.access_token)
+    Ok(McToken {
+        access_token: data.access_token,
+        expires_in: data.expires_in,
+    })
 }
 
 /// Fetch the Minecraft profile (username, UUID, skin) using an MC access token.
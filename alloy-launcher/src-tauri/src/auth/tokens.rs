@@ -1,7 +1,7 @@
 use reqwest::Client;
 use std::path::PathBuf;
 
-use crate::state::AuthTokens;
+use crate::state::{AppState, AuthTokens, MinecraftProfile};
 
 /// File-based token storage path: <base_dir>/auth.json
 /// This is more reliable than OS keychain because it doesn't depend on
@@ -11,113 +11,232 @@ fn token_path() -> PathBuf {
     base.join("auth.json")
 }
 
-#[derive(serde::Serialize, serde::Deserialize)]
-struct StoredAuth {
-    refresh_token: String,
+/// A signed-in account as persisted between runs.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredAccount {
+    pub uuid: String,
+    pub username: String,
+    #[serde(default)]
+    pub skin_url: Option<String>,
+    pub refresh_token: String,
 }
 
-/// Store the MS refresh token to disk.
-pub fn store_refresh_token(token: &str) -> Result<(), String> {
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+pub struct StoredAuth {
+    #[serde(default)]
+    pub accounts: Vec<StoredAccount>,
+    /// UUID of the account used for launching
+    #[serde(default)]
+    pub active: Option<String>,
+    /// Single-account format from before multiple accounts were supported
+    #[serde(default, skip_serializing)]
+    refresh_token: Option<String>,
+}
+
+impl StoredAuth {
+    pub fn active_account(&self) -> Option<&StoredAccount> {
+        let uuid = self.active.as_ref()?;
+        self.accounts.iter().find(|a| &a.uuid == uuid)
+    }
+}
+
+/// Load all stored accounts from disk.
+pub fn load_accounts() -> Result<StoredAuth, String> {
+    let path = token_path();
+    if !path.exists() {
+        log::info!("No stored auth at {}", path.display());
+        return Ok(StoredAuth::default());
+    }
+    let json =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read auth file: {}", e))?;
+    let mut data: StoredAuth =
+        serde_json::from_str(&json).map_err(|e| format!("Failed to parse auth file: {}", e))?;
+
+    // An old single-account file: keep the token under a placeholder until
+    // the next refresh tells us whose it is
+    if let Some(token) = data.refresh_token.take() {
+        if data.accounts.is_empty() {
+            data.accounts.push(StoredAccount {
+                uuid: String::new(),
+                username: String::new(),
+                skin_url: None,
+                refresh_token: token,
+            });
+            data.active = Some(String::new());
+        }
+    }
+
+    log::info!(
+        "Loaded {} account(s) from {}",
+        data.accounts.len(),
+        path.display()
+    );
+    Ok(data)
+}
+
+/// Write all accounts to disk, readable only by the current user.
+pub fn save_accounts(data: &StoredAuth) -> Result<(), String> {
     let path = token_path();
     if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent)
-            .map_err(|e| format!("Failed to create auth dir: {}", e))?;
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create auth dir: {}", e))?;
     }
-    let data = StoredAuth {
-        refresh_token: token.to_string(),
-    };
-    let json = serde_json::to_string_pretty(&data)
+    let json = serde_json::to_string_pretty(data)
         .map_err(|e| format!("Failed to serialize auth: {}", e))?;
-    std::fs::write(&path, &json)
-        .map_err(|e| format!("Failed to write auth file: {}", e))?;
-    log::info!("Refresh token stored to {}", path.display());
+    std::fs::write(&path, &json).map_err(|e| format!("Failed to write auth file: {}", e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600));
+    }
+
+    log::info!(
+        "Stored {} account(s) to {}",
+        data.accounts.len(),
+        path.display()
+    );
     Ok(())
 }
 
-/// Load the MS refresh token from disk.
-pub fn load_refresh_token() -> Result<Option<String>, String> {
-    let path = token_path();
-    if !path.exists() {
-        log::info!("No stored auth at {}", path.display());
-        return Ok(None);
+/// Add or update an account's refresh token and make it the active account.
+pub fn store_account(profile: &MinecraftProfile, refresh_token: &str) -> Result<(), String> {
+    let mut data = load_accounts()?;
+    // Drop this account's old entry and any unidentified legacy token
+    data.accounts
+        .retain(|a| a.uuid != profile.uuid && !a.uuid.is_empty());
+    data.accounts.push(StoredAccount {
+        uuid: profile.uuid.clone(),
+        username: profile.username.clone(),
+        skin_url: profile.skin_url.clone(),
+        refresh_token: refresh_token.to_string(),
+    });
+    data.active = Some(profile.uuid.clone());
+    save_accounts(&data)
+}
+
+/// Remove one account. Returns the UUID of the account that is active afterwards.
+pub fn remove_account(uuid: &str) -> Result<Option<String>, String> {
+    let mut data = load_accounts()?;
+    data.accounts.retain(|a| a.uuid != uuid);
+    if data.active.as_deref() == Some(uuid) {
+        data.active = data.accounts.first().map(|a| a.uuid.clone());
+    }
+    if data.accounts.is_empty() {
+        delete_all_accounts()?;
+    } else {
+        save_accounts(&data)?;
     }
-    let json = std::fs::read_to_string(&path)
-        .map_err(|e| format!("Failed to read auth file: {}", e))?;
-    let data: StoredAuth = serde_json::from_str(&json)
-        .map_err(|e| format!("Failed to parse auth file: {}", e))?;
-    log::info!("Loaded refresh token from {}", path.display());
-    Ok(Some(data.refresh_token))
+    Ok(data.active)
 }
 
-/// Delete the stored refresh token.
-pub fn delete_refresh_token() -> Result<(), String> {
+/// Make a stored account the active one.
+pub fn set_active_account(uuid: &str) -> Result<(), String> {
+    let mut data = load_accounts()?;
+    if !data.accounts.iter().any(|a| a.uuid == uuid) {
+        return Err(format!("No such account: {}", uuid));
+    }
+    data.active = Some(uuid.to_string());
+    save_accounts(&data)
+}
+
+/// Delete every stored account.
+pub fn delete_all_accounts() -> Result<(), String> {
     let path = token_path();
     if path.exists() {
-        std::fs::remove_file(&path)
-            .map_err(|e| format!("Failed to delete auth file: {}", e))?;
+        std::fs::remove_file(&path).map_err(|e| format!("Failed to delete auth file: {}", e))?;
     }
     Ok(())
 }
 
-/// Attempt to refresh the full auth chain using a stored refresh token.
-/// Returns None if no token is stored or refresh fails.
-pub async fn try_refresh(client: &Client, client_id: &str) -> Option<AuthTokens> {
-    let refresh_token = match load_refresh_token() {
-        Ok(Some(token)) => token,
-        Ok(None) => {
-            log::info!("No stored refresh token — login required");
-            return None;
+/// Prefix on errors that mean the stored session is unusable and the user
+/// must sign in again, so the frontend can tell them apart from network or
+/// service failures.
+pub const RELOGIN_REQUIRED: &str = "relogin_required";
+
+/// Refresh the session this far ahead of expiry so it cannot lapse mid-launch.
+const EXPIRY_MARGIN_SECS: u64 = 5 * 60;
+
+pub fn relogin_required(detail: &str) -> String {
+    if detail.is_empty() {
+        format!(
+            "{}: Your session has expired. Please sign in again.",
+            RELOGIN_REQUIRED
+        )
+    } else {
+        format!(
+            "{}: Your session has expired. Please sign in again. ({})",
+            RELOGIN_REQUIRED, detail
+        )
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Return the active account's session, first re-running MS → Xbox → XSTS
+/// → MC with its stored refresh token if the Minecraft token is missing or
+/// about to expire. Errors starting with `RELOGIN_REQUIRED` drop the session.
+pub async fn ensure_fresh(state: &AppState) -> Result<AuthTokens, String> {
+    let stored = load_accounts()?;
+    let active = {
+        let mut accounts = state.accounts.lock().unwrap();
+        if accounts.active.is_none() {
+            accounts.active = stored.active.clone();
         }
-        Err(e) => {
-            log::error!("Failed to load refresh token: {}", e);
-            return None;
+        if let Some(auth) = accounts.active_session() {
+            if auth.mc_expires_at > now_secs() + EXPIRY_MARGIN_SECS {
+                return Ok(auth.clone());
+            }
+            log::info!("Minecraft token expired or expiring soon — refreshing");
         }
+        accounts.active.clone()
     };
 
-    log::info!("Attempting token refresh...");
+    let refresh_token = active
+        .as_deref()
+        .and_then(|uuid| stored.accounts.iter().find(|a| a.uuid == uuid))
+        .map(|a| a.refresh_token.clone())
+        .ok_or_else(|| relogin_required(""))?;
 
-    let ms_tokens = match super::microsoft::refresh_tokens(client, client_id, &refresh_token).await
-    {
-        Ok(t) => t,
-        Err(e) => {
-            log::error!("MS token refresh failed: {}", e);
-            return None;
+    let client_id = state.settings.lock().unwrap().client_id.clone();
+    match refresh_session(&state.http_client, &client_id, &refresh_token).await {
+        Ok(auth) => {
+            state.accounts.lock().unwrap().upsert(auth.clone());
+            Ok(auth)
         }
-    };
-
-    // Store new refresh token if provided
-    if let Some(ref new_refresh) = ms_tokens.refresh_token {
-        if let Err(e) = store_refresh_token(new_refresh) {
-            log::error!("Failed to store new refresh token: {}", e);
+        Err(e) => {
+            if e.starts_with(RELOGIN_REQUIRED) {
+                if let Some(uuid) = active {
+                    state.accounts.lock().unwrap().drop_session(&uuid);
+                }
+            }
+            Err(e)
         }
     }
+}
 
-    let xbox = super::xbox::authenticate_xbox(client, &ms_tokens.access_token)
-        .await
-        .ok()?;
-
-    let xsts = super::xbox::authenticate_xsts(client, &xbox.token)
-        .await
-        .ok()?;
-
-    let mc_token =
-        super::minecraft::authenticate_minecraft(client, &xsts.user_hash, &xsts.token)
-            .await
-            .ok()?;
+/// Refresh the full auth chain from a stored MS refresh token.
+pub async fn refresh_session(
+    client: &Client,
+    client_id: &str,
+    refresh_token: &str,
+) -> Result<AuthTokens, String> {
+    log::info!("Attempting token refresh...");
 
-    let profile = super::minecraft::fetch_profile(client, &mc_token)
-        .await
-        .ok()?;
+    let ms_tokens = super::microsoft::refresh_tokens(client, client_id, refresh_token).await?;
 
-    log::info!("Auto-login successful: {}", profile.username);
+    // Microsoft rotates refresh tokens; keep the old one if none came back
+    let refresh_token = ms_tokens
+        .refresh_token
+        .unwrap_or_else(|| refresh_token.to_string());
+    let auth = full_login(client, &ms_tokens.access_token, &refresh_token).await?;
 
-    Some(AuthTokens {
-        mc_access_token: mc_token,
-        ms_refresh_token: ms_tokens
-            .refresh_token
-            .unwrap_or_else(|| refresh_token.clone()),
-        profile,
-    })
+    log::info!("Auto-login successful: {}", auth.profile.username);
+    Ok(auth)
 }
 
 /// Perform the full login flow: MS code → Xbox → XSTS → MC → Profile.
@@ -130,12 +249,13 @@ pub async fn full_login(
     let xsts = super::xbox::authenticate_xsts(client, &xbox.token).await?;
     let mc_token =
         super::minecraft::authenticate_minecraft(client, &xsts.user_hash, &xsts.token).await?;
-    let profile = super::minecraft::fetch_profile(client, &mc_token).await?;
+    let profile = super::minecraft::fetch_profile(client, &mc_token.access_token).await?;
 
-    store_refresh_token(ms_refresh_token)?;
+    store_account(&profile, ms_refresh_token)?;
 
     Ok(AuthTokens {
-        mc_access_token: mc_token,
+        mc_access_token: mc_token.access_token,
+        mc_expires_at: now_secs() + mc_token.expires_in,
         ms_refresh_token: ms_refresh_token.to_string(),
         profile,
     })
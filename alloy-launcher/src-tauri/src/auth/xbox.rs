@@ -115,10 +115,7 @@ pub async fn authenticate_xbox(
 }
 
 /// Exchange Xbox token for XSTS token.
-pub async fn authenticate_xsts(
-    client: &Client,
-    xbox_token: &str,
-) -> Result<XboxTokens, String> {
+pub async fn authenticate_xsts(client: &Client, xbox_token: &str) -> Result<XboxTokens, String> {
     let body = XstsRequest {
         properties: XstsProperties {
             sandbox_id: "RETAIL".to_string(),
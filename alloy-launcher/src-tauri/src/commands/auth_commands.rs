@@ -1,6 +1,6 @@
 use crate::auth::{microsoft, tokens};
 use crate::state::{AppState, MinecraftProfile};
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
 
 #[derive(serde::Serialize)]
 pub struct AuthResult {
@@ -10,13 +10,55 @@ pub struct AuthResult {
 }
 
 /// Start the full Microsoft OAuth login flow.
+///
+/// `method` is `"browser"` (default, PKCE redirect to a local server) or
+/// `"device_code"`, which emits `device-code` with a code for the user to
+/// enter on another device and polls until they finish.
 #[tauri::command]
-pub async fn login(state: State<'_, AppState>) -> Result<AuthResult, String> {
+pub async fn login(
+    method: Option<String>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<AuthResult, String> {
     let client_id = {
         let settings = state.settings.lock().unwrap();
         settings.client_id.clone()
     };
 
+    let ms_tokens = match method.as_deref().unwrap_or("browser") {
+        "browser" => browser_login(&state, &client_id).await?,
+        "device_code" => {
+            let device = microsoft::request_device_code(&state.http_client, &client_id).await?;
+            let _ = app.emit("device-code", &device);
+            microsoft::poll_device_code(&state.http_client, &client_id, &device).await?
+        }
+        other => return Err(format!("Unknown login method: {}", other)),
+    };
+
+    let refresh_token = ms_tokens
+        .refresh_token
+        .clone()
+        .ok_or("No refresh token received")?;
+
+    // Full chain: Xbox → XSTS → MC → Profile
+    let auth =
+        tokens::full_login(&state.http_client, &ms_tokens.access_token, &refresh_token).await?;
+
+    let profile = auth.profile.clone();
+    state.accounts.lock().unwrap().upsert(auth);
+
+    Ok(AuthResult {
+        success: true,
+        profile: Some(profile),
+        error: None,
+    })
+}
+
+/// PKCE authorization-code flow through the system browser.
+async fn browser_login(
+    state: &AppState,
+    client_id: &str,
+) -> Result<microsoft::MsTokenResponse, String> {
     // Generate PKCE
     let pkce = microsoft::generate_pkce();
 
@@ -30,7 +72,7 @@ pub async fn login(state: State<'_, AppState>) -> Result<AuthResult, String> {
     drop(listener); // Release so wait_for_callback can bind
 
     // Build auth URL and open browser
-    let auth_url = microsoft::build_auth_url(&client_id, port, &pkce.challenge);
+    let auth_url = microsoft::build_auth_url(client_id, port, &pkce.challenge);
     open::that(&auth_url).map_err(|e| format!("Failed to open browser: {}", e))?;
 
     // Wait for callback (blocking — runs on Tauri's async thread pool)
@@ -40,76 +82,113 @@ pub async fn login(state: State<'_, AppState>) -> Result<AuthResult, String> {
         .map_err(|e| format!("Auth callback failed: {}", e))?;
 
     // Exchange code for tokens
-    let ms_tokens =
-        microsoft::exchange_code(&state.http_client, &client_id, &code, port, &pkce.verifier)
-            .await?;
-
-    let refresh_token = ms_tokens
-        .refresh_token
-        .clone()
-        .ok_or("No refresh token received")?;
-
-    // Full chain: Xbox → XSTS → MC → Profile
-    let auth = tokens::full_login(
-        &state.http_client,
-        &ms_tokens.access_token,
-        &refresh_token,
-    )
-    .await?;
-
-    let profile = auth.profile.clone();
-    *state.auth.lock().unwrap() = Some(auth);
-
-    Ok(AuthResult {
-        success: true,
-        profile: Some(profile),
-        error: None,
-    })
+    microsoft::exchange_code(&state.http_client, client_id, &code, port, &pkce.verifier).await
 }
 
-/// Check if we have a valid session (try refreshing stored tokens).
+/// Check if we have a valid session, refreshing expired tokens from the
+/// stored refresh token.
 #[tauri::command]
 pub async fn check_auth(state: State<'_, AppState>) -> Result<AuthResult, String> {
-    // Already authenticated in this session
-    {
-        let auth = state.auth.lock().unwrap();
-        if let Some(ref a) = *auth {
-            return Ok(AuthResult {
-                success: true,
-                profile: Some(a.profile.clone()),
-                error: None,
-            });
+    match tokens::ensure_fresh(&state).await {
+        Ok(auth) => Ok(AuthResult {
+            success: true,
+            profile: Some(auth.profile),
+            error: None,
+        }),
+        Err(e) => {
+            log::info!("No usable session: {}", e);
+            Ok(AuthResult {
+                success: false,
+                profile: None,
+                // Nothing stored yet is the normal first-run state, not an error
+                error: if tokens::load_accounts()
+                    .map(|a| a.active_account().is_some())
+                    .unwrap_or(false)
+                {
+                    Some(e)
+                } else {
+                    None
+                },
+            })
         }
     }
+}
 
-    let client_id = {
-        let settings = state.settings.lock().unwrap();
-        settings.client_id.clone()
-    };
+/// A stored account as shown in the account switcher.
+#[derive(serde::Serialize)]
+pub struct AccountSummary {
+    pub profile: MinecraftProfile,
+    pub active: bool,
+}
 
-    // Try to refresh from stored token
-    match tokens::try_refresh(&state.http_client, &client_id).await {
-        Some(auth) => {
-            let profile = auth.profile.clone();
-            *state.auth.lock().unwrap() = Some(auth);
-            Ok(AuthResult {
-                success: true,
-                profile: Some(profile),
-                error: None,
-            })
-        }
-        None => Ok(AuthResult {
+/// List every stored account, marking the active one.
+#[tauri::command]
+pub async fn list_accounts() -> Result<Vec<AccountSummary>, String> {
+    let stored = tokens::load_accounts()?;
+    Ok(stored
+        .accounts
+        .iter()
+        // Legacy tokens have no profile until their first refresh
+        .filter(|a| !a.uuid.is_empty())
+        .map(|a| AccountSummary {
+            profile: MinecraftProfile {
+                username: a.username.clone(),
+                uuid: a.uuid.clone(),
+                skin_url: a.skin_url.clone(),
+            },
+            active: stored.active.as_deref() == Some(a.uuid.as_str()),
+        })
+        .collect())
+}
+
+/// Make another stored account active, refreshing its session if needed.
+#[tauri::command]
+pub async fn switch_account(
+    uuid: String,
+    state: State<'_, AppState>,
+) -> Result<AuthResult, String> {
+    tokens::set_active_account(&uuid)?;
+    state.accounts.lock().unwrap().active = Some(uuid);
+
+    match tokens::ensure_fresh(&state).await {
+        Ok(auth) => Ok(AuthResult {
+            success: true,
+            profile: Some(auth.profile),
+            error: None,
+        }),
+        Err(e) => Ok(AuthResult {
             success: false,
             profile: None,
-            error: None,
+            error: Some(e),
         }),
     }
 }
 
-/// Log out: clear stored tokens and auth state.
+/// Forget a stored account. If it was active, the next remaining account
+/// becomes active.
 #[tauri::command]
-pub async fn logout(state: State<'_, AppState>) -> Result<(), String> {
-    *state.auth.lock().unwrap() = None;
-    tokens::delete_refresh_token()?;
+pub async fn remove_account(uuid: String, state: State<'_, AppState>) -> Result<(), String> {
+    let active = tokens::remove_account(&uuid)?;
+    let mut accounts = state.accounts.lock().unwrap();
+    accounts.drop_session(&uuid);
+    accounts.active = active;
+    Ok(())
+}
+
+/// Log out the active account, or every account when `all` is set.
+#[tauri::command]
+pub async fn logout(all: Option<bool>, state: State<'_, AppState>) -> Result<(), String> {
+    if all.unwrap_or(false) {
+        *state.accounts.lock().unwrap() = Default::default();
+        return tokens::delete_all_accounts();
+    }
+
+    let active = state.accounts.lock().unwrap().active.clone();
+    if let Some(uuid) = active.or(tokens::load_accounts()?.active) {
+        let next = tokens::remove_account(&uuid)?;
+        let mut accounts = state.accounts.lock().unwrap();
+        accounts.drop_session(&uuid);
+        accounts.active = next;
+    }
     Ok(())
 }
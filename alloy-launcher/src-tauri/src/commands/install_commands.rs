@@ -0,0 +1,185 @@
+use crate::loaders;
+use crate::minecraft::download::{self, GameFile};
+use crate::minecraft::versions::{self, VersionDetails};
+use crate::state::{AppState, ModLoader};
+use std::path::Path;
+use tauri::{AppHandle, Emitter, State};
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FileProblem {
+    #[serde(flatten)]
+    pub file: GameFile,
+    /// "missing" or "corrupt"
+    pub kind: &'static str,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct VerifyReport {
+    pub version_id: String,
+    pub checked: usize,
+    pub problems: Vec<FileProblem>,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct Progress<'a> {
+    stage: &'a str,
+    message: String,
+    percent: f64,
+    bytes_downloaded: u64,
+    bytes_total: u64,
+    file: &'a str,
+    downloaded: usize,
+    total: usize,
+}
+
+/// Every file the configured installation needs: the version's client
+/// jar, libraries and assets, plus the libraries of a Fabric or Quilt loader.
+async fn expected_files(
+    state: &AppState,
+    cache_dir: &Path,
+) -> Result<(VersionDetails, Vec<GameFile>), String> {
+    let settings = state.settings.lock().unwrap().clone();
+    let client = &state.http_client;
+    let version_id = &settings.minecraft_version;
+
+    let entry = versions::fetch_version_list(client)
+        .await?
+        .into_iter()
+        .find(|v| &v.id == version_id)
+        .ok_or(format!("Version {} not found in manifest", version_id))?;
+    let version = versions::fetch_version_details(client, &entry).await?;
+
+    let mut files = download::version_files(&version);
+    files.extend(download::asset_files(client, cache_dir, &version).await?);
+
+    if settings.mod_loader != ModLoader::Alloy {
+        let loader_version = loaders::resolve_version(
+            client,
+            settings.mod_loader,
+            version_id,
+            settings.loader_version.as_deref(),
+        )
+        .await?;
+        files.extend(
+            loaders::expected_files(client, settings.mod_loader, version_id, &loader_version)
+                .await?,
+        );
+    }
+
+    // Assets shared by name, and libraries vanilla and the loader both list,
+    // are one file on disk
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    files.dedup_by(|a, b| a.path == b.path);
+    Ok((version, files))
+}
+
+async fn verify(
+    app: &AppHandle,
+    state: &AppState,
+) -> Result<(VersionDetails, VerifyReport), String> {
+    let _ = app.emit(
+        "setup-stage",
+        serde_json::json!({ "stage": "verify", "message": "Checking game files" }),
+    );
+    let cache_dir = state.cache_dir();
+    let (version, files) = expected_files(state, &cache_dir).await?;
+    let version_id = version.id.clone();
+
+    let app = app.clone();
+    let report = tokio::task::spawn_blocking(move || {
+        let total = files.len();
+        let bytes_total: u64 = files.iter().filter_map(|f| f.size).sum();
+        let mut bytes_checked = 0u64;
+        let mut problems = Vec::new();
+
+        for (i, file) in files.iter().enumerate() {
+            let path = cache_dir.join(&file.path);
+            if let Some(kind) = download::check_file(&path, file) {
+                if kind == "corrupt" {
+                    let _ = app.emit(
+                        "verify-failed",
+                        serde_json::json!({
+                            "file": file.path,
+                            "expected": file.sha1.as_deref().unwrap_or_default(),
+                            "actual": std::fs::read(&path)
+                                .map(|b| download::sha1_hex(&b))
+                                .unwrap_or_default(),
+                        }),
+                    );
+                }
+                problems.push(FileProblem {
+                    file: file.clone(),
+                    kind,
+                });
+            }
+            bytes_checked += file.size.unwrap_or(0);
+
+            // Thousands of small assets: report every 50 files
+            if i % 50 == 0 || i + 1 == total {
+                let _ = app.emit(
+                    "download-progress",
+                    Progress {
+                        stage: "verify",
+                        message: format!("Verifying files ({}/{})", i + 1, total),
+                        percent: if bytes_total > 0 {
+                            bytes_checked as f64 / bytes_total as f64 * 100.0
+                        } else {
+                            100.0
+                        },
+                        bytes_downloaded: bytes_checked,
+                        bytes_total,
+                        file: &file.path,
+                        downloaded: i + 1,
+                        total,
+                    },
+                );
+            }
+        }
+
+        VerifyReport {
+            version_id,
+            checked: total,
+            problems,
+        }
+    })
+    .await
+    .map_err(|e| format!("Verification task failed: {}", e))?;
+    Ok((version, report))
+}
+
+/// Check every library, asset, and the client jar of the configured version,
+/// plus the libraries of the selected loader, against their published
+/// SHA-1 and size.
+#[tauri::command]
+pub async fn verify_installation(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<VerifyReport, String> {
+    verify(&app, &state).await.map(|(_, report)| report)
+}
+
+/// Re-download only the files that are missing or corrupt, through the same
+/// download path a fresh install uses. Returns the report of a fresh
+/// verification afterwards.
+#[tauri::command]
+pub async fn repair_installation(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<VerifyReport, String> {
+    let (version, report) = verify(&app, &state).await?;
+    if report.problems.is_empty() {
+        return Ok(report);
+    }
+
+    let _ = app.emit(
+        "setup-stage",
+        serde_json::json!({ "stage": "repair", "message": "Repairing game files" }),
+    );
+    let cache_dir = state.cache_dir();
+    let files = report.problems.into_iter().map(|p| p.file).collect();
+    download::download_files(&app, &state.http_client, &cache_dir, files, "repair").await?;
+    // Re-extracts natives and marks the version installed
+    download::setup_version(&app, &state.http_client, &version, &cache_dir).await?;
+
+    verify(&app, &state).await.map(|(_, report)| report)
+}
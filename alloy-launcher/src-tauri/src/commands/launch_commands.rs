@@ -1,5 +1,9 @@
+use crate::auth::tokens;
+use crate::game_log::{self, CrashReport};
+use crate::java;
+use crate::loaders;
 use crate::minecraft::{download, launch, versions};
-use crate::state::AppState;
+use crate::state::{AppState, ModLoader};
 use tauri::{AppHandle, Emitter, State};
 
 #[derive(serde::Serialize)]
@@ -14,18 +18,24 @@ pub struct SetupStatus {
 /// Check if everything is ready to launch.
 #[tauri::command]
 pub async fn check_setup(state: State<'_, AppState>) -> Result<SetupStatus, String> {
-    let (version_id, java_path_setting) = {
+    let (version_id, mod_loader, java_path_setting) = {
         let settings = state.settings.lock().unwrap();
         (
             settings.minecraft_version.clone(),
-            settings.java_path.clone(),
+            settings.mod_loader,
+            settings
+                .java_path
+                .clone()
+                .or_else(|| settings.managed_java_path.clone()),
         )
     };
 
     let cache_dir = state.cache_dir();
     let is_cached = download::is_version_cached(&cache_dir, &version_id);
 
-    let loader_found = launch::find_loader_jar(&state.base_dir, &cache_dir).is_ok();
+    // Fabric and Quilt are installed from their metadata at launch
+    let loader_found = mod_loader != ModLoader::Alloy
+        || launch::find_loader_jar(&state.base_dir, &cache_dir).is_ok();
 
     let java_path = java_path_setting.or_else(|| launch::detect_java().ok());
     let java_found = java_path.is_some();
@@ -39,31 +49,68 @@ pub async fn check_setup(state: State<'_, AppState>) -> Result<SetupStatus, Stri
     })
 }
 
-/// Launch the game. Downloads if needed, then spawns the Java process.
+/// Returned as soon as the game process has started, so it can't say how the
+/// session ended: that, including the crash report path, comes later in the
+/// `game-exit` event.
+#[derive(serde::Serialize)]
+pub struct LaunchResult {
+    /// The command line that was run, with the access token redacted
+    pub command: String,
+    /// Where the game's output for this session is being written
+    pub log_file: String,
+}
+
+/// Render a command for display, quoting arguments with spaces and hiding secrets.
+fn describe_command(cmd: &std::process::Command, secrets: &[&str]) -> String {
+    std::iter::once(cmd.get_program())
+        .chain(cmd.get_args())
+        .map(|part| {
+            let part = part.to_string_lossy();
+            if secrets.iter().any(|s| !s.is_empty() && part.contains(s)) {
+                "<redacted>".to_string()
+            } else if part.contains(char::is_whitespace) {
+                format!("\"{}\"", part)
+            } else {
+                part.into_owned()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Payload of `setup-stage`, marking the phase of launch setup in progress.
+#[derive(Clone, serde::Serialize)]
+struct SetupStage<'a> {
+    stage: &'a str,
+    message: &'a str,
+}
+
+fn emit_stage(app: &AppHandle, stage: &str, message: &str) {
+    let _ = app.emit("setup-stage", SetupStage { stage, message });
+}
+
+/// Launch the game. Downloads if needed, then spawns the Java process and
+/// returns while it runs; its output arrives as `game-log` events and its
+/// exit, with any crash report, as `game-exit`.
 #[tauri::command]
 pub async fn launch_game(
     app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<(), String> {
-    // Get auth tokens
-    let auth = {
-        let auth = state.auth.lock().unwrap();
-        auth.clone()
-            .ok_or("Not authenticated. Please sign in first.")?
-    };
-
-    let (version_id, memory_mb, jvm_args, java_path_setting) = {
-        let settings = state.settings.lock().unwrap();
-        (
-            settings.minecraft_version.clone(),
-            settings.memory_mb,
-            settings.jvm_args.clone(),
-            settings.java_path.clone(),
-        )
-    };
+) -> Result<LaunchResult, String> {
+    // Get auth tokens, refreshing them if they expire soon
+    let auth = tokens::ensure_fresh(&state).await?;
+
+    let settings = state.settings.lock().unwrap().clone();
+    settings.validate()?;
+    let (version_id, memory_mb, jvm_args) = (
+        settings.minecraft_version.clone(),
+        settings.memory_mb,
+        settings.jvm_flags().join(" "),
+    );
 
     let cache_dir = state.cache_dir();
     let run_dir = state.run_dir();
+    let log_path = game_log::session_log_path(&state.base_dir);
 
     // Ensure run directory exists
     tokio::fs::create_dir_all(&run_dir)
@@ -74,6 +121,7 @@ pub async fn launch_game(
     let _ = app.emit("launch-state", "preparing");
 
     // Fetch version details
+    emit_stage(&app, "manifest", "Fetching version manifest");
     let version_list = versions::fetch_version_list(&state.http_client).await?;
     let version_entry = version_list
         .iter()
@@ -86,39 +134,79 @@ pub async fn launch_game(
     // Download Minecraft if needed
     if !download::is_version_cached(&cache_dir, &version_id) {
         let _ = app.emit("launch-state", "downloading");
+        emit_stage(
+            &app,
+            "game",
+            &format!("Downloading Minecraft {}", version_id),
+        );
         download::setup_version(&app, &state.http_client, &version_details, &cache_dir).await?;
     }
 
-    // Find or download loader JAR
-    let loader_jar = match launch::find_loader_jar(&state.base_dir, &cache_dir) {
-        Ok(jar) => jar,
-        Err(_) => {
-            let _ = app.emit("launch-state", "downloading");
-            launch::download_loader_jar(&state.http_client, &cache_dir).await?
+    // Find or download the Alloy loader JAR, or install the chosen
+    // third-party loader's libraries
+    let (loader_jar, mod_loader) = match settings.mod_loader {
+        ModLoader::Alloy => {
+            let jar = match launch::find_loader_jar(&state.base_dir, &cache_dir) {
+                Ok(jar) => jar,
+                Err(_) => {
+                    let _ = app.emit("launch-state", "downloading");
+                    emit_stage(&app, "loader", "Downloading Alloy loader");
+                    launch::download_loader_jar(&state.http_client, &cache_dir).await?
+                }
+            };
+            (Some(jar), None)
+        }
+        loader => {
+            let loader_version = loaders::resolve_version(
+                &state.http_client,
+                loader,
+                &version_id,
+                settings.loader_version.as_deref(),
+            )
+            .await?;
+            emit_stage(
+                &app,
+                "loader",
+                &format!("Installing {} {}", loader.display_name(), loader_version),
+            );
+            let launch = loaders::install(
+                &app,
+                &state.http_client,
+                &cache_dir,
+                loader,
+                &version_id,
+                &loader_version,
+            )
+            .await?;
+            (None, Some(launch))
         }
     };
 
-    // Detect Java
-    let java_path = java_path_setting
-        .unwrap_or_else(|| launch::detect_java().unwrap_or_else(|_| "java".to_string()));
+    // Find a Java runtime the version supports, downloading one if needed
+    emit_stage(&app, "java", "Locating Java");
+    let java_path = java::ensure_java(&app, &state, &version_id).await?;
 
     // Build and launch
     let _ = app.emit("launch-state", "launching");
 
+    let mc_token = auth.mc_access_token;
     let config = launch::LaunchConfig {
         java_path,
         memory_mb,
         jvm_args,
-        mc_token: auth.mc_access_token,
+        mc_token: mc_token.clone(),
         username: auth.profile.username,
         uuid: auth.profile.uuid,
         version: version_details,
         loader_jar,
+        mod_loader,
         cache_dir,
-        run_dir,
+        run_dir: run_dir.clone(),
     };
 
     let mut cmd = launch::build_launch_command(&config);
+    let command = describe_command(&cmd, &[&mc_token]);
+    log::info!("Launching: {}", command);
 
     let child = cmd
         .stdout(std::process::Stdio::piped())
@@ -128,25 +216,34 @@ pub async fn launch_game(
 
     let _ = app.emit("launch-state", "running");
 
-    // Monitor game process in background
+    // Stream the game's output and watch for it exiting in the background
     let app_handle = app.clone();
+    let log_file = log_path.to_string_lossy().into_owned();
     tokio::task::spawn_blocking(move || {
-        let output = child.wait_with_output();
-        match output {
-            Ok(out) => {
-                if !out.status.success() {
-                    let stderr = String::from_utf8_lossy(&out.stderr);
-                    log::error!("Game exited with error: {}", stderr);
-                    let _ = app_handle.emit("game-error", stderr.to_string());
-                }
-            }
-            Err(e) => {
-                log::error!("Failed to wait for game: {}", e);
-                let _ = app_handle.emit("game-error", e.to_string());
-            }
-        }
+        game_log::capture(app_handle.clone(), child, run_dir, log_path);
         let _ = app_handle.emit("launch-state", "ready");
     });
 
-    Ok(())
+    Ok(LaunchResult { command, log_file })
+}
+
+/// The newest crash report the game wrote, if any. For a session that just
+/// ended, `game-exit` already names its report; this also finds older ones.
+#[tauri::command]
+pub async fn get_last_crash_report(
+    state: State<'_, AppState>,
+) -> Result<Option<CrashReport>, String> {
+    game_log::last_crash_report(&state.run_dir())
+}
+
+/// Open a crash report in the system's default viewer. Only files in the
+/// game's crash-reports directory can be opened.
+#[tauri::command]
+pub async fn open_crash_report(state: State<'_, AppState>, path: String) -> Result<(), String> {
+    let reports = state.run_dir().join("crash-reports");
+    let path = std::path::Path::new(&path);
+    if path.parent() != Some(reports.as_path()) || !path.is_file() {
+        return Err(format!("Not a crash report: {}", path.display()));
+    }
+    open::that(path).map_err(|e| format!("Failed to open crash report: {}", e))
 }
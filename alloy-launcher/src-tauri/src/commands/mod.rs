@@ -1,3 +1,4 @@
 pub mod auth_commands;
+pub mod install_commands;
 pub mod launch_commands;
 pub mod settings_commands;
@@ -1,4 +1,6 @@
-use crate::state::{AppState, LauncherSettings};
+use crate::loaders::{self, LoaderVersion};
+use crate::settings;
+use crate::state::{AppState, LauncherSettings, ModLoader};
 use tauri::State;
 
 #[tauri::command]
@@ -12,7 +14,35 @@ pub async fn update_settings(
     state: State<'_, AppState>,
     settings: LauncherSettings,
 ) -> Result<(), String> {
+    settings.validate()?;
+    // A pinned loader version must exist for the selected Minecraft version
+    if settings.mod_loader != ModLoader::Alloy && settings.loader_version.is_some() {
+        loaders::resolve_version(
+            &state.http_client,
+            settings.mod_loader,
+            &settings.minecraft_version,
+            settings.loader_version.as_deref(),
+        )
+        .await?;
+    }
+    settings::save_settings(&settings)?;
     let mut current = state.settings.lock().unwrap();
     *current = settings;
     Ok(())
 }
+
+/// Versions of `loader` that support `minecraft_version` (default: the
+/// configured one), newest first.
+#[tauri::command]
+pub async fn list_loader_versions(
+    state: State<'_, AppState>,
+    loader: ModLoader,
+    minecraft_version: Option<String>,
+) -> Result<Vec<LoaderVersion>, String> {
+    if loader == ModLoader::Alloy {
+        return Ok(Vec::new());
+    }
+    let mc_version = minecraft_version
+        .unwrap_or_else(|| state.settings.lock().unwrap().minecraft_version.clone());
+    loaders::list_versions(&state.http_client, loader, &mc_version).await
+}
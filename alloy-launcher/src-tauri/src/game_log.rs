@@ -0,0 +1,197 @@
+//! Capturing the game's output: every line is streamed to the UI as a
+//! `game-log` event and written to a session log under `<base_dir>/logs`, and
+//! when the game exits with an error the crash report it wrote is located.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
+
+/// Session logs kept in `<base_dir>/logs`; older ones are deleted.
+const KEPT_SESSION_LOGS: usize = 10;
+
+/// Output lines quoted in `game-error` when no crash report was written.
+const ERROR_TAIL_LINES: usize = 40;
+
+/// Payload of `game-log`: one line of the game's output.
+#[derive(Clone, serde::Serialize)]
+struct GameLogLine<'a> {
+    stream: &'a str,
+    line: &'a str,
+}
+
+/// Payload of `game-exit`, sent once the game process has ended.
+#[derive(Clone, serde::Serialize)]
+struct GameExit {
+    /// `None` when the process was killed by a signal
+    exit_code: Option<i32>,
+    /// Crash report written during this session, if the game crashed
+    crash_report: Option<String>,
+    log_file: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct CrashReport {
+    pub path: String,
+    pub content: String,
+}
+
+/// Where this session's log goes: `<base_dir>/logs/session-<unix secs>.log`.
+pub fn session_log_path(base_dir: &Path) -> PathBuf {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    base_dir.join("logs").join(format!("session-{}.log", secs))
+}
+
+/// Delete all but the newest session logs.
+fn prune_session_logs(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut logs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("session-") && n.ends_with(".log"))
+        })
+        .collect();
+    // Names embed the start time, so they sort oldest first
+    logs.sort();
+    let excess = logs.len().saturating_sub(KEPT_SESSION_LOGS);
+    for old in &logs[..excess] {
+        let _ = std::fs::remove_file(old);
+    }
+}
+
+/// The newest `crash-reports/*.txt` in the game directory, optionally only
+/// one written at or after `since`.
+pub fn find_crash_report(run_dir: &Path, since: Option<SystemTime>) -> Option<PathBuf> {
+    std::fs::read_dir(run_dir.join("crash-reports"))
+        .ok()?
+        .flatten()
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "txt"))
+        .filter_map(|e| Some((e.metadata().ok()?.modified().ok()?, e.path())))
+        .filter(|(modified, _)| since.is_none_or(|since| *modified >= since))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
+/// Read the newest crash report in the game directory.
+pub fn last_crash_report(run_dir: &Path) -> Result<Option<CrashReport>, String> {
+    let Some(path) = find_crash_report(run_dir, None) else {
+        return Ok(None);
+    };
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read crash report: {}", e))?;
+    Ok(Some(CrashReport {
+        path: path.to_string_lossy().into_owned(),
+        content,
+    }))
+}
+
+/// Forward one output stream line by line to the UI and the session log,
+/// keeping the last lines for the error message.
+fn pump(
+    app: AppHandle,
+    stream: &'static str,
+    output: impl Read + Send + 'static,
+    log: Arc<Mutex<Option<File>>>,
+    tail: Arc<Mutex<VecDeque<String>>>,
+) -> std::thread::JoinHandle<()> {
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(output);
+        let mut buf = Vec::new();
+        // Read raw bytes so output that isn't valid UTF-8 doesn't end capture
+        while reader.read_until(b'\n', &mut buf).is_ok_and(|n| n > 0) {
+            let line = String::from_utf8_lossy(&buf);
+            let line = line.trim_end_matches(['\r', '\n']);
+            let _ = app.emit("game-log", GameLogLine { stream, line });
+            if let Some(file) = log.lock().unwrap().as_mut() {
+                let _ = writeln!(file, "[{}] {}", stream, line);
+            }
+            let mut tail = tail.lock().unwrap();
+            if tail.len() == ERROR_TAIL_LINES {
+                tail.pop_front();
+            }
+            tail.push_back(line.to_string());
+            buf.clear();
+        }
+    })
+}
+
+/// Stream the game's stdout and stderr until it exits, then report how it
+/// ended with `game-exit`, plus `game-error` when it failed. Blocks, so run it
+/// on a blocking task.
+pub fn capture(app: AppHandle, mut child: Child, run_dir: PathBuf, log_path: PathBuf) {
+    let started = SystemTime::now();
+    if let Some(dir) = log_path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    let log_file = File::create(&log_path)
+        .map_err(|e| log::warn!("Could not create session log {}: {}", log_path.display(), e))
+        .ok();
+    if let Some(dir) = log_path.parent() {
+        prune_session_logs(dir);
+    }
+    let log = Arc::new(Mutex::new(log_file));
+    let tail = Arc::new(Mutex::new(VecDeque::new()));
+
+    let mut readers = Vec::new();
+    if let Some(out) = child.stdout.take() {
+        readers.push(pump(app.clone(), "stdout", out, log.clone(), tail.clone()));
+    }
+    if let Some(err) = child.stderr.take() {
+        readers.push(pump(app.clone(), "stderr", err, log.clone(), tail.clone()));
+    }
+
+    let status = child.wait();
+    for reader in readers {
+        let _ = reader.join();
+    }
+    let status = match status {
+        Ok(status) => status,
+        Err(e) => {
+            log::error!("Failed to wait for game: {}", e);
+            let _ = app.emit("game-error", e.to_string());
+            return;
+        }
+    };
+
+    // A crash writes its report just before exiting with code 1
+    let crash_report = (!status.success())
+        .then(|| find_crash_report(&run_dir, Some(started)))
+        .flatten()
+        .map(|p| p.to_string_lossy().into_owned());
+
+    if status.success() {
+        log::info!("Game exited normally");
+    } else {
+        log::error!("Game exited with {}", status);
+        let message = match &crash_report {
+            Some(path) => format!("Minecraft crashed ({}). Crash report: {}", status, path),
+            None => {
+                let tail = tail.lock().unwrap();
+                let lines: Vec<&str> = tail.iter().map(String::as_str).collect();
+                format!("Minecraft exited with {}:\n{}", status, lines.join("\n"))
+            }
+        };
+        let _ = app.emit("game-error", message);
+    }
+
+    let _ = app.emit(
+        "game-exit",
+        GameExit {
+            exit_code: status.code(),
+            crash_report,
+            log_file: log_path.to_string_lossy().into_owned(),
+        },
+    );
+}
@@ -0,0 +1,342 @@
+//! Finding a Java runtime that matches the version's `javaVersion`, and
+//! downloading an Eclipse Temurin JRE into `<base_dir>/runtime` when the
+//! machine has none.
+
+use reqwest::Client;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+
+use crate::state::AppState;
+
+const ADOPTIUM_API: &str = "https://api.adoptium.net/v3";
+
+/// Used when the manifest does not say (pre-1.17 versions).
+const DEFAULT_JAVA_MAJOR: u32 = 8;
+
+pub fn java_binary() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "java.exe"
+    } else {
+        "java"
+    }
+}
+
+/// The major Java version a Minecraft version requires.
+pub async fn required_major(client: &Client, version_id: &str) -> Result<u32, String> {
+    let version = crate::manifest::fetch_version_json(client, version_id).await?;
+    Ok(version
+        .get("javaVersion")
+        .and_then(|j| j.get("majorVersion"))
+        .and_then(|m| m.as_u64())
+        .map(|m| m as u32)
+        .unwrap_or(DEFAULT_JAVA_MAJOR))
+}
+
+/// Parse the major version from `java -version` output, which goes to stderr:
+/// `openjdk version "21.0.2" 2024-01-16` or `java version "1.8.0_392"`.
+fn parse_major(output: &str) -> Option<u32> {
+    let quoted = output.split('"').nth(1)?;
+    let mut parts = quoted.split(['.', '_', '-', '+']);
+    let first: u32 = parts.next()?.parse().ok()?;
+    if first == 1 {
+        parts.next()?.parse().ok()
+    } else {
+        Some(first)
+    }
+}
+
+/// Run `java -version` and return the major version, if it runs at all.
+pub fn java_major_version(java: &Path) -> Option<u32> {
+    let out = std::process::Command::new(java)
+        .arg("-version")
+        .output()
+        .ok()?;
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&out.stderr),
+        String::from_utf8_lossy(&out.stdout)
+    );
+    parse_major(&text)
+}
+
+/// `bin/java` under a JDK/JRE home, accounting for macOS bundle layout.
+fn java_in_home(home: &Path) -> Option<PathBuf> {
+    [
+        home.join("bin").join(java_binary()),
+        home.join("Contents")
+            .join("Home")
+            .join("bin")
+            .join(java_binary()),
+    ]
+    .into_iter()
+    .find(|p| p.is_file())
+}
+
+/// Directory the launcher downloads runtimes of `major` into.
+pub fn managed_runtime_dir(base_dir: &Path, major: u32) -> PathBuf {
+    base_dir.join("runtime").join(format!("java-{}", major))
+}
+
+/// Every Java home in the directories JDK installers commonly use.
+fn common_java_homes() -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = Vec::new();
+    if cfg!(target_os = "macos") {
+        roots.push("/Library/Java/JavaVirtualMachines".into());
+        if let Ok(home) = std::env::var("HOME") {
+            roots.push(Path::new(&home).join("Library/Java/JavaVirtualMachines"));
+        }
+    } else if cfg!(target_os = "windows") {
+        for var in ["ProgramFiles", "ProgramFiles(x86)"] {
+            if let Ok(pf) = std::env::var(var) {
+                for vendor in [
+                    "Java",
+                    "Eclipse Adoptium",
+                    "Microsoft",
+                    "Zulu",
+                    "Amazon Corretto",
+                ] {
+                    roots.push(Path::new(&pf).join(vendor));
+                }
+            }
+        }
+    } else {
+        roots.push("/usr/lib/jvm".into());
+        roots.push("/usr/java".into());
+        roots.push("/opt/java".into());
+    }
+
+    let mut homes = Vec::new();
+    for root in roots {
+        if let Ok(entries) = std::fs::read_dir(&root) {
+            homes.extend(entries.flatten().map(|e| e.path()));
+        }
+    }
+    homes
+}
+
+/// Find an installed Java runtime for `major`: the preferred path first,
+/// then the managed runtime, `JAVA_HOME`, `PATH`, and common install
+/// locations. An exact major match beats a newer runtime.
+pub fn find_java(major: u32, preferred: Option<&str>, base_dir: &Path) -> Option<PathBuf> {
+    let mut candidates: Vec<PathBuf> = Vec::new();
+    if let Some(path) = preferred.filter(|p| !p.trim().is_empty()) {
+        candidates.push(PathBuf::from(path));
+    }
+    candidates.extend(find_managed_java(base_dir, major));
+    if let Ok(home) = std::env::var("JAVA_HOME") {
+        candidates.extend(java_in_home(Path::new(&home)));
+    }
+    candidates.push(PathBuf::from(java_binary()));
+    candidates.extend(common_java_homes().iter().filter_map(|h| java_in_home(h)));
+
+    let mut newer: Option<PathBuf> = None;
+    for candidate in candidates {
+        match java_major_version(&candidate) {
+            Some(v) if v == major => return Some(candidate),
+            Some(v) if v > major && newer.is_none() => newer = Some(candidate),
+            _ => {}
+        }
+    }
+    newer
+}
+
+/// The java binary inside a previously downloaded runtime.
+fn find_managed_java(base_dir: &Path, major: u32) -> Option<PathBuf> {
+    let dir = managed_runtime_dir(base_dir, major);
+    // Archives unpack into a single versioned directory (e.g. jdk-21.0.5+11-jre)
+    std::fs::read_dir(&dir)
+        .ok()?
+        .flatten()
+        .find_map(|e| java_in_home(&e.path()))
+}
+
+fn adoptium_os_arch() -> Result<(&'static str, &'static str), String> {
+    let os = match std::env::consts::OS {
+        "macos" => "mac",
+        "windows" => "windows",
+        "linux" => "linux",
+        other => return Err(format!("No Java download available for {}", other)),
+    };
+    let arch = match std::env::consts::ARCH {
+        "x86_64" => "x64",
+        "aarch64" => "aarch64",
+        "x86" => "x32",
+        other => return Err(format!("No Java download available for {}", other)),
+    };
+    Ok((os, arch))
+}
+
+/// Download and unpack the latest Temurin JRE for `major`, verifying its
+/// SHA-256. Returns the path to the java binary.
+pub async fn download_runtime(
+    app: &AppHandle,
+    client: &Client,
+    base_dir: &Path,
+    major: u32,
+) -> Result<PathBuf, String> {
+    let (os, arch) = adoptium_os_arch()?;
+    let url = format!(
+        "{}/assets/latest/{}/hotspot?os={}&architecture={}&image_type=jre&vendor=eclipse",
+        ADOPTIUM_API, major, os, arch
+    );
+    let releases = crate::manifest::fetch_json(client, &url).await?;
+    let package = releases
+        .as_array()
+        .and_then(|r| r.first())
+        .and_then(|r| r.get("binary"))
+        .and_then(|b| b.get("package"))
+        .cloned()
+        .ok_or(format!(
+            "No Java {} runtime published for {}-{}",
+            major, os, arch
+        ))?;
+    let field = |key: &str| package.get(key).and_then(Value::as_str).map(String::from);
+    let (Some(link), Some(name), Some(checksum)) =
+        (field("link"), field("name"), field("checksum"))
+    else {
+        return Err("Malformed runtime release metadata".to_string());
+    };
+    let total = package.get("size").and_then(Value::as_u64).unwrap_or(0);
+
+    let _ = app.emit(
+        "setup-stage",
+        serde_json::json!({ "stage": "java", "message": format!("Downloading Java {}", major) }),
+    );
+
+    let mut resp = client
+        .get(&link)
+        .timeout(std::time::Duration::from_secs(600))
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("Java download failed: {}", e))?;
+
+    let mut archive: Vec<u8> = Vec::with_capacity(total as usize);
+    let mut hasher = Sha256::new();
+    while let Some(chunk) = resp
+        .chunk()
+        .await
+        .map_err(|e| format!("Java download failed: {}", e))?
+    {
+        hasher.update(&chunk);
+        archive.extend_from_slice(&chunk);
+        let _ = app.emit(
+            "download-progress",
+            serde_json::json!({
+                "stage": "java",
+                "message": format!("Downloading Java {}", major),
+                "percent": if total > 0 { archive.len() as f64 / total as f64 * 100.0 } else { 0.0 },
+                "bytes_downloaded": archive.len(),
+                "bytes_total": total,
+                "file": name,
+            }),
+        );
+    }
+
+    let actual = hex::encode(hasher.finalize());
+    if !actual.eq_ignore_ascii_case(&checksum) {
+        return Err(format!(
+            "Java download is corrupt (sha256 {} != {})",
+            actual, checksum
+        ));
+    }
+
+    let dir = managed_runtime_dir(base_dir, major);
+    // Start clean so a half-extracted earlier attempt is not picked up
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+    let _ = app.emit(
+        "setup-stage",
+        serde_json::json!({ "stage": "java", "message": format!("Installing Java {}", major) }),
+    );
+    let extract_dir = dir.clone();
+    tokio::task::spawn_blocking(move || extract(&name, archive, &extract_dir))
+        .await
+        .map_err(|e| format!("Java extraction task failed: {}", e))??;
+
+    find_managed_java(base_dir, major)
+        .ok_or("Downloaded Java runtime has no java binary".to_string())
+}
+
+fn extract(name: &str, archive: Vec<u8>, dest: &Path) -> Result<(), String> {
+    if name.ends_with(".zip") {
+        let mut zip = zip::ZipArchive::new(std::io::Cursor::new(archive))
+            .map_err(|e| format!("Failed to open Java archive: {}", e))?;
+        return zip
+            .extract(dest)
+            .map_err(|e| format!("Failed to extract Java archive: {}", e));
+    }
+
+    // .tar.gz: the system tar handles permissions and symlinks correctly
+    let tarball = dest.join(name);
+    std::fs::write(&tarball, &archive)
+        .map_err(|e| format!("Failed to write Java archive: {}", e))?;
+    let status = std::process::Command::new("tar")
+        .arg("-xzf")
+        .arg(&tarball)
+        .arg("-C")
+        .arg(dest)
+        .status()
+        .map_err(|e| format!("Failed to run tar: {}", e))?;
+    let _ = std::fs::remove_file(&tarball);
+    if !status.success() {
+        return Err(format!("tar exited with {}", status));
+    }
+    Ok(())
+}
+
+/// Resolve the Java binary to launch `version_id` with. A Java path set in
+/// Settings is always used as-is; otherwise a matching runtime is searched
+/// for and, failing that, downloaded and remembered in settings.
+pub async fn ensure_java(
+    app: &AppHandle,
+    state: &AppState,
+    version_id: &str,
+) -> Result<String, String> {
+    let (configured, managed) = {
+        let settings = state.settings.lock().unwrap();
+        (
+            settings.java_path.clone(),
+            settings.managed_java_path.clone(),
+        )
+    };
+    if let Some(path) = configured.filter(|p| !p.trim().is_empty()) {
+        return Ok(path);
+    }
+    let major = required_major(&state.http_client, version_id).await?;
+
+    let base_dir = state.base_dir.clone();
+    let found =
+        tokio::task::spawn_blocking(move || find_java(major, managed.as_deref(), &base_dir))
+            .await
+            .map_err(|e| format!("Java detection failed: {}", e))?;
+    if let Some(java) = found {
+        return Ok(java.to_string_lossy().into_owned());
+    }
+
+    log::info!("No Java {} found — downloading a runtime", major);
+    let java = download_runtime(app, &state.http_client, &state.base_dir, major)
+        .await
+        .map_err(|e| {
+            format!(
+                "Java {} is required but could not be installed automatically ({}). \
+                 Download it from https://adoptium.net/temurin/releases/?version={} \
+                 and set the Java path in Settings.",
+                major, e, major
+            )
+        })?;
+    let java = java.to_string_lossy().into_owned();
+    let settings = {
+        let mut settings = state.settings.lock().unwrap();
+        settings.managed_java_path = Some(java.clone());
+        settings.clone()
+    };
+    if let Err(e) = crate::settings::save_settings(&settings) {
+        log::warn!("Could not remember the downloaded runtime: {}", e);
+    }
+    Ok(java)
+}
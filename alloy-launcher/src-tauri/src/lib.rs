@@ -1,6 +1,11 @@
 mod auth;
 mod commands;
+mod game_log;
+mod java;
+mod loaders;
+mod manifest;
 mod minecraft;
+mod settings;
 pub mod state;
 
 // TODO: Switch back to Alloy's client ID once app registration is approved
@@ -16,10 +21,18 @@ pub fn run() {
             commands::auth_commands::login,
             commands::auth_commands::logout,
             commands::auth_commands::check_auth,
+            commands::auth_commands::list_accounts,
+            commands::auth_commands::switch_account,
+            commands::auth_commands::remove_account,
             commands::launch_commands::launch_game,
             commands::launch_commands::check_setup,
+            commands::launch_commands::get_last_crash_report,
+            commands::launch_commands::open_crash_report,
+            commands::install_commands::verify_installation,
+            commands::install_commands::repair_installation,
             commands::settings_commands::get_settings,
             commands::settings_commands::update_settings,
+            commands::settings_commands::list_loader_versions,
         ])
         .run(tauri::generate_context!())
         .expect("error while running alloy launcher");
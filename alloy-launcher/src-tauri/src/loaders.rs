@@ -0,0 +1,231 @@
+//! Third-party mod loaders (Fabric, Quilt): version lists, launch profiles,
+//! and installing their libraries into the shared `libraries/` directory.
+
+use reqwest::Client;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use crate::manifest::fetch_json;
+use crate::minecraft::download::{self, GameFile};
+use crate::state::ModLoader;
+
+const FABRIC_META: &str = "https://meta.fabricmc.net/v2";
+const QUILT_META: &str = "https://meta.quiltmc.org/v3";
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LoaderVersion {
+    pub version: String,
+    pub stable: bool,
+}
+
+/// What a loader adds to the vanilla launch: libraries prepended to the
+/// classpath, a replacement main class, and extra arguments.
+#[derive(Debug, Clone)]
+pub struct LoaderLaunch {
+    pub main_class: String,
+    pub classpath: Vec<PathBuf>,
+    pub jvm_args: Vec<String>,
+    pub game_args: Vec<String>,
+}
+
+fn meta_url(loader: ModLoader) -> Result<&'static str, String> {
+    match loader {
+        ModLoader::Fabric => Ok(FABRIC_META),
+        ModLoader::Quilt => Ok(QUILT_META),
+        ModLoader::Alloy => Err("Alloy is not a metadata-based loader".to_string()),
+    }
+}
+
+/// Loader versions that support `mc_version`, newest first.
+pub async fn list_versions(
+    client: &Client,
+    loader: ModLoader,
+    mc_version: &str,
+) -> Result<Vec<LoaderVersion>, String> {
+    let url = format!("{}/versions/loader/{}", meta_url(loader)?, mc_version);
+    let entries = fetch_json(client, &url).await?;
+    Ok(entries
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|e| {
+            let loader = e.get("loader")?;
+            let version = loader.get("version")?.as_str()?.to_string();
+            // Quilt does not publish a stable flag; its pre-releases carry a suffix
+            let stable = loader
+                .get("stable")
+                .and_then(|s| s.as_bool())
+                .unwrap_or_else(|| !version.contains('-'));
+            Some(LoaderVersion { version, stable })
+        })
+        .collect())
+}
+
+/// The loader version to launch with: `requested` if it supports
+/// `mc_version`, otherwise the latest stable one.
+pub async fn resolve_version(
+    client: &Client,
+    loader: ModLoader,
+    mc_version: &str,
+    requested: Option<&str>,
+) -> Result<String, String> {
+    let versions = list_versions(client, loader, mc_version).await?;
+    if versions.is_empty() {
+        return Err(format!(
+            "{} does not support Minecraft {} yet",
+            loader.display_name(),
+            mc_version
+        ));
+    }
+    match requested.filter(|v| !v.trim().is_empty()) {
+        Some(wanted) => versions
+            .iter()
+            .find(|v| v.version == wanted)
+            .map(|v| v.version.clone())
+            .ok_or(format!(
+                "{} {} does not support Minecraft {}",
+                loader.display_name(),
+                wanted,
+                mc_version
+            )),
+        None => Ok(versions
+            .iter()
+            .find(|v| v.stable)
+            .unwrap_or(&versions[0])
+            .version
+            .clone()),
+    }
+}
+
+/// `group:artifact:version[:classifier]` → `group/path/artifact/version/artifact-version[-classifier].jar`
+fn maven_path(coordinate: &str) -> Option<String> {
+    let mut parts = coordinate.split(':');
+    let group = parts.next()?;
+    let artifact = parts.next()?;
+    let version = parts.next()?;
+    let file = match parts.next() {
+        Some(classifier) => format!("{}-{}-{}.jar", artifact, version, classifier),
+        None => format!("{}-{}.jar", artifact, version),
+    };
+    Some(format!(
+        "{}/{}/{}/{}",
+        group.replace('.', "/"),
+        artifact,
+        version,
+        file
+    ))
+}
+
+fn string_args(profile: &Value, kind: &str) -> Vec<String> {
+    profile
+        .get("arguments")
+        .and_then(|a| a.get(kind))
+        .and_then(|a| a.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|a| a.as_str().map(String::from))
+        .collect()
+}
+
+async fn fetch_profile(
+    client: &Client,
+    loader: ModLoader,
+    mc_version: &str,
+    loader_version: &str,
+) -> Result<Value, String> {
+    let url = format!(
+        "{}/versions/loader/{}/{}/profile/json",
+        meta_url(loader)?,
+        mc_version,
+        loader_version
+    );
+    fetch_json(client, &url).await
+}
+
+/// The profile's libraries, in classpath order. Checksums and sizes are
+/// only known for the libraries whose repository publishes them.
+fn library_files(profile: &Value) -> Result<Vec<GameFile>, String> {
+    let mut files = Vec::new();
+    for lib in profile
+        .get("libraries")
+        .and_then(|l| l.as_array())
+        .into_iter()
+        .flatten()
+    {
+        let (Some(name), Some(repo)) = (
+            lib.get("name").and_then(|n| n.as_str()),
+            lib.get("url").and_then(|u| u.as_str()),
+        ) else {
+            continue;
+        };
+        let path = maven_path(name).ok_or(format!("Invalid library coordinate {}", name))?;
+        files.push(GameFile {
+            url: format!("{}/{}", repo.trim_end_matches('/'), path),
+            path: format!("libraries/{}", path),
+            sha1: lib.get("sha1").and_then(|s| s.as_str()).map(String::from),
+            size: lib.get("size").and_then(|s| s.as_u64()),
+        });
+    }
+    Ok(files)
+}
+
+/// Every library file of the given loader version, for verifying an
+/// installation.
+pub async fn expected_files(
+    client: &Client,
+    loader: ModLoader,
+    mc_version: &str,
+    loader_version: &str,
+) -> Result<Vec<GameFile>, String> {
+    library_files(&fetch_profile(client, loader, mc_version, loader_version).await?)
+}
+
+/// Fetch the loader's launch profile and download any of its libraries
+/// missing from `<cache_dir>/libraries`. The profile itself is saved as
+/// `versions/<id>/<id>.json`, the layout other launchers expect.
+pub async fn install(
+    app: &AppHandle,
+    client: &Client,
+    cache_dir: &Path,
+    loader: ModLoader,
+    mc_version: &str,
+    loader_version: &str,
+) -> Result<LoaderLaunch, String> {
+    let profile = fetch_profile(client, loader, mc_version, loader_version).await?;
+
+    let main_class = profile
+        .get("mainClass")
+        .and_then(|m| m.as_str())
+        .ok_or(format!(
+            "{} profile has no main class",
+            loader.display_name()
+        ))?
+        .to_string();
+
+    if let Some(id) = profile.get("id").and_then(|i| i.as_str()) {
+        let dir = cache_dir.join("versions").join(id);
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        let _ = tokio::fs::write(dir.join(format!("{}.json", id)), profile.to_string()).await;
+    }
+
+    let files = library_files(&profile)?;
+    let classpath = files.iter().map(|f| cache_dir.join(&f.path)).collect();
+    download::download_files(
+        app,
+        client,
+        cache_dir,
+        download::missing(cache_dir, files),
+        "loader",
+    )
+    .await?;
+
+    Ok(LoaderLaunch {
+        main_class,
+        classpath,
+        jvm_args: string_args(&profile, "jvm"),
+        game_args: string_args(&profile, "game"),
+    })
+}
@@ -0,0 +1,39 @@
+//! Direct access to Mojang's version manifests, for features that need raw
+//! manifest fields (checksums, Java requirements).
+
+use reqwest::Client;
+use serde_json::Value;
+
+pub const VERSION_MANIFEST_URL: &str =
+    "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json";
+
+pub async fn fetch_json(client: &Client, url: &str) -> Result<Value, String> {
+    let resp = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Request to {} failed: {}", url, e))?;
+    if !resp.status().is_success() {
+        return Err(format!("Request to {} failed: {}", url, resp.status()));
+    }
+    resp.json()
+        .await
+        .map_err(|e| format!("Failed to parse {}: {}", url, e))
+}
+
+/// Fetch the full version JSON for `version_id`.
+pub async fn fetch_version_json(client: &Client, version_id: &str) -> Result<Value, String> {
+    let manifest = fetch_json(client, VERSION_MANIFEST_URL).await?;
+    let version_url = manifest
+        .get("versions")
+        .and_then(|v| v.as_array())
+        .and_then(|versions| {
+            versions
+                .iter()
+                .find(|v| v.get("id").and_then(|id| id.as_str()) == Some(version_id))
+        })
+        .and_then(|v| v.get("url"))
+        .and_then(|u| u.as_str())
+        .ok_or(format!("Version {} not found in manifest", version_id))?;
+    fetch_json(client, version_url).await
+}
@@ -0,0 +1,391 @@
+//! Downloading a version's client jar, libraries, natives and assets into
+//! the cache, in the vanilla launcher's layout: `versions/<id>/<id>.jar`,
+//! `libraries/<path>`, `assets/indexes/<id>.json` and
+//! `assets/objects/<xx>/<hash>`.
+
+use reqwest::Client;
+use serde_json::Value;
+use sha1::{Digest, Sha1};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+use tokio::task::JoinSet;
+
+use super::versions::{Artifact, VersionDetails};
+
+const RESOURCES_URL: &str = "https://resources.download.minecraft.net";
+
+/// Assets are thousands of small files; fetch a few at a time.
+const PARALLEL_DOWNLOADS: usize = 8;
+
+/// Written once every file of a version is in place.
+const INSTALLED_MARKER: &str = ".installed";
+
+/// A file an installation needs.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct GameFile {
+    /// Path relative to the cache directory
+    pub path: String,
+    pub url: String,
+    /// Mojang publishes both for every file; loader repositories may not
+    pub sha1: Option<String>,
+    pub size: Option<u64>,
+}
+
+impl GameFile {
+    fn from_artifact(path: String, artifact: &Artifact) -> Self {
+        GameFile {
+            path,
+            url: artifact.url.clone(),
+            sha1: Some(artifact.sha1.clone()),
+            size: Some(artifact.size),
+        }
+    }
+}
+
+#[derive(Clone, serde::Serialize)]
+struct Progress {
+    stage: &'static str,
+    message: String,
+    percent: f64,
+    bytes_downloaded: u64,
+    bytes_total: u64,
+    /// The file that just finished
+    file: String,
+    downloaded: usize,
+    total: usize,
+}
+
+/// Payload of `verify-failed`, sent when a download's checksum is wrong.
+#[derive(Clone, serde::Serialize)]
+struct VerifyFailed<'a> {
+    file: &'a str,
+    expected: &'a str,
+    actual: &'a str,
+}
+
+pub fn version_dir(cache_dir: &Path, version_id: &str) -> PathBuf {
+    cache_dir.join("versions").join(version_id)
+}
+
+pub fn client_jar(cache_dir: &Path, version_id: &str) -> PathBuf {
+    version_dir(cache_dir, version_id).join(format!("{}.jar", version_id))
+}
+
+/// Where pre-1.19 native libraries are unpacked for `-Djava.library.path`.
+pub fn natives_dir(cache_dir: &Path, version_id: &str) -> PathBuf {
+    version_dir(cache_dir, version_id).join("natives")
+}
+
+pub fn assets_dir(cache_dir: &Path) -> PathBuf {
+    cache_dir.join("assets")
+}
+
+/// Whether `setup_version` has completed for this version.
+pub fn is_version_cached(cache_dir: &Path, version_id: &str) -> bool {
+    version_dir(cache_dir, version_id)
+        .join(INSTALLED_MARKER)
+        .is_file()
+        && client_jar(cache_dir, version_id).is_file()
+}
+
+/// The client jar and every library (including native jars) this platform
+/// needs, in classpath order.
+pub fn version_files(version: &VersionDetails) -> Vec<GameFile> {
+    let mut files = vec![GameFile::from_artifact(
+        format!("versions/{0}/{0}.jar", version.id),
+        &version.downloads.client,
+    )];
+    for lib in version.libraries.iter().filter(|lib| lib.allowed()) {
+        let artifacts = lib.downloads.artifact.iter().chain(lib.native_artifact());
+        for artifact in artifacts {
+            if let Some(path) = &artifact.path {
+                files.push(GameFile::from_artifact(
+                    format!("libraries/{}", path),
+                    artifact,
+                ));
+            }
+        }
+    }
+    files
+}
+
+/// The asset index file itself.
+pub fn asset_index_file(version: &VersionDetails) -> GameFile {
+    GameFile::from_artifact(
+        format!("assets/indexes/{}.json", version.asset_index.id),
+        &version.asset_index.file,
+    )
+}
+
+/// Every object listed in an asset index, deduplicated by hash.
+fn asset_objects(index: &Value) -> Vec<GameFile> {
+    let mut files: Vec<GameFile> = index
+        .get("objects")
+        .and_then(|o| o.as_object())
+        .into_iter()
+        .flat_map(|objects| objects.values())
+        .filter_map(|object| {
+            let hash = object.get("hash")?.as_str()?;
+            let size = object.get("size")?.as_u64()?;
+            let prefix = hash.get(..2)?;
+            Some(GameFile {
+                path: format!("assets/objects/{}/{}", prefix, hash),
+                url: format!("{}/{}/{}", RESOURCES_URL, prefix, hash),
+                sha1: Some(hash.to_string()),
+                size: Some(size),
+            })
+        })
+        .collect();
+    // Assets shared by name collapse to one object on disk
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    files.dedup_by(|a, b| a.path == b.path);
+    files
+}
+
+/// The asset index and every object it lists. A cached index is used if
+/// it is intact, so checking an installation works offline.
+pub async fn asset_files(
+    client: &Client,
+    cache_dir: &Path,
+    version: &VersionDetails,
+) -> Result<Vec<GameFile>, String> {
+    let index_file = asset_index_file(version);
+    let cached = cache_dir.join(&index_file.path);
+    let index = match check_file(&cached, &index_file) {
+        None => std::fs::read_to_string(&cached)
+            .ok()
+            .and_then(|s| serde_json::from_str::<Value>(&s).ok()),
+        Some(_) => None,
+    };
+    let index = match index {
+        Some(index) => index,
+        None => crate::manifest::fetch_json(client, &index_file.url).await?,
+    };
+    let mut files = vec![index_file];
+    files.extend(asset_objects(&index));
+    Ok(files)
+}
+
+pub fn sha1_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha1::digest(bytes))
+}
+
+/// `None` if the file is intact, otherwise why it is not: "missing" or
+/// "corrupt".
+pub fn check_file(path: &Path, expected: &GameFile) -> Option<&'static str> {
+    let Ok(meta) = std::fs::metadata(path) else {
+        return Some("missing");
+    };
+    if expected.size.is_some_and(|size| size != meta.len()) {
+        return Some("corrupt");
+    }
+    let Some(sha1) = &expected.sha1 else {
+        return None;
+    };
+    match std::fs::read(path) {
+        Ok(bytes) if sha1_hex(&bytes).eq_ignore_ascii_case(sha1) => None,
+        Ok(_) => Some("corrupt"),
+        Err(_) => Some("missing"),
+    }
+}
+
+async fn fetch(client: &Client, file: &GameFile) -> Result<Vec<u8>, String> {
+    client
+        .get(&file.url)
+        .timeout(std::time::Duration::from_secs(600))
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("Failed to download {}: {}", file.path, e))?
+        .bytes()
+        .await
+        .map(Vec::from)
+        .map_err(|e| format!("Failed to download {}: {}", file.path, e))
+}
+
+/// Download `file` to `dest`, checking it against its SHA-1. A mismatch is
+/// reported with `verify-failed` and the file downloaded once more before
+/// giving up. The file is written next to `dest` first so an interrupted
+/// download never leaves a truncated file behind.
+async fn fetch_verified(
+    app: &AppHandle,
+    client: &Client,
+    file: &GameFile,
+    dest: &Path,
+) -> Result<(), String> {
+    let mut bytes = fetch(client, file).await?;
+    if let Some(expected) = &file.sha1 {
+        let actual = sha1_hex(&bytes);
+        if !actual.eq_ignore_ascii_case(expected) {
+            let _ = app.emit(
+                "verify-failed",
+                VerifyFailed {
+                    file: &file.path,
+                    expected,
+                    actual: &actual,
+                },
+            );
+            bytes = fetch(client, file).await?;
+            let actual = sha1_hex(&bytes);
+            if !actual.eq_ignore_ascii_case(expected) {
+                return Err(format!(
+                    "Downloaded {} does not match its checksum (sha1 {} != {})",
+                    file.path, actual, expected
+                ));
+            }
+        }
+    }
+
+    if let Some(parent) = dest.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let partial = dest.with_file_name(format!(
+        "{}.part",
+        dest.file_name().unwrap_or_default().to_string_lossy()
+    ));
+    tokio::fs::write(&partial, &bytes)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", dest.display(), e))?;
+    tokio::fs::rename(&partial, dest)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", dest.display(), e))
+}
+
+/// Download every file in `files`, a few at a time, reporting progress
+/// under `stage`.
+pub async fn download_files(
+    app: &AppHandle,
+    client: &Client,
+    cache_dir: &Path,
+    files: Vec<GameFile>,
+    stage: &'static str,
+) -> Result<(), String> {
+    let total = files.len();
+    let bytes_total: u64 = files.iter().filter_map(|f| f.size).sum();
+    let mut bytes_downloaded = 0u64;
+    let mut done = 0usize;
+
+    let mut pending = files.into_iter();
+    let mut tasks = JoinSet::new();
+    loop {
+        while tasks.len() < PARALLEL_DOWNLOADS {
+            let Some(file) = pending.next() else { break };
+            let (app, client) = (app.clone(), client.clone());
+            let dest = cache_dir.join(&file.path);
+            tasks.spawn(async move {
+                fetch_verified(&app, &client, &file, &dest)
+                    .await
+                    .map(|()| file)
+            });
+        }
+        let Some(result) = tasks.join_next().await else {
+            break;
+        };
+        let file = result.map_err(|e| format!("Download task failed: {}", e))??;
+        done += 1;
+        bytes_downloaded += file.size.unwrap_or(0);
+
+        let _ = app.emit(
+            "download-progress",
+            Progress {
+                stage,
+                message: format!("Downloading files ({}/{})", done, total),
+                percent: bytes_downloaded as f64 / bytes_total.max(1) as f64 * 100.0,
+                bytes_downloaded,
+                bytes_total,
+                file: file.path,
+                downloaded: done,
+                total,
+            },
+        );
+    }
+    Ok(())
+}
+
+/// Files from `files` that are not on disk at their expected size. Cheaper
+/// than `check_file`, which hashes every file.
+pub fn missing(cache_dir: &Path, files: Vec<GameFile>) -> Vec<GameFile> {
+    files
+        .into_iter()
+        .filter(|f| match std::fs::metadata(cache_dir.join(&f.path)) {
+            Ok(meta) => f.size.is_some_and(|size| size != meta.len()),
+            Err(_) => true,
+        })
+        .collect()
+}
+
+/// Unpack the version's native jars into its natives directory, skipping
+/// their `META-INF` signatures.
+fn extract_natives(version: &VersionDetails, cache_dir: &Path) -> Result<(), String> {
+    let dest = natives_dir(cache_dir, &version.id);
+    for artifact in version
+        .libraries
+        .iter()
+        .filter(|lib| lib.allowed())
+        .filter_map(|lib| lib.native_artifact())
+    {
+        let Some(path) = &artifact.path else { continue };
+        let jar = cache_dir.join("libraries").join(path);
+        let file = std::fs::File::open(&jar)
+            .map_err(|e| format!("Failed to open {}: {}", jar.display(), e))?;
+        let mut zip = zip::ZipArchive::new(file)
+            .map_err(|e| format!("Failed to read {}: {}", jar.display(), e))?;
+        for i in 0..zip.len() {
+            let mut entry = zip
+                .by_index(i)
+                .map_err(|e| format!("Failed to read {}: {}", jar.display(), e))?;
+            let Some(name) = entry.enclosed_name() else {
+                continue;
+            };
+            if entry.is_dir() || name.starts_with("META-INF") {
+                continue;
+            }
+            let target = dest.join(name);
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+            let mut out = std::fs::File::create(&target)
+                .map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+            std::io::copy(&mut entry, &mut out)
+                .map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Download everything `version` needs to launch, then mark it installed.
+pub async fn setup_version(
+    app: &AppHandle,
+    client: &Client,
+    version: &VersionDetails,
+    cache_dir: &Path,
+) -> Result<(), String> {
+    // The index goes to disk first so listing its objects reads it back
+    let index_file = asset_index_file(version);
+    download_files(
+        app,
+        client,
+        cache_dir,
+        missing(cache_dir, vec![index_file]),
+        "assets",
+    )
+    .await?;
+
+    let mut files = version_files(version);
+    files.extend(asset_files(client, cache_dir, version).await?);
+    download_files(app, client, cache_dir, missing(cache_dir, files), "game").await?;
+
+    let natives_version = version.clone();
+    let natives_cache = cache_dir.to_path_buf();
+    tokio::task::spawn_blocking(move || extract_natives(&natives_version, &natives_cache))
+        .await
+        .map_err(|e| format!("Native extraction task failed: {}", e))??;
+
+    let marker = version_dir(cache_dir, &version.id).join(INSTALLED_MARKER);
+    tokio::fs::write(&marker, b"")
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", marker.display(), e))
+}
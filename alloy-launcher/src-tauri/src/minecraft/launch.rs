@@ -0,0 +1,236 @@
+//! Building the JVM command line for a downloaded version, with the Alloy
+//! loader injected or a third-party loader's profile applied on top.
+
+use reqwest::Client;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::download;
+use super::versions::{self, VersionDetails};
+use crate::loaders::LoaderLaunch;
+
+const LOADER_JAR_NAME: &str = "alloy-loader.jar";
+const LOADER_JAR_URL: &str =
+    "https://github.com/arch-linux/alloy/releases/latest/download/alloy-loader.jar";
+
+/// Entry point of the Alloy loader; it bootstraps mods, then hands off to
+/// the version's own main class.
+const ALLOY_MAIN_CLASS: &str = "net.alloymc.loader.AlloyLoader";
+
+pub struct LaunchConfig {
+    pub java_path: String,
+    pub memory_mb: u32,
+    /// Extra JVM flags from settings, space-separated
+    pub jvm_args: String,
+    pub mc_token: String,
+    pub username: String,
+    pub uuid: String,
+    pub version: VersionDetails,
+    /// The Alloy loader, injected as a java agent
+    pub loader_jar: Option<PathBuf>,
+    /// A third-party loader's classpath, main class and arguments
+    pub mod_loader: Option<LoaderLaunch>,
+    pub cache_dir: PathBuf,
+    pub run_dir: PathBuf,
+}
+
+/// The Alloy loader jar: one placed next to the launcher's data takes
+/// precedence over the downloaded copy in the cache.
+pub fn find_loader_jar(base_dir: &Path, cache_dir: &Path) -> Result<PathBuf, String> {
+    [
+        base_dir.join(LOADER_JAR_NAME),
+        cache_dir.join("loader").join(LOADER_JAR_NAME),
+    ]
+    .into_iter()
+    .find(|p| p.is_file())
+    .ok_or("Alloy loader not found".to_string())
+}
+
+/// Download the latest released Alloy loader into the cache.
+pub async fn download_loader_jar(client: &Client, cache_dir: &Path) -> Result<PathBuf, String> {
+    let bytes = client
+        .get(LOADER_JAR_URL)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("Failed to download Alloy loader: {}", e))?
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to download Alloy loader: {}", e))?;
+    if !bytes.starts_with(b"PK") {
+        return Err("Downloaded Alloy loader is not a jar".to_string());
+    }
+
+    let dir = cache_dir.join("loader");
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let dest = dir.join(LOADER_JAR_NAME);
+    tokio::fs::write(&dest, &bytes)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", dest.display(), e))?;
+    Ok(dest)
+}
+
+/// A Java binary from `JAVA_HOME` or, failing that, `PATH`.
+pub fn detect_java() -> Result<String, String> {
+    if let Ok(home) = std::env::var("JAVA_HOME") {
+        let java = Path::new(&home)
+            .join("bin")
+            .join(crate::java::java_binary());
+        if java.is_file() {
+            return Ok(java.to_string_lossy().into_owned());
+        }
+    }
+    let java = crate::java::java_binary();
+    crate::java::java_major_version(Path::new(java))
+        .map(|_| java.to_string())
+        .ok_or("No Java runtime found".to_string())
+}
+
+/// `libraries/<group>/<artifact>` of a library jar, which identifies it
+/// regardless of version.
+fn library_key(libraries_dir: &Path, jar: &Path) -> Option<PathBuf> {
+    let relative = jar.strip_prefix(libraries_dir).ok()?;
+    Some(relative.parent()?.parent()?.to_path_buf())
+}
+
+/// Classpath entries in launch order: the loader's libraries first, then the
+/// version's libraries the loader does not replace, then the client jar.
+fn classpath(config: &LaunchConfig) -> Vec<PathBuf> {
+    let libraries_dir = config.cache_dir.join("libraries");
+    let mut entries: Vec<PathBuf> = Vec::new();
+    if let Some(jar) = &config.loader_jar {
+        entries.push(jar.clone());
+    }
+    if let Some(loader) = &config.mod_loader {
+        entries.extend(loader.classpath.iter().cloned());
+    }
+    let replaced: HashSet<PathBuf> = entries
+        .iter()
+        .filter_map(|jar| library_key(&libraries_dir, jar))
+        .collect();
+
+    for lib in config.version.libraries.iter().filter(|lib| lib.allowed()) {
+        let Some(path) = lib
+            .downloads
+            .artifact
+            .as_ref()
+            .and_then(|a| a.path.as_ref())
+        else {
+            continue;
+        };
+        let jar = libraries_dir.join(path);
+        if library_key(&libraries_dir, &jar).is_some_and(|key| replaced.contains(&key)) {
+            continue;
+        }
+        if !entries.contains(&jar) {
+            entries.push(jar);
+        }
+    }
+    entries.push(download::client_jar(&config.cache_dir, &config.version.id));
+    entries
+}
+
+/// Replace the launcher's `${...}` placeholders in a version argument.
+fn substitute(arg: &str, vars: &[(&str, &str)]) -> String {
+    let mut out = arg.to_string();
+    for (name, value) in vars {
+        out = out.replace(&format!("${{{}}}", name), value);
+    }
+    out
+}
+
+/// The full command that launches `config.version`, with the loader's
+/// classpath, main class and arguments applied.
+pub fn build_launch_command(config: &LaunchConfig) -> Command {
+    let version = &config.version;
+    let separator = if cfg!(target_os = "windows") {
+        ";"
+    } else {
+        ":"
+    };
+    let classpath = classpath(config)
+        .iter()
+        .map(|p| p.to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join(separator);
+    let natives = download::natives_dir(&config.cache_dir, &version.id);
+    let natives = natives.to_string_lossy();
+    let libraries = config.cache_dir.join("libraries");
+    let libraries = libraries.to_string_lossy();
+    let assets = download::assets_dir(&config.cache_dir);
+    let assets = assets.to_string_lossy();
+    let game_dir = config.run_dir.to_string_lossy();
+    let vars = [
+        ("auth_player_name", config.username.as_str()),
+        ("auth_uuid", config.uuid.as_str()),
+        ("auth_access_token", config.mc_token.as_str()),
+        ("auth_session", config.mc_token.as_str()),
+        ("auth_xuid", ""),
+        ("clientid", ""),
+        ("user_type", "msa"),
+        ("user_properties", "{}"),
+        ("version_name", version.id.as_str()),
+        ("version_type", version.kind.as_str()),
+        ("game_directory", &*game_dir),
+        ("assets_root", &*assets),
+        ("game_assets", &*assets),
+        ("assets_index_name", version.asset_index.id.as_str()),
+        ("natives_directory", &*natives),
+        ("library_directory", &*libraries),
+        ("classpath", &classpath),
+        ("classpath_separator", separator),
+        ("launcher_name", "alloy-launcher"),
+        ("launcher_version", env!("CARGO_PKG_VERSION")),
+    ];
+
+    let (version_jvm, version_game) = match (&version.arguments, &version.minecraft_arguments) {
+        (Some(args), _) => (
+            versions::argument_values(&args.jvm),
+            versions::argument_values(&args.game),
+        ),
+        // Pre-1.13 versions leave the JVM arguments to the launcher
+        (None, legacy) => (
+            vec![
+                "-Djava.library.path=${natives_directory}".to_string(),
+                "-cp".to_string(),
+                "${classpath}".to_string(),
+            ],
+            legacy
+                .as_deref()
+                .unwrap_or_default()
+                .split_whitespace()
+                .map(String::from)
+                .collect(),
+        ),
+    };
+
+    let mut cmd = Command::new(&config.java_path);
+    cmd.current_dir(&config.run_dir);
+    cmd.arg(format!("-Xmx{}M", config.memory_mb));
+    cmd.args(config.jvm_args.split_whitespace());
+    cmd.args(version_jvm.iter().map(|a| substitute(a, &vars)));
+
+    let main_class = match (&config.loader_jar, &config.mod_loader) {
+        (Some(jar), _) => {
+            cmd.arg(format!("-javaagent:{}", jar.to_string_lossy()));
+            cmd.arg("-Dalloy.environment=client");
+            cmd.arg(format!("-Dalloy.gameDir={}", game_dir));
+            ALLOY_MAIN_CLASS
+        }
+        (None, Some(loader)) => {
+            cmd.args(loader.jvm_args.iter().map(|a| substitute(a, &vars)));
+            loader.main_class.as_str()
+        }
+        (None, None) => version.main_class.as_str(),
+    };
+    cmd.arg(main_class);
+
+    cmd.args(version_game.iter().map(|a| substitute(a, &vars)));
+    if let Some(loader) = &config.mod_loader {
+        cmd.args(loader.game_args.iter().map(|a| substitute(a, &vars)));
+    }
+    cmd
+}
@@ -0,0 +1,6 @@
+//! Vanilla Minecraft: the version manifest, downloading a version's files
+//! into the cache, and building the JVM command that launches it.
+
+pub mod download;
+pub mod launch;
+pub mod versions;
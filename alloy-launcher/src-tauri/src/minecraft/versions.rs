@@ -0,0 +1,192 @@
+//! Mojang's version manifest, and the parts of a version JSON the launcher
+//! needs to download and launch it.
+
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::manifest::{fetch_json, VERSION_MANIFEST_URL};
+
+/// One version in the manifest's `versions` list.
+#[derive(Debug, Clone, Deserialize)]
+pub struct VersionEntry {
+    pub id: String,
+    pub url: String,
+}
+
+#[derive(Deserialize)]
+struct VersionList {
+    versions: Vec<VersionEntry>,
+}
+
+/// A downloadable file with the checksum and size Mojang publishes for it.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Artifact {
+    /// Path under `libraries/`, for library artifacts
+    #[serde(default)]
+    pub path: Option<String>,
+    pub url: String,
+    pub sha1: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AssetIndex {
+    pub id: String,
+    #[serde(flatten)]
+    pub file: Artifact,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct VersionDownloads {
+    pub client: Artifact,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LibraryDownloads {
+    #[serde(default)]
+    pub artifact: Option<Artifact>,
+    /// Native jars of pre-1.19 versions, keyed by classifier
+    #[serde(default)]
+    pub classifiers: HashMap<String, Artifact>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Library {
+    #[serde(default)]
+    pub downloads: LibraryDownloads,
+    #[serde(default)]
+    pub rules: Vec<Value>,
+    /// OS name → classifier of the library's native jar
+    #[serde(default)]
+    pub natives: HashMap<String, String>,
+}
+
+impl Library {
+    pub fn allowed(&self) -> bool {
+        rules_allow(&self.rules)
+    }
+
+    /// The native jar for this OS, if the library has one.
+    pub fn native_artifact(&self) -> Option<&Artifact> {
+        let classifier = self.natives.get(current_os())?.replace(
+            "${arch}",
+            if cfg!(target_pointer_width = "64") {
+                "64"
+            } else {
+                "32"
+            },
+        );
+        self.downloads.classifiers.get(&classifier)
+    }
+}
+
+/// 1.13+ `arguments`; each entry is a string or `{ rules, value }`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Arguments {
+    #[serde(default)]
+    pub game: Vec<Value>,
+    #[serde(default)]
+    pub jvm: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionDetails {
+    pub id: String,
+    /// "release", "snapshot", "old_beta" or "old_alpha"
+    #[serde(rename = "type", default)]
+    pub kind: String,
+    pub main_class: String,
+    pub downloads: VersionDownloads,
+    #[serde(default)]
+    pub libraries: Vec<Library>,
+    pub asset_index: AssetIndex,
+    #[serde(default)]
+    pub arguments: Option<Arguments>,
+    /// Pre-1.13 game arguments as one space-separated template
+    #[serde(default)]
+    pub minecraft_arguments: Option<String>,
+}
+
+/// The OS name Mojang's rules use for this platform.
+pub fn current_os() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "osx"
+    } else if cfg!(target_os = "windows") {
+        "windows"
+    } else {
+        "linux"
+    }
+}
+
+/// Evaluate `rules` the way the vanilla launcher does: the last matching
+/// rule wins, and no rules means allowed. Rules that depend on launcher
+/// features (demo mode, custom resolution) never match.
+pub fn rules_allow(rules: &[Value]) -> bool {
+    if rules.is_empty() {
+        return true;
+    }
+    let mut allowed = false;
+    for rule in rules {
+        let os = rule.get("os");
+        let os_matches = os
+            .and_then(|o| o.get("name"))
+            .and_then(|n| n.as_str())
+            .is_none_or(|name| name == current_os())
+            && os
+                .and_then(|o| o.get("arch"))
+                .and_then(|a| a.as_str())
+                .is_none_or(|arch| arch == std::env::consts::ARCH);
+        if os_matches && rule.get("features").is_none() {
+            allowed = rule.get("action").and_then(|a| a.as_str()) == Some("allow");
+        }
+    }
+    allowed
+}
+
+/// Flatten 1.13+ argument entries into the strings whose rules allow them.
+pub fn argument_values(args: &[Value]) -> Vec<String> {
+    let mut out = Vec::new();
+    for arg in args {
+        match arg {
+            Value::String(s) => out.push(s.clone()),
+            Value::Object(obj) => {
+                let rules = obj
+                    .get("rules")
+                    .and_then(|r| r.as_array())
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                if !rules_allow(rules) {
+                    continue;
+                }
+                match obj.get("value") {
+                    Some(Value::String(s)) => out.push(s.clone()),
+                    Some(Value::Array(values)) => {
+                        out.extend(values.iter().filter_map(|v| v.as_str().map(String::from)))
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Every version in Mojang's manifest, newest first.
+pub async fn fetch_version_list(client: &Client) -> Result<Vec<VersionEntry>, String> {
+    let manifest = fetch_json(client, VERSION_MANIFEST_URL).await?;
+    serde_json::from_value::<VersionList>(manifest)
+        .map(|list| list.versions)
+        .map_err(|e| format!("Malformed version manifest: {}", e))
+}
+
+pub async fn fetch_version_details(
+    client: &Client,
+    entry: &VersionEntry,
+) -> Result<VersionDetails, String> {
+    let json = fetch_json(client, &entry.url).await?;
+    serde_json::from_value(json).map_err(|e| format!("Malformed version {}: {}", entry.id, e))
+}
@@ -0,0 +1,130 @@
+//! `<base_dir>/settings.json`: launcher settings kept between runs.
+//!
+//! The file carries a `schema_version`; `migrate` upgrades older shapes on
+//! load so a file written by an earlier launcher still launches.
+
+use serde_json::Value;
+use std::path::PathBuf;
+
+use crate::state::{LauncherSettings, SETTINGS_SCHEMA_VERSION};
+
+fn settings_path() -> PathBuf {
+    crate::state::base_dir().join("settings.json")
+}
+
+/// A JVM heap size such as `4G`, `2048m` or `524288k`, in megabytes.
+fn parse_heap_mb(size: &str) -> Option<u64> {
+    let end = size
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(size.len());
+    let (digits, unit) = size.split_at(end);
+    let n: u64 = digits.parse().ok()?;
+    match unit {
+        "g" | "G" => n.checked_mul(1024),
+        "m" | "M" => Some(n),
+        "k" | "K" => Some(n / 1024),
+        "" => Some(n / (1024 * 1024)),
+        _ => None,
+    }
+}
+
+/// Upgrade an older settings file in place.
+fn migrate(value: &mut Value) {
+    let Some(obj) = value.as_object_mut() else {
+        return;
+    };
+    let version = obj
+        .get("schema_version")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(1);
+    if version < 2 {
+        // v1 had no heap settings of its own, so sizes were passed as custom
+        // JVM flags, which validation now rejects
+        if let Some(args) = obj
+            .get("jvm_args")
+            .and_then(|a| a.as_str())
+            .map(String::from)
+        {
+            let mut kept: Vec<&str> = Vec::new();
+            for arg in args.split_whitespace() {
+                let (key, size) = if let Some(size) = arg.strip_prefix("-Xmx") {
+                    ("memory_mb", size)
+                } else if let Some(size) = arg.strip_prefix("-Xms") {
+                    ("min_memory_mb", size)
+                } else {
+                    kept.push(arg);
+                    continue;
+                };
+                if let Some(mb) = parse_heap_mb(size) {
+                    obj.insert(key.to_string(), Value::from(mb));
+                }
+            }
+            obj.insert("jvm_args".to_string(), Value::from(kept.join(" ")));
+        }
+    }
+    obj.insert(
+        "schema_version".to_string(),
+        Value::from(SETTINGS_SCHEMA_VERSION),
+    );
+}
+
+/// Load settings from disk. A missing file gives the defaults; an unreadable
+/// one is moved aside to settings.json.bak so it isn't overwritten by the
+/// next save.
+pub fn load_settings() -> LauncherSettings {
+    let path = settings_path();
+    if !path.exists() {
+        log::info!("No stored settings at {}", path.display());
+        return LauncherSettings::default();
+    }
+
+    let parsed = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read settings file: {}", e))
+        .and_then(|json| {
+            serde_json::from_str::<Value>(&json)
+                .map_err(|e| format!("Failed to parse settings file: {}", e))
+        })
+        .and_then(|mut value| {
+            migrate(&mut value);
+            serde_json::from_value::<LauncherSettings>(value)
+                .map_err(|e| format!("Invalid settings file: {}", e))
+        });
+
+    match parsed {
+        Ok(settings) => {
+            log::info!("Loaded settings from {}", path.display());
+            settings
+        }
+        Err(e) => {
+            let backup = path.with_extension("json.bak");
+            log::warn!(
+                "{} — using defaults, old file kept at {}",
+                e,
+                backup.display()
+            );
+            let _ = std::fs::rename(&path, &backup);
+            LauncherSettings::default()
+        }
+    }
+}
+
+/// Write settings to disk at the current schema version.
+pub fn save_settings(settings: &LauncherSettings) -> Result<(), String> {
+    let path = settings_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create settings dir: {}", e))?;
+    }
+    let settings = LauncherSettings {
+        schema_version: SETTINGS_SCHEMA_VERSION,
+        ..settings.clone()
+    };
+    let json = serde_json::to_string_pretty(&settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+
+    // Write then rename so a crash mid-save never leaves a truncated file
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, json)
+        .and_then(|_| std::fs::rename(&tmp, &path))
+        .map_err(|e| format!("Failed to write settings file: {}", e))
+}
@@ -1,6 +1,6 @@
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use std::time::Duration;
 
@@ -14,40 +14,307 @@ pub struct MinecraftProfile {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AuthTokens {
     pub mc_access_token: String,
+    /// Unix seconds after which `mc_access_token` is no longer accepted
+    #[serde(default)]
+    pub mc_expires_at: u64,
     pub ms_refresh_token: String,
     pub profile: MinecraftProfile,
 }
 
+/// Signed-in accounts and which one is used for launching.
+#[derive(Debug, Default)]
+pub struct Accounts {
+    /// Sessions authenticated during this run, at most one per account
+    pub sessions: Vec<AuthTokens>,
+    /// UUID of the active account
+    pub active: Option<String>,
+}
+
+impl Accounts {
+    pub fn active_session(&self) -> Option<&AuthTokens> {
+        let uuid = self.active.as_ref()?;
+        self.sessions.iter().find(|s| &s.profile.uuid == uuid)
+    }
+
+    /// Add or replace an account's session and make it active.
+    pub fn upsert(&mut self, auth: AuthTokens) {
+        self.sessions
+            .retain(|s| s.profile.uuid != auth.profile.uuid);
+        self.active = Some(auth.profile.uuid.clone());
+        self.sessions.push(auth);
+    }
+
+    /// Forget a session without changing which account is active.
+    pub fn drop_session(&mut self, uuid: &str) {
+        self.sessions.retain(|s| s.profile.uuid != uuid);
+    }
+}
+
+/// Which mod loader the game is launched with.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase", try_from = "String")]
+pub enum ModLoader {
+    #[default]
+    Alloy,
+    Fabric,
+    Quilt,
+}
+
+impl ModLoader {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            ModLoader::Alloy => "Alloy",
+            ModLoader::Fabric => "Fabric",
+            ModLoader::Quilt => "Quilt",
+        }
+    }
+}
+
+impl TryFrom<String> for ModLoader {
+    type Error = String;
+
+    fn try_from(name: String) -> Result<Self, String> {
+        match name.to_ascii_lowercase().as_str() {
+            "alloy" => Ok(ModLoader::Alloy),
+            "fabric" => Ok(ModLoader::Fabric),
+            "quilt" => Ok(ModLoader::Quilt),
+            _ => Err(format!(
+                "Unknown mod loader \"{}\": expected alloy, fabric or quilt",
+                name
+            )),
+        }
+    }
+}
+
+/// Bump when the shape of `LauncherSettings` changes; `settings::migrate`
+/// upgrades older files.
+pub const SETTINGS_SCHEMA_VERSION: u32 = 2;
+
+/// Missing fields fall back to defaults so older and newer files still load.
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
 pub struct LauncherSettings {
+    /// Schema version of the stored file
+    pub schema_version: u32,
+    /// Maximum heap (-Xmx)
     pub memory_mb: u32,
+    /// Initial heap (-Xms)
+    pub min_memory_mb: u32,
     pub java_path: Option<String>,
+    /// Runtime the launcher downloaded itself, used when `java_path` is unset
+    pub managed_java_path: Option<String>,
     pub jvm_args: String,
     pub client_id: String,
     pub cache_dir: Option<String>,
     pub minecraft_version: String,
+    pub mod_loader: ModLoader,
+    /// Loader version; `None` means the latest stable one for `minecraft_version`
+    pub loader_version: Option<String>,
 }
 
 impl Default for LauncherSettings {
     fn default() -> Self {
         Self {
+            schema_version: SETTINGS_SCHEMA_VERSION,
             memory_mb: 2048,
+            min_memory_mb: 512,
             java_path: None,
+            managed_java_path: None,
             jvm_args: String::new(),
             client_id: crate::DEFAULT_CLIENT_ID.to_string(),
             cache_dir: None,
             minecraft_version: "1.21.11".to_string(),
+            mod_loader: ModLoader::default(),
+            loader_version: None,
+        }
+    }
+}
+
+/// The JVM rejects heaps smaller than this with a cryptic startup error.
+const MIN_HEAP_MB: u32 = 128;
+
+/// Flags the launcher sets itself; overriding them breaks the launch.
+const RESERVED_JVM_FLAGS: &[&str] = &["-cp", "-classpath", "--class-path", "-jar"];
+
+impl LauncherSettings {
+    /// Custom JVM flags, one per whitespace-separated token.
+    pub fn extra_jvm_args(&self) -> Vec<String> {
+        self.jvm_args.split_whitespace().map(String::from).collect()
+    }
+
+    /// Check memory limits, paths, versions and custom flags before saving or
+    /// launching.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_memory_mb < MIN_HEAP_MB {
+            return Err(format!(
+                "Minimum memory must be at least {} MB",
+                MIN_HEAP_MB
+            ));
+        }
+        if self.memory_mb < self.min_memory_mb {
+            return Err(format!(
+                "Maximum memory ({} MB) must be at least the minimum ({} MB)",
+                self.memory_mb, self.min_memory_mb
+            ));
+        }
+        if let Some(total) = system_memory_mb() {
+            if u64::from(self.memory_mb) > total {
+                return Err(format!(
+                    "Maximum memory ({} MB) exceeds this machine's RAM ({} MB)",
+                    self.memory_mb, total
+                ));
+            }
         }
+
+        if let Some(java) = self.java_path.as_deref().filter(|p| !p.trim().is_empty()) {
+            let path = Path::new(java);
+            if !path.exists() {
+                return Err(format!("Java path \"{}\" does not exist", java));
+            }
+            if !path.is_file() {
+                return Err(format!(
+                    "Java path \"{}\" is not a file; point it at the java executable",
+                    java
+                ));
+            }
+        }
+        if let Some(dir) = self.cache_dir.as_deref() {
+            let path = Path::new(dir);
+            if !path.is_absolute() {
+                return Err(format!(
+                    "Cache directory \"{}\" must be an absolute path",
+                    dir
+                ));
+            }
+            if path.exists() && !path.is_dir() {
+                return Err(format!("Cache directory \"{}\" is not a directory", dir));
+            }
+        }
+
+        if self.minecraft_version.trim().is_empty() {
+            return Err("Minecraft version cannot be empty".to_string());
+        }
+        if self
+            .loader_version
+            .as_deref()
+            .is_some_and(|v| v.trim().is_empty())
+        {
+            return Err(
+                "Loader version cannot be empty; leave it unset to use the latest".to_string(),
+            );
+        }
+        if self.client_id.trim().is_empty() {
+            return Err("Client ID cannot be empty".to_string());
+        }
+
+        for arg in self.extra_jvm_args() {
+            if !arg.starts_with('-') || arg.len() < 2 {
+                return Err(format!(
+                    "Invalid JVM argument \"{}\": flags must start with '-'",
+                    arg
+                ));
+            }
+            if arg.starts_with("-Xmx") || arg.starts_with("-Xms") {
+                return Err(format!(
+                    "Remove \"{}\" from custom JVM arguments and use the memory settings instead",
+                    arg
+                ));
+            }
+            if RESERVED_JVM_FLAGS.contains(&arg.as_str()) {
+                return Err(format!(
+                    "\"{}\" is set by the launcher and cannot be overridden",
+                    arg
+                ));
+            }
+            if let Some(prop) = arg.strip_prefix("-D") {
+                if prop.is_empty() || prop.starts_with('=') {
+                    return Err(format!("Invalid system property \"{}\"", arg));
+                }
+            }
+            if let Some(opt) = arg.strip_prefix("-XX:") {
+                let valid = match opt.as_bytes().first() {
+                    Some(b'+') | Some(b'-') => opt.len() > 1,
+                    Some(_) => opt.contains('=') && !opt.ends_with('='),
+                    None => false,
+                };
+                if !valid {
+                    return Err(format!(
+                        "Invalid JVM option \"{}\": use -XX:+Flag, -XX:-Flag or -XX:Name=value",
+                        arg
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Every JVM flag the launcher adds besides -Xmx: initial heap, then custom flags.
+    pub fn jvm_flags(&self) -> Vec<String> {
+        let mut flags = vec![format!("-Xms{}M", self.min_memory_mb)];
+        flags.extend(self.extra_jvm_args());
+        flags
+    }
+}
+
+/// Total physical memory, if the platform lets us find out.
+pub fn system_memory_mb() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+        let kb: u64 = meminfo
+            .lines()
+            .find_map(|l| l.strip_prefix("MemTotal:"))?
+            .trim()
+            .trim_end_matches("kB")
+            .trim()
+            .parse()
+            .ok()?;
+        Some(kb / 1024)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let out = std::process::Command::new("sysctl")
+            .args(["-n", "hw.memsize"])
+            .output()
+            .ok()?;
+        let bytes: u64 = String::from_utf8_lossy(&out.stdout).trim().parse().ok()?;
+        Some(bytes / (1024 * 1024))
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let out = std::process::Command::new("powershell")
+            .args([
+                "-NoProfile",
+                "-Command",
+                "(Get-CimInstance Win32_ComputerSystem).TotalPhysicalMemory",
+            ])
+            .output()
+            .ok()?;
+        let bytes: u64 = String::from_utf8_lossy(&out.stdout).trim().parse().ok()?;
+        Some(bytes / (1024 * 1024))
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+    {
+        None
     }
 }
 
 pub struct AppState {
     pub http_client: Client,
     pub base_dir: PathBuf,
-    pub auth: Mutex<Option<AuthTokens>>,
+    pub accounts: Mutex<Accounts>,
     pub settings: Mutex<LauncherSettings>,
 }
 
+impl Default for AppState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AppState {
     pub fn new() -> Self {
         let base_dir = dirs_base_dir();
@@ -59,8 +326,8 @@ impl AppState {
                 .build()
                 .expect("failed to build HTTP client"),
             base_dir,
-            auth: Mutex::new(None),
-            settings: Mutex::new(LauncherSettings::default()),
+            accounts: Mutex::new(Accounts::default()),
+            settings: Mutex::new(crate::settings::load_settings()),
         }
     }
 
@@ -92,7 +359,10 @@ fn dirs_base_dir() -> PathBuf {
     #[cfg(target_os = "macos")]
     {
         if let Some(home) = dirs_home() {
-            return home.join("Library").join("Application Support").join("alloy");
+            return home
+                .join("Library")
+                .join("Application Support")
+                .join("alloy");
         }
     }
 
@@ -6,7 +6,18 @@ import type {
   AuthResult,
   LauncherSettings,
   DownloadProgress,
+  SetupStage,
+  VerifyFailed,
   LaunchState,
+  LaunchResult,
+  GameLogLine,
+  GameExit,
+  VerifyReport,
+  DeviceCodeInfo,
+  LoginMethod,
+  AccountSummary,
+  ModLoader,
+  LoaderVersion,
 } from "./types";
 
 interface AppStore {
@@ -15,78 +26,125 @@ interface AppStore {
   profile: MinecraftProfile | null;
   isLoggingIn: boolean;
   authError: string | null;
+  deviceCode: DeviceCodeInfo | null;
+  accounts: AccountSummary[];
 
   // Launch
   launchState: LaunchState;
   downloadProgress: number;
   downloadMessage: string;
+  setupStage: string | null;
   gameError: string | null;
+  lastLaunchCommand: string | null;
+  gameLog: GameLogLine[];
+  crashReport: string | null;
+  verifyReport: VerifyReport | null;
+  isVerifying: boolean;
 
   // Settings
   settings: LauncherSettings;
   settingsLoaded: boolean;
+  settingsError: string | null;
 
   // Actions
-  login: () => Promise<void>;
-  logout: () => Promise<void>;
+  login: (method?: LoginMethod) => Promise<void>;
+  logout: (all?: boolean) => Promise<void>;
   checkAuth: () => Promise<void>;
+  loadAccounts: () => Promise<void>;
+  switchAccount: (uuid: string) => Promise<void>;
+  removeAccount: (uuid: string) => Promise<void>;
   launchGame: () => Promise<void>;
+  openCrashReport: () => Promise<void>;
+  verifyInstallation: (repair?: boolean) => Promise<void>;
   loadSettings: () => Promise<void>;
   updateSettings: (settings: LauncherSettings) => Promise<void>;
+  listLoaderVersions: (loader: ModLoader) => Promise<LoaderVersion[]>;
   initListeners: () => Promise<() => void>;
 }
 
 const DEFAULT_SETTINGS: LauncherSettings = {
+  schema_version: 2,
   memory_mb: 2048,
+  min_memory_mb: 512,
   java_path: null,
+  managed_java_path: null,
   jvm_args: "",
   client_id: "c36a9fb6-4f2a-41ff-90bd-ae7cc92031eb",
   cache_dir: null,
   minecraft_version: "1.21.11",
+  mod_loader: "alloy",
+  loader_version: null,
 };
 
+/** Game output lines kept for display; older ones are dropped. */
+const MAX_LOG_LINES = 500;
+
+/** Error prefix from the backend when the stored session can't be refreshed. */
+const RELOGIN_REQUIRED = "relogin_required";
+
+function authMessage(error: string): string {
+  return error.startsWith(RELOGIN_REQUIRED)
+    ? error.slice(RELOGIN_REQUIRED.length).replace(/^:\s*/, "")
+    : error;
+}
+
 export const useStore = create<AppStore>((set, get) => ({
   isAuthenticated: false,
   profile: null,
   isLoggingIn: false,
   authError: null,
+  deviceCode: null,
+  accounts: [],
 
   launchState: "ready",
   downloadProgress: 0,
   downloadMessage: "",
+  setupStage: null,
   gameError: null,
+  lastLaunchCommand: null,
+  gameLog: [],
+  crashReport: null,
+  verifyReport: null,
+  isVerifying: false,
 
   settings: DEFAULT_SETTINGS,
   settingsLoaded: false,
+  settingsError: null,
 
-  login: async () => {
-    set({ isLoggingIn: true, authError: null });
+  login: async (method: LoginMethod = "browser") => {
+    set({ isLoggingIn: true, authError: null, deviceCode: null });
     try {
-      const result = await invoke<AuthResult>("login");
+      const result = await invoke<AuthResult>("login", { method });
       if (result.success && result.profile) {
         set({
           isAuthenticated: true,
           profile: result.profile,
           isLoggingIn: false,
+          deviceCode: null,
         });
+        get().loadAccounts();
       } else {
         set({
           isLoggingIn: false,
           authError: result.error || "Login failed",
+          deviceCode: null,
         });
       }
     } catch (e) {
-      set({ isLoggingIn: false, authError: String(e) });
+      set({ isLoggingIn: false, authError: String(e), deviceCode: null });
     }
   },
 
-  logout: async () => {
+  logout: async (all = false) => {
     try {
-      await invoke("logout");
+      await invoke("logout", { all });
     } catch {
       // ignore
     }
     set({ isAuthenticated: false, profile: null, launchState: "ready" });
+    // Another stored account may have become active
+    await get().checkAuth();
+    await get().loadAccounts();
   },
 
   checkAuth: async () => {
@@ -94,18 +152,92 @@ export const useStore = create<AppStore>((set, get) => ({
       const result = await invoke<AuthResult>("check_auth");
       if (result.success && result.profile) {
         set({ isAuthenticated: true, profile: result.profile });
+      } else if (result.error) {
+        set({ isAuthenticated: false, profile: null, authError: authMessage(result.error) });
       }
     } catch {
       // Not authenticated, that's fine
     }
   },
 
+  loadAccounts: async () => {
+    try {
+      const accounts = await invoke<AccountSummary[]>("list_accounts");
+      set({ accounts });
+    } catch {
+      set({ accounts: [] });
+    }
+  },
+
+  switchAccount: async (uuid: string) => {
+    set({ isLoggingIn: true, authError: null });
+    try {
+      const result = await invoke<AuthResult>("switch_account", { uuid });
+      if (result.success && result.profile) {
+        set({ isAuthenticated: true, profile: result.profile, isLoggingIn: false });
+      } else {
+        set({
+          isAuthenticated: false,
+          profile: null,
+          isLoggingIn: false,
+          authError: authMessage(result.error || "Failed to switch account"),
+        });
+      }
+    } catch (e) {
+      set({ isLoggingIn: false, authError: String(e) });
+    }
+    await get().loadAccounts();
+  },
+
+  removeAccount: async (uuid: string) => {
+    try {
+      await invoke("remove_account", { uuid });
+    } catch (e) {
+      console.error("Failed to remove account:", e);
+    }
+    await get().loadAccounts();
+  },
+
   launchGame: async () => {
-    set({ launchState: "preparing", gameError: null });
+    set({ launchState: "preparing", gameError: null, gameLog: [], crashReport: null });
+    try {
+      const result = await invoke<LaunchResult>("launch_game");
+      set({ lastLaunchCommand: result.command });
+    } catch (e) {
+      const error = String(e);
+      if (error.startsWith(RELOGIN_REQUIRED)) {
+        // Session can't be refreshed — send the user back to sign in
+        set({
+          launchState: "ready",
+          isAuthenticated: false,
+          profile: null,
+          authError: authMessage(error),
+        });
+      } else {
+        set({ launchState: "ready", gameError: error });
+      }
+    }
+  },
+
+  openCrashReport: async () => {
+    const path = get().crashReport;
+    if (!path) return;
     try {
-      await invoke("launch_game");
+      await invoke("open_crash_report", { path });
     } catch (e) {
-      set({ launchState: "ready", gameError: String(e) });
+      set({ gameError: String(e) });
+    }
+  },
+
+  verifyInstallation: async (repair = false) => {
+    set({ isVerifying: true, gameError: null });
+    try {
+      const report = await invoke<VerifyReport>(
+        repair ? "repair_installation" : "verify_installation",
+      );
+      set({ verifyReport: report, isVerifying: false });
+    } catch (e) {
+      set({ isVerifying: false, gameError: String(e) });
     }
   },
 
@@ -121,9 +253,19 @@ export const useStore = create<AppStore>((set, get) => ({
   updateSettings: async (settings: LauncherSettings) => {
     try {
       await invoke("update_settings", { settings });
-      set({ settings });
+      set({ settings, settingsError: null });
     } catch (e) {
       console.error("Failed to save settings:", e);
+      set({ settingsError: String(e) });
+    }
+  },
+
+  listLoaderVersions: async (loader: ModLoader) => {
+    try {
+      return await invoke<LoaderVersion[]>("list_loader_versions", { loader });
+    } catch (e) {
+      set({ settingsError: String(e) });
+      return [];
     }
   },
 
@@ -131,13 +273,26 @@ export const useStore = create<AppStore>((set, get) => ({
     const unlistenProgress = await listen<DownloadProgress>(
       "download-progress",
       (event) => {
+        const { file, message } = event.payload;
         set({
           downloadProgress: event.payload.percent,
-          downloadMessage: event.payload.message,
+          downloadMessage: file ? `${message} (${file})` : message,
         });
       },
     );
 
+    const unlistenStage = await listen<SetupStage>("setup-stage", (event) => {
+      set({
+        setupStage: event.payload.stage,
+        downloadMessage: event.payload.message,
+      });
+    });
+
+    const unlistenVerify = await listen<VerifyFailed>("verify-failed", (event) => {
+      console.warn("Checksum mismatch, re-downloading:", event.payload);
+      set({ downloadMessage: `Re-downloading corrupt file ${event.payload.file}` });
+    });
+
     const unlistenState = await listen<string>("launch-state", (event) => {
       set({ launchState: event.payload as LaunchState });
     });
@@ -146,10 +301,30 @@ export const useStore = create<AppStore>((set, get) => ({
       set({ gameError: event.payload, launchState: "ready" });
     });
 
+    const unlistenLog = await listen<GameLogLine>("game-log", (event) => {
+      set((s) => ({ gameLog: [...s.gameLog, event.payload].slice(-MAX_LOG_LINES) }));
+    });
+
+    const unlistenExit = await listen<GameExit>("game-exit", (event) => {
+      set({ crashReport: event.payload.crash_report });
+    });
+
+    const unlistenDeviceCode = await listen<DeviceCodeInfo>(
+      "device-code",
+      (event) => {
+        set({ deviceCode: event.payload });
+      },
+    );
+
     return () => {
+      unlistenDeviceCode();
       unlistenProgress();
+      unlistenStage();
+      unlistenVerify();
       unlistenState();
       unlistenError();
+      unlistenLog();
+      unlistenExit();
     };
   },
 }));
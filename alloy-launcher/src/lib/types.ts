@@ -10,13 +10,75 @@ export interface AuthResult {
   error: string | null;
 }
 
+export interface AccountSummary {
+  profile: MinecraftProfile;
+  active: boolean;
+}
+
+export interface DeviceCodeInfo {
+  user_code: string;
+  verification_uri: string;
+  expires_in: number;
+  interval: number;
+  message: string;
+}
+
+export type LoginMethod = "browser" | "device_code";
+
+export type ModLoader = "alloy" | "fabric" | "quilt";
+
+export interface LoaderVersion {
+  version: string;
+  stable: boolean;
+}
+
 export interface LauncherSettings {
+  schema_version: number;
   memory_mb: number;
+  min_memory_mb: number;
   java_path: string | null;
+  managed_java_path: string | null;
   jvm_args: string;
   client_id: string;
   cache_dir: string | null;
   minecraft_version: string;
+  mod_loader: ModLoader;
+  /** null means the latest stable loader for minecraft_version */
+  loader_version: string | null;
+}
+
+export interface LaunchResult {
+  command: string;
+  /** Session log the game's output is written to */
+  log_file: string;
+}
+
+export interface GameLogLine {
+  stream: "stdout" | "stderr";
+  line: string;
+}
+
+export interface GameExit {
+  /** null when the process was killed by a signal */
+  exit_code: number | null;
+  /** Crash report written during the session, if the game crashed */
+  crash_report: string | null;
+  log_file: string;
+}
+
+export interface FileProblem {
+  path: string;
+  url: string;
+  /** null for loader libraries whose repository publishes no checksum */
+  sha1: string | null;
+  size: number | null;
+  kind: "missing" | "corrupt";
+}
+
+export interface VerifyReport {
+  version_id: string;
+  checked: number;
+  problems: FileProblem[];
 }
 
 export interface SetupStatus {
@@ -33,6 +95,21 @@ export interface DownloadProgress {
   percent: number;
   bytes_downloaded: number;
   bytes_total: number;
+  /** File currently being fetched, when reported per file */
+  file?: string;
+  downloaded?: number;
+  total?: number;
+}
+
+export interface SetupStage {
+  stage: string;
+  message: string;
+}
+
+export interface VerifyFailed {
+  file: string;
+  expected: string;
+  actual: string;
 }
 
 export type LaunchState =
@@ -2,6 +2,7 @@ import { useStore } from "../lib/store";
 import PlayerCard from "../components/home/PlayerCard";
 import LaunchButton from "../components/home/LaunchButton";
 import ProgressBar from "../components/home/ProgressBar";
+import Button from "../components/ui/Button";
 import EmberGlow from "../components/effects/EmberGlow";
 import GridOverlay from "../components/effects/GridOverlay";
 
@@ -11,6 +12,9 @@ export default function HomePage() {
   const downloadProgress = useStore((s) => s.downloadProgress);
   const downloadMessage = useStore((s) => s.downloadMessage);
   const gameError = useStore((s) => s.gameError);
+  const gameLog = useStore((s) => s.gameLog);
+  const crashReport = useStore((s) => s.crashReport);
+  const openCrashReport = useStore((s) => s.openCrashReport);
   const settings = useStore((s) => s.settings);
 
   return (
@@ -36,10 +40,25 @@ export default function HomePage() {
 
         {/* Game error */}
         {gameError && (
-          <div className="max-w-md rounded-lg border border-red-600/20 bg-red-600/10 p-3 text-center text-sm text-red-400">
+          <div className="max-h-48 max-w-md overflow-y-auto whitespace-pre-wrap break-words rounded-lg border border-red-600/20 bg-red-600/10 p-3 text-center text-sm text-red-400">
             {gameError}
           </div>
         )}
+        {crashReport && (
+          <Button variant="danger" onClick={() => openCrashReport()}>
+            Open crash report
+          </Button>
+        )}
+
+        {/* Game output */}
+        {gameLog.length > 0 && (
+          <details className="w-full max-w-md text-xs text-stone-400">
+            <summary className="cursor-pointer select-none">Game log</summary>
+            <pre className="mt-2 max-h-64 overflow-y-auto rounded-lg border border-obsidian-600 bg-obsidian-900 p-3 font-mono">
+              {gameLog.map((l) => l.line).join("\n")}
+            </pre>
+          </details>
+        )}
 
         {/* Version badge */}
         <div className="flex items-center gap-2 text-xs text-stone-500">
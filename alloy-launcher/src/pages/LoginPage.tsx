@@ -14,6 +14,7 @@ export default function LoginPage() {
   const isAuthenticated = useStore((s) => s.isAuthenticated);
   const isLoggingIn = useStore((s) => s.isLoggingIn);
   const authError = useStore((s) => s.authError);
+  const deviceCode = useStore((s) => s.deviceCode);
   const login = useStore((s) => s.login);
 
   useEffect(() => {
@@ -47,7 +48,7 @@ export default function LoginPage() {
           {/* Login button */}
           <div className="flex flex-col items-center gap-3">
             <Button
-              onClick={login}
+              onClick={() => login("browser")}
               disabled={isLoggingIn}
               className="min-w-[240px] text-base py-3"
             >
@@ -74,6 +75,35 @@ export default function LoginPage() {
               )}
             </Button>
 
+            {deviceCode ? (
+              <div className="flex max-w-xs flex-col items-center gap-2 text-center">
+                <p className="text-xs text-stone-400">
+                  Go to{" "}
+                  <a
+                    href={deviceCode.verification_uri}
+                    target="_blank"
+                    rel="noopener noreferrer"
+                    className="text-ember hover:underline"
+                  >
+                    {deviceCode.verification_uri}
+                  </a>{" "}
+                  and enter this code:
+                </p>
+                <code className="select-all rounded-md bg-obsidian-800 px-4 py-2 font-mono text-xl tracking-widest text-stone-100">
+                  {deviceCode.user_code}
+                </code>
+              </div>
+            ) : (
+              !isLoggingIn && (
+                <button
+                  onClick={() => login("device_code")}
+                  className="text-xs text-stone-500 transition-colors hover:text-stone-300"
+                >
+                  Sign in with a code instead
+                </button>
+              )
+            )}
+
             {authError && (
               <p className="max-w-xs text-center text-xs text-red-400">
                 {authError}
@@ -3,32 +3,74 @@ import { useStore } from "../lib/store";
 import Card from "../components/ui/Card";
 import Input from "../components/ui/Input";
 import Button from "../components/ui/Button";
+import type { LoaderVersion, ModLoader } from "../lib/types";
 
 export default function SettingsPage() {
   const settings = useStore((s) => s.settings);
   const updateSettings = useStore((s) => s.updateSettings);
   const profile = useStore((s) => s.profile);
   const logout = useStore((s) => s.logout);
+  const login = useStore((s) => s.login);
+  const accounts = useStore((s) => s.accounts);
+  const loadAccounts = useStore((s) => s.loadAccounts);
+  const switchAccount = useStore((s) => s.switchAccount);
+  const removeAccount = useStore((s) => s.removeAccount);
+  const isLoggingIn = useStore((s) => s.isLoggingIn);
 
+  const settingsError = useStore((s) => s.settingsError);
+  const lastLaunchCommand = useStore((s) => s.lastLaunchCommand);
+  const verifyReport = useStore((s) => s.verifyReport);
+  const isVerifying = useStore((s) => s.isVerifying);
+  const verifyInstallation = useStore((s) => s.verifyInstallation);
+  const downloadMessage = useStore((s) => s.downloadMessage);
+  const listLoaderVersions = useStore((s) => s.listLoaderVersions);
   const [memory, setMemory] = useState(settings.memory_mb);
+  const [minMemory, setMinMemory] = useState(settings.min_memory_mb);
   const [javaPath, setJavaPath] = useState(settings.java_path || "");
   const [jvmArgs, setJvmArgs] = useState(settings.jvm_args);
   const [clientId, setClientId] = useState(settings.client_id);
+  const [modLoader, setModLoader] = useState<ModLoader>(settings.mod_loader);
+  const [loaderVersion, setLoaderVersion] = useState(settings.loader_version || "");
+  const [loaderVersions, setLoaderVersions] = useState<LoaderVersion[]>([]);
+
+  useEffect(() => {
+    loadAccounts();
+  }, [loadAccounts]);
+
+  useEffect(() => {
+    if (modLoader === "alloy") {
+      setLoaderVersions([]);
+      return;
+    }
+    let cancelled = false;
+    listLoaderVersions(modLoader).then((versions) => {
+      if (!cancelled) setLoaderVersions(versions);
+    });
+    return () => {
+      cancelled = true;
+    };
+  }, [modLoader, settings.minecraft_version, listLoaderVersions]);
 
   useEffect(() => {
     setMemory(settings.memory_mb);
+    setMinMemory(settings.min_memory_mb);
     setJavaPath(settings.java_path || "");
     setJvmArgs(settings.jvm_args);
     setClientId(settings.client_id);
+    setModLoader(settings.mod_loader);
+    setLoaderVersion(settings.loader_version || "");
   }, [settings]);
 
   const save = () => {
     updateSettings({
       ...settings,
       memory_mb: memory,
+      min_memory_mb: minMemory,
       java_path: javaPath || null,
       jvm_args: jvmArgs,
       client_id: clientId,
+      mod_loader: modLoader,
+      loader_version: modLoader === "alloy" ? null : loaderVersion || null,
     });
   };
 
@@ -60,13 +102,56 @@ export default function SettingsPage() {
                 </p>
               </div>
             </div>
-            <Button variant="danger" onClick={logout}>
+            <Button variant="danger" onClick={() => logout()}>
               Log out
             </Button>
           </div>
         ) : (
           <p className="text-sm text-stone-400">Not signed in</p>
         )}
+
+        {/* Other accounts */}
+        {accounts.filter((a) => !a.active).length > 0 && (
+          <div className="mt-4 space-y-2 border-t border-obsidian-700 pt-4">
+            {accounts
+              .filter((a) => !a.active)
+              .map((a) => (
+                <div key={a.profile.uuid} className="flex items-center justify-between">
+                  <div className="flex items-center gap-3">
+                    <img
+                      src={`https://mc-heads.net/head/${a.profile.uuid.replaceAll("-", "")}/32`}
+                      alt={a.profile.username}
+                      className="h-8 w-8 rounded"
+                    />
+                    <p className="text-sm text-stone-300">{a.profile.username}</p>
+                  </div>
+                  <div className="flex gap-2">
+                    <Button
+                      variant="secondary"
+                      disabled={isLoggingIn}
+                      onClick={() => switchAccount(a.profile.uuid)}
+                    >
+                      Switch
+                    </Button>
+                    <Button variant="ghost" onClick={() => removeAccount(a.profile.uuid)}>
+                      Remove
+                    </Button>
+                  </div>
+                </div>
+              ))}
+          </div>
+        )}
+
+        <div className="mt-4 flex gap-2">
+          <Button variant="secondary" disabled={isLoggingIn} onClick={() => login()}>
+            Add account
+          </Button>
+          {accounts.length > 1 && (
+            <Button variant="ghost" onClick={() => logout(true)}>
+              Log out all
+            </Button>
+          )}
+        </div>
       </Card>
 
       {/* Game */}
@@ -100,9 +185,67 @@ export default function SettingsPage() {
           </div>
         </div>
 
+        {/* Initial heap */}
+        <div className="space-y-2">
+          <div className="flex items-center justify-between">
+            <label className="text-xs font-medium text-stone-400">
+              Initial Memory
+            </label>
+            <span className="font-mono text-xs text-ember">
+              {(minMemory / 1024).toFixed(1)} GB
+            </span>
+          </div>
+          <input
+            type="range"
+            min={512}
+            max={memory}
+            step={512}
+            value={Math.min(minMemory, memory)}
+            onChange={(e) => setMinMemory(Number(e.target.value))}
+            className="w-full accent-ember"
+          />
+        </div>
+
+        {/* Mod loader */}
+        <div className="flex gap-3">
+          <div className="flex flex-1 flex-col gap-1.5">
+            <label className="text-xs font-medium text-stone-400">Mod Loader</label>
+            <select
+              value={modLoader}
+              onChange={(e) => {
+                setModLoader(e.target.value as ModLoader);
+                setLoaderVersion("");
+              }}
+              className="rounded-lg border border-obsidian-600 bg-obsidian-900 px-3 py-2 text-sm text-stone-200 outline-none focus:border-ember/50"
+            >
+              <option value="alloy">Alloy</option>
+              <option value="fabric">Fabric</option>
+              <option value="quilt">Quilt</option>
+            </select>
+          </div>
+          {modLoader !== "alloy" && (
+            <div className="flex flex-1 flex-col gap-1.5">
+              <label className="text-xs font-medium text-stone-400">Loader Version</label>
+              <select
+                value={loaderVersion}
+                onChange={(e) => setLoaderVersion(e.target.value)}
+                className="rounded-lg border border-obsidian-600 bg-obsidian-900 px-3 py-2 text-sm text-stone-200 outline-none focus:border-ember/50"
+              >
+                <option value="">Latest stable</option>
+                {loaderVersions.map((v) => (
+                  <option key={v.version} value={v.version}>
+                    {v.version}
+                    {v.stable ? "" : " (beta)"}
+                  </option>
+                ))}
+              </select>
+            </div>
+          )}
+        </div>
+
         <Input
           label="Java Path"
-          placeholder="Auto-detect"
+          placeholder={settings.managed_java_path ?? "Auto-detect (downloads Java if missing)"}
           value={javaPath}
           onChange={(e) => setJavaPath(e.target.value)}
         />
@@ -113,6 +256,51 @@ export default function SettingsPage() {
           value={jvmArgs}
           onChange={(e) => setJvmArgs(e.target.value)}
         />
+
+        {/* Installation integrity */}
+        <div className="space-y-2">
+          <div className="flex items-center justify-between">
+            <label className="text-xs font-medium text-stone-400">
+              Game Files
+            </label>
+            <div className="flex gap-2">
+              <Button
+                variant="secondary"
+                disabled={isVerifying}
+                onClick={() => verifyInstallation(false)}
+              >
+                Verify
+              </Button>
+              {verifyReport && verifyReport.problems.length > 0 && (
+                <Button disabled={isVerifying} onClick={() => verifyInstallation(true)}>
+                  Repair
+                </Button>
+              )}
+            </div>
+          </div>
+          {isVerifying ? (
+            <p className="text-xs text-stone-500">{downloadMessage || "Checking..."}</p>
+          ) : (
+            verifyReport && (
+              <p className="text-xs text-stone-500">
+                {verifyReport.problems.length === 0
+                  ? `All ${verifyReport.checked} files are intact.`
+                  : `${verifyReport.problems.length} of ${verifyReport.checked} files are missing or corrupt.`}
+              </p>
+            )
+          )}
+        </div>
+
+        {lastLaunchCommand && (
+          <details className="text-xs text-stone-500">
+            <summary className="cursor-pointer hover:text-stone-300">
+              Last launch command
+            </summary>
+            <pre className="mt-2 max-h-40 overflow-auto whitespace-pre-wrap break-all rounded-md bg-obsidian-900 p-2 font-mono text-[10px] text-stone-400">
+              {lastLaunchCommand}
+            </pre>
+          </details>
+        )}
       </Card>
 
       {/* Launcher */}
@@ -138,7 +326,10 @@ export default function SettingsPage() {
       </Card>
 
       {/* Save */}
-      <div className="flex justify-end">
+      <div className="flex items-center justify-end gap-3">
+        {settingsError && (
+          <p className="text-xs text-red-400">{settingsError}</p>
+        )}
         <Button onClick={save}>Save Settings</Button>
       </div>
 
@@ -0,0 +1,638 @@
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::process::{Child, Command};
+use tokio::sync::{mpsc, watch, Mutex};
+
+use super::transport::{LspMessage, LspTransport};
+
+/// How long to wait for JDT LS to finish importing the project before
+/// sending requests anyway. Gradle imports of large mods can be slow.
+const READY_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// Upper bound for a single request, so a wedged server can't hang a tool call.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Restart delays after consecutive crashes double up to this cap.
+const MAX_RESTART_BACKOFF: Duration = Duration::from_secs(30);
+
+/// A session that stayed up this long counts as healthy, resetting the backoff.
+const HEALTHY_UPTIME: Duration = Duration::from_secs(120);
+
+/// Latest `publishDiagnostics` per document URI, tagged with the publish
+/// sequence number it arrived at.
+type DiagnosticStore = Arc<std::sync::Mutex<HashMap<String, (u64, Vec<Value>)>>>;
+
+/// A running JDT LS bound to one project root.
+struct LspSession {
+    transport: Arc<LspTransport>,
+    root: String,
+    ready: watch::Receiver<bool>,
+    /// Documents announced with didOpen: URI → (version, text last sent)
+    opened: HashMap<String, (i32, String)>,
+    diagnostics: DiagnosticStore,
+    /// Bumped on every `publishDiagnostics`
+    published: watch::Receiver<u64>,
+    child: Child,
+    started_at: Instant,
+}
+
+/// Crash history, kept across sessions.
+#[derive(Default)]
+struct LspHealth {
+    /// Crashes since the last session that stayed up `HEALTHY_UPTIME`
+    consecutive_crashes: u32,
+    restarts: u32,
+    last_error: Option<String>,
+}
+
+/// Snapshot reported by `code_lsp_status`.
+#[derive(Debug, serde::Serialize)]
+pub struct LspStatus {
+    pub running: bool,
+    pub initialized: bool,
+    pub project_root: Option<String>,
+    pub pid: Option<u32>,
+    pub uptime_secs: Option<u64>,
+    pub open_documents: usize,
+    pub restarts: u32,
+    pub consecutive_crashes: u32,
+    pub last_error: Option<String>,
+}
+
+/// Starts the Eclipse JDT Language Server on demand and keeps one session
+/// per project root.
+pub struct LspManager {
+    session: Mutex<Option<LspSession>>,
+    health: std::sync::Mutex<LspHealth>,
+}
+
+impl LspManager {
+    pub fn new() -> Self {
+        Self {
+            session: Mutex::new(None),
+            health: std::sync::Mutex::new(LspHealth::default()),
+        }
+    }
+
+    /// Return a transport to a server bound to `root`, and wait until the
+    /// server reports it has finished importing the project. Starts the
+    /// server if needed, restarts it on a different root, and brings it back
+    /// (after a backoff if it keeps dying) if it crashed.
+    pub async fn ensure(&self, root: &str) -> Result<Arc<LspTransport>, String> {
+        let (transport, mut ready) = {
+            let mut session = self.session.lock().await;
+            let exited = match session.as_mut() {
+                Some(s) => match s.child.try_wait() {
+                    Ok(None) => None,
+                    Ok(Some(status)) => Some(format!("Language server exited ({})", status)),
+                    Err(e) => Some(format!("Language server state unknown: {}", e)),
+                },
+                None => None,
+            };
+
+            if let Some(reason) = exited {
+                let crashed = session.take().expect("exited implies a session");
+                let backoff = self.record_crash(reason, crashed.started_at.elapsed());
+                tokio::time::sleep(backoff).await;
+            }
+
+            let reusable = session.as_ref().is_some_and(|s| s.root == root);
+            if !reusable {
+                if let Some(old) = session.take() {
+                    Self::shutdown(old).await;
+                }
+                *session = Some(self.spawn_recorded(root).await?);
+            }
+            let s = session.as_ref().expect("session was just ensured");
+            (s.transport.clone(), s.ready.clone())
+        };
+
+        // Requests sent mid-import come back empty, so wait for readiness,
+        // but fall through on timeout rather than failing outright
+        let _ = tokio::time::timeout(READY_TIMEOUT, ready.wait_for(|r| *r)).await;
+        Ok(transport)
+    }
+
+    /// Stop any running server and start a fresh one for `root`, clearing
+    /// the crash backoff.
+    pub async fn restart(&self, root: &str) -> Result<(), String> {
+        let mut session = self.session.lock().await;
+        if let Some(old) = session.take() {
+            Self::shutdown(old).await;
+        }
+        if let Ok(mut health) = self.health.lock() {
+            health.consecutive_crashes = 0;
+            health.restarts += 1;
+        }
+        *session = Some(self.spawn_recorded(root).await?);
+        Ok(())
+    }
+
+    pub async fn status(&self) -> LspStatus {
+        let mut session = self.session.lock().await;
+        let (consecutive_crashes, restarts, last_error) = match self.health.lock() {
+            Ok(h) => (h.consecutive_crashes, h.restarts, h.last_error.clone()),
+            Err(_) => (0, 0, None),
+        };
+        match session.as_mut() {
+            Some(s) => LspStatus {
+                running: matches!(s.child.try_wait(), Ok(None)),
+                initialized: *s.ready.borrow(),
+                project_root: Some(s.root.clone()),
+                pid: s.child.id(),
+                uptime_secs: Some(s.started_at.elapsed().as_secs()),
+                open_documents: s.opened.len(),
+                restarts,
+                consecutive_crashes,
+                last_error,
+            },
+            None => LspStatus {
+                running: false,
+                initialized: false,
+                project_root: None,
+                pid: None,
+                uptime_secs: None,
+                open_documents: 0,
+                restarts,
+                consecutive_crashes,
+                last_error,
+            },
+        }
+    }
+
+    /// Note a crash and return how long to wait before restarting.
+    fn record_crash(&self, reason: String, uptime: Duration) -> Duration {
+        let Ok(mut health) = self.health.lock() else {
+            return Duration::ZERO;
+        };
+        if uptime >= HEALTHY_UPTIME {
+            health.consecutive_crashes = 0;
+        }
+        health.consecutive_crashes += 1;
+        health.restarts += 1;
+        health.last_error = Some(reason);
+        // 1s, 2s, 4s, ... capped
+        let delay = Duration::from_secs(1 << (health.consecutive_crashes - 1).min(5));
+        delay.min(MAX_RESTART_BACKOFF)
+    }
+
+    async fn spawn_recorded(&self, root: &str) -> Result<LspSession, String> {
+        let result = Self::spawn(root).await;
+        if let (Err(e), Ok(mut health)) = (&result, self.health.lock()) {
+            health.last_error = Some(e.clone());
+        }
+        result
+    }
+
+    /// Send a request to the server for `root`.
+    pub async fn request(&self, root: &str, method: &str, params: Value) -> Result<Value, String> {
+        let transport = self.ensure(root).await?;
+        tokio::time::timeout(REQUEST_TIMEOUT, transport.request(method, Some(params)))
+            .await
+            .map_err(|_| format!("Language server did not answer {} in time", method))?
+    }
+
+    /// Announce a file to the server so position-based requests work on it,
+    /// or resend it if it changed on disk since. Returns the document URI.
+    pub async fn open_document(&self, root: &str, path: &Path) -> Result<String, String> {
+        self.sync_document(root, path).await.map(|(uri, _)| uri)
+    }
+
+    /// `open_document`, also reporting whether anything was sent.
+    async fn sync_document(&self, root: &str, path: &Path) -> Result<(String, bool), String> {
+        let transport = self.ensure(root).await?;
+        let uri = path_to_uri(path);
+        let text = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+        let mut session = self.session.lock().await;
+        let Some(session) = session.as_mut() else {
+            return Err("Language server stopped".to_string());
+        };
+
+        match session.opened.get_mut(&uri) {
+            Some((_, sent)) if *sent == text => return Ok((uri, false)),
+            Some((version, sent)) => {
+                *version += 1;
+                transport
+                    .notify(
+                        "textDocument/didChange",
+                        Some(json!({
+                            "textDocument": { "uri": uri, "version": *version },
+                            "contentChanges": [{ "text": text }]
+                        })),
+                    )
+                    .await?;
+                *sent = text;
+            }
+            None => {
+                let language_id = match path.extension().and_then(|e| e.to_str()) {
+                    Some("kt") => "kotlin",
+                    _ => "java",
+                };
+                transport
+                    .notify(
+                        "textDocument/didOpen",
+                        Some(json!({
+                            "textDocument": {
+                                "uri": uri,
+                                "languageId": language_id,
+                                "version": 1,
+                                "text": text
+                            }
+                        })),
+                    )
+                    .await?;
+                session.opened.insert(uri.clone(), (1, text));
+            }
+        }
+        Ok((uri, true))
+    }
+
+    /// Diagnostics for one file. If the server has not analysed the current
+    /// contents yet, waits up to `wait` for it to publish them.
+    pub async fn document_diagnostics(
+        &self,
+        root: &str,
+        path: &Path,
+        wait: Duration,
+    ) -> Result<Vec<Value>, String> {
+        self.ensure(root).await?;
+        let (store, mut published) = self.diagnostic_handles().await?;
+        let before = *published.borrow();
+
+        let (uri, sent) = self.sync_document(root, path).await?;
+        let latest = |store: &DiagnosticStore| store.lock().ok().and_then(|d| d.get(&uri).cloned());
+        if !sent {
+            if let Some((_, diagnostics)) = latest(&store) {
+                return Ok(diagnostics);
+            }
+        }
+
+        let fresh = async {
+            loop {
+                if let Some((seq, diagnostics)) = latest(&store) {
+                    if seq > before {
+                        return diagnostics;
+                    }
+                }
+                if published.changed().await.is_err() {
+                    return Vec::new();
+                }
+            }
+        };
+        match tokio::time::timeout(wait, fresh).await {
+            Ok(diagnostics) => Ok(diagnostics),
+            // Nothing new yet: report whatever is known, possibly nothing
+            Err(_) => Ok(latest(&store).map(|(_, d)| d).unwrap_or_default()),
+        }
+    }
+
+    /// Every diagnostic the server has published for `root`, by file URI.
+    pub async fn all_diagnostics(&self, root: &str) -> Result<HashMap<String, Vec<Value>>, String> {
+        self.ensure(root).await?;
+        let (store, _) = self.diagnostic_handles().await?;
+        let store = store.lock().map_err(|e| e.to_string())?;
+        Ok(store
+            .iter()
+            .filter(|(_, (_, d))| !d.is_empty())
+            .map(|(uri, (_, d))| (uri.clone(), d.clone()))
+            .collect())
+    }
+
+    async fn diagnostic_handles(&self) -> Result<(DiagnosticStore, watch::Receiver<u64>), String> {
+        let session = self.session.lock().await;
+        let session = session.as_ref().ok_or("Language server stopped")?;
+        Ok((session.diagnostics.clone(), session.published.clone()))
+    }
+
+    /// Tell the server a document is gone (deleted or renamed away).
+    pub async fn close_document(&self, uri: &str) {
+        let mut session = self.session.lock().await;
+        if let Some(session) = session.as_mut() {
+            if session.opened.remove(uri).is_some() {
+                let _ = session
+                    .transport
+                    .notify(
+                        "textDocument/didClose",
+                        Some(json!({ "textDocument": { "uri": uri } })),
+                    )
+                    .await;
+            }
+        }
+    }
+
+    async fn spawn(root: &str) -> Result<LspSession, String> {
+        let jdtls_home = find_jdtls().ok_or_else(|| {
+            "Eclipse JDT LS not found. Set JDTLS_HOME or install via: brew install jdtls"
+                .to_string()
+        })?;
+        let launcher_jar = find_launcher_jar(&jdtls_home.join("plugins"))?;
+        let config_dir = find_config_dir(&jdtls_home)?;
+
+        let data_dir = workspace_data_dir(root);
+        tokio::fs::create_dir_all(&data_dir)
+            .await
+            .map_err(|e| format!("Failed to create workspace dir: {}", e))?;
+
+        let mut child = Command::new(find_java())
+            .arg("-Declipse.application=org.eclipse.jdt.ls.core.id1")
+            .arg("-Dosgi.bundles.defaultStartLevel=4")
+            .arg("-Declipse.product=org.eclipse.jdt.ls.core.product")
+            .arg("-Xmx512m")
+            .arg("--add-modules=ALL-SYSTEM")
+            .arg("--add-opens")
+            .arg("java.base/java.util=ALL-UNNAMED")
+            .arg("--add-opens")
+            .arg("java.base/java.lang=ALL-UNNAMED")
+            .arg("-jar")
+            .arg(&launcher_jar)
+            .arg("-configuration")
+            .arg(&config_dir)
+            .arg("-data")
+            .arg(&data_dir)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| format!("Failed to spawn JDT LS: {}", e))?;
+
+        let stdin = child.stdin.take().ok_or("Failed to get JDT LS stdin")?;
+        let stdout = child.stdout.take().ok_or("Failed to get JDT LS stdout")?;
+        let (transport, incoming) = LspTransport::new(stdin, stdout);
+        let transport = Arc::new(transport);
+
+        let (ready_tx, ready) = watch::channel(false);
+        let (published_tx, published) = watch::channel(0u64);
+        let diagnostics = DiagnosticStore::default();
+        tokio::spawn(Self::pump(
+            transport.clone(),
+            incoming,
+            ready_tx,
+            diagnostics.clone(),
+            published_tx,
+        ));
+
+        let root_uri = path_to_uri(Path::new(root));
+        let initialize = transport.request(
+            "initialize",
+            Some(json!({
+                "processId": std::process::id(),
+                "rootUri": root_uri,
+                "capabilities": {
+                    "textDocument": {
+                        "references": {},
+                        "rename": { "prepareSupport": false },
+                        "publishDiagnostics": { "relatedInformation": true },
+                        "synchronization": { "didSave": true }
+                    },
+                    "workspace": {
+                        "workspaceFolders": true,
+                        "workspaceEdit": {
+                            "documentChanges": true,
+                            "resourceOperations": ["create", "rename", "delete"]
+                        }
+                    }
+                },
+                "workspaceFolders": [{
+                    "uri": root_uri,
+                    "name": Path::new(root)
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or("project")
+                }],
+                "initializationOptions": {
+                    "extendedClientCapabilities": { "progressReportProvider": false },
+                    "settings": {
+                        "java": { "import": { "gradle": { "enabled": true } } }
+                    }
+                }
+            })),
+        );
+        tokio::time::timeout(READY_TIMEOUT, initialize)
+            .await
+            .map_err(|_| "Language server did not finish initializing".to_string())??;
+        transport.notify("initialized", Some(json!({}))).await?;
+
+        Ok(LspSession {
+            transport,
+            root: root.to_string(),
+            ready,
+            opened: HashMap::new(),
+            diagnostics,
+            published,
+            child,
+            started_at: Instant::now(),
+        })
+    }
+
+    /// Handle everything the server sends unprompted. Server requests must
+    /// be answered or JDT LS stalls waiting on them.
+    async fn pump(
+        transport: Arc<LspTransport>,
+        mut incoming: mpsc::UnboundedReceiver<LspMessage>,
+        ready_tx: watch::Sender<bool>,
+        diagnostics: DiagnosticStore,
+        published_tx: watch::Sender<u64>,
+    ) {
+        while let Some(msg) = incoming.recv().await {
+            let method = msg.method.as_deref().unwrap_or("");
+            if msg.is_request() {
+                let result = match method {
+                    // One (empty) config value per requested item
+                    "workspace/configuration" => {
+                        let items = msg
+                            .params
+                            .as_ref()
+                            .and_then(|p| p.get("items"))
+                            .and_then(|i| i.as_array())
+                            .map_or(0, |i| i.len());
+                        Value::Array(vec![Value::Null; items])
+                    }
+                    _ => Value::Null,
+                };
+                if let Some(id) = msg.id {
+                    let _ = transport.respond(id, result).await;
+                }
+                continue;
+            }
+
+            if method == "textDocument/publishDiagnostics" {
+                let Some(params) = msg.params else { continue };
+                let Some(uri) = params.get("uri").and_then(|u| u.as_str()) else {
+                    continue;
+                };
+                let list = params
+                    .get("diagnostics")
+                    .and_then(|d| d.as_array())
+                    .cloned()
+                    .unwrap_or_default();
+                let seq = *published_tx.borrow() + 1;
+                if let Ok(mut store) = diagnostics.lock() {
+                    store.insert(uri.to_string(), (seq, list));
+                }
+                let _ = published_tx.send(seq);
+                continue;
+            }
+
+            if method == "language/status" {
+                let status = msg
+                    .params
+                    .as_ref()
+                    .and_then(|p| p.get("type"))
+                    .and_then(|t| t.as_str());
+                if matches!(status, Some("ServiceReady") | Some("Started")) {
+                    let _ = ready_tx.send(true);
+                }
+            }
+        }
+    }
+
+    async fn shutdown(mut session: LspSession) {
+        let transport = session.transport.clone();
+        let polite = async move {
+            let _ = transport.request("shutdown", None).await;
+            let _ = transport.notify("exit", None).await;
+        };
+        let _ = tokio::time::timeout(Duration::from_secs(5), polite).await;
+        let _ = session.child.kill().await;
+    }
+}
+
+/// `file://` URI for an absolute path, percent-encoding characters that
+/// would otherwise break URI parsing.
+pub fn path_to_uri(path: &Path) -> String {
+    let mut uri = String::from("file://");
+    let path = path.to_string_lossy().replace('\\', "/");
+    if !path.starts_with('/') {
+        // Windows drive paths: file:///C:/...
+        uri.push('/');
+    }
+    for c in path.chars() {
+        match c {
+            ' ' => uri.push_str("%20"),
+            '#' => uri.push_str("%23"),
+            '%' => uri.push_str("%25"),
+            '?' => uri.push_str("%3F"),
+            _ => uri.push(c),
+        }
+    }
+    uri
+}
+
+/// Inverse of `path_to_uri`, decoding any percent escapes.
+pub fn uri_to_path(uri: &str) -> PathBuf {
+    let raw = uri.strip_prefix("file://").unwrap_or(uri);
+    let bytes = raw.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(b) = hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                decoded.push(b);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    let path = String::from_utf8_lossy(&decoded).into_owned();
+    // file:///C:/x → C:/x
+    match path.as_bytes() {
+        [b'/', _, b':', ..] => PathBuf::from(&path[1..]),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Find the JDT LS installation: `JDTLS_HOME`, then common install locations.
+fn find_jdtls() -> Option<PathBuf> {
+    if let Ok(home) = std::env::var("JDTLS_HOME") {
+        let p = PathBuf::from(home);
+        if p.exists() {
+            return Some(p);
+        }
+    }
+
+    let user_local = format!(
+        "{}/.local/share/jdtls",
+        std::env::var("HOME").unwrap_or_default()
+    );
+    [
+        "/opt/homebrew/opt/jdtls/libexec",
+        "/usr/local/opt/jdtls/libexec",
+        "/usr/share/java/jdtls",
+        "/usr/local/share/jdtls",
+        &user_local,
+    ]
+    .iter()
+    .map(PathBuf::from)
+    .find(|p| p.exists())
+}
+
+fn find_java() -> PathBuf {
+    std::env::var("JAVA_HOME")
+        .ok()
+        .map(|home| PathBuf::from(home).join("bin/java"))
+        .filter(|java| java.exists())
+        .unwrap_or_else(|| PathBuf::from("java"))
+}
+
+fn find_launcher_jar(plugins_dir: &Path) -> Result<PathBuf, String> {
+    let entries = std::fs::read_dir(plugins_dir).map_err(|e| {
+        format!(
+            "JDT LS plugins directory not found: {} ({})",
+            plugins_dir.display(),
+            e
+        )
+    })?;
+    entries
+        .flatten()
+        .find(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with("org.eclipse.equinox.launcher_") && name.ends_with(".jar")
+        })
+        .map(|entry| entry.path())
+        .ok_or_else(|| "Equinox launcher JAR not found in JDT LS plugins directory".to_string())
+}
+
+fn find_config_dir(jdtls_home: &Path) -> Result<PathBuf, String> {
+    let os_config = if cfg!(target_os = "macos") {
+        "config_mac"
+    } else if cfg!(target_os = "windows") {
+        "config_win"
+    } else {
+        "config_linux"
+    };
+    [os_config, "config"]
+        .iter()
+        .map(|dir| jdtls_home.join(dir))
+        .find(|dir| dir.exists())
+        .ok_or_else(|| {
+            format!(
+                "JDT LS config directory not found (tried {} and config)",
+                os_config
+            )
+        })
+}
+
+/// Per-project JDT LS data directory. Kept apart from the IDE's: JDT LS
+/// locks its workspace, so the IDE and this server can't share one.
+fn workspace_data_dir(project_root: &str) -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
+    let mut hash: u64 = 5381;
+    for byte in project_root.bytes() {
+        hash = hash.wrapping_mul(33).wrapping_add(byte as u64);
+    }
+    PathBuf::from(home)
+        .join(".alloy-ide")
+        .join("mcp-jdtls-workspace")
+        .join(format!("{:x}", hash))
+}
@@ -0,0 +1,6 @@
+//! Client for the Eclipse JDT Language Server, backing the `code_*` tools.
+
+pub mod manager;
+pub mod transport;
+
+pub use manager::LspManager;
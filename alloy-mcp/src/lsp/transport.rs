@@ -0,0 +1,211 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{ChildStdin, ChildStdout};
+use tokio::sync::{mpsc, oneshot, Mutex};
+
+/// A JSON-RPC request/response ID
+pub type RequestId = i64;
+
+/// JSON-RPC message sent to the LSP server
+#[derive(Debug, Serialize)]
+struct LspRequest<'a> {
+    jsonrpc: &'static str,
+    id: RequestId,
+    method: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    params: Option<Value>,
+}
+
+/// JSON-RPC notification (no id, no response expected)
+#[derive(Debug, Serialize)]
+struct LspNotification<'a> {
+    jsonrpc: &'static str,
+    method: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    params: Option<Value>,
+}
+
+/// An incoming JSON-RPC message from the LSP server
+#[derive(Debug, Deserialize)]
+pub struct LspMessage {
+    #[serde(default)]
+    pub id: Option<Value>,
+    #[serde(default)]
+    pub method: Option<String>,
+    #[serde(default)]
+    pub result: Option<Value>,
+    #[serde(default)]
+    pub error: Option<Value>,
+    #[serde(default)]
+    pub params: Option<Value>,
+}
+
+impl LspMessage {
+    pub fn is_response(&self) -> bool {
+        self.id.is_some() && self.method.is_none()
+    }
+
+    pub fn is_request(&self) -> bool {
+        self.id.is_some() && self.method.is_some()
+    }
+}
+
+type PendingRequests = Arc<Mutex<HashMap<RequestId, oneshot::Sender<LspMessage>>>>;
+
+/// Bidirectional JSON-RPC communication with an LSP server over stdio.
+pub struct LspTransport {
+    writer: Mutex<ChildStdin>,
+    next_id: AtomicI64,
+    pending: PendingRequests,
+}
+
+impl LspTransport {
+    /// Create a new transport. Spawns a reader task that routes incoming messages.
+    /// Returns the transport and a receiver for server-initiated messages
+    /// (notifications and requests). The receiver closes when the server exits.
+    pub fn new(
+        stdin: ChildStdin,
+        stdout: ChildStdout,
+    ) -> (Self, mpsc::UnboundedReceiver<LspMessage>) {
+        let (incoming_tx, incoming_rx) = mpsc::unbounded_channel();
+        let pending: PendingRequests = Arc::new(Mutex::new(HashMap::new()));
+
+        let transport = Self {
+            writer: Mutex::new(stdin),
+            next_id: AtomicI64::new(1),
+            pending: pending.clone(),
+        };
+
+        tokio::spawn(Self::reader_loop(stdout, pending, incoming_tx));
+
+        (transport, incoming_rx)
+    }
+
+    /// Send a request and wait for the response.
+    pub async fn request(&self, method: &str, params: Option<Value>) -> Result<Value, String> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let req = LspRequest {
+            jsonrpc: "2.0",
+            id,
+            method,
+            params,
+        };
+
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().await.insert(id, tx);
+
+        if let Err(e) = self
+            .send_raw(&serde_json::to_string(&req).map_err(|e| e.to_string())?)
+            .await
+        {
+            self.pending.lock().await.remove(&id);
+            return Err(e);
+        }
+
+        let response = rx
+            .await
+            .map_err(|_| "Language server exited before responding".to_string())?;
+
+        match response.error {
+            Some(err) => Err(format!(
+                "LSP error: {}",
+                err.get("message")
+                    .and_then(|m| m.as_str())
+                    .map(String::from)
+                    .unwrap_or_else(|| err.to_string())
+            )),
+            None => Ok(response.result.unwrap_or(Value::Null)),
+        }
+    }
+
+    /// Send a notification (no response expected).
+    pub async fn notify(&self, method: &str, params: Option<Value>) -> Result<(), String> {
+        let notif = LspNotification {
+            jsonrpc: "2.0",
+            method,
+            params,
+        };
+        self.send_raw(&serde_json::to_string(&notif).map_err(|e| e.to_string())?)
+            .await
+    }
+
+    /// Answer a request the server sent us.
+    pub async fn respond(&self, id: Value, result: Value) -> Result<(), String> {
+        let msg = serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": result });
+        self.send_raw(&msg.to_string()).await
+    }
+
+    /// Send a raw JSON-RPC message with Content-Length header framing.
+    async fn send_raw(&self, json: &str) -> Result<(), String> {
+        let msg = format!("Content-Length: {}\r\n\r\n{}", json.len(), json);
+        let mut writer = self.writer.lock().await;
+        writer
+            .write_all(msg.as_bytes())
+            .await
+            .map_err(|e| format!("Failed to write to LSP: {}", e))?;
+        writer
+            .flush()
+            .await
+            .map_err(|e| format!("Failed to flush LSP: {}", e))
+    }
+
+    /// Read loop that parses incoming LSP messages and routes them.
+    async fn reader_loop(
+        stdout: ChildStdout,
+        pending: PendingRequests,
+        incoming_tx: mpsc::UnboundedSender<LspMessage>,
+    ) {
+        let mut reader = BufReader::new(stdout);
+
+        loop {
+            // Read headers until empty line
+            let mut content_length: Option<usize> = None;
+            loop {
+                let mut header_line = String::new();
+                match reader.read_line(&mut header_line).await {
+                    Ok(0) | Err(_) => {
+                        // EOF: fail everything still waiting on a response
+                        pending.lock().await.clear();
+                        return;
+                    }
+                    Ok(_) => {}
+                }
+                let trimmed = header_line.trim();
+                if trimmed.is_empty() {
+                    break;
+                }
+                if let Some(val) = trimmed.strip_prefix("Content-Length: ") {
+                    content_length = val.parse().ok();
+                }
+            }
+
+            let Some(length) = content_length else {
+                continue;
+            };
+
+            let mut body = vec![0u8; length];
+            if reader.read_exact(&mut body).await.is_err() {
+                pending.lock().await.clear();
+                return;
+            }
+
+            let Ok(msg) = serde_json::from_slice::<LspMessage>(&body) else {
+                continue;
+            };
+
+            if msg.is_response() {
+                if let Some(id) = msg.id.as_ref().and_then(|id| id.as_i64()) {
+                    if let Some(tx) = pending.lock().await.remove(&id) {
+                        let _ = tx.send(msg);
+                    }
+                }
+            } else {
+                let _ = incoming_tx.send(msg);
+            }
+        }
+    }
+}
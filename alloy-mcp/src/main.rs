@@ -1,8 +1,10 @@
+mod lsp;
 mod server;
 mod state;
 mod tools;
 mod types;
 
+use alloy_common::http;
 use clap::Parser;
 use std::sync::Arc;
 
@@ -14,16 +16,81 @@ struct Args {
     /// Path to the Alloy project directory
     #[arg(long)]
     project: Option<String>,
+
+    /// Validate every .block.json under --project, print the issues, and exit
+    /// non-zero if any block has errors. Does not start the MCP server.
+    #[arg(long, requires = "project")]
+    validate_all_blocks: bool,
+
+    /// Serve MCP over Streamable HTTP on ADDR instead of stdio. Each client
+    /// session gets its own project state, starting at --project if given.
+    #[arg(long, value_name = "ADDR", num_args = 0..=1, default_missing_value = http::DEFAULT_ADDR)]
+    http: Option<std::net::SocketAddr>,
+
+    /// Default page size for fs_search
+    #[arg(long, value_name = "N", default_value_t = tools::filesystem::DEFAULT_MAX_SEARCH_RESULTS)]
+    max_search_results: usize,
+
+    /// How deep fs_search and fs_replace walk, and the most fs_list_directory
+    /// descends
+    #[arg(long, value_name = "N", default_value_t = tools::filesystem::DEFAULT_MAX_WALK_DEPTH)]
+    max_walk_depth: usize,
+
+    /// Refuse whole-file fs_read_file reads above this size; ranged reads
+    /// still work. Unlimited when not given.
+    #[arg(long, value_name = "BYTES")]
+    max_file_bytes: Option<u64>,
+
+    /// Extra regex masked in terminal and build output, on top of the
+    /// built-in secret patterns. Repeat for several; a `secret` group masks
+    /// only that part of the match.
+    #[arg(long, value_name = "REGEX")]
+    redact_pattern: Vec<String>,
 }
 
 #[tokio::main]
 async fn main() {
     let args = Args::parse();
+    let fs_limits = tools::filesystem::FsLimits {
+        max_search_results: args.max_search_results.max(1),
+        max_walk_depth: args.max_walk_depth.max(1),
+        max_file_bytes: args.max_file_bytes,
+    };
+    let redactor = match alloy_common::redact::Redactor::new(&args.redact_pattern) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+
+    if args.validate_all_blocks {
+        let project = args.project.as_deref().unwrap_or(".");
+        std::process::exit(tools::block::validate_all_blocks_cli(project));
+    }
+
+    if let Some(addr) = args.http {
+        let project = args.project.clone();
+        let served = http::run_http_server(addr, move || {
+            server::AlloyMcpServer::new(Arc::new(
+                state::ProjectState::for_session(project.as_deref())
+                    .with_fs_limits(fs_limits)
+                    .with_redactor(redactor.clone()),
+            ))
+        })
+        .await;
+        if let Err(e) = served {
+            eprintln!("Failed to serve MCP over HTTP on {}: {}", addr, e);
+            std::process::exit(1);
+        }
+        return;
+    }
 
     let state = match args.project {
-        Some(ref path) => Arc::new(state::ProjectState::with_project(path)),
-        None => Arc::new(state::ProjectState::new()),
+        Some(ref path) => state::ProjectState::with_project(path),
+        None => state::ProjectState::new(),
     };
+    let state = Arc::new(state.with_fs_limits(fs_limits).with_redactor(redactor));
 
     server::run_server(state).await;
 }
@@ -2,8 +2,9 @@ use std::borrow::Cow;
 use std::sync::Arc;
 use rmcp::model::{
     CallToolRequestParams, CallToolResult, Content, Implementation,
-    ListResourcesResult, ListToolsResult, PaginatedRequestParams, ReadResourceRequestParams,
-    ReadResourceResult, RawResource, ResourceContents, ServerCapabilities, ServerInfo, Tool,
+    ListResourcesResult, ListToolsResult, PaginatedRequestParams, ProgressNotificationParam,
+    ReadResourceRequestParams, ReadResourceResult, RawResource, ResourceContents,
+    ServerCapabilities, ServerInfo, Tool,
 };
 use rmcp::model::AnnotateAble;
 use rmcp::service::RequestContext;
@@ -12,6 +13,7 @@ use rmcp::{ErrorData as McpError, RoleServer, ServerHandler, ServiceExt};
 use crate::state::ProjectState;
 use crate::tools::ToolRegistry;
 use crate::tools::resources;
+use crate::types::ProgressSink;
 
 /// Standalone MCP Server that exposes all Alloy IDE tools to Claude.
 #[derive(Clone)]
@@ -81,7 +83,7 @@ impl ServerHandler for AlloyMcpServer {
     fn call_tool(
         &self,
         request: CallToolRequestParams,
-        _context: RequestContext<RoleServer>,
+        context: RequestContext<RoleServer>,
     ) -> impl std::future::Future<Output = Result<CallToolResult, McpError>> + Send + '_ {
         async move {
             let name = request.name.as_ref();
@@ -90,18 +92,53 @@ impl ServerHandler for AlloyMcpServer {
                 None => serde_json::Value::Null,
             };
 
-            let result = ToolRegistry::execute(name, params, &self.state).await;
+            // Forward partial results as progress notifications when the client asked for them
+            let (progress, forwarder) = match context.meta.get_progress_token() {
+                Some(token) => {
+                    let (sink, mut updates) = ProgressSink::channel();
+                    let peer = context.peer.clone();
+                    let forwarder = tokio::spawn(async move {
+                        while let Some((progress, update)) = updates.recv().await {
+                            let _ = peer
+                                .notify_progress(ProgressNotificationParam {
+                                    progress_token: token.clone(),
+                                    progress,
+                                    total: None,
+                                    message: Some(update.to_string()),
+                                })
+                                .await;
+                        }
+                    });
+                    (Some(sink), Some(forwarder))
+                }
+                None => (None, None),
+            };
+
+            let result = ToolRegistry::execute(name, params, &self.state, progress.as_ref()).await;
+
+            // Let every notification go out before the result does
+            drop(progress);
+            if let Some(forwarder) = forwarder {
+                let _ = forwarder.await;
+            }
 
             let content: Vec<Content> = result
                 .content
                 .into_iter()
                 .map(|block| match block {
                     crate::types::ContentBlock::Text { text } => Content::text(text),
+                    crate::types::ContentBlock::Image { source } => {
+                        Content::image(source.data, source.media_type)
+                    }
                 })
                 .collect();
 
             if result.is_error {
-                Ok(CallToolResult::error(content))
+                let mut error = CallToolResult::error(content);
+                error.structured_content = result
+                    .error_code
+                    .map(|code| serde_json::json!({ "error_code": code }));
+                Ok(error)
             } else {
                 Ok(CallToolResult::success(content))
             }
@@ -1,5 +1,9 @@
+use crate::lsp::LspManager;
+use crate::tools::filesystem::{FsLimits, FsWatch};
+use alloy_common::redact::Redactor;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -8,6 +12,11 @@ pub struct ProjectInfo {
     pub path: String,
     pub project_type: ProjectType,
     pub environment: Option<String>,
+    /// "alloy", "fabric", "quilt", "forge" or "neoforge" for mods
+    #[serde(default)]
+    pub loader: Option<String>,
+    #[serde(default)]
+    pub loader_version: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -35,6 +44,78 @@ pub(crate) fn load_from_shared_file() -> Option<ProjectInfo> {
     }
 }
 
+/// A mod loader recognised from a project's metadata or build script.
+pub(crate) struct DetectedLoader {
+    pub loader: &'static str,
+    pub version: Option<String>,
+    pub environment: Option<String>,
+}
+
+/// Identify Fabric, Quilt, Forge and NeoForge projects that have no Alloy
+/// manifest. Loader metadata under `src/main/resources` wins over the loader
+/// plugin applied in the build script.
+pub(crate) fn detect_loader(project_dir: &Path) -> Option<DetectedLoader> {
+    let resources = project_dir.join("src/main/resources");
+    let build_script = ["build.gradle", "build.gradle.kts"]
+        .iter()
+        .find_map(|f| std::fs::read_to_string(project_dir.join(f)).ok())
+        .unwrap_or_default();
+
+    let read_json = |file: &str| {
+        std::fs::read_to_string(resources.join(file))
+            .ok()
+            .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+    };
+    let environment = |value: Option<&serde_json::Value>| {
+        value
+            .and_then(|e| e.as_str())
+            .map(|e| if e == "*" { "both" } else { e }.to_string())
+    };
+
+    let (loader, environment) = if let Some(quilt) = read_json("quilt.mod.json") {
+        ("quilt", environment(quilt.pointer("/minecraft/environment")))
+    } else if let Some(fabric) = read_json("fabric.mod.json") {
+        ("fabric", environment(fabric.get("environment")))
+    } else if resources.join("META-INF/neoforge.mods.toml").exists() {
+        ("neoforge", None)
+    } else if resources.join("META-INF/mods.toml").exists() {
+        // NeoForge for 1.20.1 still used the Forge file name
+        let loader = if build_script.contains("net.neoforged") { "neoforge" } else { "forge" };
+        (loader, None)
+    } else if build_script.contains("org.quiltmc.loom") {
+        ("quilt", None)
+    } else if build_script.contains("fabric-loom") {
+        ("fabric", None)
+    } else if build_script.contains("net.neoforged") {
+        ("neoforge", None)
+    } else if build_script.contains("net.minecraftforge.gradle") {
+        ("forge", None)
+    } else {
+        return None;
+    };
+
+    // Loader version as the usual templates name it in gradle.properties
+    let version_key = match loader {
+        "forge" => "forge_version",
+        "neoforge" => "neo_version",
+        _ => "loader_version",
+    };
+    let version = std::fs::read_to_string(project_dir.join("gradle.properties"))
+        .ok()
+        .and_then(|props| {
+            props.lines().find_map(|line| {
+                let (key, value) = line.split_once('=')?;
+                (key.trim() == version_key).then(|| value.trim().to_string())
+            })
+        });
+
+    Some(DetectedLoader {
+        loader,
+        version,
+        environment,
+    })
+}
+
 /// Write project info to ~/.alloy-ide/current-project.json for bidirectional sync.
 pub(crate) fn write_current_project_file(info: &ProjectInfo) {
     let Some(home) = std::env::var_os("HOME")
@@ -51,26 +132,94 @@ pub(crate) fn write_current_project_file(info: &ProjectInfo) {
     }
 }
 
+/// Commands kept per terminal session by `terminal_execute`.
+const TERMINAL_HISTORY_LIMIT: usize = 50;
+
+/// Terminal session used when a tool call gives no `session_id`.
+pub const DEFAULT_TERMINAL_SESSION: &str = "default";
+
 pub struct ProjectState {
+    /// The project tools fall back to when no `project_path` is given.
     pub current_project: Mutex<Option<ProjectInfo>>,
-    pub terminal_output: Mutex<Vec<String>>,
+    /// Every open project, keyed by path. Always includes the current one.
+    pub open_projects: Mutex<BTreeMap<String, ProjectInfo>>,
+    /// `terminal_execute` history, keyed by terminal session id.
+    pub terminal_output: Mutex<HashMap<String, Vec<String>>>,
     pub build_errors: Mutex<Vec<String>>,
+    /// Active fs_watch watchers, keyed by watcher id.
+    pub watchers: Mutex<HashMap<String, FsWatch>>,
+    /// Java language server behind the `code_*` tools, started on first use.
+    pub lsp: LspManager,
+    /// Follow and update the IDE's ~/.alloy-ide/current-project.json. Off for
+    /// HTTP sessions so each client keeps its own project.
+    pub sync_with_ide: bool,
+    /// Result, depth and file-size limits for the fs_* tools.
+    pub fs_limits: FsLimits,
+    /// Masks secrets in terminal and build output.
+    pub redactor: Redactor,
 }
 
 impl ProjectState {
     pub fn new() -> Self {
         // Try to load from the shared IDE file as a starting point
         let initial = load_from_shared_file();
+        let open = initial
+            .iter()
+            .map(|info| (normalize_path(&info.path).to_string(), info.clone()))
+            .collect();
         Self {
             current_project: Mutex::new(initial),
-            terminal_output: Mutex::new(Vec::new()),
+            open_projects: Mutex::new(open),
+            terminal_output: Mutex::new(HashMap::new()),
+            build_errors: Mutex::new(Vec::new()),
+            watchers: Mutex::new(HashMap::new()),
+            lsp: LspManager::new(),
+            sync_with_ide: true,
+            fs_limits: FsLimits::default(),
+            redactor: Redactor::default(),
+        }
+    }
+
+    /// State for one HTTP client: starts at `project_path` (if given) and never
+    /// reads or writes the IDE's shared project file.
+    pub fn for_session(project_path: Option<&str>) -> Self {
+        let state = Self {
+            current_project: Mutex::new(None),
+            open_projects: Mutex::new(BTreeMap::new()),
+            terminal_output: Mutex::new(HashMap::new()),
             build_errors: Mutex::new(Vec::new()),
+            watchers: Mutex::new(HashMap::new()),
+            lsp: LspManager::new(),
+            sync_with_ide: false,
+            fs_limits: FsLimits::default(),
+            redactor: Redactor::default(),
+        };
+        if let Some(path) = project_path {
+            state.open_manifest_project(path);
         }
+        state
     }
 
     pub fn with_project(project_path: &str) -> Self {
         let state = Self::new();
+        state.open_manifest_project(project_path);
+        state
+    }
+
+    /// Replace the default fs tool limits.
+    pub fn with_fs_limits(mut self, limits: FsLimits) -> Self {
+        self.fs_limits = limits;
+        self
+    }
+
+    /// Replace the built-in-only secret redaction.
+    pub fn with_redactor(mut self, redactor: Redactor) -> Self {
+        self.redactor = redactor;
+        self
+    }
 
+    /// Make `project_path` the current project if it has an Alloy manifest.
+    fn open_manifest_project(&self, project_path: &str) {
         let path = std::path::Path::new(project_path);
         if path.exists() && path.is_dir() {
             let mod_json = path.join("alloy.mod.json");
@@ -85,7 +234,7 @@ impl ProjectState {
                     .unwrap_or("Unknown")
                     .to_string();
 
-                let (project_type, environment) = if mod_json.exists() {
+                let (project_type, environment, loader) = if mod_json.exists() {
                     let env = std::fs::read_to_string(&mod_json)
                         .ok()
                         .and_then(|content| {
@@ -97,9 +246,9 @@ impl ProjectState {
                                         .map(String::from)
                                 })
                         });
-                    (ProjectType::Mod, env)
+                    (ProjectType::Mod, env, Some("alloy".to_string()))
                 } else {
-                    (ProjectType::Modpack, None)
+                    (ProjectType::Modpack, None, None)
                 };
 
                 let info = ProjectInfo {
@@ -107,14 +256,67 @@ impl ProjectState {
                     path: project_path.to_string(),
                     project_type,
                     environment,
+                    loader,
+                    loader_version: None,
                 };
 
-                *state.current_project.lock().unwrap() = Some(info);
+                self.set_current(info);
             }
             // If no manifest found, keep whatever new() loaded from the shared file
         }
+    }
 
-        state
+    /// Append a command's output to a terminal session's history, keeping
+    /// only the most recent `TERMINAL_HISTORY_LIMIT` entries.
+    pub fn record_terminal_output(&self, session_id: &str, entry: String) {
+        if let Ok(mut sessions) = self.terminal_output.lock() {
+            let history = sessions.entry(session_id.to_string()).or_default();
+            history.push(entry);
+            if history.len() > TERMINAL_HISTORY_LIMIT {
+                let drain_to = history.len() - TERMINAL_HISTORY_LIMIT;
+                history.drain(..drain_to);
+            }
+        }
+    }
+
+    /// Open `info` (or refresh it if already open) and make it the current project.
+    pub fn set_current(&self, info: ProjectInfo) {
+        if let Ok(mut open) = self.open_projects.lock() {
+            open.insert(normalize_path(&info.path).to_string(), info.clone());
+        }
+        if let Ok(mut current) = self.current_project.lock() {
+            *current = Some(info);
+        }
+    }
+
+    /// Close the project at `path`. If it was current, another open project
+    /// (if any) becomes current. Returns the closed project.
+    pub fn close_project(&self, path: &str) -> Option<ProjectInfo> {
+        let mut open = self.open_projects.lock().ok()?;
+        let closed = open.remove(normalize_path(path))?;
+        if let Ok(mut current) = self.current_project.lock() {
+            if current.as_ref().is_some_and(|c| c.path == closed.path) {
+                *current = open.values().next().cloned();
+            }
+        }
+        Some(closed)
+    }
+
+    /// All open projects, ordered by path.
+    pub fn open_projects(&self) -> Vec<ProjectInfo> {
+        self.open_projects
+            .lock()
+            .map(|open| open.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// The project a tool call targets: its `project_path` param if given,
+    /// otherwise the current project.
+    pub fn resolve_project_path(&self, params: &serde_json::Value) -> Result<String, String> {
+        match params.get("project_path").and_then(|v| v.as_str()) {
+            Some(p) if !p.is_empty() => Ok(p.to_string()),
+            _ => self.project_path(),
+        }
     }
 
     /// Get the current project path, or an error message.
@@ -126,3 +328,13 @@ impl ProjectState {
         }
     }
 }
+
+/// Strip trailing separators so `/a/b/` and `/a/b` name the same open project.
+fn normalize_path(path: &str) -> &str {
+    let trimmed = path.trim_end_matches(['/', '\\']);
+    if trimmed.is_empty() {
+        path
+    } else {
+        trimmed
+    }
+}
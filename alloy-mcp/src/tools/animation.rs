@@ -8,14 +8,25 @@ struct Keyframe {
     tick: u32,
     value: f64,
     easing: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bezier_handles: Option<[f64; 4]>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct AnimationTrack {
     property: String,
+    /// Bone (model element) the track animates; empty for the whole model
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    target_element: Option<String>,
+    /// "linear" (default) or "catmull_rom"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    interpolation: Option<String>,
     keyframes: Vec<Keyframe>,
 }
 
+const INTERPOLATIONS: &[&str] = &["linear", "catmull_rom"];
+const EASINGS: &[&str] = &["linear", "ease-in", "ease-out", "ease-in-out", "cubic-bezier"];
+
 #[derive(Debug, Serialize, Deserialize)]
 struct AnimationProject {
     name: String,
@@ -58,8 +69,10 @@ pub fn definitions() -> Vec<ToolDefinition> {
                 "type": "object",
                 "properties": {
                     "path": { "type": "string", "description": "Path to the .anim.json file" },
-                    "action": { "type": "string", "enum": ["add_track", "remove_track", "add_keyframe", "remove_keyframe", "set_duration", "set_looping"], "description": "Action to perform" },
+                    "action": { "type": "string", "enum": ["add_track", "remove_track", "add_keyframe", "remove_keyframe", "set_duration", "set_looping", "set_interpolation"], "description": "Action to perform" },
                     "property": { "type": "string", "description": "Track property name (e.g. 'uv_offset', 'opacity', 'color', 'scale', 'rotation')" },
+                    "target_element": { "type": "string", "description": "Bone the track animates (optional). When given, only the track for this bone is matched." },
+                    "interpolation": { "type": "string", "enum": ["linear", "catmull_rom"], "description": "How the track blends between keyframes (for add_track/set_interpolation, default: linear)" },
                     "keyframe": { "type": "object", "description": "Keyframe with tick, value, easing" },
                     "tick": { "type": "integer", "description": "Tick to remove keyframe at (for remove_keyframe)" },
                     "duration_ticks": { "type": "integer", "description": "New duration (for set_duration)" },
@@ -81,6 +94,37 @@ pub fn definitions() -> Vec<ToolDefinition> {
                 "required": ["path", "project_path", "mod_id"]
             }),
         },
+        ToolDefinition {
+            name: "anim_validate".into(),
+            description: "Validate a .anim.json: keyframe ticks strictly increase within each track, \
+                ticks fall inside the duration, values are plausible for their property (rotation, \
+                scale, translation, opacity, color), interpolation and easing names are known, and \
+                every target bone exists in the model when model_path is given."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string", "description": "Path to the .anim.json file" },
+                    "model_path": { "type": "string", "description": "Model to check target bones against: Bedrock .geo.json, Blockbench .bbmodel, or a Java model JSON with named elements (optional)" }
+                },
+                "required": ["path"]
+            }),
+        },
+        ToolDefinition {
+            name: "anim_sample".into(),
+            description: "Evaluate an animation at time t and return the interpolated value of every \
+                track, grouped by bone, so a preview can be drawn without running the game. Looping \
+                animations wrap t; others clamp it to the duration."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string", "description": "Path to the .anim.json file" },
+                    "t": { "type": "number", "description": "Time in ticks (fractional ticks allowed)" }
+                },
+                "required": ["path", "t"]
+            }),
+        },
     ]
 }
 
@@ -90,6 +134,8 @@ pub async fn execute(name: &str, params: Value) -> ToolResult {
         "anim_read" => anim_read(params).await,
         "anim_update" => anim_update(params).await,
         "anim_generate_code" => anim_generate_code(params).await,
+        "anim_validate" => anim_validate(params).await,
+        "anim_sample" => anim_sample(params).await,
         _ => ToolResult::error(format!("Unknown animation tool: {}", name)),
     }
 }
@@ -97,11 +143,11 @@ pub async fn execute(name: &str, params: Value) -> ToolResult {
 async fn anim_create(params: Value) -> ToolResult {
     let path = match params.get("path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: path"),
+        None => return ToolResult::missing_param("path"),
     };
     let name = match params.get("name").and_then(|v| v.as_str()) {
         Some(n) => n,
-        None => return ToolResult::error("Missing required parameter: name"),
+        None => return ToolResult::missing_param("name"),
     };
     let duration = params.get("duration_ticks").and_then(|v| v.as_u64()).unwrap_or(20) as u32;
     let looping = params.get("looping").and_then(|v| v.as_bool()).unwrap_or(true);
@@ -134,7 +180,7 @@ async fn anim_create(params: Value) -> ToolResult {
 async fn anim_read(params: Value) -> ToolResult {
     let path = match params.get("path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: path"),
+        None => return ToolResult::missing_param("path"),
     };
 
     let content = match std::fs::read_to_string(path) {
@@ -151,11 +197,11 @@ async fn anim_read(params: Value) -> ToolResult {
 async fn anim_update(params: Value) -> ToolResult {
     let path = match params.get("path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: path"),
+        None => return ToolResult::missing_param("path"),
     };
     let action = match params.get("action").and_then(|v| v.as_str()) {
         Some(a) => a,
-        None => return ToolResult::error("Missing required parameter: action"),
+        None => return ToolResult::missing_param("action"),
     };
 
     let content = match std::fs::read_to_string(path) {
@@ -174,11 +220,22 @@ async fn anim_update(params: Value) -> ToolResult {
                 Some(p) => p,
                 None => return ToolResult::error("Missing 'property' for add_track"),
             };
-            if anim.tracks.iter().any(|t| t.property == property) {
+            let target = params.get("target_element").and_then(|v| v.as_str());
+            let interpolation = match parse_interpolation(&params) {
+                Ok(i) => i,
+                Err(e) => return ToolResult::error(e),
+            };
+            if anim
+                .tracks
+                .iter()
+                .any(|t| t.property == property && t.target_element.as_deref() == target)
+            {
                 return ToolResult::error(format!("Track '{}' already exists", property));
             }
             anim.tracks.push(AnimationTrack {
                 property: property.to_string(),
+                target_element: target.map(String::from),
+                interpolation,
                 keyframes: Vec::new(),
             });
         }
@@ -187,8 +244,9 @@ async fn anim_update(params: Value) -> ToolResult {
                 Some(p) => p,
                 None => return ToolResult::error("Missing 'property' for remove_track"),
             };
+            let target = params.get("target_element").and_then(|v| v.as_str());
             let before = anim.tracks.len();
-            anim.tracks.retain(|t| t.property != property);
+            anim.tracks.retain(|t| !track_matches(t, property, target));
             if anim.tracks.len() == before {
                 return ToolResult::error(format!("Track '{}' not found", property));
             }
@@ -207,7 +265,8 @@ async fn anim_update(params: Value) -> ToolResult {
                 Err(e) => return ToolResult::error(format!("Invalid keyframe: {}", e)),
             };
 
-            let track = match anim.tracks.iter_mut().find(|t| t.property == property) {
+            let target = params.get("target_element").and_then(|v| v.as_str());
+            let track = match anim.tracks.iter_mut().find(|t| track_matches(t, property, target)) {
                 Some(t) => t,
                 None => return ToolResult::error(format!("Track '{}' not found. Add it first.", property)),
             };
@@ -224,7 +283,8 @@ async fn anim_update(params: Value) -> ToolResult {
                 None => return ToolResult::error("Missing 'tick' for remove_keyframe"),
             };
 
-            let track = match anim.tracks.iter_mut().find(|t| t.property == property) {
+            let target = params.get("target_element").and_then(|v| v.as_str());
+            let track = match anim.tracks.iter_mut().find(|t| track_matches(t, property, target)) {
                 Some(t) => t,
                 None => return ToolResult::error(format!("Track '{}' not found", property)),
             };
@@ -244,6 +304,26 @@ async fn anim_update(params: Value) -> ToolResult {
             };
             anim.looping = looping;
         }
+        "set_interpolation" => {
+            let property = match params.get("property").and_then(|v| v.as_str()) {
+                Some(p) => p,
+                None => return ToolResult::error("Missing 'property' for set_interpolation"),
+            };
+            let interpolation = match parse_interpolation(&params) {
+                Ok(Some(i)) => i,
+                Ok(None) => return ToolResult::error("Missing 'interpolation' for set_interpolation"),
+                Err(e) => return ToolResult::error(e),
+            };
+            let target = params.get("target_element").and_then(|v| v.as_str());
+            let mut found = false;
+            for track in anim.tracks.iter_mut().filter(|t| track_matches(t, property, target)) {
+                track.interpolation = Some(interpolation.clone());
+                found = true;
+            }
+            if !found {
+                return ToolResult::error(format!("Track '{}' not found", property));
+            }
+        }
         _ => return ToolResult::error(format!("Unknown action: {}", action)),
     }
 
@@ -260,6 +340,28 @@ async fn anim_update(params: Value) -> ToolResult {
     }
 }
 
+/// Whether a track is the one an anim_update call refers to. Without a
+/// target_element every track for the property matches.
+fn track_matches(track: &AnimationTrack, property: &str, target: Option<&str>) -> bool {
+    track.property == property && (target.is_none() || track.target_element.as_deref() == target)
+}
+
+/// The optional `interpolation` param, normalised ("catmull-rom" is accepted).
+fn parse_interpolation(params: &Value) -> Result<Option<String>, String> {
+    let Some(mode) = params.get("interpolation").and_then(|v| v.as_str()) else {
+        return Ok(None);
+    };
+    let mode = mode.replace('-', "_");
+    if !INTERPOLATIONS.contains(&mode.as_str()) {
+        return Err(format!(
+            "Unknown interpolation '{}'. Expected one of: {}",
+            mode,
+            INTERPOLATIONS.join(", ")
+        ));
+    }
+    Ok(Some(mode))
+}
+
 fn to_pascal_case(s: &str) -> String {
     s.split(|c: char| c == '-' || c == '_' || c == ' ')
         .filter(|w| !w.is_empty())
@@ -276,15 +378,15 @@ fn to_pascal_case(s: &str) -> String {
 async fn anim_generate_code(params: Value) -> ToolResult {
     let path = match params.get("path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: path"),
+        None => return ToolResult::missing_param("path"),
     };
     let project_path = match params.get("project_path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: project_path"),
+        None => return ToolResult::missing_param("project_path"),
     };
     let mod_id = match params.get("mod_id").and_then(|v| v.as_str()) {
         Some(m) => m,
-        None => return ToolResult::error("Missing required parameter: mod_id"),
+        None => return ToolResult::missing_param("mod_id"),
     };
 
     let content = match std::fs::read_to_string(path) {
@@ -373,3 +475,360 @@ public class {class_name} extends Animation {{
         "track_count": anim.tracks.len(),
     }))
 }
+
+// ---------------------------------------------------------------------------
+// Interpolation
+// ---------------------------------------------------------------------------
+
+/// Apply a keyframe's easing to segment progress `t` (0..1). Mirrors the
+/// Animation Editor preview so sampled values match what the IDE shows.
+fn apply_easing(t: f64, easing: &str, handles: Option<[f64; 4]>) -> f64 {
+    match easing.replace('_', "-").as_str() {
+        "ease-in" => t * t,
+        "ease-out" => 1.0 - (1.0 - t) * (1.0 - t),
+        "ease-in-out" => {
+            if t < 0.5 {
+                2.0 * t * t
+            } else {
+                1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+            }
+        }
+        "cubic-bezier" => {
+            let [x1, y1, x2, y2] = handles.unwrap_or([0.42, 0.0, 0.58, 1.0]);
+            let cb = |u: f64, p1: f64, p2: f64| {
+                3.0 * (1.0 - u) * (1.0 - u) * u * p1 + 3.0 * (1.0 - u) * u * u * p2 + u * u * u
+            };
+            // Newton-Raphson for the curve parameter whose x is t
+            let mut u = t;
+            for _ in 0..8 {
+                let bx = cb(u, x1, x2) - t;
+                if bx.abs() < 1e-6 {
+                    break;
+                }
+                let dx = 3.0 * (1.0 - u) * (1.0 - u) * x1
+                    + 6.0 * (1.0 - u) * u * (x2 - x1)
+                    + 3.0 * u * u * (1.0 - x2);
+                if dx.abs() < 1e-6 {
+                    break;
+                }
+                u -= bx / dx;
+            }
+            cb(u.clamp(0.0, 1.0), y1, y2)
+        }
+        _ => t,
+    }
+}
+
+/// Uniform Catmull-Rom spline through p1..p2, using p0 and p3 for tangents.
+fn catmull_rom(p0: f64, p1: f64, p2: f64, p3: f64, t: f64) -> f64 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    0.5 * (2.0 * p1
+        + (p2 - p0) * t
+        + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+        + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3)
+}
+
+/// A track's value at `tick`. Before the first and after the last keyframe
+/// the end values hold. Catmull-Rom tracks ignore per-keyframe easing.
+fn sample_track(track: &AnimationTrack, tick: f64) -> Option<f64> {
+    let mut kfs: Vec<&Keyframe> = track.keyframes.iter().collect();
+    kfs.sort_by_key(|k| k.tick);
+    let first = kfs.first()?;
+    let last = kfs[kfs.len() - 1];
+    if tick <= first.tick as f64 {
+        return Some(first.value);
+    }
+    if tick >= last.tick as f64 {
+        return Some(last.value);
+    }
+
+    let i = kfs.windows(2).position(|w| tick < w[1].tick as f64)?;
+    let (a, b) = (kfs[i], kfs[i + 1]);
+    let span = (b.tick - a.tick) as f64;
+    let t = if span > 0.0 { (tick - a.tick as f64) / span } else { 1.0 };
+
+    if track.interpolation.as_deref() == Some("catmull_rom") {
+        let before = if i > 0 { kfs[i - 1].value } else { a.value };
+        let after = kfs.get(i + 2).map(|k| k.value).unwrap_or(b.value);
+        return Some(catmull_rom(before, a.value, b.value, after, t));
+    }
+    let eased = apply_easing(t, &a.easing, a.bezier_handles);
+    Some(a.value + (b.value - a.value) * eased)
+}
+
+fn read_animation(path: &str) -> Result<AnimationProject, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse animation JSON: {}", e))
+}
+
+// ---------------------------------------------------------------------------
+// anim_validate
+// ---------------------------------------------------------------------------
+
+/// Plausible value range for a property, with what the unit means. `None`
+/// for properties with no natural bounds.
+fn plausible_range(property: &str) -> Option<(f64, f64, &'static str)> {
+    let p = property.to_lowercase();
+    if p.starts_with("rotation") {
+        // The editor stores rotation in full turns
+        Some((-10.0, 10.0, "Rotation is in full turns (1.0 = 360°)"))
+    } else if p.starts_with("scale") {
+        Some((0.0, 16.0, "Scale 1.0 is the original size; 0 or below collapses or mirrors the bone"))
+    } else if p.starts_with("translation") || p.starts_with("position") {
+        Some((-256.0, 256.0, "Translation is in model pixels (16 per block)"))
+    } else if p.starts_with("opacity") || p.starts_with("color") {
+        Some((0.0, 1.0, "Opacity and color channels range from 0.0 to 1.0"))
+    } else if p.starts_with("uv_offset") {
+        Some((-1.0, 1.0, "UV offsets are a fraction of the texture size"))
+    } else if p.starts_with("sprite_frame") {
+        Some((0.0, f64::MAX, "Sprite frames are indices starting at 0"))
+    } else {
+        None
+    }
+}
+
+/// Bone names in a model file: Bedrock geometry bones, Blockbench groups, or
+/// named elements of a Java model.
+fn model_bones(model: &Value) -> Vec<String> {
+    fn outliner_names(node: &Value, names: &mut Vec<String>) {
+        if let Some(name) = node.get("name").and_then(|n| n.as_str()) {
+            names.push(name.to_string());
+        }
+        for child in node.get("children").and_then(|c| c.as_array()).into_iter().flatten() {
+            outliner_names(child, names);
+        }
+    }
+
+    let mut names = Vec::new();
+    for geometry in model.get("minecraft:geometry").and_then(|g| g.as_array()).into_iter().flatten() {
+        for bone in geometry.get("bones").and_then(|b| b.as_array()).into_iter().flatten() {
+            if let Some(name) = bone.get("name").and_then(|n| n.as_str()) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    for group in model
+        .get("outliner")
+        .and_then(|o| o.as_array())
+        .into_iter()
+        .flatten()
+        .chain(model.get("groups").and_then(|g| g.as_array()).into_iter().flatten())
+    {
+        outliner_names(group, &mut names);
+    }
+    for element in model.get("elements").and_then(|e| e.as_array()).into_iter().flatten() {
+        if let Some(name) = element.get("name").and_then(|n| n.as_str()) {
+            names.push(name.to_string());
+        }
+    }
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn track_label(track: &AnimationTrack) -> String {
+    match track.target_element.as_deref().filter(|t| !t.is_empty()) {
+        Some(bone) => format!("{}.{}", bone, track.property),
+        None => track.property.clone(),
+    }
+}
+
+async fn anim_validate(params: Value) -> ToolResult {
+    let path = match params.get("path").and_then(|v| v.as_str()) {
+        Some(p) => p,
+        None => return ToolResult::missing_param("path"),
+    };
+    let anim = match read_animation(path) {
+        Ok(a) => a,
+        Err(e) => return ToolResult::error(e),
+    };
+
+    let bones = match params.get("model_path").and_then(|v| v.as_str()) {
+        Some(model_path) => {
+            let model = match std::fs::read_to_string(model_path)
+                .map_err(|e| e.to_string())
+                .and_then(|c| serde_json::from_str::<Value>(&c).map_err(|e| e.to_string()))
+            {
+                Ok(m) => m,
+                Err(e) => return ToolResult::error(format!("Failed to read model {}: {}", model_path, e)),
+            };
+            Some(model_bones(&model))
+        }
+        None => None,
+    };
+
+    let mut issues: Vec<Value> = Vec::new();
+
+    if anim.duration_ticks == 0 {
+        issues.push(json!({
+            "severity": "error",
+            "message": "Animation duration is 0 ticks.",
+            "suggestion": "Set duration_ticks (20 ticks = 1 second)."
+        }));
+    }
+
+    if let Some(bones) = &bones {
+        if bones.is_empty() {
+            issues.push(json!({
+                "severity": "warning",
+                "message": "The model has no named bones, so bone references cannot be checked.",
+                "suggestion": "Pass a .geo.json, .bbmodel, or a model whose elements have names."
+            }));
+        }
+    }
+
+    for track in &anim.tracks {
+        let label = track_label(track);
+
+        if let Some(mode) = &track.interpolation {
+            if !INTERPOLATIONS.contains(&mode.as_str()) {
+                issues.push(json!({
+                    "severity": "error",
+                    "message": format!("Track '{}' has unknown interpolation '{}'.", label, mode),
+                    "suggestion": format!("Use one of: {}.", INTERPOLATIONS.join(", "))
+                }));
+            }
+        }
+
+        if let (Some(bones), Some(bone)) = (&bones, track.target_element.as_deref().filter(|t| !t.is_empty())) {
+            if !bones.is_empty() && !bones.iter().any(|b| b == bone) {
+                issues.push(json!({
+                    "severity": "error",
+                    "message": format!("Track '{}' targets bone '{}', which the model does not have.", label, bone),
+                    "suggestion": format!("Use one of the model's bones: {}.", bones.join(", "))
+                }));
+            }
+        }
+
+        if track.keyframes.is_empty() {
+            issues.push(json!({
+                "severity": "warning",
+                "message": format!("Track '{}' has no keyframes.", label),
+                "suggestion": "Add keyframes or remove the track."
+            }));
+        }
+
+        // Keyframes are checked in file order: hand-edited files may be unsorted
+        for pair in track.keyframes.windows(2) {
+            if pair[1].tick <= pair[0].tick {
+                issues.push(json!({
+                    "severity": "error",
+                    "message": format!(
+                        "Track '{}': keyframe at tick {} follows tick {}. Keyframe ticks must strictly increase.",
+                        label, pair[1].tick, pair[0].tick
+                    ),
+                    "suggestion": if pair[1].tick == pair[0].tick {
+                        "Remove the duplicate keyframe or move it to another tick."
+                    } else {
+                        "Reorder the keyframes by tick."
+                    }
+                }));
+            }
+        }
+
+        let range = plausible_range(&track.property);
+        for kf in &track.keyframes {
+            if kf.tick > anim.duration_ticks {
+                issues.push(json!({
+                    "severity": "warning",
+                    "message": format!(
+                        "Track '{}': keyframe at tick {} is after the end of the animation ({} ticks).",
+                        label, kf.tick, anim.duration_ticks
+                    ),
+                    "suggestion": "Extend duration_ticks or move the keyframe earlier."
+                }));
+            }
+            if !kf.value.is_finite() {
+                issues.push(json!({
+                    "severity": "error",
+                    "message": format!("Track '{}': keyframe at tick {} has a non-finite value.", label, kf.tick),
+                    "suggestion": null
+                }));
+            } else if let Some((min, max, unit)) = range {
+                if kf.value < min || kf.value > max {
+                    issues.push(json!({
+                        "severity": "warning",
+                        "message": format!(
+                            "Track '{}': value {} at tick {} is outside the plausible range {} to {}.",
+                            label, kf.value, kf.tick, min, max
+                        ),
+                        "suggestion": unit
+                    }));
+                }
+            }
+            if !EASINGS.contains(&kf.easing.replace('_', "-").as_str()) {
+                issues.push(json!({
+                    "severity": "warning",
+                    "message": format!("Track '{}': unknown easing '{}' at tick {}; it plays as linear.", label, kf.easing, kf.tick),
+                    "suggestion": format!("Use one of: {}.", EASINGS.join(", "))
+                }));
+            }
+        }
+    }
+
+    let has_errors = issues
+        .iter()
+        .any(|i| i.get("severity").and_then(|s| s.as_str()) == Some("error"));
+
+    ToolResult::json(&json!({
+        "path": path,
+        "name": anim.name,
+        "track_count": anim.tracks.len(),
+        "bones": bones,
+        "issues": issues,
+        "issue_count": issues.len(),
+        "valid": !has_errors
+    }))
+}
+
+// ---------------------------------------------------------------------------
+// anim_sample
+// ---------------------------------------------------------------------------
+
+async fn anim_sample(params: Value) -> ToolResult {
+    let path = match params.get("path").and_then(|v| v.as_str()) {
+        Some(p) => p,
+        None => return ToolResult::missing_param("path"),
+    };
+    let t = match params.get("t").and_then(|v| v.as_f64()) {
+        Some(t) if t.is_finite() => t,
+        _ => return ToolResult::missing_param("t"),
+    };
+    let anim = match read_animation(path) {
+        Ok(a) => a,
+        Err(e) => return ToolResult::error(e),
+    };
+
+    let duration = anim.duration_ticks as f64;
+    let tick = if duration <= 0.0 {
+        0.0
+    } else if anim.looping {
+        t.rem_euclid(duration)
+    } else {
+        t.clamp(0.0, duration)
+    };
+
+    // Tracks without a target animate the whole model
+    let mut bones = serde_json::Map::new();
+    for track in &anim.tracks {
+        let Some(value) = sample_track(track, tick) else {
+            continue;
+        };
+        let bone = track
+            .target_element
+            .as_deref()
+            .filter(|b| !b.is_empty())
+            .unwrap_or("root");
+        let entry = bones.entry(bone.to_string()).or_insert_with(|| json!({}));
+        entry[&track.property] = json!(value);
+    }
+
+    ToolResult::json(&json!({
+        "path": path,
+        "t": t,
+        "tick": tick,
+        "looping": anim.looping,
+        "bones": bones
+    }))
+}
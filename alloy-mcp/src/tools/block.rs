@@ -1,8 +1,11 @@
 use crate::state::ProjectState;
-use crate::types::{ToolDefinition, ToolResult};
+use crate::types::{require_str, ErrorCode, ToolDefinition, ToolResult};
+use alloy_common::generated::{self, GeneratedManifest, GeneratedWriter};
+use alloy_common::mod_id::{is_valid_mod_id, sanitize_mod_id};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 // ---------------------------------------------------------------------------
 // Vanilla Minecraft block IDs for conflict checking (~900 entries, sorted
@@ -333,6 +336,55 @@ struct BlockProps {
     has_gravity: bool,
     flammable: bool,
     slipperiness: f64,
+    /// One of `SOUND_GROUPS`; absent means stone.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sound_group: Option<String>,
+    /// Outline and collision shape; absent means a full cube.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    shape: Option<BlockShape>,
+}
+
+/// Sound groups a block can use, matching the `BlockSoundGroup` constants.
+const SOUND_GROUPS: &[&str] = &[
+    "stone", "wood", "metal", "glass", "wool", "gravel", "sand", "grass", "snow",
+    "slime", "honey", "ladder", "anvil", "lantern", "chain", "copper", "deepslate",
+    "tuff", "calcite", "basalt", "netherrack", "nether_bricks", "ancient_debris",
+    "netherite_block", "bamboo_wood", "cherry_wood", "nether_wood", "moss", "mud",
+    "amethyst_block", "bone",
+];
+
+/// A block's bounds, either a named preset or an explicit box.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+enum BlockShape {
+    Preset(String),
+    /// `[x1, y1, z1, x2, y2, z2]` in pixels (0-16)
+    Box([f64; 6]),
+}
+
+const FULL_CUBE: [f64; 6] = [0.0, 0.0, 0.0, 16.0, 16.0, 16.0];
+
+/// Named shape presets, in pixels.
+const SHAPE_PRESETS: &[(&str, [f64; 6])] = &[
+    ("full", FULL_CUBE),
+    ("slab", [0.0, 0.0, 0.0, 16.0, 8.0, 16.0]),
+    ("carpet", [0.0, 0.0, 0.0, 16.0, 1.0, 16.0]),
+    ("pressure_plate", [1.0, 0.0, 1.0, 15.0, 1.0, 15.0]),
+    ("fence_post", [6.0, 0.0, 6.0, 10.0, 16.0, 10.0]),
+    ("pane_post", [7.0, 0.0, 7.0, 9.0, 16.0, 9.0]),
+];
+
+impl BlockShape {
+    /// The box in pixels, or `None` for an unknown preset.
+    fn bounds(&self) -> Option<[f64; 6]> {
+        match self {
+            BlockShape::Preset(name) => SHAPE_PRESETS
+                .iter()
+                .find(|(preset, _)| preset == name)
+                .map(|(_, bounds)| *bounds),
+            BlockShape::Box(bounds) => Some(*bounds),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -414,7 +466,15 @@ pub fn definitions() -> Vec<ToolDefinition> {
                             "is_transparent": { "type": "boolean", "description": "Whether the block is transparent/non-opaque (default: false)" },
                             "has_gravity": { "type": "boolean", "description": "Whether the block falls like sand (default: false)" },
                             "flammable": { "type": "boolean", "description": "Whether the block can catch fire (default: false)" },
-                            "slipperiness": { "type": "number", "description": "Surface slipperiness 0.0-1.0 (default: 0.6, ice is ~0.98)" }
+                            "slipperiness": { "type": "number", "description": "Surface slipperiness 0.0-1.0 (default: 0.6, ice is ~0.98)" },
+                            "sound_group": { "type": "string", "description": "Sound played when the block is walked on, placed or broken, e.g. 'stone', 'wood', 'metal', 'glass', 'wool', 'gravel', 'sand'. Defaults to 'stone'." },
+                            "shape": {
+                                "description": "Outline/collision shape for non-full blocks: a preset ('slab', 'carpet', 'pressure_plate', 'fence_post', 'pane_post') or a box [x1, y1, z1, x2, y2, z2] in pixels (0-16). Omit for a full cube.",
+                                "oneOf": [
+                                    { "type": "string" },
+                                    { "type": "array", "items": { "type": "number" }, "minItems": 6, "maxItems": 6 }
+                                ]
+                            }
                         }
                     },
                     "has_gui": {
@@ -445,7 +505,9 @@ pub fn definitions() -> Vec<ToolDefinition> {
             name: "block_update".into(),
             description: "Update properties of an existing .block.json definition. Performs a \
                 read-modify-write: reads the current definition, merges the provided updates, and \
-                writes back. Only the fields you specify are changed."
+                writes back. Only the fields you specify are changed. The merged result is checked \
+                with the same rules as block_validate and the issues are returned; with strict, an \
+                update that would introduce an error is rejected and nothing is written."
                 .into(),
             input_schema: json!({
                 "type": "object",
@@ -457,17 +519,58 @@ pub fn definitions() -> Vec<ToolDefinition> {
                     "updates": {
                         "type": "object",
                         "description": "Object with fields to merge into the block definition. Any top-level field from the block JSON can be updated: name, display_name, mod_id, texture_mode, textures, properties, has_gui, gui_file, has_block_entity, custom_code, code_overrides."
+                    },
+                    "project_path": {
+                        "type": "string",
+                        "description": "Project root used to resolve textures and GUI files during validation. Defaults to the nearest directory above the block containing alloy.mod.json, then the current project."
+                    },
+                    "strict": {
+                        "type": "boolean",
+                        "description": "If true, refuse to write when the merge would introduce an error-severity issue. Default: false."
                     }
                 },
                 "required": ["path", "updates"]
             }),
         },
+        ToolDefinition {
+            name: "block_clone".into(),
+            description: "Copy an existing .block.json to a new block as a starting point. The \
+                definition is copied with the new name and display name, every texture it \
+                references is copied to a new file named after the new block, and a linked \
+                .gui.json is copied too. The new name is checked against vanilla blocks and the \
+                project's existing blocks. No code is generated; run block_generate_code on the \
+                new file afterwards."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Absolute path to the .block.json file to copy"
+                    },
+                    "name": {
+                        "type": "string",
+                        "description": "Identifier for the new block (lowercase, a-z0-9_ only, must not start with a digit)"
+                    },
+                    "display_name": {
+                        "type": "string",
+                        "description": "Human-readable display name for the new block"
+                    },
+                    "project_path": {
+                        "type": "string",
+                        "description": "Project root holding the textures and other blocks. Defaults to the nearest directory above the block containing alloy.mod.json, then the current project."
+                    }
+                },
+                "required": ["path", "name", "display_name"]
+            }),
+        },
         ToolDefinition {
             name: "block_validate".into(),
             description: "Validate a .block.json definition against all rules: name format, \
                 vanilla block conflicts, texture assignments, texture file existence, GUI file \
-                references, environment compatibility, and property value ranges. Returns an array \
-                of issues with severity and suggestions."
+                references, environment compatibility, and property value ranges, plus \
+                cross-field sanity warnings (unusual hardness, ice-like slipperiness, falling \
+                blocks with GUIs). Returns an array of issues with severity and suggestions."
                 .into(),
             input_schema: json!({
                 "type": "object",
@@ -479,18 +582,51 @@ pub fn definitions() -> Vec<ToolDefinition> {
                     "path": {
                         "type": "string",
                         "description": "Absolute path to the .block.json file to validate"
+                    },
+                    "check_texture_dimensions": {
+                        "type": "boolean",
+                        "description": "Also check each assigned texture is square and power-of-two sized, as resource_validate_texture does. Default: true"
                     }
                 },
                 "required": ["project_path", "path"]
             }),
         },
+        ToolDefinition {
+            name: "block_validate_all".into(),
+            description: "Validate every .block.json in a project with the same rules as \
+                block_validate, plus a check that no two blocks share a name. Returns each file's \
+                issues keyed by path, aggregate error and warning counts, and an overall valid \
+                flag. A file that can't be read or parsed is reported as an error for that file \
+                without stopping the rest. Hidden, build, target and node_modules directories are \
+                skipped."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_path": {
+                        "type": "string",
+                        "description": "Absolute path to the Alloy project directory"
+                    },
+                    "check_texture_dimensions": {
+                        "type": "boolean",
+                        "description": "Also check each assigned texture is square and power-of-two sized. Default: true"
+                    }
+                },
+                "required": ["project_path"]
+            }),
+        },
         ToolDefinition {
             name: "block_generate_code".into(),
             description: "Generate all Java source files, model JSONs, blockstate JSON, and \
                 registration code from a .block.json definition. Creates: Block Java class, block \
-                model JSON, blockstate JSON, item model JSON, and optionally a BlockEntity class \
-                if the block has a GUI. Returns the list of created files and a registration \
-                snippet to paste into the mod initializer."
+                model JSON, blockstate JSON, item model JSON, mineable/needs-tool tags when a \
+                tool is required, and optionally a BlockEntity class if the block has a GUI. Returns the list of created files and a registration \
+                snippet to paste into the mod initializer. With register: true the snippet and \
+                its imports are inserted into the initializer class instead (found via \
+                alloy.mod.json's entry_point, or a ModInit.java); safe to re-run. Records the \
+                generated files and their hashes in .alloy/generated.json; files edited by hand \
+                since the last generation are left alone and listed in skipped_modified unless \
+                force is true."
                 .into(),
             input_schema: json!({
                 "type": "object",
@@ -502,11 +638,38 @@ pub fn definitions() -> Vec<ToolDefinition> {
                     "path": {
                         "type": "string",
                         "description": "Absolute path to the .block.json file to generate code from"
+                    },
+                    "register": {
+                        "type": "boolean",
+                        "description": "Insert the registration code into the mod initializer (default: false)"
+                    },
+                    "force": {
+                        "type": "boolean",
+                        "description": "Overwrite generated files even if they were edited by hand (default: false)"
                     }
                 },
                 "required": ["project_path", "path"]
             }),
         },
+        ToolDefinition {
+            name: "block_check_item_models".into(),
+            description: "Scan every item model under src/main/resources/assets/*/models/item \
+                and resolve its 'parent' to a model file on disk. Reports dangling parents, \
+                which render as the missing-texture cube in game and commonly appear after \
+                block models are renamed or moved. Vanilla (minecraft:) and builtin parents \
+                are assumed to exist."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_path": {
+                        "type": "string",
+                        "description": "Absolute path to the Alloy project directory"
+                    }
+                },
+                "required": ["project_path"]
+            }),
+        },
     ]
 }
 
@@ -519,9 +682,12 @@ pub async fn execute(name: &str, params: Value, state: &ProjectState) -> ToolRes
     match name {
         "block_create" => handle_block_create(params, state).await,
         "block_read" => handle_block_read(params).await,
-        "block_update" => handle_block_update(params).await,
+        "block_update" => handle_block_update(params, state).await,
+        "block_clone" => handle_block_clone(params, state).await,
         "block_validate" => handle_block_validate(params, state).await,
+        "block_validate_all" => handle_block_validate_all(params).await,
         "block_generate_code" => handle_block_generate_code(params, state).await,
+        "block_check_item_models" => handle_block_check_item_models(params).await,
         _ => ToolResult::error(format!("Unknown block tool: {}", name)),
     }
 }
@@ -532,7 +698,7 @@ pub async fn execute(name: &str, params: Value, state: &ProjectState) -> ToolRes
 
 /// Validate a block name: must be non-empty, lowercase a-z0-9_ only, must not
 /// start with a digit.
-fn is_valid_block_name(name: &str) -> bool {
+pub(crate) fn is_valid_block_name(name: &str) -> bool {
     !name.is_empty()
         && !name.starts_with(|c: char| c.is_ascii_digit())
         && name
@@ -541,12 +707,12 @@ fn is_valid_block_name(name: &str) -> bool {
 }
 
 /// Check if a name collides with a vanilla Minecraft block.
-fn is_vanilla_block(name: &str) -> bool {
+pub(crate) fn is_vanilla_block(name: &str) -> bool {
     VANILLA_BLOCKS.binary_search(&name).is_ok()
 }
 
 /// Convert a snake_case string to PascalCase.
-fn to_pascal_case(s: &str) -> String {
+pub(crate) fn to_pascal_case(s: &str) -> String {
     s.split(|c: char| c == '-' || c == '_' || c == ' ')
         .filter(|w| !w.is_empty())
         .map(|word| {
@@ -578,6 +744,8 @@ fn props_from_params(params: &Value) -> BlockProps {
         has_gravity: p.get("has_gravity").and_then(|v| v.as_bool()).unwrap_or(false),
         flammable: p.get("flammable").and_then(|v| v.as_bool()).unwrap_or(false),
         slipperiness: p.get("slipperiness").and_then(|v| v.as_f64()).unwrap_or(0.6),
+        sound_group: p.get("sound_group").and_then(|v| v.as_str()).map(String::from),
+        shape: p.get("shape").and_then(|v| serde_json::from_value(v.clone()).ok()),
     }
 }
 
@@ -595,6 +763,123 @@ fn textures_from_params(params: &Value) -> BlockTextures {
     }
 }
 
+/// Best-effort material category for sanity heuristics: the sound group when
+/// one is set, otherwise inferred from the harvesting tool.
+fn material_category(props: &BlockProps) -> &'static str {
+    match props.sound_group.as_deref() {
+        Some("stone" | "deepslate" | "tuff" | "calcite" | "basalt" | "netherrack"
+            | "nether_bricks" | "ancient_debris") => "stone",
+        Some("metal" | "netherite_block" | "copper" | "anvil" | "chain" | "lantern") => "metal",
+        Some("glass") => "ice",
+        Some("wood" | "bamboo_wood" | "cherry_wood" | "nether_wood") => "wood",
+        Some(_) => "other",
+        None => match props.tool_type.as_str() {
+            "pickaxe" => "stone",
+            "axe" => "wood",
+            _ => "other",
+        },
+    }
+}
+
+/// Cross-field "are you sure?" checks. These flag combinations that are legal
+/// but rarely intended, so they are always warnings, never errors.
+fn sanity_warnings(block: &BlockProject) -> Vec<Value> {
+    let props = &block.properties;
+    let material = material_category(props);
+    let mut warnings = Vec::new();
+
+    // Iron blocks sit at 5.0, obsidian at 50.0; anything above 10 outside
+    // stone and metal is almost always a typo.
+    if props.hardness > 10.0 && material != "stone" && material != "metal" {
+        warnings.push(json!({
+            "severity": "warning",
+            "message": format!(
+                "Hardness {} is unusually high for a {} block. Values above 10 are normally reserved for stone- or metal-like blocks such as obsidian (50.0).",
+                props.hardness,
+                props.sound_group.as_deref().unwrap_or(material)
+            ),
+            "suggestion": "Most blocks use 0.5-5.0. Double-check the value, or set a stone/metal sound_group if this block really is that tough."
+        }));
+    }
+
+    // Ice is 0.98, slime 0.8; >= 0.9 feels like ice to players
+    // Without a sound group only axe-harvested blocks are confidently non-icy
+    let known_non_ice = props.sound_group.is_some() || material == "wood";
+    if props.slipperiness >= 0.9 && material != "ice" && known_non_ice {
+        warnings.push(json!({
+            "severity": "warning",
+            "message": format!(
+                "Slipperiness {} is ice-level, but the block is {}. Players will slide on it like ice.",
+                props.slipperiness,
+                props.sound_group.as_deref().unwrap_or(material)
+            ),
+            "suggestion": "Use 0.6 for normal footing, or a glass sound_group if the block is meant to be icy."
+        }));
+    }
+
+    if props.has_gravity && (block.has_gui || block.has_block_entity) {
+        warnings.push(json!({
+            "severity": "warning",
+            "message": "Block has gravity and a GUI/block entity. When it falls it becomes a falling-block entity, which drops its block entity data (inventory, progress) and closes any open screen.",
+            "suggestion": "Disable has_gravity, or make sure the block entity's contents can safely be lost."
+        }));
+    }
+
+    warnings
+}
+
+/// Resolve a model reference such as "mymod:block/furnace" to its JSON file
+/// under the project's assets. Returns `None` for vanilla and builtin models,
+/// which are not present on disk.
+fn resolve_model_path(project: &Path, reference: &str) -> Option<PathBuf> {
+    if reference.starts_with("builtin/") {
+        return None;
+    }
+    let (namespace, model) = reference.split_once(':').unwrap_or(("minecraft", reference));
+    if namespace == "minecraft" {
+        return None;
+    }
+    Some(
+        project
+            .join("src/main/resources/assets")
+            .join(namespace)
+            .join("models")
+            .join(format!("{}.json", model)),
+    )
+}
+
+/// Check that an item model's parent resolves to an existing model file.
+/// Returns an issue when the parent is dangling, or `None` if the model is
+/// absent, has no parent, or the parent is fine.
+pub(crate) fn check_item_model_parent(project: &Path, item_model_path: &Path) -> Option<Value> {
+    let content = std::fs::read_to_string(item_model_path).ok()?;
+    let model: Value = match serde_json::from_str(&content) {
+        Ok(v) => v,
+        Err(e) => {
+            return Some(json!({
+                "severity": "error",
+                "message": format!("Item model {} is not valid JSON: {}", item_model_path.display(), e),
+                "suggestion": "Fix the JSON syntax or regenerate the block code."
+            }))
+        }
+    };
+    let parent = model.get("parent").and_then(|p| p.as_str())?;
+    let parent_path = resolve_model_path(project, parent)?;
+    if parent_path.exists() {
+        return None;
+    }
+    Some(json!({
+        "severity": "error",
+        "message": format!(
+            "Item model {} references parent \"{}\", but {} does not exist. The item will render as a missing model.",
+            item_model_path.display(),
+            parent,
+            parent_path.display()
+        ),
+        "suggestion": "Restore the block model, update the item model's parent, or regenerate the block code."
+    }))
+}
+
 // ---------------------------------------------------------------------------
 // Code generation
 // ---------------------------------------------------------------------------
@@ -623,6 +908,26 @@ import net.alloymc.api.registry.Registry;
         code.push_str("import net.alloymc.api.util.BlockPos;\n");
     }
 
+    let shape = block
+        .properties
+        .shape
+        .as_ref()
+        .and_then(|s| s.bounds())
+        .filter(|b| *b != FULL_CUBE);
+    let sound_group = block
+        .properties
+        .sound_group
+        .as_deref()
+        .filter(|g| *g != "stone");
+    if sound_group.is_some() {
+        code.push_str("import net.alloymc.api.block.BlockSoundGroup;\n");
+    }
+
+    if shape.is_some() {
+        code.push_str("import net.alloymc.api.util.shape.VoxelShape;\n");
+        code.push_str("import net.alloymc.api.util.shape.VoxelShapes;\n");
+    }
+
     if block.has_gui {
         code.push_str("import net.alloymc.api.player.Player;\n");
         code.push_str("import net.alloymc.api.util.Hand;\n");
@@ -675,8 +980,35 @@ public class {class_name} extends {extends} {{
         ));
     }
 
+    if let Some(group) = sound_group {
+        code.push_str(&format!(
+            "            .sounds(BlockSoundGroup.{})\n",
+            group.to_uppercase()
+        ));
+    }
+
     code.push_str("        );\n    }\n");
 
+    // Custom bounds, converted from pixels to block units
+    if let Some(bounds) = shape {
+        let [x1, y1, z1, x2, y2, z2] = bounds.map(|px| px / 16.0);
+        code.push_str(&format!(
+            r#"
+    private static final VoxelShape SHAPE = VoxelShapes.cuboid({x1:?}, {y1:?}, {z1:?}, {x2:?}, {y2:?}, {z2:?});
+
+    @Override
+    public VoxelShape getOutlineShape(BlockState state) {{
+        return SHAPE;
+    }}
+
+    @Override
+    public VoxelShape getCollisionShape(BlockState state) {{
+        return SHAPE;
+    }}
+"#
+        ));
+    }
+
     // Block entity creation method
     if block.has_block_entity {
         code.push_str(&format!(
@@ -791,26 +1123,26 @@ fn generate_block_model(block: &BlockProject) -> String {
 // ---------------------------------------------------------------------------
 
 async fn handle_block_create(params: Value, _state: &ProjectState) -> ToolResult {
-    let project_path = match params.get("project_path").and_then(|v| v.as_str()) {
-        Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: project_path"),
+    let project_path = match require_str(&params, "project_path", "block_create") {
+        Ok(p) => p,
+        Err(e) => return e,
     };
-    let name = match params.get("name").and_then(|v| v.as_str()) {
-        Some(n) => n,
-        None => return ToolResult::error("Missing required parameter: name"),
+    let name = match require_str(&params, "name", "block_create") {
+        Ok(n) => n,
+        Err(e) => return e,
     };
-    let display_name = match params.get("display_name").and_then(|v| v.as_str()) {
-        Some(d) => d,
-        None => return ToolResult::error("Missing required parameter: display_name"),
+    let display_name = match require_str(&params, "display_name", "block_create") {
+        Ok(d) => d,
+        Err(e) => return e,
     };
-    let mod_id = match params.get("mod_id").and_then(|v| v.as_str()) {
-        Some(m) => m,
-        None => return ToolResult::error("Missing required parameter: mod_id"),
+    let mod_id = match require_str(&params, "mod_id", "block_create") {
+        Ok(m) => m,
+        Err(e) => return e,
     };
 
     let project = Path::new(project_path);
     if !project.exists() {
-        return ToolResult::error(format!(
+        return ToolResult::error_with(ErrorCode::NotFound, format!(
             "Project directory does not exist: {}",
             project_path
         ));
@@ -818,16 +1150,25 @@ async fn handle_block_create(params: Value, _state: &ProjectState) -> ToolResult
 
     // Validate block name
     if !is_valid_block_name(name) {
-        return ToolResult::error(format!(
+        return ToolResult::error_with(ErrorCode::InvalidParams, format!(
             "Invalid block name '{}'. Must be lowercase, contain only a-z, 0-9, and underscores, \
              and must not start with a digit.",
             name
         ));
     }
 
+    if !is_valid_mod_id(mod_id) {
+        return ToolResult::error_with(ErrorCode::InvalidParams, format!(
+            "Invalid mod_id '{}'. Must start with a lowercase letter and contain only a-z, 0-9, \
+             and underscores. Suggestion: use '{}'",
+            mod_id,
+            sanitize_mod_id(mod_id)
+        ));
+    }
+
     // Check vanilla conflict
     if is_vanilla_block(name) {
-        return ToolResult::error(format!(
+        return ToolResult::error_with(ErrorCode::Conflict, format!(
             "Block name '{}' conflicts with a vanilla Minecraft block. \
              Suggestion: use 'custom_{}'",
             name, name
@@ -875,7 +1216,10 @@ async fn handle_block_create(params: Value, _state: &ProjectState) -> ToolResult
 
     let block_json_path = project.join(format!("{}.block.json", name));
     if let Err(e) = std::fs::write(&block_json_path, &block_json) {
-        return ToolResult::error(format!("Failed to write block JSON: {}", e));
+        return ToolResult::error_with(
+            ErrorCode::from(&e),
+            format!("Failed to write block JSON: {}", e),
+        );
     }
 
     let mut created_files = vec![json!({
@@ -901,7 +1245,10 @@ async fn handle_block_create(params: Value, _state: &ProjectState) -> ToolResult
                 }
             };
             if let Err(e) = std::fs::write(&gui_path, &gui_str) {
-                return ToolResult::error(format!("Failed to write GUI file: {}", e));
+                return ToolResult::error_with(
+                    ErrorCode::from(&e),
+                    format!("Failed to write GUI file: {}", e),
+                );
             }
 
             created_files.push(json!({
@@ -923,28 +1270,44 @@ async fn handle_block_create(params: Value, _state: &ProjectState) -> ToolResult
 // ---------------------------------------------------------------------------
 
 async fn handle_block_read(params: Value) -> ToolResult {
-    let path = match params.get("path").and_then(|v| v.as_str()) {
-        Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: path"),
+    let path = match require_str(&params, "path", "block_read") {
+        Ok(p) => p,
+        Err(e) => return e,
     };
 
     let block_path = Path::new(path);
     if !block_path.exists() {
-        return ToolResult::error(format!("Block JSON file does not exist: {}", path));
+        return ToolResult::error_with(
+            ErrorCode::NotFound,
+            format!("Block JSON file does not exist: {}", path),
+        );
     }
     if !block_path.is_file() {
-        return ToolResult::error(format!("Path is not a file: {}", path));
+        return ToolResult::error_with(
+            ErrorCode::InvalidParams,
+            format!("Path is not a file: {}", path),
+        );
     }
 
     let content = match std::fs::read_to_string(block_path) {
         Ok(c) => c,
-        Err(e) => return ToolResult::error(format!("Failed to read block JSON: {}", e)),
+        Err(e) => {
+            return ToolResult::error_with(
+                ErrorCode::from(&e),
+                format!("Failed to read block JSON: {}", e),
+            )
+        }
     };
 
     // Parse to validate it is proper BlockProject JSON, then return the raw value
     let block_value: Value = match serde_json::from_str(&content) {
         Ok(v) => v,
-        Err(e) => return ToolResult::error(format!("Failed to parse block JSON: {}", e)),
+        Err(e) => {
+            return ToolResult::error_with(
+                ErrorCode::InvalidParams,
+                format!("Failed to parse block JSON: {}", e),
+            )
+        }
     };
 
     ToolResult::json(&block_value)
@@ -954,31 +1317,87 @@ async fn handle_block_read(params: Value) -> ToolResult {
 // block_update
 // ---------------------------------------------------------------------------
 
-async fn handle_block_update(params: Value) -> ToolResult {
-    let path = match params.get("path").and_then(|v| v.as_str()) {
-        Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: path"),
+/// The project a block file belongs to: an explicit `project_path`, else the
+/// nearest ancestor holding alloy.mod.json, else the current project.
+fn block_project_root(params: &Value, block_path: &Path, state: &ProjectState) -> PathBuf {
+    if let Some(p) = params.get("project_path").and_then(|v| v.as_str()) {
+        return PathBuf::from(p);
+    }
+    if let Some(dir) = block_path
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.join("alloy.mod.json").is_file())
+    {
+        return dir.to_path_buf();
+    }
+    state
+        .project_path()
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| block_path.parent().unwrap_or(Path::new(".")).to_path_buf())
+}
+
+/// Validate a block definition held in memory, reporting a definition that
+/// no longer parses as a single error.
+fn validate_block_value(project: &Path, block_path: &Path, value: &Value) -> Vec<Value> {
+    match serde_json::from_value::<BlockProject>(value.clone()) {
+        Ok(block) => validate_block(project, block_path, block, true).1,
+        Err(e) => vec![json!({
+            "severity": "error",
+            "message": format!("Definition is not a valid block: {}", e),
+            "suggestion": "Restore or correct the field named in the message."
+        })],
+    }
+}
+
+async fn handle_block_update(params: Value, state: &ProjectState) -> ToolResult {
+    let path = match require_str(&params, "path", "block_update") {
+        Ok(p) => p,
+        Err(e) => return e,
     };
     let updates = match params.get("updates") {
         Some(u) => u.clone(),
-        None => return ToolResult::error("Missing required parameter: updates"),
+        None => {
+            return ToolResult::error_with(
+                ErrorCode::InvalidParams,
+                "tool 'block_update': missing required object parameter 'updates'",
+            )
+        }
     };
 
     let block_path = Path::new(path);
     if !block_path.exists() {
-        return ToolResult::error(format!("Block JSON file does not exist: {}", path));
+        return ToolResult::error_with(
+            ErrorCode::NotFound,
+            format!("Block JSON file does not exist: {}", path),
+        );
     }
 
     // Read existing content
     let content = match std::fs::read_to_string(block_path) {
         Ok(c) => c,
-        Err(e) => return ToolResult::error(format!("Failed to read block JSON: {}", e)),
+        Err(e) => {
+            return ToolResult::error_with(
+                ErrorCode::from(&e),
+                format!("Failed to read block JSON: {}", e),
+            )
+        }
     };
 
     let mut block_value: Value = match serde_json::from_str(&content) {
         Ok(v) => v,
-        Err(e) => return ToolResult::error(format!("Failed to parse block JSON: {}", e)),
+        Err(e) => {
+            return ToolResult::error_with(
+                ErrorCode::InvalidParams,
+                format!("Failed to parse block JSON: {}", e),
+            )
+        }
     };
+    let strict = params
+        .get("strict")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let project = block_project_root(&params, block_path, state);
+    let issues_before = validate_block_value(&project, block_path, &block_value);
 
     // Merge updates into the existing value
     if let (Some(existing), Some(updates_obj)) = (block_value.as_object_mut(), updates.as_object())
@@ -1002,11 +1421,31 @@ async fn handle_block_update(params: Value) -> ToolResult {
             existing.insert(key.clone(), value.clone());
         }
     } else {
-        return ToolResult::error(
+        return ToolResult::error_with(
+            ErrorCode::InvalidParams,
             "Updates must be a JSON object with fields to merge into the block definition.",
         );
     }
 
+    let issues = validate_block_value(&project, block_path, &block_value);
+    let is_error = |i: &&Value| i.get("severity").and_then(|s| s.as_str()) == Some("error");
+    let introduced: Vec<&Value> = issues
+        .iter()
+        .filter(is_error)
+        .filter(|i| !issues_before.iter().filter(is_error).any(|b| b["message"] == i["message"]))
+        .collect();
+
+    if strict && !introduced.is_empty() {
+        return ToolResult::json(&json!({
+            "status": "rejected",
+            "path": path,
+            "message": "The update would introduce errors; nothing was written.",
+            "introduced_errors": introduced,
+            "issues": issues,
+            "valid": false
+        }));
+    }
+
     // Write back
     let new_content = match serde_json::to_string_pretty(&block_value) {
         Ok(s) => s,
@@ -1014,13 +1453,259 @@ async fn handle_block_update(params: Value) -> ToolResult {
     };
 
     if let Err(e) = std::fs::write(block_path, &new_content) {
-        return ToolResult::error(format!("Failed to write updated block JSON: {}", e));
+        return ToolResult::error_with(
+            ErrorCode::from(&e),
+            format!("Failed to write updated block JSON: {}", e),
+        );
     }
 
     ToolResult::json(&json!({
         "status": "updated",
         "path": path,
-        "block": block_value
+        "block": block_value,
+        "introduced_errors": introduced,
+        "issues": issues,
+        "valid": !has_errors(&issues)
+    }))
+}
+
+// ---------------------------------------------------------------------------
+// block_clone
+// ---------------------------------------------------------------------------
+
+/// The name a cloned block's copy of `texture` gets: the source block's name
+/// swapped for the new one, or the new name as a prefix when it isn't there.
+fn cloned_texture_name(texture: &str, from: &str, to: &str) -> String {
+    if texture.contains(from) {
+        texture.replace(from, to)
+    } else {
+        format!("{}_{}", to, texture)
+    }
+}
+
+async fn handle_block_clone(params: Value, state: &ProjectState) -> ToolResult {
+    let path = match require_str(&params, "path", "block_clone") {
+        Ok(p) => p,
+        Err(e) => return e,
+    };
+    let name = match require_str(&params, "name", "block_clone") {
+        Ok(n) => n,
+        Err(e) => return e,
+    };
+    let display_name = match require_str(&params, "display_name", "block_clone") {
+        Ok(d) => d,
+        Err(e) => return e,
+    };
+
+    let source_path = Path::new(path);
+    if !source_path.is_file() {
+        return ToolResult::error_with(
+            ErrorCode::NotFound,
+            format!("Block JSON file does not exist: {}", path),
+        );
+    }
+    let content = match std::fs::read_to_string(source_path) {
+        Ok(c) => c,
+        Err(e) => {
+            return ToolResult::error_with(
+                ErrorCode::from(&e),
+                format!("Failed to read block JSON: {}", e),
+            )
+        }
+    };
+    let mut block_value: Value = match serde_json::from_str(&content) {
+        Ok(v) => v,
+        Err(e) => {
+            return ToolResult::error_with(
+                ErrorCode::InvalidParams,
+                format!("Failed to parse block JSON: {}", e),
+            )
+        }
+    };
+    let source: BlockProject = match serde_json::from_value(block_value.clone()) {
+        Ok(b) => b,
+        Err(e) => {
+            return ToolResult::error_with(
+                ErrorCode::InvalidParams,
+                format!("Definition is not a valid block: {}", e),
+            )
+        }
+    };
+
+    if !is_valid_block_name(name) {
+        return ToolResult::error_with(ErrorCode::InvalidParams, format!(
+            "Invalid block name '{}'. Must be lowercase, contain only a-z, 0-9, and underscores, \
+             and must not start with a digit.",
+            name
+        ));
+    }
+    if is_vanilla_block(name) {
+        return ToolResult::error_with(ErrorCode::Conflict, format!(
+            "Block name '{}' conflicts with a vanilla Minecraft block. \
+             Suggestion: use 'custom_{}'",
+            name, name
+        ));
+    }
+
+    let project = block_project_root(&params, source_path, state);
+    let block_dir = source_path.parent().unwrap_or(Path::new("."));
+    let block_json_path = block_dir.join(format!("{}.block.json", name));
+    let existing = find_block_files(&project).into_iter().find(|p| {
+        std::fs::read_to_string(p)
+            .ok()
+            .and_then(|c| serde_json::from_str::<Value>(&c).ok())
+            .is_some_and(|v| v.get("name").and_then(|n| n.as_str()) == Some(name))
+    });
+    let existing = existing.or_else(|| block_json_path.exists().then(|| block_json_path.clone()));
+    if let Some(existing) = existing {
+        return ToolResult::error_with(
+            ErrorCode::Conflict,
+            format!("A block named '{}' already exists: {}", name, existing.display()),
+        );
+    }
+
+    // Work out every copy before writing anything, so a conflict leaves the
+    // project untouched
+    let textures_dir = project
+        .join("src/main/resources/assets")
+        .join(&source.mod_id)
+        .join("textures/block");
+    let mut renamed: BTreeMap<String, String> = BTreeMap::new();
+    let mut missing_textures: Vec<String> = Vec::new();
+    let references = [
+        &source.textures.all,
+        &source.textures.top,
+        &source.textures.bottom,
+        &source.textures.north,
+        &source.textures.south,
+        &source.textures.east,
+        &source.textures.west,
+    ];
+    for texture in references.into_iter().flatten() {
+        if renamed.contains_key(texture) || missing_textures.contains(texture) {
+            continue;
+        }
+        if !textures_dir.join(format!("{}.png", texture)).exists() {
+            // Nothing to copy; the clone keeps pointing at the same texture
+            missing_textures.push(texture.clone());
+            continue;
+        }
+        let new_texture = cloned_texture_name(texture, &source.name, name);
+        let target = textures_dir.join(format!("{}.png", new_texture));
+        if target.exists() {
+            return ToolResult::error_with(
+                ErrorCode::Conflict,
+                format!("Texture file already exists: {}", target.display()),
+            );
+        }
+        renamed.insert(texture.clone(), new_texture);
+    }
+
+    let gui_copy = match (source.has_gui, &source.gui_file) {
+        (true, Some(gui_file)) if block_dir.join(gui_file).is_file() => {
+            let target = block_dir.join(format!("{}.gui.json", name));
+            if target.exists() {
+                return ToolResult::error_with(
+                    ErrorCode::Conflict,
+                    format!("GUI file already exists: {}", target.display()),
+                );
+            }
+            Some((block_dir.join(gui_file), target))
+        }
+        _ => None,
+    };
+
+    let mut created_files: Vec<Value> = Vec::new();
+    for (texture, new_texture) in &renamed {
+        // Animated textures carry their frame timing in a .png.mcmeta
+        for ext in ["png", "png.mcmeta"] {
+            let from = textures_dir.join(format!("{}.{}", texture, ext));
+            if ext != "png" && !from.exists() {
+                continue;
+            }
+            let to = textures_dir.join(format!("{}.{}", new_texture, ext));
+            if let Err(e) = std::fs::copy(&from, &to) {
+                return ToolResult::error_with(
+                    ErrorCode::from(&e),
+                    format!("Failed to copy texture {}: {}", from.display(), e),
+                );
+            }
+            created_files.push(json!({
+                "path": to.to_string_lossy(),
+                "file_type": if ext == "png" { "texture" } else { "texture_mcmeta" }
+            }));
+        }
+    }
+
+    if let Some((from, to)) = &gui_copy {
+        let gui_content = match std::fs::read_to_string(from) {
+            Ok(c) => c,
+            Err(e) => {
+                return ToolResult::error_with(
+                    ErrorCode::from(&e),
+                    format!("Failed to read GUI file: {}", e),
+                )
+            }
+        };
+        // Rename the copy when it parses; otherwise copy it as it is
+        let gui_content = match serde_json::from_str::<Value>(&gui_content) {
+            Ok(mut gui) if gui.is_object() => {
+                gui["name"] = json!(name);
+                serde_json::to_string_pretty(&gui).unwrap_or(gui_content)
+            }
+            _ => gui_content,
+        };
+        if let Err(e) = std::fs::write(to, &gui_content) {
+            return ToolResult::error_with(
+                ErrorCode::from(&e),
+                format!("Failed to write GUI file: {}", e),
+            );
+        }
+        created_files.push(json!({
+            "path": to.to_string_lossy(),
+            "file_type": "gui_json"
+        }));
+    }
+
+    block_value["name"] = json!(name);
+    block_value["display_name"] = json!(display_name);
+    if let Some(textures) = block_value.get_mut("textures").and_then(|t| t.as_object_mut()) {
+        for texture in textures.values_mut() {
+            if let Some(new_texture) = texture.as_str().and_then(|t| renamed.get(t)) {
+                *texture = json!(new_texture);
+            }
+        }
+    }
+    if let Some((_, to)) = &gui_copy {
+        block_value["gui_file"] = json!(to.file_name().unwrap_or_default().to_string_lossy());
+    }
+
+    let block_json = match serde_json::to_string_pretty(&block_value) {
+        Ok(j) => j,
+        Err(e) => return ToolResult::error(format!("Failed to serialize block: {}", e)),
+    };
+    if let Err(e) = std::fs::write(&block_json_path, &block_json) {
+        return ToolResult::error_with(
+            ErrorCode::from(&e),
+            format!("Failed to write block JSON: {}", e),
+        );
+    }
+    created_files.insert(0, json!({
+        "path": block_json_path.to_string_lossy(),
+        "file_type": "block_json"
+    }));
+
+    let issues = validate_block_value(&project, &block_json_path, &block_value);
+    ToolResult::json(&json!({
+        "status": "cloned",
+        "source": path,
+        "block_json_path": block_json_path.to_string_lossy(),
+        "created_files": created_files,
+        "renamed_textures": renamed,
+        "missing_textures": missing_textures,
+        "issues": issues,
+        "valid": !has_errors(&issues),
+        "next_step": "Run block_generate_code on the new .block.json to generate its code and assets."
     }))
 }
 
@@ -1029,32 +1714,144 @@ async fn handle_block_update(params: Value) -> ToolResult {
 // ---------------------------------------------------------------------------
 
 async fn handle_block_validate(params: Value, _state: &ProjectState) -> ToolResult {
-    let project_path = match params.get("project_path").and_then(|v| v.as_str()) {
-        Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: project_path"),
+    let project_path = match require_str(&params, "project_path", "block_validate") {
+        Ok(p) => p,
+        Err(e) => return e,
     };
-    let path = match params.get("path").and_then(|v| v.as_str()) {
-        Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: path"),
+    let path = match require_str(&params, "path", "block_validate") {
+        Ok(p) => p,
+        Err(e) => return e,
     };
 
+    let check_dimensions = params
+        .get("check_texture_dimensions")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true);
+
+    let (block_name, issues) = match validate_block_file(Path::new(project_path), Path::new(path), check_dimensions) {
+        Ok(result) => result,
+        Err(e) => return ToolResult::error(e),
+    };
+
+    ToolResult::json(&json!({
+        "path": path,
+        "block_name": block_name,
+        "issues": issues,
+        "issue_count": issues.len(),
+        "valid": !has_errors(&issues)
+    }))
+}
+
+async fn handle_block_validate_all(params: Value) -> ToolResult {
+    let project_path = match require_str(&params, "project_path", "block_validate_all") {
+        Ok(p) => p,
+        Err(e) => return e,
+    };
+    let check_dimensions = params
+        .get("check_texture_dimensions")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true);
+
     let project = Path::new(project_path);
-    let block_path = Path::new(path);
+    if !project.is_dir() {
+        return ToolResult::error_with(
+            ErrorCode::NotFound,
+            format!("Project directory does not exist: {}", project_path),
+        );
+    }
 
-    if !block_path.exists() {
-        return ToolResult::error(format!("Block JSON file does not exist: {}", path));
+    let block_files = find_block_files(project);
+    let mut results: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+    let mut names: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for block_path in &block_files {
+        let key = block_path.to_string_lossy().into_owned();
+        let issues = match validate_block_file(project, block_path, check_dimensions) {
+            Ok((name, issues)) => {
+                names.entry(name).or_default().push(key.clone());
+                issues
+            }
+            Err(e) => vec![json!({
+                "severity": "error",
+                "message": e,
+                "suggestion": "Fix the file so it parses as a block definition, or remove it."
+            })],
+        };
+        results.insert(key, issues);
     }
 
-    let content = match std::fs::read_to_string(block_path) {
-        Ok(c) => c,
-        Err(e) => return ToolResult::error(format!("Failed to read block JSON: {}", e)),
-    };
+    // Two definitions with one name would register the same block twice
+    for (name, paths) in names.iter().filter(|(_, paths)| paths.len() > 1) {
+        for path in paths {
+            let others: Vec<&str> =
+                paths.iter().filter(|p| *p != path).map(String::as_str).collect();
+            results.entry(path.clone()).or_default().push(json!({
+                "severity": "error",
+                "message": format!(
+                    "Block name \"{}\" is also used by {}.",
+                    name,
+                    others.join(", ")
+                ),
+                "suggestion": "Rename one of the blocks so every name is unique."
+            }));
+        }
+    }
 
-    let block: BlockProject = match serde_json::from_str(&content) {
-        Ok(b) => b,
-        Err(e) => return ToolResult::error(format!("Failed to parse block JSON: {}", e)),
+    let count = |severity: &str| {
+        results
+            .values()
+            .flatten()
+            .filter(|i| i.get("severity").and_then(|s| s.as_str()) == Some(severity))
+            .count()
     };
+    let (error_count, warning_count) = (count("error"), count("warning"));
+    let invalid_files = results.values().filter(|issues| has_errors(issues)).count();
+
+    ToolResult::json(&json!({
+        "project_path": project_path,
+        "blocks_checked": block_files.len(),
+        "results": results,
+        "error_count": error_count,
+        "warning_count": warning_count,
+        "invalid_files": invalid_files,
+        "valid": error_count == 0
+    }))
+}
+
+/// Whether any issue in the list has "error" severity.
+fn has_errors(issues: &[Value]) -> bool {
+    issues
+        .iter()
+        .any(|i| i.get("severity").and_then(|s| s.as_str()) == Some("error"))
+}
+
+/// Run every block_validate rule against one .block.json file, returning the
+/// block name and its issues.
+fn validate_block_file(
+    project: &Path,
+    block_path: &Path,
+    check_dimensions: bool,
+) -> Result<(String, Vec<Value>), String> {
+    if !block_path.exists() {
+        return Err(format!("Block JSON file does not exist: {}", block_path.display()));
+    }
+
+    let content = std::fs::read_to_string(block_path)
+        .map_err(|e| format!("Failed to read block JSON: {}", e))?;
+
+    let block: BlockProject = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse block JSON: {}", e))?;
 
+    Ok(validate_block(project, block_path, block, check_dimensions))
+}
+
+/// Run every block_validate rule against a parsed definition stored at
+/// `block_path`, returning the block name and its issues.
+fn validate_block(
+    project: &Path,
+    block_path: &Path,
+    block: BlockProject,
+    check_dimensions: bool,
+) -> (String, Vec<Value>) {
     let mut issues: Vec<Value> = Vec::new();
 
     // Check name validity
@@ -1066,6 +1863,17 @@ async fn handle_block_validate(params: Value, _state: &ProjectState) -> ToolResu
         }));
     }
 
+    if !is_valid_mod_id(&block.mod_id) {
+        issues.push(json!({
+            "severity": "error",
+            "message": format!(
+                "mod_id \"{}\" is invalid. Must start with a lowercase letter and contain only a-z, 0-9, and underscores, or textures and models won't be found.",
+                block.mod_id
+            ),
+            "suggestion": format!("Change mod_id to \"{}\"", sanitize_mod_id(&block.mod_id))
+        }));
+    }
+
     // Check vanilla conflict
     if is_vanilla_block(&block.name) {
         issues.push(json!({
@@ -1113,24 +1921,43 @@ async fn handle_block_validate(params: Value, _state: &ProjectState) -> ToolResu
         .join(&block.mod_id)
         .join("textures/block");
 
-    let check_texture_file = |tex_name: &Option<String>| -> Option<Value> {
-        if let Some(ref name) = tex_name {
-            let tex_path = textures_dir.join(format!("{}.png", name));
-            if !tex_path.exists() {
-                return Some(json!({
-                    "severity": "warning",
-                    "message": format!("Texture file not found: {}.png", name),
-                    "suggestion": "Import the texture file into the project assets."
-                }));
-            }
+    let check_texture_file = |tex_name: &Option<String>| -> Vec<Value> {
+        let Some(ref name) = tex_name else {
+            return Vec::new();
+        };
+        let tex_path = textures_dir.join(format!("{}.png", name));
+        if !tex_path.exists() {
+            return vec![json!({
+                "severity": "warning",
+                "message": format!("Texture file not found: {}.png", name),
+                "suggestion": "Import the texture file into the project assets."
+            })];
+        }
+        if !check_dimensions {
+            return Vec::new();
+        }
+        match super::resources::inspect_texture(&tex_path) {
+            Ok(report) => report
+                .issues
+                .into_iter()
+                .map(|mut issue| {
+                    let message = issue["message"].as_str().unwrap_or("").to_string();
+                    issue["message"] = json!(format!("{}.png: {}", name, message));
+                    issue
+                })
+                .collect(),
+            Err(e) => vec![json!({
+                "severity": "error",
+                "message": e,
+                "suggestion": "Re-export the texture as a valid PNG."
+            })],
         }
-        None
     };
 
+    // Faces sharing a texture only need reporting once
+    let mut checked: Vec<&str> = Vec::new();
     if block.texture_mode == "all" {
-        if let Some(issue) = check_texture_file(&block.textures.all) {
-            issues.push(issue);
-        }
+        issues.extend(check_texture_file(&block.textures.all));
     } else {
         for tex in [
             &block.textures.top,
@@ -1140,9 +1967,13 @@ async fn handle_block_validate(params: Value, _state: &ProjectState) -> ToolResu
             &block.textures.east,
             &block.textures.west,
         ] {
-            if let Some(issue) = check_texture_file(tex) {
-                issues.push(issue);
+            if let Some(name) = tex.as_deref() {
+                if checked.contains(&name) {
+                    continue;
+                }
+                checked.push(name);
             }
+            issues.extend(check_texture_file(tex));
         }
     }
 
@@ -1189,6 +2020,16 @@ async fn handle_block_validate(params: Value, _state: &ProjectState) -> ToolResu
         }
     }
 
+    // Check the generated item model still points at an existing block model
+    let item_model_path = project
+        .join("src/main/resources/assets")
+        .join(&block.mod_id)
+        .join("models/item")
+        .join(format!("{}.json", block.name));
+    if let Some(issue) = check_item_model_parent(project, &item_model_path) {
+        issues.push(issue);
+    }
+
     // Check property ranges
     if block.properties.light_level > 15 {
         issues.push(json!({
@@ -1206,13 +2047,112 @@ async fn handle_block_validate(params: Value, _state: &ProjectState) -> ToolResu
         }));
     }
 
-    ToolResult::json(&json!({
-        "path": path,
-        "block_name": block.name,
-        "issues": issues,
-        "issue_count": issues.len(),
-        "valid": issues.iter().all(|i| i.get("severity").and_then(|s| s.as_str()) != Some("error"))
-    }))
+    if let Some(group) = &block.properties.sound_group {
+        if !SOUND_GROUPS.contains(&group.as_str()) {
+            issues.push(json!({
+                "severity": "error",
+                "message": format!("Unknown sound group \"{}\".", group),
+                "suggestion": format!("Use one of: {}.", SOUND_GROUPS.join(", "))
+            }));
+        }
+    }
+
+    if let Some(shape) = &block.properties.shape {
+        match (shape, shape.bounds()) {
+            (BlockShape::Preset(name), None) => issues.push(json!({
+                "severity": "error",
+                "message": format!("Unknown shape preset \"{}\".", name),
+                "suggestion": format!(
+                    "Use one of: {}, or an explicit [x1, y1, z1, x2, y2, z2] box.",
+                    SHAPE_PRESETS.iter().map(|(n, _)| *n).collect::<Vec<_>>().join(", ")
+                )
+            })),
+            (_, Some(bounds)) => {
+                if bounds.iter().any(|c| !(0.0..=16.0).contains(c)) {
+                    issues.push(json!({
+                        "severity": "error",
+                        "message": format!("Shape box {:?} has coordinates outside 0-16.", bounds),
+                        "suggestion": "Shape coordinates are in pixels, from 0 to 16."
+                    }));
+                } else if (0..3).any(|axis| bounds[axis] >= bounds[axis + 3]) {
+                    issues.push(json!({
+                        "severity": "error",
+                        "message": format!("Shape box {:?} is empty on at least one axis.", bounds),
+                        "suggestion": "Each of x2, y2 and z2 must be greater than x1, y1 and z1."
+                    }));
+                }
+            }
+            // Explicit boxes always have bounds
+            (BlockShape::Box(_), None) => {}
+        }
+    }
+
+    // Cross-field sanity heuristics
+    issues.extend(sanity_warnings(&block));
+
+    (block.name, issues)
+}
+
+/// Every .block.json under a project, sorted, skipping hidden and build
+/// directories.
+fn find_block_files(project: &Path) -> Vec<PathBuf> {
+    let mut block_files: Vec<PathBuf> = walkdir::WalkDir::new(project)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| {
+            let name = e.file_name().to_string_lossy();
+            let skipped = name.starts_with('.')
+                || name == "build"
+                || name == "target"
+                || name == "node_modules";
+            e.depth() == 0 || !skipped
+        })
+        .flatten()
+        .filter(|e| {
+            e.file_type().is_file() && e.file_name().to_string_lossy().ends_with(".block.json")
+        })
+        .map(|e| e.path().to_path_buf())
+        .collect();
+    block_files.sort();
+    block_files
+}
+
+/// Validate every .block.json under a project, for the `--validate-all-blocks`
+/// CLI mode. Prints one line per issue and returns the process exit code:
+/// 0 when no block has errors, 1 otherwise.
+pub fn validate_all_blocks_cli(project_path: &str) -> i32 {
+    let project = Path::new(project_path);
+    let block_files = find_block_files(project);
+
+    let mut failed = 0;
+    for block_path in &block_files {
+        match validate_block_file(project, block_path, true) {
+            Ok((_, issues)) => {
+                for issue in &issues {
+                    println!(
+                        "{}: {}: {}",
+                        block_path.display(),
+                        issue.get("severity").and_then(|s| s.as_str()).unwrap_or("warning"),
+                        issue.get("message").and_then(|m| m.as_str()).unwrap_or("")
+                    );
+                }
+                if has_errors(&issues) {
+                    failed += 1;
+                }
+            }
+            Err(e) => {
+                println!("{}: error: {}", block_path.display(), e);
+                failed += 1;
+            }
+        }
+    }
+
+    println!("Validated {} block(s): {} with errors", block_files.len(), failed);
+    if failed > 0 {
+        1
+    } else {
+        0
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -1220,31 +2160,58 @@ async fn handle_block_validate(params: Value, _state: &ProjectState) -> ToolResu
 // ---------------------------------------------------------------------------
 
 async fn handle_block_generate_code(params: Value, _state: &ProjectState) -> ToolResult {
-    let project_path = match params.get("project_path").and_then(|v| v.as_str()) {
-        Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: project_path"),
+    let project_path = match require_str(&params, "project_path", "block_generate_code") {
+        Ok(p) => p,
+        Err(e) => return e,
     };
-    let path = match params.get("path").and_then(|v| v.as_str()) {
-        Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: path"),
+    let path = match require_str(&params, "path", "block_generate_code") {
+        Ok(p) => p,
+        Err(e) => return e,
     };
 
+    let register = params
+        .get("register")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let force = params.get("force").and_then(|v| v.as_bool()).unwrap_or(false);
+
     let project = Path::new(project_path);
     let block_path = Path::new(path);
 
     if !block_path.exists() {
-        return ToolResult::error(format!("Block JSON file does not exist: {}", path));
+        return ToolResult::error_with(
+            ErrorCode::NotFound,
+            format!("Block JSON file does not exist: {}", path),
+        );
     }
 
     let content = match std::fs::read_to_string(block_path) {
         Ok(c) => c,
-        Err(e) => return ToolResult::error(format!("Failed to read block JSON: {}", e)),
+        Err(e) => {
+            return ToolResult::error_with(
+                ErrorCode::from(&e),
+                format!("Failed to read block JSON: {}", e),
+            )
+        }
     };
 
     let block: BlockProject = match serde_json::from_str(&content) {
         Ok(b) => b,
-        Err(e) => return ToolResult::error(format!("Failed to parse block JSON: {}", e)),
+        Err(e) => {
+            return ToolResult::error_with(
+                ErrorCode::InvalidParams,
+                format!("Failed to parse block JSON: {}", e),
+            )
+        }
+    };
+
+    // What this block generated last time, so hand edits are not overwritten
+    let block_key = generated::relative_path(project, block_path);
+    let mut manifest = match GeneratedManifest::load(project) {
+        Ok(m) => m,
+        Err(e) => return ToolResult::error(format!("{}; fix or delete it and retry", e)),
     };
+    let mut writer = GeneratedWriter::new(project, manifest.blocks.get(&block_key), force);
 
     let class_name = to_pascal_case(&block.name);
     let mut created_files: Vec<Value> = Vec::new();
@@ -1260,46 +2227,65 @@ async fn handle_block_generate_code(params: Value, _state: &ProjectState) -> Too
         .join("block");
 
     if let Err(e) = std::fs::create_dir_all(&java_dir) {
-        return ToolResult::error(format!("Failed to create directory: {}", e));
+        return ToolResult::error_with(
+            ErrorCode::from(&e),
+            format!("Failed to create directory: {}", e),
+        );
     }
 
     let java_path = java_dir.join(format!("{}.java", class_name));
-    if let Err(e) = std::fs::write(&java_path, &java_code) {
-        return ToolResult::error(format!("Failed to write Java file: {}", e));
+    match writer.write(&java_path, &java_code, "java_class") {
+        Ok(true) => created_files.push(json!({
+            "path": java_path.to_string_lossy(),
+            "file_type": "java_class"
+        })),
+        Ok(false) => {}
+        Err(e) => {
+            return ToolResult::error_with(
+                ErrorCode::from(&e),
+                format!("Failed to write Java file: {}", e),
+            )
+        }
     }
 
-    created_files.push(json!({
-        "path": java_path.to_string_lossy(),
-        "file_type": "java_class"
-    }));
-
     // 2. Generate Block model JSON
     let models_dir = project
         .join("src/main/resources/assets")
         .join(&block.mod_id)
         .join("models/block");
     if let Err(e) = std::fs::create_dir_all(&models_dir) {
-        return ToolResult::error(format!("Failed to create models directory: {}", e));
+        return ToolResult::error_with(
+            ErrorCode::from(&e),
+            format!("Failed to create models directory: {}", e),
+        );
     }
 
     let model_json = generate_block_model(&block);
     let model_path = models_dir.join(format!("{}.json", block.name));
-    if let Err(e) = std::fs::write(&model_path, &model_json) {
-        return ToolResult::error(format!("Failed to write block model: {}", e));
+    match writer.write(&model_path, &model_json, "block_model") {
+        Ok(true) => created_files.push(json!({
+            "path": model_path.to_string_lossy(),
+            "file_type": "block_model"
+        })),
+        Ok(false) => {}
+        Err(e) => {
+            return ToolResult::error_with(
+                ErrorCode::from(&e),
+                format!("Failed to write block model: {}", e),
+            )
+        }
     }
 
-    created_files.push(json!({
-        "path": model_path.to_string_lossy(),
-        "file_type": "block_model"
-    }));
-
     // 3. Generate Blockstate JSON
     let blockstates_dir = project
         .join("src/main/resources/assets")
         .join(&block.mod_id)
         .join("blockstates");
     if let Err(e) = std::fs::create_dir_all(&blockstates_dir) {
-        return ToolResult::error(format!("Failed to create blockstates directory: {}", e));
+        return ToolResult::error_with(
+            ErrorCode::from(&e),
+            format!("Failed to create blockstates directory: {}", e),
+        );
     }
 
     let blockstate_json = format!(
@@ -1311,22 +2297,30 @@ async fn handle_block_generate_code(params: Value, _state: &ProjectState) -> Too
         block.mod_id, block.name
     );
     let blockstate_path = blockstates_dir.join(format!("{}.json", block.name));
-    if let Err(e) = std::fs::write(&blockstate_path, &blockstate_json) {
-        return ToolResult::error(format!("Failed to write blockstate: {}", e));
+    match writer.write(&blockstate_path, &blockstate_json, "blockstate") {
+        Ok(true) => created_files.push(json!({
+            "path": blockstate_path.to_string_lossy(),
+            "file_type": "blockstate"
+        })),
+        Ok(false) => {}
+        Err(e) => {
+            return ToolResult::error_with(
+                ErrorCode::from(&e),
+                format!("Failed to write blockstate: {}", e),
+            )
+        }
     }
 
-    created_files.push(json!({
-        "path": blockstate_path.to_string_lossy(),
-        "file_type": "blockstate"
-    }));
-
     // 4. Generate Item model JSON
     let item_models_dir = project
         .join("src/main/resources/assets")
         .join(&block.mod_id)
         .join("models/item");
     if let Err(e) = std::fs::create_dir_all(&item_models_dir) {
-        return ToolResult::error(format!("Failed to create item models directory: {}", e));
+        return ToolResult::error_with(
+            ErrorCode::from(&e),
+            format!("Failed to create item models directory: {}", e),
+        );
     }
 
     let item_model_json = format!(
@@ -1336,30 +2330,64 @@ async fn handle_block_generate_code(params: Value, _state: &ProjectState) -> Too
         block.mod_id, block.name
     );
     let item_model_path = item_models_dir.join(format!("{}.json", block.name));
-    if let Err(e) = std::fs::write(&item_model_path, &item_model_json) {
-        return ToolResult::error(format!("Failed to write item model: {}", e));
+    match writer.write(&item_model_path, &item_model_json, "item_model") {
+        Ok(true) => created_files.push(json!({
+            "path": item_model_path.to_string_lossy(),
+            "file_type": "item_model"
+        })),
+        Ok(false) => {}
+        Err(e) => {
+            return ToolResult::error_with(
+                ErrorCode::from(&e),
+                format!("Failed to write item model: {}", e),
+            )
+        }
     }
 
-    created_files.push(json!({
-        "path": item_model_path.to_string_lossy(),
-        "file_type": "item_model"
-    }));
-
     // 5. Generate BlockEntity class if needed
     if block.has_block_entity {
         let be_code = generate_block_entity_class(&block, &class_name, &package_name);
         let be_path = java_dir.join(format!("{}BlockEntity.java", class_name));
-        if let Err(e) = std::fs::write(&be_path, &be_code) {
-            return ToolResult::error(format!("Failed to write BlockEntity: {}", e));
+        match writer.write(&be_path, &be_code, "java_class") {
+            Ok(true) => created_files.push(json!({
+                "path": be_path.to_string_lossy(),
+                "file_type": "java_class"
+            })),
+            Ok(false) => {}
+            Err(e) => {
+                return ToolResult::error_with(
+                    ErrorCode::from(&e),
+                    format!("Failed to write BlockEntity: {}", e),
+                )
+            }
         }
+    }
 
-        created_files.push(json!({
-            "path": be_path.to_string_lossy(),
-            "file_type": "java_class"
-        }));
+    // 6. Mineable / needs-tool tags, only meaningful when a tool is required
+    if block.properties.requires_tool {
+        let tags_dir = project.join("src/main/resources/data/minecraft/tags/blocks");
+        let block_id = format!("{}:{}", block.mod_id, block.name);
+        let mut tags = vec![format!("mineable/{}", block.properties.tool_type)];
+        if let Some(tier) = needs_tool_tag(block.properties.tool_level) {
+            tags.push(tier.to_string());
+        }
+        for tag in tags {
+            let tag_path = tags_dir.join(format!("{}.json", tag));
+            if let Err(e) = append_to_tag(&tag_path, &block_id) {
+                return ToolResult::error_with(
+                    ErrorCode::Io,
+                    format!("Failed to write tag {}: {}", tag, e),
+                );
+            }
+            writer.record_shared(&tag_path, "tag");
+            created_files.push(json!({
+                "path": tag_path.to_string_lossy(),
+                "file_type": "tag"
+            }));
+        }
     }
 
-    // 6. Registration snippet
+    // 7. Registration snippet
     let upper = block.name.to_uppercase();
     let registration_snippet = format!(
         r#"// Register block: {class_name}
@@ -1380,10 +2408,304 @@ public static final Item {upper}_ITEM = Registry.register(
         name = block.name,
     );
 
-    ToolResult::json(&json!({
+    let mut result = json!({
         "status": "generated",
         "created_files": created_files,
         "block_class_path": java_path.to_string_lossy(),
         "registration_snippet": registration_snippet
+    });
+
+    // 8. Optionally wire the snippet into the mod initializer
+    if register {
+        let imports = [
+            format!("{}.block.{}", package_name, class_name),
+            "net.alloymc.api.block.Block".to_string(),
+            "net.alloymc.api.block.Blocks".to_string(),
+            "net.alloymc.api.item.BlockItem".to_string(),
+            "net.alloymc.api.item.Item".to_string(),
+            "net.alloymc.api.item.ItemProperties".to_string(),
+            "net.alloymc.api.item.Items".to_string(),
+            "net.alloymc.api.registry.Registry".to_string(),
+        ];
+        let marker = format!("Block {} =", upper);
+        result["registration"] =
+            match register_in_initializer(project, &marker, &registration_snippet, &imports) {
+                Ok(registration) => registration,
+                Err(e) => {
+                    return ToolResult::error_with(
+                        ErrorCode::Io,
+                        format!("Failed to update mod initializer: {}", e),
+                    )
+                }
+            };
+    }
+
+    // 9. Record what was generated. The initializer collects entries from
+    // several blocks, so like tags it is shared rather than owned.
+    if let Some(init_path) = result.pointer("/registration/initializer_path").and_then(|p| p.as_str()) {
+        writer.record_shared(Path::new(init_path), "initializer");
+    }
+    result["skipped_modified"] = json!(writer.skipped_modified);
+    if !writer.skipped_modified.is_empty() {
+        result["warning"] = json!(
+            "Files in skipped_modified were edited by hand since they were generated and were left unchanged. Pass force: true to overwrite them."
+        );
+    }
+    if !writer.overwritten_modified.is_empty() {
+        result["overwritten_modified"] = json!(writer.overwritten_modified);
+    }
+    manifest.blocks.insert(block_key, writer.finish());
+    match manifest.save(project) {
+        Ok(()) => result["manifest"] = json!(generated::MANIFEST_PATH),
+        Err(e) => result["manifest_error"] = json!(e),
+    }
+
+    ToolResult::json(&result)
+}
+
+/// The vanilla `needs_*_tool` tag for a minimum tool tier. Wood (0) needs no
+/// tag, and diamond is the highest tier vanilla gates on.
+fn needs_tool_tag(tool_level: u32) -> Option<&'static str> {
+    match tool_level {
+        0 => None,
+        1 => Some("needs_stone_tool"),
+        2 => Some("needs_iron_tool"),
+        _ => Some("needs_diamond_tool"),
+    }
+}
+
+/// Add `id` to the tag file at `path`, creating it if needed and keeping any
+/// entries already there.
+fn append_to_tag(path: &Path, id: &str) -> Result<(), String> {
+    let mut tag: Value = match std::fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content).map_err(|e| e.to_string())?,
+        Err(_) => json!({ "replace": false, "values": [] }),
+    };
+    if !tag.get("values").is_some_and(|v| v.is_array()) {
+        tag["values"] = json!([]);
+    }
+    let values = tag["values"].as_array_mut().unwrap();
+    if !values.iter().any(|v| v.as_str() == Some(id)) {
+        values.push(json!(id));
+    }
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string_pretty(&tag).map_err(|e| e.to_string())?;
+    std::fs::write(path, content).map_err(|e| e.to_string())
+}
+
+/// Locate the mod initializer: the class named by `entry_point` in
+/// alloy.mod.json, else the first `ModInit.java` under src/main/java.
+fn find_mod_initializer(project: &Path) -> Option<PathBuf> {
+    let java_root = project.join("src/main/java");
+    let entry_point = std::fs::read_to_string(project.join("alloy.mod.json"))
+        .ok()
+        .and_then(|c| serde_json::from_str::<Value>(&c).ok())
+        .and_then(|m| {
+            m.get("entry_point")
+                .or_else(|| m.get("entrypoint"))
+                .and_then(|e| e.as_str())
+                .map(String::from)
+        });
+    if let Some(class) = entry_point {
+        let path = java_root.join(format!("{}.java", class.replace('.', "/")));
+        if path.is_file() {
+            return Some(path);
+        }
+    }
+
+    let mut candidates: Vec<PathBuf> = walkdir::WalkDir::new(&java_root)
+        .into_iter()
+        .flatten()
+        .filter(|e| e.file_type().is_file() && e.file_name() == "ModInit.java")
+        .map(|e| e.path().to_path_buf())
+        .collect();
+    candidates.sort();
+    candidates.into_iter().next()
+}
+
+/// Insert `snippet` at the top of the initializer's class body and add any
+/// missing `imports`. Returns a `registration` report; does nothing when
+/// `marker` shows the block is already registered.
+fn register_in_initializer(
+    project: &Path,
+    marker: &str,
+    snippet: &str,
+    imports: &[String],
+) -> Result<Value, String> {
+    let Some(init_path) = find_mod_initializer(project) else {
+        return Ok(json!({
+            "status": "initializer_not_found",
+            "message": "No mod initializer found (checked alloy.mod.json entry_point and ModInit.java). Paste registration_snippet manually."
+        }));
+    };
+    let source = std::fs::read_to_string(&init_path).map_err(|e| e.to_string())?;
+
+    if source.contains(marker) {
+        return Ok(json!({
+            "status": "already_registered",
+            "initializer_path": init_path.to_string_lossy()
+        }));
+    }
+
+    let class_name = init_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("ModInit");
+    let Some(updated) = insert_into_class_body(&source, class_name, snippet) else {
+        return Ok(json!({
+            "status": "initializer_not_found",
+            "initializer_path": init_path.to_string_lossy(),
+            "message": format!("Could not find the body of class {}. Paste registration_snippet manually.", class_name)
+        }));
+    };
+    let (updated, added_imports) = add_java_imports(&updated, imports);
+
+    std::fs::write(&init_path, updated).map_err(|e| e.to_string())?;
+    Ok(json!({
+        "status": "inserted",
+        "initializer_path": init_path.to_string_lossy(),
+        "added_imports": added_imports
+    }))
+}
+
+/// Insert `snippet`, indented one level, right after the opening brace of
+/// `class <class_name>`.
+fn insert_into_class_body(source: &str, class_name: &str, snippet: &str) -> Option<String> {
+    let decl = format!("class {}", class_name);
+    let decl_start = source.match_indices(&decl).map(|(i, _)| i).find(|&i| {
+        let after = source[i + decl.len()..].chars().next();
+        after.is_some_and(|c| !c.is_alphanumeric() && c != '_')
+    })?;
+    let brace = decl_start + source[decl_start..].find('{')?;
+    let insert_at = match source[brace..].find('\n') {
+        Some(nl) => brace + nl + 1,
+        None => source.len(),
+    };
+
+    let mut block = String::from("\n");
+    for line in snippet.lines() {
+        if !line.is_empty() {
+            block.push_str("    ");
+            block.push_str(line);
+        }
+        block.push('\n');
+    }
+
+    let mut out = String::with_capacity(source.len() + block.len());
+    out.push_str(&source[..insert_at]);
+    out.push_str(&block);
+    out.push_str(&source[insert_at..]);
+    Some(out)
+}
+
+/// Add `import` lines for any of `imports` the source doesn't already have,
+/// after the last existing import (or the package line). Returns the new
+/// source and the imports that were added.
+fn add_java_imports(source: &str, imports: &[String]) -> (String, Vec<String>) {
+    let missing: Vec<String> = imports
+        .iter()
+        .filter(|i| !source.contains(&format!("import {};", i)))
+        .cloned()
+        .collect();
+    if missing.is_empty() {
+        return (source.to_string(), missing);
+    }
+
+    let lines: Vec<&str> = source.lines().collect();
+    let anchor = lines
+        .iter()
+        .rposition(|l| l.trim_start().starts_with("import "))
+        .or_else(|| lines.iter().position(|l| l.trim_start().starts_with("package ")));
+
+    let new_lines: Vec<String> = missing.iter().map(|i| format!("import {};", i)).collect();
+    let mut out: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+    match anchor {
+        Some(idx) => {
+            let mut at = idx + 1;
+            // Keep a blank line between the package line and the first import
+            if lines[idx].trim_start().starts_with("package ") {
+                out.insert(at, String::new());
+                at += 1;
+            }
+            for (offset, line) in new_lines.into_iter().enumerate() {
+                out.insert(at + offset, line);
+            }
+        }
+        None => {
+            out.splice(0..0, new_lines.into_iter().chain(std::iter::once(String::new())));
+        }
+    }
+
+    let mut joined = out.join("\n");
+    if source.ends_with('\n') {
+        joined.push('\n');
+    }
+    (joined, missing)
+}
+
+// ---------------------------------------------------------------------------
+// block_check_item_models
+// ---------------------------------------------------------------------------
+
+async fn handle_block_check_item_models(params: Value) -> ToolResult {
+    let project_path = match require_str(&params, "project_path", "block_check_item_models") {
+        Ok(p) => p,
+        Err(e) => return e,
+    };
+
+    let project = Path::new(project_path);
+    let assets_dir = project.join("src/main/resources/assets");
+    if !assets_dir.is_dir() {
+        return ToolResult::error_with(ErrorCode::NotFound, format!(
+            "Assets directory does not exist: {}",
+            assets_dir.display()
+        ));
+    }
+
+    let namespaces = match std::fs::read_dir(&assets_dir) {
+        Ok(rd) => rd,
+        Err(e) => {
+            return ToolResult::error_with(
+                ErrorCode::from(&e),
+                format!("Failed to read assets directory: {}", e),
+            )
+        }
+    };
+
+    let mut checked: usize = 0;
+    let mut issues: Vec<Value> = Vec::new();
+
+    for namespace in namespaces.flatten() {
+        let item_dir = namespace.path().join("models/item");
+        if !item_dir.is_dir() {
+            continue;
+        }
+        for entry in walkdir::WalkDir::new(&item_dir)
+            .follow_links(false)
+            .into_iter()
+            .flatten()
+        {
+            let path = entry.path();
+            if !entry.file_type().is_file()
+                || path.extension().and_then(|e| e.to_str()) != Some("json")
+            {
+                continue;
+            }
+            checked += 1;
+            if let Some(issue) = check_item_model_parent(project, path) {
+                issues.push(issue);
+            }
+        }
+    }
+
+    ToolResult::json(&json!({
+        "project_path": project_path,
+        "models_checked": checked,
+        "issues": issues,
+        "issue_count": issues.len(),
+        "valid": issues.is_empty()
     }))
 }
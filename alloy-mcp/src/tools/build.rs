@@ -1,5 +1,6 @@
 use crate::state::ProjectState;
 use crate::types::{ToolDefinition, ToolResult};
+use alloy_common::gradle::SHELL_METACHARACTERS;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
@@ -16,24 +17,36 @@ pub fn definitions() -> Vec<ToolDefinition> {
     vec![
         ToolDefinition {
             name: "build_run".into(),
-            description: "Run a Gradle build task (build, clean, test, jar, etc.)".into(),
+            description: "Run a Gradle build task (build, clean, test, jar, etc.). The task is passed first, followed by args in order.".into(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
                     "task": { "type": "string", "description": "Gradle task name (default: \"build\")" },
-                    "args": { "type": "array", "items": { "type": "string" }, "description": "Additional Gradle arguments" }
+                    "args": { "type": "array", "items": { "type": "string" }, "description": "Additional Gradle arguments" },
+                    "offline": { "type": "boolean", "description": "Run with --offline (default: false)" },
+                    "project_path": { "type": "string", "description": "Project to build (defaults to the current project)" }
                 }
             }),
         },
         ToolDefinition {
             name: "build_list_tasks".into(),
             description: "List available Gradle tasks in the project".into(),
-            input_schema: json!({ "type": "object", "properties": {} }),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_path": { "type": "string", "description": "Project to inspect (defaults to the current project)" }
+                }
+            }),
         },
         ToolDefinition {
             name: "build_validate_env".into(),
             description: "Validate mod environment constraints (client/server import violations)".into(),
-            input_schema: json!({ "type": "object", "properties": {} }),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_path": { "type": "string", "description": "Project to validate (defaults to the current project)" }
+                }
+            }),
         },
         ToolDefinition {
             name: "build_get_errors".into(),
@@ -51,15 +64,15 @@ pub fn definitions() -> Vec<ToolDefinition> {
 pub async fn execute(name: &str, params: Value, state: &ProjectState) -> ToolResult {
     match name {
         "build_run" => build_run(params, state).await,
-        "build_list_tasks" => build_list_tasks(state).await,
-        "build_validate_env" => build_validate_env(state).await,
+        "build_list_tasks" => build_list_tasks(params, state).await,
+        "build_validate_env" => build_validate_env(params, state).await,
         "build_get_errors" => build_get_errors(params, state).await,
         _ => ToolResult::error(format!("Unknown build tool: {}", name)),
     }
 }
 
 async fn build_run(params: Value, state: &ProjectState) -> ToolResult {
-    let project_path = match state.project_path() {
+    let project_path = match state.resolve_project_path(&params) {
         Ok(p) => p,
         Err(e) => return ToolResult::error(e),
     };
@@ -80,12 +93,25 @@ async fn build_run(params: Value, state: &ProjectState) -> ToolResult {
     cmd.arg("--console=plain");
 
     // Add extra args if provided
-    if let Some(args) = params.get("args").and_then(|v| v.as_array()) {
-        for arg in args {
-            if let Some(a) = arg.as_str() {
-                cmd.arg(a);
-            }
-        }
+    let args: Vec<&str> = params
+        .get("args")
+        .and_then(|v| v.as_array())
+        .map(|a| a.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default();
+    if let Some(bad) = std::iter::once(task)
+        .chain(args.iter().copied())
+        .find(|a| a.contains(SHELL_METACHARACTERS))
+    {
+        return ToolResult::error(format!(
+            "Gradle argument contains shell metacharacters: {}",
+            bad
+        ));
+    }
+    cmd.args(&args);
+
+    let offline = params.get("offline").and_then(|v| v.as_bool()).unwrap_or(false);
+    if offline && !args.contains(&"--offline") {
+        cmd.arg("--offline");
     }
 
     cmd.current_dir(&project_path);
@@ -97,7 +123,7 @@ async fn build_run(params: Value, state: &ProjectState) -> ToolResult {
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     let stderr = String::from_utf8_lossy(&output.stderr);
-    let full_output = format!("{}{}", stdout, stderr);
+    let full_output = state.redactor.redact(&format!("{}{}", stdout, stderr));
 
     // Parse and cache errors
     let errors = parse_build_errors(&full_output);
@@ -123,8 +149,8 @@ async fn build_run(params: Value, state: &ProjectState) -> ToolResult {
     }
 }
 
-async fn build_list_tasks(state: &ProjectState) -> ToolResult {
-    let project_path = match state.project_path() {
+async fn build_list_tasks(params: Value, state: &ProjectState) -> ToolResult {
+    let project_path = match state.resolve_project_path(&params) {
         Ok(p) => p,
         Err(e) => return ToolResult::error(e),
     };
@@ -168,8 +194,8 @@ async fn build_list_tasks(state: &ProjectState) -> ToolResult {
     }
 }
 
-async fn build_validate_env(state: &ProjectState) -> ToolResult {
-    let project_path = match state.project_path() {
+async fn build_validate_env(params: Value, state: &ProjectState) -> ToolResult {
+    let project_path = match state.resolve_project_path(&params) {
         Ok(p) => p,
         Err(e) => return ToolResult::error(e),
     };
@@ -74,7 +74,7 @@ pub async fn execute(name: &str, params: Value) -> ToolResult {
 async fn editor_open(params: Value) -> ToolResult {
     let path = match params.get("path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: path"),
+        None => return ToolResult::missing_param("path"),
     };
 
     if !Path::new(path).exists() {
@@ -122,7 +122,7 @@ async fn editor_list_open() -> ToolResult {
 async fn editor_get_content(params: Value) -> ToolResult {
     let path = match params.get("path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: path"),
+        None => return ToolResult::missing_param("path"),
     };
 
     match std::fs::read_to_string(path) {
@@ -134,11 +134,11 @@ async fn editor_get_content(params: Value) -> ToolResult {
 async fn editor_set_content(params: Value) -> ToolResult {
     let path = match params.get("path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: path"),
+        None => return ToolResult::missing_param("path"),
     };
     let content = match params.get("content").and_then(|v| v.as_str()) {
         Some(c) => c,
-        None => return ToolResult::error("Missing required parameter: content"),
+        None => return ToolResult::missing_param("content"),
     };
 
     match std::fs::write(path, content) {
@@ -150,19 +150,19 @@ async fn editor_set_content(params: Value) -> ToolResult {
 async fn editor_insert_at(params: Value) -> ToolResult {
     let path = match params.get("path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: path"),
+        None => return ToolResult::missing_param("path"),
     };
     let line = match params.get("line").and_then(|v| v.as_u64()) {
         Some(l) => l as usize,
-        None => return ToolResult::error("Missing required parameter: line"),
+        None => return ToolResult::missing_param("line"),
     };
     let column = match params.get("column").and_then(|v| v.as_u64()) {
         Some(c) => c as usize,
-        None => return ToolResult::error("Missing required parameter: column"),
+        None => return ToolResult::missing_param("column"),
     };
     let text = match params.get("text").and_then(|v| v.as_str()) {
         Some(t) => t,
-        None => return ToolResult::error("Missing required parameter: text"),
+        None => return ToolResult::missing_param("text"),
     };
 
     let content = match std::fs::read_to_string(path) {
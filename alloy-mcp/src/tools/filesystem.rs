@@ -1,7 +1,13 @@
 use crate::state::ProjectState;
-use crate::types::{ToolDefinition, ToolResult};
+use crate::tools::paging;
+use crate::types::{require_str, ErrorCode, ProgressSink, ToolDefinition, ToolResult};
+use notify::{EventKind, RecursiveMode, Watcher};
 use serde_json::{json, Value};
-use std::path::Path;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 /// Return tool definitions for all filesystem tools.
 pub fn definitions() -> Vec<ToolDefinition> {
@@ -10,7 +16,10 @@ pub fn definitions() -> Vec<ToolDefinition> {
             name: "fs_list_directory".into(),
             description: "List files and directories at a path, sorted with directories first \
                 then files, both in alphabetical order. Hidden files (starting with '.') are \
-                skipped."
+                skipped. With recursive: true, lists the whole tree below the path as relative \
+                paths (skipping hidden, node_modules, target, build, and dist directories), up to \
+                max_depth levels (capped by the server's --max-walk-depth) and 1000 entries; \
+                truncated is set when the cap was hit."
                 .into(),
             input_schema: json!({
                 "type": "object",
@@ -18,19 +27,38 @@ pub fn definitions() -> Vec<ToolDefinition> {
                     "path": {
                         "type": "string",
                         "description": "Absolute path to list. Defaults to the project root if omitted."
+                    },
+                    "recursive": {
+                        "type": "boolean",
+                        "description": "List subdirectories too. Default: false."
+                    },
+                    "max_depth": {
+                        "type": "integer",
+                        "description": "How many levels to descend when recursive (default: 3, max: the server's walk depth, 10 unless configured)"
                     }
                 }
             }),
         },
         ToolDefinition {
             name: "fs_read_file".into(),
-            description: "Read the full contents of a file and return it as text.".into(),
+            description: "Read the contents of a file and return it as text. Pass start_line \
+                and/or end_line to read only part of it. Files over the server's --max-file-bytes \
+                limit (unlimited unless configured) can only be read in ranges."
+                .into(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
                     "path": {
                         "type": "string",
                         "description": "Absolute path to the file to read"
+                    },
+                    "start_line": {
+                        "type": "integer",
+                        "description": "First line to return, 1-based. Default: 1."
+                    },
+                    "end_line": {
+                        "type": "integer",
+                        "description": "Last line to return, inclusive. Default: the end of the file."
                     }
                 },
                 "required": ["path"]
@@ -124,17 +152,24 @@ pub fn definitions() -> Vec<ToolDefinition> {
         },
         ToolDefinition {
             name: "fs_copy".into(),
-            description: "Copy a file to a new destination. Does not copy directories.".into(),
+            description: "Copy a file to a new destination. With recursive set, copies a \
+                directory tree instead, skipping the same hidden and build directories as \
+                fs_search."
+                .into(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
                     "source": {
                         "type": "string",
-                        "description": "Absolute path of the source file"
+                        "description": "Absolute path of the source file or directory"
                     },
                     "destination": {
                         "type": "string",
-                        "description": "Absolute path for the destination file"
+                        "description": "Absolute path for the destination file or directory"
+                    },
+                    "recursive": {
+                        "type": "boolean",
+                        "description": "If true and source is a directory, recreate its tree under destination. Default: false."
                     }
                 },
                 "required": ["source", "destination"]
@@ -143,9 +178,12 @@ pub fn definitions() -> Vec<ToolDefinition> {
         ToolDefinition {
             name: "fs_search".into(),
             description: "Search file contents for a text pattern (grep-like). Walks directories \
-                up to depth 10, skipping hidden files, node_modules, target, build, and dist \
-                directories. Returns matching lines with file path and line number. Limited to \
-                100 results."
+                up to depth 10 (or the server's --max-walk-depth), skipping hidden files, node_modules, target, build, and dist \
+                directories, and inside a git repo anything .gitignore excludes. Returns matching lines with file path and line number, 100 per page \
+                by default (or the server's --max-search-results). When more matches remain the result includes next_cursor; pass it \
+                back as cursor to continue where the previous call stopped. Clients that send a \
+                progress token also receive matches as they are found, one progress \
+                notification per file, ahead of the complete result."
                 .into(),
             input_schema: json!({
                 "type": "object",
@@ -160,7 +198,19 @@ pub fn definitions() -> Vec<ToolDefinition> {
                     },
                     "glob": {
                         "type": "string",
-                        "description": "Optional glob filter for file names (e.g. '*.java', '*.rs')"
+                        "description": "Optional glob filter (e.g. '*.java', '*.{json,toml}', 'src/**/*.json'). Patterns without '/' match the file name; patterns with '/' match the path relative to the search directory."
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum matches to return (default: 100 or the server's --max-search-results, max: 1000 or that value if larger)"
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "next_cursor from a previous call with the same query, path, and glob"
+                    },
+                    "include_ignored": {
+                        "type": "boolean",
+                        "description": "If true, also search files excluded by .gitignore. Default: false."
                     }
                 },
                 "required": ["query"]
@@ -170,18 +220,19 @@ pub fn definitions() -> Vec<ToolDefinition> {
             name: "fs_replace".into(),
             description: "Find and replace text across files in a directory tree. Walks \
                 directories with the same filters as fs_search. Supports dry_run mode to preview \
-                changes without modifying files."
+                changes without modifying files, and regex mode for structural rewrites using \
+                capture groups ($1, ${name}) in the replacement."
                 .into(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
                     "search": {
                         "type": "string",
-                        "description": "Text to search for (exact string match)"
+                        "description": "Text to search for (exact string match, or a regular expression when regex is true)"
                     },
                     "replace": {
                         "type": "string",
-                        "description": "Replacement text"
+                        "description": "Replacement text. In regex mode, $1 or ${name} insert capture groups."
                     },
                     "path": {
                         "type": "string",
@@ -189,32 +240,77 @@ pub fn definitions() -> Vec<ToolDefinition> {
                     },
                     "glob": {
                         "type": "string",
-                        "description": "Optional glob filter for file names (e.g. '*.java', '*.rs')"
+                        "description": "Optional glob filter (e.g. '*.java', '*.{json,toml}', 'src/**/*.json'). Patterns without '/' match the file name; patterns with '/' match the path relative to the search directory."
                     },
                     "dry_run": {
                         "type": "boolean",
                         "description": "If true, report what would change without modifying files. Default: false."
+                    },
+                    "regex": {
+                        "type": "boolean",
+                        "description": "If true, treat search as a regular expression. Default: false."
+                    },
+                    "include_ignored": {
+                        "type": "boolean",
+                        "description": "If true, also rewrite files excluded by .gitignore. Default: false."
                     }
                 },
                 "required": ["search", "replace"]
             }),
         },
+        ToolDefinition {
+            name: "fs_watch".into(),
+            description: "Watch a directory tree for file changes. Call without watcher_id to \
+                register a watcher and get its id; call again with the watcher_id to receive \
+                the created/modified/removed events accumulated since the previous call. Rapid \
+                bursts on the same file are coalesced into one event. Skips the same hidden and \
+                build directories as fs_search. Watchers that are not polled for 5 minutes \
+                expire automatically."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Directory to watch recursively. Defaults to the project root. Ignored when polling."
+                    },
+                    "glob": {
+                        "type": "string",
+                        "description": "Optional glob filter with the same syntax as fs_search. Ignored when polling."
+                    },
+                    "watcher_id": {
+                        "type": "string",
+                        "description": "Id returned by a previous fs_watch call. When present, returns pending events instead of registering a new watcher."
+                    },
+                    "stop": {
+                        "type": "boolean",
+                        "description": "With watcher_id, stop and remove the watcher after returning its final events. Default: false."
+                    }
+                }
+            }),
+        },
     ]
 }
 
 /// Dispatch execution to the appropriate filesystem tool handler.
-pub async fn execute(name: &str, params: Value, state: &ProjectState) -> ToolResult {
+pub async fn execute(
+    name: &str,
+    params: Value,
+    state: &ProjectState,
+    progress: Option<&ProgressSink>,
+) -> ToolResult {
     match name {
         "fs_list_directory" => handle_list_directory(params, state).await,
-        "fs_read_file" => handle_read_file(params).await,
+        "fs_read_file" => handle_read_file(params, state).await,
         "fs_write_file" => handle_write_file(params).await,
         "fs_create_file" => handle_create_file(params).await,
         "fs_create_directory" => handle_create_directory(params).await,
         "fs_delete" => handle_delete(params).await,
         "fs_rename" => handle_rename(params).await,
         "fs_copy" => handle_copy(params).await,
-        "fs_search" => handle_search(params, state).await,
+        "fs_search" => handle_search(params, state, progress).await,
         "fs_replace" => handle_replace(params, state).await,
+        "fs_watch" => handle_watch(params, state).await,
         _ => ToolResult::error(format!("Unknown filesystem tool: {}", name)),
     }
 }
@@ -244,26 +340,219 @@ fn is_skipped_dir(name: &str) -> bool {
         || name == "dist"
 }
 
-/// Check if a filename matches a simple glob pattern.
-/// Supports patterns like "*.java", "*.rs", "Cargo.*", etc.
-fn matches_glob(filename: &str, pattern: &str) -> bool {
-    if pattern == "*" {
-        return true;
+/// Default page size for fs_search.
+pub const DEFAULT_MAX_SEARCH_RESULTS: usize = 100;
+
+/// Default depth fs_search and fs_replace descend below the search root.
+pub const DEFAULT_MAX_WALK_DEPTH: usize = 10;
+
+/// Server-wide limits for the filesystem tools, set from `alloy-mcp`'s
+/// command line.
+#[derive(Debug, Clone, Copy)]
+pub struct FsLimits {
+    /// fs_search page size when the caller gives no `limit`
+    pub max_search_results: usize,
+    /// How deep fs_search/fs_replace walk, and the cap on fs_list_directory's `max_depth`
+    pub max_walk_depth: usize,
+    /// Files larger than this can only be read in line ranges; `None` is unlimited
+    pub max_file_bytes: Option<u64>,
+}
+
+impl Default for FsLimits {
+    fn default() -> Self {
+        Self {
+            max_search_results: DEFAULT_MAX_SEARCH_RESULTS,
+            max_walk_depth: DEFAULT_MAX_WALK_DEPTH,
+            max_file_bytes: None,
+        }
     }
+}
 
-    if let Some(suffix) = pattern.strip_prefix("*.") {
-        // *.ext pattern
-        return filename.ends_with(&format!(".{}", suffix));
+/// Files fs_search and fs_replace visit under `root`, at most `max_depth`
+/// levels down and sorted by path so a cursor can resume. Hidden and build
+/// directories are always skipped; inside a git repo .gitignore is honoured
+/// too unless `include_ignored` is set.
+pub(crate) fn search_files(root: &Path, max_depth: usize, include_ignored: bool) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = ignore::WalkBuilder::new(root)
+        .max_depth(Some(max_depth))
+        .follow_links(false)
+        .standard_filters(!include_ignored)
+        // Hidden entries are left to is_skipped_dir, which applies either way
+        .hidden(false)
+        .filter_entry(|e| e.depth() == 0 || !is_skipped_dir(&e.file_name().to_string_lossy()))
+        .build()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_some_and(|t| t.is_file()))
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+    files
+}
+
+/// Write `content` to `path` by writing a sibling temp file and renaming it
+/// over the original, so a failure mid-write never leaves a truncated file.
+/// The temp file sits in the same directory, so the rename never crosses
+/// devices.
+fn write_atomic(path: &Path, content: &str) -> std::io::Result<()> {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let temp_path = path.with_file_name(format!(".{}.alloy-tmp-{}", file_name, std::process::id()));
+
+    if let Err(e) = std::fs::write(&temp_path, content) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(e);
+    }
+    // Keep the original file's permissions (e.g. executable scripts)
+    if let Ok(metadata) = std::fs::metadata(path) {
+        let _ = std::fs::set_permissions(&temp_path, metadata.permissions());
+    }
+
+    std::fs::rename(&temp_path, path).inspect_err(|_| {
+        let _ = std::fs::remove_file(&temp_path);
+    })
+}
+
+/// Check if a file matches a glob pattern.
+///
+/// Supports `*` (any characters within a path segment), `?` (a single
+/// character), `[abc]`/`[a-z]`/`[!a-z]` character classes, `**` (zero or
+/// more whole path segments), and `{a,b}` alternation. Patterns containing a
+/// `/` are matched against the path relative to the search root; patterns
+/// without one are matched against the file name alone, so `*.java` finds
+/// Java files at any depth.
+pub(crate) fn matches_glob(relative_path: &str, pattern: &str) -> bool {
+    let relative_path = relative_path.replace('\\', "/");
+    let target = if pattern.contains('/') {
+        relative_path.as_str()
+    } else {
+        relative_path.rsplit('/').next().unwrap_or(&relative_path)
+    };
+    matches_path_glob(target, pattern)
+}
+
+/// Match a glob against the whole `/`-separated `path`, even when the
+/// pattern has no `/` of its own.
+pub(crate) fn matches_path_glob(path: &str, pattern: &str) -> bool {
+    let target_segments: Vec<&str> = path.split('/').collect();
+    expand_braces(pattern).iter().any(|alt| {
+        let pattern_segments: Vec<&str> = alt.split('/').collect();
+        match_segments(&pattern_segments, &target_segments)
+    })
+}
+
+/// Expand `{a,b}` alternation groups into every concrete pattern they
+/// describe. Nested groups are expanded recursively; an unbalanced `{` is
+/// treated as a literal character.
+fn expand_braces(pattern: &str) -> Vec<String> {
+    let Some(open) = pattern.find('{') else {
+        return vec![pattern.to_string()];
+    };
+
+    let mut depth = 0;
+    let mut close = None;
+    let mut splits = Vec::new();
+    for (i, c) in pattern[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(open + i);
+                    break;
+                }
+            }
+            ',' if depth == 1 => splits.push(open + i),
+            _ => {}
+        }
+    }
+    let Some(close) = close else {
+        return vec![pattern.to_string()];
+    };
+
+    let prefix = &pattern[..open];
+    let suffix = &pattern[close + 1..];
+    let mut bounds = vec![open];
+    bounds.extend(splits);
+    bounds.push(close);
+
+    let mut expanded = Vec::new();
+    for window in bounds.windows(2) {
+        let option = &pattern[window[0] + 1..window[1]];
+        expanded.extend(expand_braces(&format!("{}{}{}", prefix, option, suffix)));
+    }
+    expanded
+}
+
+/// Match pattern segments against path segments, where a `**` segment
+/// consumes zero or more path segments.
+fn match_segments(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| match_segments(rest, &path[skip..])),
+        Some((segment, rest)) => match path.split_first() {
+            Some((name, path_rest)) => {
+                match_segment(segment.as_bytes(), name.as_bytes()) && match_segments(rest, path_rest)
+            }
+            None => false,
+        },
     }
+}
 
-    if let Some(prefix) = pattern.strip_suffix(".*") {
-        // prefix.* pattern
-        let base = filename.split('.').next().unwrap_or(filename);
-        return base == prefix;
+/// Match a single path segment against a pattern segment containing `*`,
+/// `?` and `[...]` wildcards.
+fn match_segment(pattern: &[u8], name: &[u8]) -> bool {
+    let first_char = || std::str::from_utf8(name).ok().and_then(|n| n.chars().next());
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((b'*', rest)) => (0..=name.len()).any(|skip| match_segment(rest, &name[skip..])),
+        Some((b'?', rest)) => match first_char() {
+            Some(c) => match_segment(rest, &name[c.len_utf8()..]),
+            None => false,
+        },
+        Some((b'[', rest)) => {
+            // A `]` right after the `[` (or `[!`) is part of the class
+            let start = if matches!(rest.first(), Some(b'!' | b'^')) { 2 } else { 1 };
+            match rest.iter().skip(start).position(|&b| b == b']') {
+                Some(i) => {
+                    let (class, after) = (&rest[..start + i], &rest[start + i + 1..]);
+                    match first_char() {
+                        Some(c) => {
+                            class_matches(class, c) && match_segment(after, &name[c.len_utf8()..])
+                        }
+                        None => false,
+                    }
+                }
+                // An unclosed `[` is a literal character
+                None => name.first() == Some(&b'[') && match_segment(rest, &name[1..]),
+            }
+        }
+        Some((c, rest)) => name.first() == Some(c) && match_segment(rest, &name[1..]),
     }
+}
 
-    // Exact match fallback
-    filename == pattern
+/// Whether `c` is in the body of a `[...]` class such as `abc`, `a-z` or
+/// `!0-9`. A leading `!` or `^` negates the class.
+fn class_matches(class: &[u8], c: char) -> bool {
+    let class = String::from_utf8_lossy(class);
+    let (negated, body) = match class.strip_prefix(['!', '^']) {
+        Some(body) => (true, body),
+        None => (false, &*class),
+    };
+    let chars: Vec<char> = body.chars().collect();
+    let mut found = false;
+    let mut i = 0;
+    while i < chars.len() {
+        if i + 2 < chars.len() && chars[i + 1] == '-' {
+            found |= (chars[i]..=chars[i + 2]).contains(&c);
+            i += 3;
+        } else {
+            found |= chars[i] == c;
+            i += 1;
+        }
+    }
+    found != negated
 }
 
 // ---------------------------------------------------------------------------
@@ -273,20 +562,36 @@ fn matches_glob(filename: &str, pattern: &str) -> bool {
 async fn handle_list_directory(params: Value, state: &ProjectState) -> ToolResult {
     let path = match resolve_path(&params, "path", state) {
         Ok(p) => p,
-        Err(e) => return ToolResult::error(e),
+        Err(e) => return ToolResult::error_with(ErrorCode::InvalidParams, e),
     };
 
     let dir = Path::new(&path);
     if !dir.exists() {
-        return ToolResult::error(format!("Directory does not exist: {}", path));
+        return ToolResult::error_with(
+            ErrorCode::NotFound,
+            format!("Directory does not exist: {}", path),
+        );
     }
     if !dir.is_dir() {
-        return ToolResult::error(format!("Path is not a directory: {}", path));
+        return ToolResult::error_with(
+            ErrorCode::InvalidParams,
+            format!("Path is not a directory: {}", path),
+        );
+    }
+
+    if params.get("recursive").and_then(|v| v.as_bool()).unwrap_or(false) {
+        let max_depth = paging::limit(params.get("max_depth"), 3, state.fs_limits.max_walk_depth);
+        return list_directory_recursive(&path, max_depth);
     }
 
     let entries = match std::fs::read_dir(dir) {
         Ok(rd) => rd,
-        Err(e) => return ToolResult::error(format!("Failed to read directory: {}", e)),
+        Err(e) => {
+            return ToolResult::error_with(
+                ErrorCode::from(&e),
+                format!("Failed to read directory: {}", e),
+            )
+        }
     };
 
     let mut dirs: Vec<String> = Vec::new();
@@ -330,28 +635,152 @@ async fn handle_list_directory(params: Value, state: &ProjectState) -> ToolResul
     }))
 }
 
+/// Most entries a recursive listing returns before it stops and reports truncation.
+const LIST_MAX_ENTRIES: usize = 1000;
+
+/// Walk `path` up to `max_depth` levels, listing each level directories first.
+fn list_directory_recursive(path: &str, max_depth: usize) -> ToolResult {
+    let root = Path::new(path);
+    let walker = walkdir::WalkDir::new(root)
+        .min_depth(1)
+        .max_depth(max_depth)
+        .follow_links(false)
+        .sort_by(|a, b| {
+            b.file_type()
+                .is_dir()
+                .cmp(&a.file_type().is_dir())
+                .then_with(|| {
+                    let a = a.file_name().to_string_lossy().to_lowercase();
+                    let b = b.file_name().to_string_lossy().to_lowercase();
+                    a.cmp(&b)
+                })
+        })
+        .into_iter()
+        .filter_entry(|e| !is_skipped_dir(&e.file_name().to_string_lossy()));
+
+    let mut listing: Vec<Value> = Vec::new();
+    let mut truncated = false;
+    for entry in walker.flatten() {
+        if listing.len() == LIST_MAX_ENTRIES {
+            truncated = true;
+            break;
+        }
+        let rel = entry
+            .path()
+            .strip_prefix(root)
+            .unwrap_or(entry.path())
+            .to_string_lossy()
+            .replace('\\', "/");
+        if entry.file_type().is_dir() {
+            listing.push(json!({ "name": format!("{}/", rel), "type": "directory", "depth": entry.depth() }));
+        } else {
+            listing.push(json!({ "name": rel, "type": "file", "depth": entry.depth() }));
+        }
+    }
+
+    ToolResult::json(&json!({
+        "path": path,
+        "entries": listing,
+        "total": listing.len(),
+        "max_depth": max_depth,
+        "truncated": truncated
+    }))
+}
+
 // ---------------------------------------------------------------------------
 // fs_read_file
 // ---------------------------------------------------------------------------
 
-async fn handle_read_file(params: Value) -> ToolResult {
-    let path = match params.get("path").and_then(|v| v.as_str()) {
-        Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: path"),
+async fn handle_read_file(params: Value, state: &ProjectState) -> ToolResult {
+    let path = match require_str(&params, "path", "fs_read_file") {
+        Ok(p) => p,
+        Err(e) => return e,
     };
 
     let file_path = Path::new(path);
     if !file_path.exists() {
-        return ToolResult::error(format!("File does not exist: {}", path));
+        return ToolResult::error_with(
+            ErrorCode::NotFound,
+            format!("File does not exist: {}", path),
+        );
     }
     if !file_path.is_file() {
-        return ToolResult::error(format!("Path is not a file: {}", path));
+        return ToolResult::error_with(
+            ErrorCode::InvalidParams,
+            format!("Path is not a file: {}", path),
+        );
+    }
+
+    let start_line = params.get("start_line").and_then(|v| v.as_u64());
+    let end_line = params.get("end_line").and_then(|v| v.as_u64());
+    if start_line.is_some() || end_line.is_some() {
+        return read_line_range(file_path, start_line.unwrap_or(1), end_line);
+    }
+
+    if let Some(max_bytes) = state.fs_limits.max_file_bytes {
+        let size = std::fs::metadata(file_path).map(|m| m.len()).unwrap_or(0);
+        if size > max_bytes {
+            return ToolResult::error_with(ErrorCode::Unsupported, format!(
+                "{} is {} bytes, over this server's max_file_bytes limit of {}. \
+                 Read it in parts with start_line and end_line instead.",
+                path, size, max_bytes
+            ));
+        }
     }
 
     match std::fs::read_to_string(file_path) {
         Ok(content) => ToolResult::text(content),
-        Err(e) => ToolResult::error(format!("Failed to read file: {}", e)),
+        Err(e) => ToolResult::error_with(
+            ErrorCode::from(&e),
+            format!("Failed to read file: {}", e),
+        ),
+    }
+}
+
+/// Lines `start..=end` of a file (1-based), streamed so huge files are never
+/// loaded whole.
+fn read_line_range(path: &Path, start: u64, end: Option<u64>) -> ToolResult {
+    use std::io::BufRead;
+    let start = start.max(1);
+    if end.is_some_and(|end| end < start) {
+        return ToolResult::error_with(
+            ErrorCode::InvalidParams,
+            "end_line must not be before start_line",
+        );
+    }
+    let file = match std::fs::File::open(path) {
+        Ok(f) => f,
+        Err(e) => {
+            return ToolResult::error_with(
+                ErrorCode::from(&e),
+                format!("Failed to read file: {}", e),
+            )
+        }
+    };
+
+    let mut text = String::new();
+    for (i, line) in std::io::BufReader::new(file).lines().enumerate() {
+        let number = i as u64 + 1;
+        if number < start {
+            continue;
+        }
+        if end.is_some_and(|end| number > end) {
+            break;
+        }
+        match line {
+            Ok(line) => {
+                text.push_str(&line);
+                text.push('\n');
+            }
+            Err(e) => {
+                return ToolResult::error_with(
+                    ErrorCode::from(&e),
+                    format!("Failed to read file: {}", e),
+                )
+            }
+        }
     }
+    ToolResult::text(text)
 }
 
 // ---------------------------------------------------------------------------
@@ -359,13 +788,13 @@ async fn handle_read_file(params: Value) -> ToolResult {
 // ---------------------------------------------------------------------------
 
 async fn handle_write_file(params: Value) -> ToolResult {
-    let path = match params.get("path").and_then(|v| v.as_str()) {
-        Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: path"),
+    let path = match require_str(&params, "path", "fs_write_file") {
+        Ok(p) => p,
+        Err(e) => return e,
     };
-    let content = match params.get("content").and_then(|v| v.as_str()) {
-        Some(c) => c,
-        None => return ToolResult::error("Missing required parameter: content"),
+    let content = match require_str(&params, "content", "fs_write_file") {
+        Ok(c) => c,
+        Err(e) => return e,
     };
 
     match std::fs::write(path, content) {
@@ -377,7 +806,10 @@ async fn handle_write_file(params: Value) -> ToolResult {
                 "bytes": bytes
             }))
         }
-        Err(e) => ToolResult::error(format!("Failed to write file '{}': {}", path, e)),
+        Err(e) => ToolResult::error_with(
+            ErrorCode::from(&e),
+            format!("Failed to write file '{}': {}", path, e),
+        ),
     }
 }
 
@@ -386,9 +818,9 @@ async fn handle_write_file(params: Value) -> ToolResult {
 // ---------------------------------------------------------------------------
 
 async fn handle_create_file(params: Value) -> ToolResult {
-    let path = match params.get("path").and_then(|v| v.as_str()) {
-        Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: path"),
+    let path = match require_str(&params, "path", "fs_create_file") {
+        Ok(p) => p,
+        Err(e) => return e,
     };
     let content = params
         .get("content")
@@ -398,7 +830,7 @@ async fn handle_create_file(params: Value) -> ToolResult {
     let file_path = Path::new(path);
 
     if file_path.exists() {
-        return ToolResult::error(format!(
+        return ToolResult::error_with(ErrorCode::Conflict, format!(
             "File already exists: {}. Use fs_write_file to overwrite.",
             path
         ));
@@ -408,7 +840,7 @@ async fn handle_create_file(params: Value) -> ToolResult {
     if let Some(parent) = file_path.parent() {
         if !parent.exists() {
             if let Err(e) = std::fs::create_dir_all(parent) {
-                return ToolResult::error(format!(
+                return ToolResult::error_with(ErrorCode::from(&e), format!(
                     "Failed to create parent directories for '{}': {}",
                     path, e
                 ));
@@ -422,7 +854,10 @@ async fn handle_create_file(params: Value) -> ToolResult {
             "path": path,
             "bytes": content.len()
         })),
-        Err(e) => ToolResult::error(format!("Failed to create file '{}': {}", path, e)),
+        Err(e) => ToolResult::error_with(
+            ErrorCode::from(&e),
+            format!("Failed to create file '{}': {}", path, e),
+        ),
     }
 }
 
@@ -431,9 +866,9 @@ async fn handle_create_file(params: Value) -> ToolResult {
 // ---------------------------------------------------------------------------
 
 async fn handle_create_directory(params: Value) -> ToolResult {
-    let path = match params.get("path").and_then(|v| v.as_str()) {
-        Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: path"),
+    let path = match require_str(&params, "path", "fs_create_directory") {
+        Ok(p) => p,
+        Err(e) => return e,
     };
 
     match std::fs::create_dir_all(path) {
@@ -441,7 +876,10 @@ async fn handle_create_directory(params: Value) -> ToolResult {
             "status": "created",
             "path": path
         })),
-        Err(e) => ToolResult::error(format!("Failed to create directory '{}': {}", path, e)),
+        Err(e) => ToolResult::error_with(
+            ErrorCode::from(&e),
+            format!("Failed to create directory '{}': {}", path, e),
+        ),
     }
 }
 
@@ -450,14 +888,17 @@ async fn handle_create_directory(params: Value) -> ToolResult {
 // ---------------------------------------------------------------------------
 
 async fn handle_delete(params: Value) -> ToolResult {
-    let path = match params.get("path").and_then(|v| v.as_str()) {
-        Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: path"),
+    let path = match require_str(&params, "path", "fs_delete") {
+        Ok(p) => p,
+        Err(e) => return e,
     };
 
     let target = Path::new(path);
     if !target.exists() {
-        return ToolResult::error(format!("Path does not exist: {}", path));
+        return ToolResult::error_with(
+            ErrorCode::NotFound,
+            format!("Path does not exist: {}", path),
+        );
     }
 
     let result = if target.is_dir() {
@@ -471,7 +912,10 @@ async fn handle_delete(params: Value) -> ToolResult {
             "status": "deleted",
             "path": path
         })),
-        Err(e) => ToolResult::error(format!("Failed to delete '{}': {}", path, e)),
+        Err(e) => ToolResult::error_with(
+            ErrorCode::from(&e),
+            format!("Failed to delete '{}': {}", path, e),
+        ),
     }
 }
 
@@ -480,17 +924,20 @@ async fn handle_delete(params: Value) -> ToolResult {
 // ---------------------------------------------------------------------------
 
 async fn handle_rename(params: Value) -> ToolResult {
-    let old_path = match params.get("old_path").and_then(|v| v.as_str()) {
-        Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: old_path"),
+    let old_path = match require_str(&params, "old_path", "fs_rename") {
+        Ok(p) => p,
+        Err(e) => return e,
     };
-    let new_path = match params.get("new_path").and_then(|v| v.as_str()) {
-        Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: new_path"),
+    let new_path = match require_str(&params, "new_path", "fs_rename") {
+        Ok(p) => p,
+        Err(e) => return e,
     };
 
     if !Path::new(old_path).exists() {
-        return ToolResult::error(format!("Source does not exist: {}", old_path));
+        return ToolResult::error_with(
+            ErrorCode::NotFound,
+            format!("Source does not exist: {}", old_path),
+        );
     }
 
     match std::fs::rename(old_path, new_path) {
@@ -499,7 +946,7 @@ async fn handle_rename(params: Value) -> ToolResult {
             "old_path": old_path,
             "new_path": new_path
         })),
-        Err(e) => ToolResult::error(format!(
+        Err(e) => ToolResult::error_with(ErrorCode::from(&e), format!(
             "Failed to rename '{}' to '{}': {}",
             old_path, new_path, e
         )),
@@ -511,21 +958,32 @@ async fn handle_rename(params: Value) -> ToolResult {
 // ---------------------------------------------------------------------------
 
 async fn handle_copy(params: Value) -> ToolResult {
-    let source = match params.get("source").and_then(|v| v.as_str()) {
-        Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: source"),
+    let source = match require_str(&params, "source", "fs_copy") {
+        Ok(p) => p,
+        Err(e) => return e,
     };
-    let destination = match params.get("destination").and_then(|v| v.as_str()) {
-        Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: destination"),
+    let destination = match require_str(&params, "destination", "fs_copy") {
+        Ok(p) => p,
+        Err(e) => return e,
     };
 
+    let recursive = params
+        .get("recursive")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
     let src = Path::new(source);
     if !src.exists() {
-        return ToolResult::error(format!("Source does not exist: {}", source));
+        return ToolResult::error_with(
+            ErrorCode::NotFound,
+            format!("Source does not exist: {}", source),
+        );
+    }
+    if recursive && src.is_dir() {
+        return copy_directory(src, Path::new(destination));
     }
     if !src.is_file() {
-        return ToolResult::error(format!(
+        return ToolResult::error_with(ErrorCode::InvalidParams, format!(
             "Source is not a file: {}. Use fs_copy only for files.",
             source
         ));
@@ -538,98 +996,200 @@ async fn handle_copy(params: Value) -> ToolResult {
             "destination": destination,
             "bytes": bytes
         })),
-        Err(e) => ToolResult::error(format!(
+        Err(e) => ToolResult::error_with(ErrorCode::from(&e), format!(
             "Failed to copy '{}' to '{}': {}",
             source, destination, e
         )),
     }
 }
 
+/// Recreate the directory tree at `src` under `dest`, applying the standard
+/// skip rules. Refuses to copy a directory into itself.
+fn copy_directory(src: &Path, dest: &Path) -> ToolResult {
+    let src_canonical = match src.canonicalize() {
+        Ok(p) => p,
+        Err(e) => {
+            return ToolResult::error_with(
+                ErrorCode::from(&e),
+                format!("Failed to resolve '{}': {}", src.display(), e),
+            )
+        }
+    };
+    // The destination may not exist yet, so resolve its nearest existing ancestor
+    let dest_resolved = dest
+        .ancestors()
+        .find_map(|a| {
+            a.canonicalize()
+                .ok()
+                .map(|c| c.join(dest.strip_prefix(a).unwrap_or(Path::new(""))))
+        })
+        .unwrap_or_else(|| dest.to_path_buf());
+    if dest_resolved.starts_with(&src_canonical) {
+        return ToolResult::error_with(ErrorCode::InvalidParams, format!(
+            "Cannot copy '{}' into itself ('{}'). Choose a destination outside the source directory.",
+            src.display(),
+            dest.display()
+        ));
+    }
+
+    let mut files_copied: usize = 0;
+    let mut directories_created: usize = 0;
+    let mut bytes: u64 = 0;
+
+    let walker = walkdir::WalkDir::new(src).follow_links(false).into_iter();
+    for entry in walker.filter_entry(|e| {
+        let name = e.file_name().to_string_lossy();
+        !(e.depth() > 0 && is_skipped_dir(&name))
+    }) {
+        let entry = match entry {
+            Ok(e) => e,
+            Err(e) => {
+                return ToolResult::error_with(
+                    ErrorCode::Io,
+                    format!("Failed to walk '{}': {}", src.display(), e),
+                )
+            }
+        };
+        let relative = entry.path().strip_prefix(src).unwrap_or(entry.path());
+        let target = dest.join(relative);
+
+        if entry.file_type().is_dir() {
+            if !target.exists() {
+                if let Err(e) = std::fs::create_dir_all(&target) {
+                    return ToolResult::error_with(ErrorCode::from(&e), format!(
+                        "Failed to create directory '{}': {}",
+                        target.display(),
+                        e
+                    ));
+                }
+                directories_created += 1;
+            }
+        } else if entry.file_type().is_file() {
+            match std::fs::copy(entry.path(), &target) {
+                Ok(n) => {
+                    files_copied += 1;
+                    bytes += n;
+                }
+                Err(e) => {
+                    return ToolResult::error_with(ErrorCode::from(&e), format!(
+                        "Failed to copy '{}' to '{}': {}",
+                        entry.path().display(),
+                        target.display(),
+                        e
+                    ))
+                }
+            }
+        }
+    }
+
+    ToolResult::json(&json!({
+        "status": "copied",
+        "source": src.display().to_string(),
+        "destination": dest.display().to_string(),
+        "recursive": true,
+        "files_copied": files_copied,
+        "directories_created": directories_created,
+        "bytes": bytes
+    }))
+}
+
 // ---------------------------------------------------------------------------
 // fs_search
 // ---------------------------------------------------------------------------
 
-async fn handle_search(params: Value, state: &ProjectState) -> ToolResult {
-    let query = match params.get("query").and_then(|v| v.as_str()) {
-        Some(q) => q,
-        None => return ToolResult::error("Missing required parameter: query"),
+async fn handle_search(params: Value, state: &ProjectState, progress: Option<&ProgressSink>) -> ToolResult {
+    let query = match require_str(&params, "query", "fs_search") {
+        Ok(q) => q,
+        Err(e) => return e,
     };
     let search_path = match resolve_path(&params, "path", state) {
         Ok(p) => p,
-        Err(e) => return ToolResult::error(e),
+        Err(e) => return ToolResult::error_with(ErrorCode::InvalidParams, e),
     };
     let glob_filter = params.get("glob").and_then(|v| v.as_str());
 
     let root = Path::new(&search_path);
     if !root.exists() {
-        return ToolResult::error(format!("Search path does not exist: {}", search_path));
+        return ToolResult::error_with(
+            ErrorCode::NotFound,
+            format!("Search path does not exist: {}", search_path),
+        );
     }
 
-    let max_results: usize = 100;
-    let max_depth: usize = 10;
+    let limits = state.fs_limits;
+    let limit = paging::limit(
+        params.get("limit"),
+        limits.max_search_results,
+        limits.max_search_results.max(1000),
+    );
+    let resume = match paging::decode_cursor(&params) {
+        Ok(Some(cursor)) => match paging::FilePosition::from_cursor(&cursor) {
+            Ok(p) => Some(p),
+            Err(e) => return ToolResult::error_with(ErrorCode::InvalidParams, e),
+        },
+        Ok(None) => None,
+        Err(e) => return ToolResult::error_with(ErrorCode::InvalidParams, e),
+    };
+    let include_ignored = params
+        .get("include_ignored")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
     let mut results: Vec<Value> = Vec::new();
-    let mut truncated = false;
-
-    let walker = walkdir::WalkDir::new(root)
-        .max_depth(max_depth)
-        .follow_links(false)
-        .into_iter();
-
-    for entry in walker.filter_entry(|e| {
-        // Skip hidden directories and common noise directories
-        let name = e.file_name().to_string_lossy();
-        if e.depth() > 0 && is_skipped_dir(&name) {
-            return false;
-        }
-        true
-    }) {
-        let entry = match entry {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
+    let mut last: Option<paging::FilePosition> = None;
+    let mut next: Option<paging::FilePosition> = None;
 
-        if !entry.file_type().is_file() {
-            continue;
+    // Streaming callers get each file's matches as soon as it has been searched
+    let report = |batch: &[Value], found: usize| {
+        if let (Some(progress), false) = (progress, batch.is_empty()) {
+            progress.send(found as f64, json!({ "results": batch, "count_so_far": found }));
         }
+    };
 
-        let file_name = entry.file_name().to_string_lossy().to_string();
-
-        // Skip hidden files
-        if file_name.starts_with('.') {
+    // Sorted so every call walks files in the same order and a cursor can resume
+    'walk: for file_path in search_files(root, limits.max_walk_depth, include_ignored) {
+        let relative = file_path.strip_prefix(root).unwrap_or(&file_path);
+        if resume.as_ref().is_some_and(|r| r.skips_file(relative)) {
             continue;
         }
 
-        // Apply glob filter
+        // Apply glob filter against the path relative to the search root
         if let Some(glob) = glob_filter {
-            if !matches_glob(&file_name, glob) {
+            if !matches_glob(&relative.to_string_lossy(), glob) {
                 continue;
             }
         }
 
         // Read file and search for the query
-        let file_path = entry.path();
-        let content = match std::fs::read_to_string(file_path) {
+        let content = match std::fs::read_to_string(&file_path) {
             Ok(c) => c,
             Err(_) => continue, // skip binary or unreadable files
         };
 
+        let file_start = results.len();
         for (line_num, line) in content.lines().enumerate() {
-            if line.contains(query) {
-                if results.len() >= max_results {
-                    truncated = true;
-                    break;
-                }
-                results.push(json!({
-                    "file": file_path.display().to_string(),
-                    "line": line_num + 1,
-                    "content": line.trim()
-                }));
+            if !line.contains(query) {
+                continue;
             }
+            if resume.as_ref().is_some_and(|r| r.covers(relative, line_num + 1)) {
+                continue;
+            }
+            if results.len() >= limit {
+                // At least one more match exists: resume after the last one returned
+                next = last.take();
+                report(&results[file_start..], results.len());
+                break 'walk;
+            }
+            results.push(json!({
+                "file": file_path.display().to_string(),
+                "line": line_num + 1,
+                "content": line.trim()
+            }));
+            last = Some(paging::FilePosition {
+                file: relative.to_string_lossy().to_string(),
+                line: line_num + 1,
+            });
         }
-
-        if truncated {
-            break;
-        }
+        report(&results[file_start..], results.len());
     }
 
     ToolResult::json(&json!({
@@ -637,8 +1197,10 @@ async fn handle_search(params: Value, state: &ProjectState) -> ToolResult {
         "path": search_path,
         "results": results,
         "count": results.len(),
-        "truncated": truncated,
-        "max_results": max_results
+        "truncated": next.is_some(),
+        "next_cursor": next.map(|n| n.to_cursor()),
+        "limit": limit,
+        "max_depth": limits.max_walk_depth
     }))
 }
 
@@ -647,88 +1209,93 @@ async fn handle_search(params: Value, state: &ProjectState) -> ToolResult {
 // ---------------------------------------------------------------------------
 
 async fn handle_replace(params: Value, state: &ProjectState) -> ToolResult {
-    let search = match params.get("search").and_then(|v| v.as_str()) {
-        Some(s) => s,
-        None => return ToolResult::error("Missing required parameter: search"),
+    let search = match require_str(&params, "search", "fs_replace") {
+        Ok(s) => s,
+        Err(e) => return e,
     };
-    let replace = match params.get("replace").and_then(|v| v.as_str()) {
-        Some(r) => r,
-        None => return ToolResult::error("Missing required parameter: replace"),
+    let replace = match require_str(&params, "replace", "fs_replace") {
+        Ok(r) => r,
+        Err(e) => return e,
     };
     let search_path = match resolve_path(&params, "path", state) {
         Ok(p) => p,
-        Err(e) => return ToolResult::error(e),
+        Err(e) => return ToolResult::error_with(ErrorCode::InvalidParams, e),
     };
     let glob_filter = params.get("glob").and_then(|v| v.as_str());
     let dry_run = params
         .get("dry_run")
         .and_then(|v| v.as_bool())
         .unwrap_or(false);
+    let use_regex = params
+        .get("regex")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    let pattern = if use_regex {
+        match regex::Regex::new(search) {
+            Ok(re) => Some(re),
+            Err(e) => {
+                return ToolResult::error_with(
+                    ErrorCode::InvalidParams,
+                    format!("Invalid regex pattern '{}': {}", search, e),
+                )
+            }
+        }
+    } else {
+        None
+    };
 
     let root = Path::new(&search_path);
     if !root.exists() {
-        return ToolResult::error(format!("Search path does not exist: {}", search_path));
+        return ToolResult::error_with(
+            ErrorCode::NotFound,
+            format!("Search path does not exist: {}", search_path),
+        );
     }
 
-    let max_depth: usize = 10;
+    let include_ignored = params
+        .get("include_ignored")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
     let mut modified_files: Vec<Value> = Vec::new();
     let mut total_replacements: usize = 0;
 
-    let walker = walkdir::WalkDir::new(root)
-        .max_depth(max_depth)
-        .follow_links(false)
-        .into_iter();
-
-    for entry in walker.filter_entry(|e| {
-        let name = e.file_name().to_string_lossy();
-        if e.depth() > 0 && is_skipped_dir(&name) {
-            return false;
-        }
-        true
-    }) {
-        let entry = match entry {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
-
-        if !entry.file_type().is_file() {
-            continue;
-        }
-
-        let file_name = entry.file_name().to_string_lossy().to_string();
-
-        // Skip hidden files
-        if file_name.starts_with('.') {
-            continue;
-        }
-
-        // Apply glob filter
+    let max_depth = state.fs_limits.max_walk_depth;
+    for file_path in search_files(root, max_depth, include_ignored) {
+        // Apply glob filter against the path relative to the search root
         if let Some(glob) = glob_filter {
-            if !matches_glob(&file_name, glob) {
+            let relative = file_path.strip_prefix(root).unwrap_or(&file_path);
+            if !matches_glob(&relative.to_string_lossy(), glob) {
                 continue;
             }
         }
 
-        let file_path = entry.path();
-        let content = match std::fs::read_to_string(file_path) {
+        let content = match std::fs::read_to_string(&file_path) {
             Ok(c) => c,
             Err(_) => continue,
         };
 
-        if !content.contains(search) {
+        let count = match &pattern {
+            Some(re) => re.find_iter(&content).count(),
+            None => content.matches(search).count(),
+        };
+        if count == 0 {
             continue;
         }
-
-        let count = content.matches(search).count();
         total_replacements += count;
 
         if !dry_run {
-            let new_content = content.replace(search, replace);
-            if let Err(e) = std::fs::write(file_path, &new_content) {
-                return ToolResult::error(format!(
-                    "Failed to write replacement to '{}': {}",
+            let new_content = match &pattern {
+                Some(re) => re.replace_all(&content, replace).into_owned(),
+                None => content.replace(search, replace),
+            };
+            if let Err(e) = write_atomic(&file_path, &new_content) {
+                return ToolResult::error_with(ErrorCode::from(&e), format!(
+                    "Failed to write replacement to '{}': {}. The file was left unchanged; \
+                     {} earlier file(s) in this run were already modified.",
                     file_path.display(),
-                    e
+                    e,
+                    modified_files.len()
                 ));
             }
         }
@@ -744,8 +1311,388 @@ async fn handle_replace(params: Value, state: &ProjectState) -> ToolResult {
         "replace": replace,
         "path": search_path,
         "dry_run": dry_run,
+        "regex": use_regex,
         "files_modified": modified_files,
         "total_files": modified_files.len(),
-        "total_replacements": total_replacements
+        "total_replacements": total_replacements,
+        "max_depth": max_depth
+    }))
+}
+
+// ---------------------------------------------------------------------------
+// fs_watch
+// ---------------------------------------------------------------------------
+
+/// How long a path must be quiet before its events are reported, so the
+/// create/modify/modify burst of a single save collapses into one event.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Watchers not polled within this window are dropped.
+const WATCH_EXPIRY: Duration = Duration::from_secs(300);
+
+/// Upper bound on buffered raw events per watcher.
+const WATCH_MAX_PENDING: usize = 10_000;
+
+static NEXT_WATCHER_ID: AtomicU64 = AtomicU64::new(1);
+
+/// A raw change notification as received from the OS watcher.
+#[derive(Clone)]
+struct RawChange {
+    path: PathBuf,
+    kind: &'static str,
+    at: Instant,
+}
+
+/// A registered fs_watch watcher and the events it has buffered.
+pub struct FsWatch {
+    _watcher: notify::RecommendedWatcher,
+    root: PathBuf,
+    glob: Option<String>,
+    pending: Arc<Mutex<Vec<RawChange>>>,
+    overflowed: Arc<Mutex<bool>>,
+    last_polled: Instant,
+}
+
+async fn handle_watch(params: Value, state: &ProjectState) -> ToolResult {
+    let mut watchers = match state.watchers.lock() {
+        Ok(w) => w,
+        Err(e) => return ToolResult::error(format!("Failed to access watchers: {}", e)),
+    };
+
+    // Expire watchers nobody is polling any more
+    let expired: Vec<String> = watchers
+        .iter()
+        .filter(|(_, w)| w.last_polled.elapsed() > WATCH_EXPIRY)
+        .map(|(id, _)| id.clone())
+        .collect();
+    for id in &expired {
+        watchers.remove(id);
+    }
+
+    if let Some(id) = params.get("watcher_id").and_then(|v| v.as_str()) {
+        let stop = params.get("stop").and_then(|v| v.as_bool()).unwrap_or(false);
+        let Some(watch) = watchers.get_mut(id) else {
+            return ToolResult::error_with(ErrorCode::NotFound, format!(
+                "Unknown watcher_id '{}'. It may have expired after {} seconds without polling; \
+                 call fs_watch without watcher_id to register a new one.",
+                id,
+                WATCH_EXPIRY.as_secs()
+            ));
+        };
+        watch.last_polled = Instant::now();
+        // A stopped watcher will never be polled again, so flush everything
+        let (events, overflowed) = drain_watch_events(watch, stop);
+        if stop {
+            watchers.remove(id);
+        }
+        return ToolResult::json(&json!({
+            "watcher_id": id,
+            "events": events,
+            "count": events.len(),
+            "overflowed": overflowed,
+            "stopped": stop
+        }));
+    }
+
+    let path = match resolve_path(&params, "path", state) {
+        Ok(p) => p,
+        Err(e) => return ToolResult::error_with(ErrorCode::InvalidParams, e),
+    };
+    let root = PathBuf::from(&path);
+    if !root.is_dir() {
+        return ToolResult::error_with(
+            ErrorCode::InvalidParams,
+            format!("Watch path is not a directory: {}", path),
+        );
+    }
+
+    let pending: Arc<Mutex<Vec<RawChange>>> = Arc::new(Mutex::new(Vec::new()));
+    let overflowed = Arc::new(Mutex::new(false));
+    let sink = pending.clone();
+    let overflow_flag = overflowed.clone();
+    let mut watcher = match notify::recommended_watcher(move |res: Result<notify::Event, notify::Error>| {
+        let Ok(event) = res else {
+            return;
+        };
+        let kind = match event.kind {
+            EventKind::Create(_) => "created",
+            EventKind::Modify(_) => "modified",
+            EventKind::Remove(_) => "removed",
+            _ => return,
+        };
+        if let Ok(mut buffer) = sink.lock() {
+            for path in event.paths {
+                if buffer.len() >= WATCH_MAX_PENDING {
+                    if let Ok(mut flag) = overflow_flag.lock() {
+                        *flag = true;
+                    }
+                    return;
+                }
+                buffer.push(RawChange { path, kind, at: Instant::now() });
+            }
+        }
+    }) {
+        Ok(w) => w,
+        Err(e) => {
+            return ToolResult::error_with(
+                ErrorCode::Io,
+                format!("Failed to create watcher: {}", e),
+            )
+        }
+    };
+    if let Err(e) = watcher.watch(&root, RecursiveMode::Recursive) {
+        return ToolResult::error_with(ErrorCode::Io, format!("Failed to watch '{}': {}", path, e));
+    }
+
+    let id = format!("watch-{}", NEXT_WATCHER_ID.fetch_add(1, Ordering::Relaxed));
+    let glob = params.get("glob").and_then(|v| v.as_str()).map(String::from);
+    watchers.insert(
+        id.clone(),
+        FsWatch {
+            _watcher: watcher,
+            root,
+            glob: glob.clone(),
+            pending,
+            overflowed,
+            last_polled: Instant::now(),
+        },
+    );
+
+    ToolResult::json(&json!({
+        "status": "watching",
+        "watcher_id": id,
+        "path": path,
+        "glob": glob,
+        "expires_after_secs": WATCH_EXPIRY.as_secs()
     }))
 }
+
+/// Take the settled events out of a watcher and coalesce them to one event
+/// per path. Paths that changed within the debounce window stay buffered for
+/// the next poll unless `flush` is set.
+fn drain_watch_events(watch: &FsWatch, flush: bool) -> (Vec<Value>, bool) {
+    let raw: Vec<RawChange> = match watch.pending.lock() {
+        Ok(mut buffer) => std::mem::take(&mut *buffer),
+        Err(_) => return (Vec::new(), false),
+    };
+    let overflowed = watch
+        .overflowed
+        .lock()
+        .map(|mut f| std::mem::replace(&mut *f, false))
+        .unwrap_or(false);
+
+    // path -> (first kind, last kind, last seen)
+    let mut by_path: BTreeMap<PathBuf, (&'static str, &'static str, Instant)> = BTreeMap::new();
+    for change in &raw {
+        by_path
+            .entry(change.path.clone())
+            .and_modify(|(_, last, at)| {
+                *last = change.kind;
+                *at = change.at;
+            })
+            .or_insert((change.kind, change.kind, change.at));
+    }
+
+    let mut events = Vec::new();
+    let mut unsettled: HashSet<PathBuf> = HashSet::new();
+    for (path, (first, last, at)) in by_path {
+        if !flush && at.elapsed() < WATCH_DEBOUNCE {
+            unsettled.insert(path);
+            continue;
+        }
+
+        let relative = path.strip_prefix(&watch.root).unwrap_or(&path);
+        let relative_str = relative.to_string_lossy().replace('\\', "/");
+        if relative_str.split('/').any(is_skipped_dir) {
+            continue;
+        }
+        if let Some(ref glob) = watch.glob {
+            if !matches_glob(&relative_str, glob) {
+                continue;
+            }
+        }
+
+        let kind = match (first, last) {
+            // Created and deleted between polls: nothing to report
+            ("created", "removed") => continue,
+            ("created", _) => "created",
+            (_, "removed") => "removed",
+            _ => "modified",
+        };
+        events.push(json!({
+            "path": path.display().to_string(),
+            "kind": kind
+        }));
+    }
+
+    if !unsettled.is_empty() {
+        // Keep the raw changes, in order, so the next poll coalesces them again
+        let mut held_back: Vec<RawChange> =
+            raw.into_iter().filter(|c| unsettled.contains(&c.path)).collect();
+        if let Ok(mut buffer) = watch.pending.lock() {
+            held_back.append(&mut buffer);
+            *buffer = held_back;
+        }
+    }
+
+    (events, overflowed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn double_star_matches_any_depth() {
+        assert!(matches_glob("Main.java", "**/*.java"));
+        assert!(matches_glob("src/main/java/Main.java", "**/*.java"));
+        assert!(matches_glob("src/Main.java", "src/**/Main.java"));
+        assert!(matches_glob("src/a/b/Main.java", "src/**/Main.java"));
+        assert!(!matches_glob("test/Main.java", "src/**/Main.java"));
+        assert!(!matches_glob("src/Main.kt", "**/*.java"));
+    }
+
+    #[test]
+    fn trailing_double_star_matches_everything_below() {
+        assert!(matches_glob("src/Main.java", "src/**"));
+        assert!(matches_glob("src/a/b/c.json", "src/**"));
+        assert!(!matches_glob("assets/src/c.json", "src/**"));
+    }
+
+    #[test]
+    fn braces_expand_to_alternatives() {
+        assert!(matches_glob("build.toml", "*.{json,toml}"));
+        assert!(matches_glob("a/b/mod.json", "*.{json,toml}"));
+        assert!(!matches_glob("mod.yaml", "*.{json,toml}"));
+        assert!(matches_glob("src/test/A.kt", "src/{main,test}/*.{java,kt}"));
+        // Nested groups, and an empty alternative
+        assert!(matches_glob("a.tar.gz", "*.{zip,tar{,.gz}}"));
+        assert!(matches_glob("a.tar", "*.{zip,tar{,.gz}}"));
+        // An unbalanced brace is literal
+        assert!(matches_glob("{odd.txt", "{odd.txt"));
+    }
+
+    #[test]
+    fn expand_braces_lists_every_pattern() {
+        assert_eq!(expand_braces("plain"), vec!["plain"]);
+        assert_eq!(expand_braces("*.{a,b}"), vec!["*.a", "*.b"]);
+        assert_eq!(expand_braces("{x,y}/{1,2}"), vec!["x/1", "x/2", "y/1", "y/2"]);
+        assert_eq!(expand_braces("f{,s}"), vec!["f", "fs"]);
+        assert_eq!(expand_braces("{a,{b,c}}"), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn question_mark_matches_one_character() {
+        assert!(matches_glob("src/test_1.rs", "test_?.rs"));
+        assert!(matches_glob("test_é.rs", "test_?.rs"));
+        assert!(!matches_glob("test_10.rs", "test_?.rs"));
+        assert!(!matches_glob("test_.rs", "test_?.rs"));
+    }
+
+    #[test]
+    fn character_classes() {
+        assert!(matches_glob("file1.txt", "file[0-9].txt"));
+        assert!(!matches_glob("filex.txt", "file[0-9].txt"));
+        assert!(matches_glob("b.rs", "[abc].rs"));
+        assert!(!matches_glob("d.rs", "[abc].rs"));
+        assert!(matches_glob("d.rs", "[!abc].rs"));
+        assert!(!matches_glob("a.rs", "[^abc].rs"));
+        // A leading `]` is a member; an unclosed `[` is literal
+        assert!(matches_glob("].rs", "[]x].rs"));
+        assert!(matches_glob("[x.rs", "[x.rs"));
+    }
+
+    #[test]
+    fn empty_segments() {
+        assert!(matches_glob("src//Main.java", "src/*/Main.java"));
+        assert!(!matches_glob("src/Main.java", "src/*/Main.java"));
+        assert!(matches_path_glob("", ""));
+        assert!(!matches_path_glob("a", ""));
+    }
+
+    /// Entries of `dir` other than `keep`, i.e. leftover temp files.
+    fn leftovers(dir: &Path, keep: &str) -> Vec<String> {
+        std::fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|name| name != keep)
+            .collect()
+    }
+
+    #[test]
+    fn write_atomic_failure_leaves_the_target_intact() {
+        // Renaming a file over a non-empty directory fails, even as root
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("Main.java");
+        std::fs::create_dir(&target).unwrap();
+        std::fs::write(target.join("keep.txt"), "original").unwrap();
+
+        assert!(write_atomic(&target, "replacement").is_err());
+        assert_eq!(std::fs::read_to_string(target.join("keep.txt")).unwrap(), "original");
+        assert!(leftovers(dir.path(), "Main.java").is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_atomic_to_unwritable_directory_leaves_the_original_intact() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("Main.java");
+        std::fs::write(&target, "original").unwrap();
+        std::fs::set_permissions(dir.path(), std::fs::Permissions::from_mode(0o555)).unwrap();
+
+        // Root ignores directory permissions, so there is nothing to test
+        let probe = dir.path().join("probe");
+        if std::fs::write(&probe, "").is_ok() {
+            let _ = std::fs::remove_file(&probe);
+            std::fs::set_permissions(dir.path(), std::fs::Permissions::from_mode(0o755)).unwrap();
+            eprintln!("skipping: chmod does not block writes for this user");
+            return;
+        }
+
+        let result = write_atomic(&target, "replacement");
+        std::fs::set_permissions(dir.path(), std::fs::Permissions::from_mode(0o755)).unwrap();
+        assert!(result.is_err());
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "original");
+        assert!(leftovers(dir.path(), "Main.java").is_empty());
+    }
+
+    #[test]
+    fn windows_separators_are_normalised() {
+        assert!(matches_glob("src\\main\\Main.java", "src/**/*.java"));
+    }
+
+    /// A project with a `run/` world that .gitignore excludes and a build dir.
+    fn project_with_ignored_run_dir() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for file in ["src/Main.java", "run/world.dat", "build/out.jar"] {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+        std::fs::write(dir.path().join(".gitignore"), "run/\n").unwrap();
+        dir
+    }
+
+    fn relative_files(root: &Path, include_ignored: bool) -> Vec<String> {
+        search_files(root, DEFAULT_MAX_WALK_DEPTH, include_ignored)
+            .iter()
+            .map(|p| p.strip_prefix(root).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect()
+    }
+
+    #[test]
+    fn search_files_honours_gitignore_in_a_repo() {
+        let dir = project_with_ignored_run_dir();
+        std::fs::create_dir(dir.path().join(".git")).unwrap();
+        assert_eq!(relative_files(dir.path(), false), ["src/Main.java"]);
+        assert_eq!(relative_files(dir.path(), true), ["run/world.dat", "src/Main.java"]);
+    }
+
+    #[test]
+    fn search_files_outside_a_repo_only_skips_hidden_and_build_dirs() {
+        let dir = project_with_ignored_run_dir();
+        assert_eq!(relative_files(dir.path(), false), ["run/world.dat", "src/Main.java"]);
+    }
+}
@@ -1,5 +1,7 @@
 use crate::state::ProjectState;
-use crate::types::{ToolDefinition, ToolResult};
+use crate::tools::paging;
+use crate::types::{json_type, require_non_empty_str, ErrorCode, ToolDefinition, ToolResult};
+use alloy_common::git::{parse_blame_porcelain, porcelain_path};
 use serde_json::{json, Value};
 
 /// Return all git tool definitions.
@@ -11,6 +13,10 @@ pub fn definitions() -> Vec<ToolDefinition> {
             input_schema: json!({
                 "type": "object",
                 "properties": {
+                    "project_path": {
+                        "type": "string",
+                        "description": "Repository directory (defaults to project root)"
+                    },
                     "path": {
                         "type": "string",
                         "description": "Path to initialize the repo in (defaults to project root)"
@@ -102,6 +108,10 @@ pub fn definitions() -> Vec<ToolDefinition> {
             input_schema: json!({
                 "type": "object",
                 "properties": {
+                    "project_path": {
+                        "type": "string",
+                        "description": "Repository directory (defaults to project root)"
+                    },
                     "remote": {
                         "type": "string",
                         "description": "Remote name (default: origin)"
@@ -119,6 +129,10 @@ pub fn definitions() -> Vec<ToolDefinition> {
             input_schema: json!({
                 "type": "object",
                 "properties": {
+                    "project_path": {
+                        "type": "string",
+                        "description": "Repository directory (defaults to project root)"
+                    },
                     "remote": {
                         "type": "string",
                         "description": "Remote name (default: origin)"
@@ -158,15 +172,74 @@ pub fn definitions() -> Vec<ToolDefinition> {
                 }
             }),
         },
+        ToolDefinition {
+            name: "git_show_file".into(),
+            description: "Show the contents of a file at a given revision (default: HEAD)".into(),
+            input_schema: json!({
+                "type": "object",
+                "required": ["file_path"],
+                "properties": {
+                    "project_path": {
+                        "type": "string",
+                        "description": "Repository directory (defaults to project root)"
+                    },
+                    "file_path": {
+                        "type": "string",
+                        "description": "File path relative to the repository root"
+                    },
+                    "revision": {
+                        "type": "string",
+                        "description": "Commit, branch, or tag to read from (default: HEAD)"
+                    }
+                }
+            }),
+        },
+        ToolDefinition {
+            name: "git_blame".into(),
+            description: "Show per-line authorship of a file as structured entries with \
+                hash, author, date, and summary"
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "required": ["file_path"],
+                "properties": {
+                    "project_path": {
+                        "type": "string",
+                        "description": "Repository directory (defaults to project root)"
+                    },
+                    "file_path": {
+                        "type": "string",
+                        "description": "File path relative to the repository root"
+                    }
+                }
+            }),
+        },
         ToolDefinition {
             name: "git_log".into(),
-            description: "Show recent commit history".into(),
+            description: "Show commit history as structured entries with hash, short_hash, \
+                author, ISO-8601 date, and subject, newest first. Optionally restricted to the \
+                history of a single file. When older commits remain the result includes \
+                next_cursor; pass it back as cursor for the next page. Pages stay stable even if \
+                new commits land in between."
+                .into(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
-                    "count": {
+                    "project_path": {
+                        "type": "string",
+                        "description": "Repository directory (defaults to project root)"
+                    },
+                    "limit": {
                         "type": "integer",
-                        "description": "Number of commits to show (default: 10)"
+                        "description": "Maximum number of commits to return (default: 50, max: 500). max_count is accepted as an alias."
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "next_cursor from a previous call with the same file_path"
+                    },
+                    "file_path": {
+                        "type": "string",
+                        "description": "Only show commits that touched this file (follows renames)"
                     }
                 }
             }),
@@ -187,6 +260,8 @@ pub async fn execute(name: &str, params: Value, state: &ProjectState) -> ToolRes
         "git_pull" => git_pull(params, state).await,
         "git_discard" => git_discard(params, state).await,
         "git_show" => git_show(params, state).await,
+        "git_show_file" => git_show_file(params, state).await,
+        "git_blame" => git_blame(params, state).await,
         "git_log" => git_log(params, state).await,
         _ => ToolResult::error(format!("Unknown git tool: {}", name)),
     }
@@ -214,18 +289,50 @@ async fn run_git(args: &[&str], cwd: &str) -> Result<String, String> {
     }
 }
 
-/// Resolve the working directory from params "cwd" or fall back to the project path.
+/// Failures worth telling apart, matched against git's lowercased output.
+const GIT_FAILURES: &[(&str, ErrorCode)] = &[
+    ("not a git repository", ErrorCode::NotFound),
+    ("did not match any file", ErrorCode::NotFound),
+    ("unknown revision", ErrorCode::NotFound),
+    ("bad revision", ErrorCode::NotFound),
+    ("does not exist", ErrorCode::NotFound),
+    ("exists on disk, but not in", ErrorCode::NotFound),
+    ("conflict", ErrorCode::Conflict),
+    ("would be overwritten", ErrorCode::Conflict),
+    ("non-fast-forward", ErrorCode::Conflict),
+    ("[rejected]", ErrorCode::Conflict),
+    ("already exists", ErrorCode::Conflict),
+    ("permission denied", ErrorCode::PermissionDenied),
+];
+
+/// The error result for a failed `run_git`, with a code when git's output
+/// says what went wrong.
+fn git_error(message: String) -> ToolResult {
+    if message.starts_with("Failed to run git") {
+        return ToolResult::error_with(ErrorCode::Io, message);
+    }
+    let lower = message.to_lowercase();
+    match GIT_FAILURES.iter().find(|(needle, _)| lower.contains(needle)) {
+        Some((_, code)) => ToolResult::error_with(*code, message),
+        None => ToolResult::error(message),
+    }
+}
+
+/// Resolve the working directory from params "project_path" or "cwd", falling
+/// back to the project path.
 fn resolve_cwd(params: &Value, state: &ProjectState) -> Result<String, String> {
-    if let Some(cwd) = params.get("cwd").and_then(|v| v.as_str()) {
-        if !cwd.is_empty() {
-            return Ok(cwd.to_string());
+    for key in ["project_path", "cwd"] {
+        if let Some(dir) = params.get(key).and_then(|v| v.as_str()) {
+            if !dir.is_empty() {
+                return Ok(dir.to_string());
+            }
         }
     }
     state.project_path()
 }
 
 /// Extract `paths` parameter as a Vec<String>, accepting both a single string and an array.
-fn extract_paths(params: &Value) -> Result<Vec<String>, String> {
+fn extract_paths(params: &Value, tool: &str) -> Result<Vec<String>, String> {
     match params.get("paths") {
         Some(Value::String(s)) => Ok(vec![s.clone()]),
         Some(Value::Array(arr)) => {
@@ -233,16 +340,26 @@ fn extract_paths(params: &Value) -> Result<Vec<String>, String> {
             for item in arr {
                 match item.as_str() {
                     Some(s) => paths.push(s.to_string()),
-                    None => return Err("Each path must be a string".to_string()),
+                    None => {
+                        return Err(format!(
+                            "tool '{}': each entry of 'paths' must be a string, got {}",
+                            tool,
+                            json_type(item)
+                        ))
+                    }
                 }
             }
             if paths.is_empty() {
-                return Err("paths array must not be empty".to_string());
+                return Err(format!("tool '{}': parameter 'paths' must not be empty", tool));
             }
             Ok(paths)
         }
-        Some(_) => Err("paths must be a string or array of strings".to_string()),
-        None => Err("Missing required parameter: paths".to_string()),
+        Some(other) => Err(format!(
+            "tool '{}': parameter 'paths' must be a string or array of strings, got {}",
+            tool,
+            json_type(other)
+        )),
+        None => Err(format!("tool '{}': missing required parameter 'paths'", tool)),
     }
 }
 
@@ -251,32 +368,29 @@ fn extract_paths(params: &Value) -> Result<Vec<String>, String> {
 // ---------------------------------------------------------------------------
 
 async fn git_init(params: Value, state: &ProjectState) -> ToolResult {
-    let base = match state.project_path() {
-        Ok(p) => p,
-        Err(e) => return ToolResult::error(e),
+    let path = match params.get("path").and_then(|v| v.as_str()) {
+        Some(p) if !p.is_empty() => p.to_string(),
+        _ => match resolve_cwd(&params, state) {
+            Ok(c) => c,
+            Err(e) => return ToolResult::error_with(ErrorCode::InvalidParams, e),
+        },
     };
 
-    let path = params
-        .get("path")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string())
-        .unwrap_or(base);
-
     match run_git(&["init"], &path).await {
         Ok(output) => ToolResult::text(output.trim_end()),
-        Err(e) => ToolResult::error(e),
+        Err(e) => git_error(e),
     }
 }
 
 async fn git_status(params: Value, state: &ProjectState) -> ToolResult {
     let cwd = match resolve_cwd(&params, state) {
         Ok(c) => c,
-        Err(e) => return ToolResult::error(e),
+        Err(e) => return ToolResult::error_with(ErrorCode::InvalidParams, e),
     };
 
     let output = match run_git(&["status", "--porcelain", "-b"], &cwd).await {
         Ok(o) => o,
-        Err(e) => return ToolResult::error(e),
+        Err(e) => return git_error(e),
     };
 
     let mut branch = String::new();
@@ -324,7 +438,7 @@ async fn git_status(params: Value, state: &ProjectState) -> ToolResult {
 
         let x = line.as_bytes()[0] as char;
         let y = line.as_bytes()[1] as char;
-        let file = &line[3..];
+        let file = porcelain_path(x, &line[3..]);
 
         // Untracked files
         if x == '?' && y == '?' {
@@ -364,7 +478,7 @@ async fn git_status(params: Value, state: &ProjectState) -> ToolResult {
 async fn git_diff(params: Value, state: &ProjectState) -> ToolResult {
     let cwd = match resolve_cwd(&params, state) {
         Ok(c) => c,
-        Err(e) => return ToolResult::error(e),
+        Err(e) => return ToolResult::error_with(ErrorCode::InvalidParams, e),
     };
 
     let staged = params
@@ -391,19 +505,19 @@ async fn git_diff(params: Value, state: &ProjectState) -> ToolResult {
                 ToolResult::text(output)
             }
         }
-        Err(e) => ToolResult::error(e),
+        Err(e) => git_error(e),
     }
 }
 
 async fn git_stage(params: Value, state: &ProjectState) -> ToolResult {
     let cwd = match resolve_cwd(&params, state) {
         Ok(c) => c,
-        Err(e) => return ToolResult::error(e),
+        Err(e) => return ToolResult::error_with(ErrorCode::InvalidParams, e),
     };
 
-    let paths = match extract_paths(&params) {
+    let paths = match extract_paths(&params, "git_stage") {
         Ok(p) => p,
-        Err(e) => return ToolResult::error(e),
+        Err(e) => return ToolResult::error_with(ErrorCode::InvalidParams, e),
     };
 
     let mut args: Vec<&str> = vec!["add"];
@@ -420,19 +534,19 @@ async fn git_stage(params: Value, state: &ProjectState) -> ToolResult {
             };
             ToolResult::text(format!("Staged: {}", label))
         }
-        Err(e) => ToolResult::error(e),
+        Err(e) => git_error(e),
     }
 }
 
 async fn git_unstage(params: Value, state: &ProjectState) -> ToolResult {
     let cwd = match resolve_cwd(&params, state) {
         Ok(c) => c,
-        Err(e) => return ToolResult::error(e),
+        Err(e) => return ToolResult::error_with(ErrorCode::InvalidParams, e),
     };
 
-    let paths = match extract_paths(&params) {
+    let paths = match extract_paths(&params, "git_unstage") {
         Ok(p) => p,
-        Err(e) => return ToolResult::error(e),
+        Err(e) => return ToolResult::error_with(ErrorCode::InvalidParams, e),
     };
 
     let mut args: Vec<&str> = vec!["restore", "--staged"];
@@ -444,31 +558,31 @@ async fn git_unstage(params: Value, state: &ProjectState) -> ToolResult {
         Ok(_) => {
             ToolResult::text(format!("Unstaged: {}", paths.join(", ")))
         }
-        Err(e) => ToolResult::error(e),
+        Err(e) => git_error(e),
     }
 }
 
 async fn git_commit(params: Value, state: &ProjectState) -> ToolResult {
     let cwd = match resolve_cwd(&params, state) {
         Ok(c) => c,
-        Err(e) => return ToolResult::error(e),
+        Err(e) => return ToolResult::error_with(ErrorCode::InvalidParams, e),
     };
 
-    let message = match params.get("message").and_then(|v| v.as_str()) {
-        Some(m) if !m.is_empty() => m,
-        _ => return ToolResult::error("Missing required parameter: message"),
+    let message = match require_non_empty_str(&params, "message", "git_commit") {
+        Ok(m) => m,
+        Err(e) => return e,
     };
 
     match run_git(&["commit", "-m", message], &cwd).await {
         Ok(output) => ToolResult::text(output.trim_end()),
-        Err(e) => ToolResult::error(e),
+        Err(e) => git_error(e),
     }
 }
 
 async fn git_push(params: Value, state: &ProjectState) -> ToolResult {
     let cwd = match resolve_cwd(&params, state) {
         Ok(c) => c,
-        Err(e) => return ToolResult::error(e),
+        Err(e) => return ToolResult::error_with(ErrorCode::InvalidParams, e),
     };
 
     let remote = params
@@ -492,14 +606,14 @@ async fn git_push(params: Value, state: &ProjectState) -> ToolResult {
                 ToolResult::text(output.trim_end())
             }
         }
-        Err(e) => ToolResult::error(e),
+        Err(e) => git_error(e),
     }
 }
 
 async fn git_pull(params: Value, state: &ProjectState) -> ToolResult {
     let cwd = match resolve_cwd(&params, state) {
         Ok(c) => c,
-        Err(e) => return ToolResult::error(e),
+        Err(e) => return ToolResult::error_with(ErrorCode::InvalidParams, e),
     };
 
     let remote = params
@@ -516,65 +630,193 @@ async fn git_pull(params: Value, state: &ProjectState) -> ToolResult {
 
     match run_git(&args, &cwd).await {
         Ok(output) => ToolResult::text(output.trim_end()),
-        Err(e) => ToolResult::error(e),
+        Err(e) => git_error(e),
     }
 }
 
 async fn git_discard(params: Value, state: &ProjectState) -> ToolResult {
     let cwd = match resolve_cwd(&params, state) {
         Ok(c) => c,
-        Err(e) => return ToolResult::error(e),
+        Err(e) => return ToolResult::error_with(ErrorCode::InvalidParams, e),
     };
 
-    let path = match params.get("path").and_then(|v| v.as_str()) {
-        Some(p) if !p.is_empty() => p,
-        _ => return ToolResult::error("Missing required parameter: path"),
+    let path = match require_non_empty_str(&params, "path", "git_discard") {
+        Ok(p) => p,
+        Err(e) => return e,
     };
 
     match run_git(&["checkout", "--", path], &cwd).await {
         Ok(_) => ToolResult::text(format!("Discarded changes to: {}", path)),
-        Err(e) => ToolResult::error(e),
+        Err(e) => git_error(e),
     }
 }
 
 async fn git_show(params: Value, state: &ProjectState) -> ToolResult {
     let cwd = match resolve_cwd(&params, state) {
         Ok(c) => c,
-        Err(e) => return ToolResult::error(e),
+        Err(e) => return ToolResult::error_with(ErrorCode::InvalidParams, e),
     };
 
-    let path = match params.get("path").and_then(|v| v.as_str()) {
-        Some(p) if !p.is_empty() => p,
-        _ => return ToolResult::error("Missing required parameter: path"),
+    let path = match require_non_empty_str(&params, "path", "git_show") {
+        Ok(p) => p,
+        Err(e) => return e,
     };
 
     let ref_path = format!("HEAD:{}", path);
     match run_git(&["show", &ref_path], &cwd).await {
         Ok(output) => ToolResult::text(output),
-        Err(e) => ToolResult::error(e),
+        Err(e) => git_error(e),
     }
 }
 
-async fn git_log(params: Value, state: &ProjectState) -> ToolResult {
+async fn git_show_file(params: Value, state: &ProjectState) -> ToolResult {
     let cwd = match resolve_cwd(&params, state) {
         Ok(c) => c,
-        Err(e) => return ToolResult::error(e),
+        Err(e) => return ToolResult::error_with(ErrorCode::InvalidParams, e),
+    };
+
+    let file_path = match require_non_empty_str(&params, "file_path", "git_show_file") {
+        Ok(p) => p,
+        Err(e) => return e,
     };
 
-    let count = params
-        .get("count")
-        .and_then(|v| v.as_u64())
-        .unwrap_or(10);
+    let revision = params
+        .get("revision")
+        .and_then(|v| v.as_str())
+        .filter(|r| !r.is_empty())
+        .unwrap_or("HEAD");
 
-    let count_str = count.to_string();
-    match run_git(&["log", "--oneline", "-n", &count_str], &cwd).await {
-        Ok(output) => {
-            if output.is_empty() {
-                ToolResult::text("No commits found.")
-            } else {
-                ToolResult::text(output.trim_end())
+    let ref_path = format!("{}:{}", revision, file_path);
+    match run_git(&["show", &ref_path], &cwd).await {
+        Ok(output) => ToolResult::text(output),
+        Err(e) => git_error(e),
+    }
+}
+
+async fn git_blame(params: Value, state: &ProjectState) -> ToolResult {
+    let cwd = match resolve_cwd(&params, state) {
+        Ok(c) => c,
+        Err(e) => return ToolResult::error_with(ErrorCode::InvalidParams, e),
+    };
+
+    let file_path = match require_non_empty_str(&params, "file_path", "git_blame") {
+        Ok(p) => p,
+        Err(e) => return e,
+    };
+
+    let output = match run_git(&["blame", "--porcelain", "--", file_path], &cwd).await {
+        Ok(o) => o,
+        Err(e) => return git_error(e),
+    };
+
+    let lines: Vec<Value> = parse_blame_porcelain(&output)
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| {
+            json!({
+                "line": i + 1,
+                "hash": line.hash,
+                "author": line.author,
+                "date": line.date,
+                "summary": line.summary,
+                "content": line.content,
+            })
+        })
+        .collect();
+
+    ToolResult::json(&json!({
+        "file_path": file_path,
+        "lines": lines,
+    }))
+}
+
+async fn git_log(params: Value, state: &ProjectState) -> ToolResult {
+    let cwd = match resolve_cwd(&params, state) {
+        Ok(c) => c,
+        Err(e) => return ToolResult::error_with(ErrorCode::InvalidParams, e),
+    };
+
+    let limit = paging::limit(
+        params.get("limit").or_else(|| params.get("max_count")),
+        50,
+        500,
+    );
+    let file_path = params.get("file_path").and_then(|v| v.as_str());
+
+    // A cursor pins the commit the first page started from, so commits made
+    // since then don't shift later pages
+    let (head, skip) = match paging::decode_cursor(&params) {
+        Ok(Some(cursor)) => match (
+            cursor.get("head").and_then(|h| h.as_str()),
+            cursor.get("skip").and_then(|s| s.as_u64()),
+        ) {
+            (Some(head), Some(skip)) => (head.to_string(), skip),
+            _ => {
+                return ToolResult::error_with(
+                    ErrorCode::InvalidParams,
+                    "Invalid cursor: not a git_log cursor",
+                )
             }
-        }
-        Err(e) => ToolResult::error(e),
+        },
+        Ok(None) => match run_git(&["rev-parse", "HEAD"], &cwd).await {
+            Ok(h) => (h.trim().to_string(), 0),
+            Err(e) => return git_error(e),
+        },
+        Err(e) => return ToolResult::error_with(ErrorCode::InvalidParams, e),
+    };
+
+    // Unit/record separators cannot appear in names or subjects.
+    // Ask for one extra commit to learn whether another page exists.
+    let count_str = (limit + 1).to_string();
+    let skip_str = skip.to_string();
+    let mut args: Vec<&str> = vec![
+        "log",
+        "--pretty=format:%H%x1f%h%x1f%an%x1f%aI%x1f%s%x1e",
+        "-n",
+        &count_str,
+        "--skip",
+        &skip_str,
+        &head,
+    ];
+    if let Some(f) = file_path {
+        args.push("--follow");
+        args.push("--");
+        args.push(f);
     }
+
+    let output = match run_git(&args, &cwd).await {
+        Ok(o) => o,
+        Err(e) => return git_error(e),
+    };
+
+    let mut entries: Vec<Value> = output
+        .split('\x1e')
+        .filter_map(|record| {
+            let fields: Vec<&str> = record.trim_start_matches('\n').split('\x1f').collect();
+            if fields.len() < 5 {
+                return None;
+            }
+            Some(json!({
+                "hash": fields[0],
+                "short_hash": fields[1],
+                "author": fields[2],
+                "date": fields[3],
+                "subject": fields[4],
+            }))
+        })
+        .collect();
+
+    let next_cursor = if entries.len() > limit {
+        entries.truncate(limit);
+        Some(paging::encode_cursor(&json!({ "head": head, "skip": skip + limit as u64 })))
+    } else {
+        None
+    };
+
+    ToolResult::json(&json!({
+        "entries": entries,
+        "count": entries.len(),
+        "file_path": file_path,
+        "next_cursor": next_cursor,
+    }))
 }
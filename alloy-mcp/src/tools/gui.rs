@@ -1,7 +1,7 @@
 use crate::types::{ToolDefinition, ToolResult};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Serialize, Deserialize)]
 struct GuiElement {
@@ -82,6 +82,22 @@ pub fn definitions() -> Vec<ToolDefinition> {
                 "required": ["path", "project_path", "mod_id"]
             }),
         },
+        ToolDefinition {
+            name: "gui_validate".into(),
+            description: "Validate a .gui.json definition: every element fits inside the declared \
+                width/height, inventory slots have unique slot_id values and do not overlap, element \
+                ids are unique, and background_texture points at an existing file. Returns issues \
+                in the same { severity, message, suggestion } shape as block_validate."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string", "description": "Path to the .gui.json file" },
+                    "project_path": { "type": "string", "description": "Project root, used to resolve 'namespace:path' texture references (optional)" }
+                },
+                "required": ["path"]
+            }),
+        },
     ]
 }
 
@@ -91,6 +107,7 @@ pub async fn execute(name: &str, params: Value) -> ToolResult {
         "gui_read" => gui_read(params).await,
         "gui_update" => gui_update(params).await,
         "gui_generate_code" => gui_generate_code(params).await,
+        "gui_validate" => gui_validate(params).await,
         _ => ToolResult::error(format!("Unknown gui tool: {}", name)),
     }
 }
@@ -98,11 +115,11 @@ pub async fn execute(name: &str, params: Value) -> ToolResult {
 async fn gui_create(params: Value) -> ToolResult {
     let path = match params.get("path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: path"),
+        None => return ToolResult::missing_param("path"),
     };
     let name = match params.get("name").and_then(|v| v.as_str()) {
         Some(n) => n,
-        None => return ToolResult::error("Missing required parameter: name"),
+        None => return ToolResult::missing_param("name"),
     };
     let width = params.get("width").and_then(|v| v.as_i64()).unwrap_or(176) as i32;
     let height = params.get("height").and_then(|v| v.as_i64()).unwrap_or(166) as i32;
@@ -141,7 +158,7 @@ async fn gui_create(params: Value) -> ToolResult {
 async fn gui_read(params: Value) -> ToolResult {
     let path = match params.get("path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: path"),
+        None => return ToolResult::missing_param("path"),
     };
 
     let content = match std::fs::read_to_string(path) {
@@ -158,11 +175,11 @@ async fn gui_read(params: Value) -> ToolResult {
 async fn gui_update(params: Value) -> ToolResult {
     let path = match params.get("path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: path"),
+        None => return ToolResult::missing_param("path"),
     };
     let action = match params.get("action").and_then(|v| v.as_str()) {
         Some(a) => a,
-        None => return ToolResult::error("Missing required parameter: action"),
+        None => return ToolResult::missing_param("action"),
     };
 
     let content = match std::fs::read_to_string(path) {
@@ -272,15 +289,15 @@ fn to_pascal_case(s: &str) -> String {
 async fn gui_generate_code(params: Value) -> ToolResult {
     let path = match params.get("path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: path"),
+        None => return ToolResult::missing_param("path"),
     };
     let project_path = match params.get("project_path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: project_path"),
+        None => return ToolResult::missing_param("project_path"),
     };
     let mod_id = match params.get("mod_id").and_then(|v| v.as_str()) {
         Some(m) => m,
-        None => return ToolResult::error("Missing required parameter: mod_id"),
+        None => return ToolResult::missing_param("mod_id"),
     };
 
     let content = match std::fs::read_to_string(path) {
@@ -439,3 +456,167 @@ public class {class_name} extends Screen {{
         "package": format!("{}.gui", package_name),
     }))
 }
+
+/// Resolve a texture reference from a .gui.json to a file: absolute paths as
+/// is, `namespace:path` under the project's assets, anything else relative to
+/// the .gui.json itself.
+fn resolve_gui_texture(reference: &str, gui_path: &Path, project_path: Option<&str>) -> Option<PathBuf> {
+    let path = Path::new(reference);
+    if path.is_absolute() {
+        return Some(path.to_path_buf());
+    }
+    if let Some((namespace, rest)) = reference.split_once(':') {
+        if namespace == "minecraft" {
+            return None;
+        }
+        let rest = if rest.ends_with(".png") { rest.to_string() } else { format!("{}.png", rest) };
+        return project_path.map(|p| {
+            Path::new(p)
+                .join("src/main/resources/assets")
+                .join(namespace)
+                .join(rest)
+        });
+    }
+    Some(gui_path.parent().unwrap_or(Path::new(".")).join(path))
+}
+
+fn rects_overlap(a: &GuiElement, b: &GuiElement) -> bool {
+    a.x < b.x + b.width && b.x < a.x + a.width && a.y < b.y + b.height && b.y < a.y + a.height
+}
+
+async fn gui_validate(params: Value) -> ToolResult {
+    let path = match params.get("path").and_then(|v| v.as_str()) {
+        Some(p) => p,
+        None => return ToolResult::missing_param("path"),
+    };
+    let project_path = params.get("project_path").and_then(|v| v.as_str());
+
+    let content = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) => return ToolResult::error(format!("Failed to read {}: {}", path, e)),
+    };
+
+    let gui: GuiProject = match serde_json::from_str(&content) {
+        Ok(g) => g,
+        Err(e) => return ToolResult::error(format!("Failed to parse GUI JSON: {}", e)),
+    };
+
+    let mut issues: Vec<Value> = Vec::new();
+
+    if gui.width <= 0 || gui.height <= 0 {
+        issues.push(json!({
+            "severity": "error",
+            "message": format!("GUI size {}x{} is not positive.", gui.width, gui.height),
+            "suggestion": "Use the vanilla container size 176x166 unless the design needs more room."
+        }));
+    }
+
+    // Element bounds
+    for elem in &gui.elements {
+        if elem.width <= 0 || elem.height <= 0 {
+            issues.push(json!({
+                "severity": "error",
+                "message": format!("Element '{}' has a non-positive size ({}x{}).", elem.id, elem.width, elem.height),
+                "suggestion": "Give the element a width and height of at least 1."
+            }));
+            continue;
+        }
+        if elem.x < 0 || elem.y < 0 || elem.x + elem.width > gui.width || elem.y + elem.height > gui.height {
+            issues.push(json!({
+                "severity": "error",
+                "message": format!(
+                    "Element '{}' at ({}, {}) size {}x{} extends outside the {}x{} GUI.",
+                    elem.id, elem.x, elem.y, elem.width, elem.height, gui.width, gui.height
+                ),
+                "suggestion": "Move the element inside the GUI or resize the GUI."
+            }));
+        }
+    }
+
+    // Duplicate element ids break gui_update's remove/update actions
+    let mut seen_ids: Vec<&str> = Vec::new();
+    for elem in &gui.elements {
+        if seen_ids.contains(&elem.id.as_str()) {
+            issues.push(json!({
+                "severity": "warning",
+                "message": format!("Element id '{}' is used more than once.", elem.id),
+                "suggestion": "Give every element a unique id."
+            }));
+        } else {
+            seen_ids.push(&elem.id);
+        }
+    }
+
+    // Inventory slots: a missing slot_id means slot 0, as in the GUI editor
+    let slots: Vec<(&GuiElement, i64)> = gui
+        .elements
+        .iter()
+        .filter(|e| e.element_type == "slot")
+        .map(|e| (e, e.properties.get("slot_id").and_then(|v| v.as_i64()).unwrap_or(0)))
+        .collect();
+    for (i, (slot, slot_id)) in slots.iter().enumerate() {
+        if *slot_id < 0 {
+            issues.push(json!({
+                "severity": "error",
+                "message": format!("Slot '{}' has a negative slot_id ({}).", slot.id, slot_id),
+                "suggestion": "Slot indices start at 0."
+            }));
+        }
+        for (other, other_id) in &slots[..i] {
+            if other_id == slot_id {
+                issues.push(json!({
+                    "severity": "error",
+                    "message": format!(
+                        "Slots '{}' and '{}' both use slot_id {}. Both would show the same inventory slot.",
+                        other.id, slot.id, slot_id
+                    ),
+                    "suggestion": "Give every slot a unique slot_id."
+                }));
+            }
+            if rects_overlap(other, slot) {
+                issues.push(json!({
+                    "severity": "warning",
+                    "message": format!("Slots '{}' and '{}' overlap.", other.id, slot.id),
+                    "suggestion": "Space slots 18 pixels apart, the vanilla slot pitch."
+                }));
+            }
+        }
+    }
+
+    // Texture references
+    let gui_path = Path::new(path);
+    let mut textures: Vec<(String, &str)> = Vec::new();
+    if let Some(bg) = gui.background_texture.as_deref().filter(|t| !t.is_empty()) {
+        textures.push(("background_texture".to_string(), bg));
+    }
+    for elem in &gui.elements {
+        if let Some(tex) = elem.properties.get("texture").and_then(|v| v.as_str()).filter(|t| !t.is_empty()) {
+            textures.push((format!("element '{}' texture", elem.id), tex));
+        }
+    }
+    for (what, reference) in textures {
+        if let Some(file) = resolve_gui_texture(reference, gui_path, project_path) {
+            if !file.exists() {
+                issues.push(json!({
+                    "severity": "warning",
+                    "message": format!("{} \"{}\" not found at {}", what, reference, file.display()),
+                    "suggestion": "Import the texture into the project or fix the path."
+                }));
+            }
+        }
+    }
+
+    let has_errors = issues
+        .iter()
+        .any(|i| i.get("severity").and_then(|s| s.as_str()) == Some("error"));
+
+    ToolResult::json(&json!({
+        "path": path,
+        "gui_name": gui.name,
+        "element_count": gui.elements.len(),
+        "slot_count": slots.len(),
+        "issues": issues,
+        "issue_count": issues.len(),
+        "valid": !has_errors
+    }))
+}
@@ -0,0 +1,849 @@
+use crate::state::ProjectState;
+use crate::types::{ToolDefinition, ToolResult};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::Path;
+
+use super::block::{check_item_model_parent, is_vanilla_block, is_valid_block_name, to_pascal_case};
+
+// ---------------------------------------------------------------------------
+// Vanilla Minecraft item IDs without a block form, for conflict checking
+// (sorted for binary search). Block items are covered by VANILLA_BLOCKS.
+// ---------------------------------------------------------------------------
+
+const VANILLA_ITEMS: &[&str] = &[
+    "acacia_boat", "acacia_chest_boat", "amethyst_shard",
+    "angler_pottery_sherd", "apple", "archer_pottery_sherd", "armadillo_scute",
+    "armor_stand", "arms_up_pottery_sherd", "arrow", "axolotl_bucket",
+    "baked_potato", "bamboo_chest_raft", "bamboo_raft", "beef", "beetroot",
+    "beetroot_seeds", "beetroot_soup", "birch_boat", "birch_chest_boat",
+    "black_bundle", "black_dye", "blade_pottery_sherd", "blaze_powder",
+    "blaze_rod", "blue_bundle", "blue_dye", "bolt_armor_trim_smithing_template",
+    "bone", "bone_meal", "book", "bordure_indented_banner_pattern", "bow",
+    "bowl", "bread", "breeze_rod", "brewer_pottery_sherd", "brick",
+    "brown_bundle", "brown_dye", "brush", "bucket", "bundle",
+    "burn_pottery_sherd", "carrot", "carrot_on_a_stick", "chainmail_boots",
+    "chainmail_chestplate", "chainmail_helmet", "chainmail_leggings",
+    "charcoal", "cherry_boat", "cherry_chest_boat", "chest_minecart", "chicken",
+    "chorus_fruit", "clay_ball", "clock", "coal",
+    "coast_armor_trim_smithing_template", "cocoa_beans", "cod", "cod_bucket",
+    "command_block_minecart", "compass", "cooked_beef", "cooked_chicken",
+    "cooked_cod", "cooked_mutton", "cooked_porkchop", "cooked_rabbit",
+    "cooked_salmon", "cookie", "copper_ingot", "creeper_banner_pattern",
+    "crossbow", "cyan_bundle", "cyan_dye", "danger_pottery_sherd",
+    "dark_oak_boat", "dark_oak_chest_boat", "debug_stick", "diamond",
+    "diamond_axe", "diamond_boots", "diamond_chestplate", "diamond_helmet",
+    "diamond_hoe", "diamond_horse_armor", "diamond_leggings", "diamond_pickaxe",
+    "diamond_shovel", "diamond_sword", "disc_fragment_5", "dried_kelp",
+    "dune_armor_trim_smithing_template", "echo_shard", "egg", "elytra",
+    "emerald", "enchanted_book", "enchanted_golden_apple", "end_crystal",
+    "ender_eye", "ender_pearl", "experience_bottle", "explorer_pottery_sherd",
+    "eye_armor_trim_smithing_template", "feather", "fermented_spider_eye",
+    "field_masoned_banner_pattern", "filled_map", "fire_charge",
+    "firework_rocket", "firework_star", "fishing_rod", "flint",
+    "flint_and_steel", "flow_armor_trim_smithing_template",
+    "flow_banner_pattern", "flow_pottery_sherd", "flower_banner_pattern",
+    "friend_pottery_sherd", "furnace_minecart", "ghast_tear", "glass_bottle",
+    "glistering_melon_slice", "globe_banner_pattern", "glow_berries",
+    "glow_ink_sac", "glow_item_frame", "glowstone_dust", "goat_horn",
+    "gold_ingot", "gold_nugget", "golden_apple", "golden_axe", "golden_boots",
+    "golden_carrot", "golden_chestplate", "golden_helmet", "golden_hoe",
+    "golden_horse_armor", "golden_leggings", "golden_pickaxe", "golden_shovel",
+    "golden_sword", "gray_bundle", "gray_dye", "green_bundle", "green_dye",
+    "gunpowder", "guster_banner_pattern", "guster_pottery_sherd",
+    "heart_of_the_sea", "heart_pottery_sherd", "heartbreak_pottery_sherd",
+    "heavy_core", "honey_bottle", "honeycomb", "hopper_minecart",
+    "host_armor_trim_smithing_template", "howl_pottery_sherd", "ink_sac",
+    "iron_axe", "iron_boots", "iron_chestplate", "iron_helmet", "iron_hoe",
+    "iron_horse_armor", "iron_ingot", "iron_leggings", "iron_nugget",
+    "iron_pickaxe", "iron_shovel", "iron_sword", "item_frame", "jungle_boat",
+    "jungle_chest_boat", "knowledge_book", "lapis_lazuli", "lava_bucket",
+    "lead", "leather", "leather_boots", "leather_chestplate", "leather_helmet",
+    "leather_horse_armor", "leather_leggings", "light_blue_bundle",
+    "light_blue_dye", "light_gray_bundle", "light_gray_dye", "lime_bundle",
+    "lime_dye", "lingering_potion", "mace", "magenta_bundle", "magenta_dye",
+    "magma_cream", "mangrove_boat", "mangrove_chest_boat", "map", "melon_seeds",
+    "melon_slice", "milk_bucket", "minecart", "miner_pottery_sherd",
+    "mojang_banner_pattern", "mourner_pottery_sherd", "mushroom_stew",
+    "music_disc_11", "music_disc_13", "music_disc_5", "music_disc_blocks",
+    "music_disc_cat", "music_disc_chirp", "music_disc_creator",
+    "music_disc_creator_music_box", "music_disc_far", "music_disc_mall",
+    "music_disc_mellohi", "music_disc_otherside", "music_disc_pigstep",
+    "music_disc_precipice", "music_disc_relic", "music_disc_stal",
+    "music_disc_strad", "music_disc_wait", "music_disc_ward", "mutton",
+    "name_tag", "nautilus_shell", "nether_brick", "nether_star", "nether_wart",
+    "netherite_axe", "netherite_boots", "netherite_chestplate",
+    "netherite_helmet", "netherite_hoe", "netherite_ingot",
+    "netherite_leggings", "netherite_pickaxe", "netherite_scrap",
+    "netherite_shovel", "netherite_sword",
+    "netherite_upgrade_smithing_template", "oak_boat", "oak_chest_boat",
+    "ominous_bottle", "ominous_trial_key", "orange_bundle", "orange_dye",
+    "painting", "pale_oak_boat", "pale_oak_chest_boat", "paper",
+    "phantom_membrane", "piglin_banner_pattern", "pink_bundle", "pink_dye",
+    "plenty_pottery_sherd", "poisonous_potato", "popped_chorus_fruit",
+    "porkchop", "potato", "potion", "powder_snow_bucket", "prismarine_crystals",
+    "prismarine_shard", "prize_pottery_sherd", "pufferfish",
+    "pufferfish_bucket", "pumpkin_pie", "pumpkin_seeds", "purple_bundle",
+    "purple_dye", "quartz", "rabbit", "rabbit_foot", "rabbit_hide",
+    "rabbit_stew", "raiser_armor_trim_smithing_template", "raw_copper",
+    "raw_gold", "raw_iron", "recovery_compass", "red_bundle", "red_dye",
+    "redstone", "resin_brick", "rib_armor_trim_smithing_template",
+    "rotten_flesh", "saddle", "salmon", "salmon_bucket", "scrape_pottery_sherd",
+    "scute", "sentry_armor_trim_smithing_template",
+    "shaper_armor_trim_smithing_template", "sheaf_pottery_sherd", "shears",
+    "shelter_pottery_sherd", "shield", "shulker_shell",
+    "silence_armor_trim_smithing_template", "skull_banner_pattern",
+    "skull_pottery_sherd", "slime_ball", "snort_pottery_sherd",
+    "snout_armor_trim_smithing_template", "snowball", "spectral_arrow",
+    "spider_eye", "spire_armor_trim_smithing_template", "splash_potion",
+    "spruce_boat", "spruce_chest_boat", "spyglass", "stick", "stone_axe",
+    "stone_hoe", "stone_pickaxe", "stone_shovel", "stone_sword", "string",
+    "sugar", "suspicious_stew", "sweet_berries", "tadpole_bucket",
+    "tide_armor_trim_smithing_template", "tipped_arrow", "tnt_minecart",
+    "torchflower_seeds", "totem_of_undying", "trial_key", "trident",
+    "tropical_fish", "tropical_fish_bucket", "turtle_helmet", "turtle_scute",
+    "vex_armor_trim_smithing_template", "ward_armor_trim_smithing_template",
+    "warped_fungus_on_a_stick", "water_bucket",
+    "wayfinder_armor_trim_smithing_template", "wheat", "wheat_seeds",
+    "white_bundle", "white_dye", "wild_armor_trim_smithing_template",
+    "wind_charge", "wolf_armor", "wooden_axe", "wooden_hoe", "wooden_pickaxe",
+    "wooden_shovel", "wooden_sword", "writable_book", "written_book",
+    "yellow_bundle", "yellow_dye",
+];
+
+const RARITIES: &[&str] = &["common", "uncommon", "rare", "epic"];
+
+// ---------------------------------------------------------------------------
+// Item JSON types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize, Serialize)]
+struct FoodProps {
+    nutrition: u32,
+    saturation: f64,
+    #[serde(default)]
+    always_edible: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct ItemProject {
+    name: String,
+    display_name: String,
+    mod_id: String,
+    max_stack_size: u32,
+    rarity: String,
+    #[serde(default)]
+    food: Option<FoodProps>,
+    texture: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Tool definitions
+// ---------------------------------------------------------------------------
+
+/// Return tool definitions for all item editor tools.
+pub fn definitions() -> Vec<ToolDefinition> {
+    vec![
+        ToolDefinition {
+            name: "item_create".into(),
+            description: "Create a new .item.json definition file for a custom Minecraft item. \
+                Validates the item name against vanilla items and blocks to prevent conflicts \
+                and writes the item definition JSON."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_path": {
+                        "type": "string",
+                        "description": "Absolute path to the Alloy project directory"
+                    },
+                    "name": {
+                        "type": "string",
+                        "description": "Item identifier (lowercase, a-z0-9_ only, must not start with a digit). Example: 'ruby'"
+                    },
+                    "display_name": {
+                        "type": "string",
+                        "description": "Human-readable display name for the item. Example: 'Ruby'"
+                    },
+                    "mod_id": {
+                        "type": "string",
+                        "description": "The mod identifier this item belongs to. Example: 'mymod'"
+                    },
+                    "max_stack_size": {
+                        "type": "integer",
+                        "description": "Maximum stack size 1-64 (default: 64, or 1 for tools and armor)"
+                    },
+                    "rarity": {
+                        "type": "string",
+                        "enum": ["common", "uncommon", "rare", "epic"],
+                        "description": "Name color rarity (default: 'common')"
+                    },
+                    "food": {
+                        "type": "object",
+                        "description": "Makes the item edible. Omit for non-food items.",
+                        "properties": {
+                            "nutrition": { "type": "integer", "description": "Hunger points restored, in half-drumsticks (bread is 5)" },
+                            "saturation": { "type": "number", "description": "Saturation modifier (bread is 0.6)" },
+                            "always_edible": { "type": "boolean", "description": "Can be eaten with a full hunger bar, like golden apples (default: false)" }
+                        },
+                        "required": ["nutrition", "saturation"]
+                    },
+                    "texture": {
+                        "type": "string",
+                        "description": "Texture name under assets/<mod_id>/textures/item, without .png. Default: the item name"
+                    }
+                },
+                "required": ["project_path", "name", "display_name", "mod_id"]
+            }),
+        },
+        ToolDefinition {
+            name: "item_read".into(),
+            description: "Read and parse a .item.json definition file, returning the full item \
+                definition including name, stack size, rarity, food properties, and texture."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Absolute path to the .item.json file"
+                    }
+                },
+                "required": ["path"]
+            }),
+        },
+        ToolDefinition {
+            name: "item_update".into(),
+            description: "Update properties of an existing .item.json definition. Performs a \
+                read-modify-write: reads the current definition, merges the provided updates, and \
+                writes back. Only the fields you specify are changed."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Absolute path to the .item.json file to update"
+                    },
+                    "updates": {
+                        "type": "object",
+                        "description": "Object with fields to merge into the item definition: name, display_name, mod_id, max_stack_size, rarity, food, texture. Set food to null to make the item inedible."
+                    }
+                },
+                "required": ["path", "updates"]
+            }),
+        },
+        ToolDefinition {
+            name: "item_validate".into(),
+            description: "Validate a .item.json definition: name format, vanilla item and block \
+                conflicts, stack size, rarity, food values, texture file existence and dimensions, \
+                and the generated item model. Returns an array of issues with severity and \
+                suggestions."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_path": {
+                        "type": "string",
+                        "description": "Absolute path to the Alloy project directory"
+                    },
+                    "path": {
+                        "type": "string",
+                        "description": "Absolute path to the .item.json file to validate"
+                    }
+                },
+                "required": ["project_path", "path"]
+            }),
+        },
+        ToolDefinition {
+            name: "item_generate_code".into(),
+            description: "Generate the Item Java class, models/item/<name>.json, and the en_us \
+                display name from a .item.json definition. Returns the list of created files and \
+                a registration snippet to paste into the mod initializer."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_path": {
+                        "type": "string",
+                        "description": "Absolute path to the Alloy project directory"
+                    },
+                    "path": {
+                        "type": "string",
+                        "description": "Absolute path to the .item.json file to generate code from"
+                    }
+                },
+                "required": ["project_path", "path"]
+            }),
+        },
+    ]
+}
+
+// ---------------------------------------------------------------------------
+// Tool dispatch
+// ---------------------------------------------------------------------------
+
+/// Dispatch execution to the appropriate item editor tool handler.
+pub async fn execute(name: &str, params: Value, _state: &ProjectState) -> ToolResult {
+    match name {
+        "item_create" => handle_item_create(params).await,
+        "item_read" => handle_item_read(params).await,
+        "item_update" => handle_item_update(params).await,
+        "item_validate" => handle_item_validate(params).await,
+        "item_generate_code" => handle_item_generate_code(params).await,
+        _ => ToolResult::error(format!("Unknown item tool: {}", name)),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Check if a name collides with a vanilla Minecraft item, including the
+/// item form of every vanilla block.
+fn is_vanilla_item(name: &str) -> bool {
+    VANILLA_ITEMS.binary_search(&name).is_ok() || is_vanilla_block(name)
+}
+
+/// The translation key Minecraft looks up for an item's name.
+fn lang_key(item: &ItemProject) -> String {
+    format!("item.{}.{}", item.mod_id, item.name)
+}
+
+fn food_from_params(params: &Value) -> Option<FoodProps> {
+    let f = params.get("food").filter(|f| f.is_object())?;
+    Some(FoodProps {
+        nutrition: f.get("nutrition").and_then(|v| v.as_u64()).unwrap_or(0) as u32,
+        saturation: f.get("saturation").and_then(|v| v.as_f64()).unwrap_or(0.0),
+        always_edible: f.get("always_edible").and_then(|v| v.as_bool()).unwrap_or(false),
+    })
+}
+
+fn read_item(path: &Path) -> Result<ItemProject, String> {
+    if !path.exists() {
+        return Err(format!("Item JSON file does not exist: {}", path.display()));
+    }
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read item JSON: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse item JSON: {}", e))
+}
+
+// ---------------------------------------------------------------------------
+// Code generation
+// ---------------------------------------------------------------------------
+
+fn generate_item_class(item: &ItemProject, class_name: &str, package_name: &str) -> String {
+    let mut code = format!(
+        r#"package {package_name}.item;
+
+import net.alloymc.api.item.Item;
+import net.alloymc.api.item.ItemProperties;
+"#,
+        package_name = package_name,
+    );
+
+    if item.rarity != "common" {
+        code.push_str("import net.alloymc.api.item.Rarity;\n");
+    }
+    if item.food.is_some() {
+        code.push_str("import net.alloymc.api.item.FoodProperties;\n");
+    }
+
+    code.push_str(&format!(
+        r#"
+/**
+ * {display_name}
+ * Generated by Alloy IDE Item Editor
+ */
+public class {class_name} extends Item {{
+
+    public {class_name}() {{
+        super(ItemProperties.of()
+            .maxStackSize({max_stack_size})
+"#,
+        display_name = item.display_name,
+        class_name = class_name,
+        max_stack_size = item.max_stack_size,
+    ));
+
+    if item.rarity != "common" {
+        code.push_str(&format!(
+            "            .rarity(Rarity.{})\n",
+            item.rarity.to_uppercase()
+        ));
+    }
+
+    if let Some(food) = &item.food {
+        code.push_str(&format!(
+            "            .food(FoodProperties.of()\n                .nutrition({})\n                .saturation({}f)\n",
+            food.nutrition, food.saturation
+        ));
+        if food.always_edible {
+            code.push_str("                .alwaysEdible()\n");
+        }
+        code.push_str("            )\n");
+    }
+
+    code.push_str("        );\n    }\n}\n");
+    code
+}
+
+fn generate_item_model(item: &ItemProject) -> String {
+    let tex = item.texture.as_deref().unwrap_or(&item.name);
+    format!(
+        r#"{{
+  "parent": "minecraft:item/generated",
+  "textures": {{
+    "layer0": "{}:item/{}"
+  }}
+}}"#,
+        item.mod_id, tex
+    )
+}
+
+// ---------------------------------------------------------------------------
+// item_create
+// ---------------------------------------------------------------------------
+
+async fn handle_item_create(params: Value) -> ToolResult {
+    let project_path = match params.get("project_path").and_then(|v| v.as_str()) {
+        Some(p) => p,
+        None => return ToolResult::missing_param("project_path"),
+    };
+    let name = match params.get("name").and_then(|v| v.as_str()) {
+        Some(n) => n,
+        None => return ToolResult::missing_param("name"),
+    };
+    let display_name = match params.get("display_name").and_then(|v| v.as_str()) {
+        Some(d) => d,
+        None => return ToolResult::missing_param("display_name"),
+    };
+    let mod_id = match params.get("mod_id").and_then(|v| v.as_str()) {
+        Some(m) => m,
+        None => return ToolResult::missing_param("mod_id"),
+    };
+
+    let project = Path::new(project_path);
+    if !project.exists() {
+        return ToolResult::error(format!(
+            "Project directory does not exist: {}",
+            project_path
+        ));
+    }
+
+    // Item and block identifiers follow the same rules
+    if !is_valid_block_name(name) {
+        return ToolResult::error(format!(
+            "Invalid item name '{}'. Must be lowercase, contain only a-z, 0-9, and underscores, \
+             and must not start with a digit.",
+            name
+        ));
+    }
+
+    if is_vanilla_item(name) {
+        return ToolResult::error(format!(
+            "Item name '{}' conflicts with a vanilla Minecraft item. \
+             Suggestion: use 'custom_{}'",
+            name, name
+        ));
+    }
+
+    let rarity = params
+        .get("rarity")
+        .and_then(|v| v.as_str())
+        .unwrap_or("common")
+        .to_string();
+    if !RARITIES.contains(&rarity.as_str()) {
+        return ToolResult::error(format!(
+            "Invalid rarity '{}'. Expected one of: {}",
+            rarity,
+            RARITIES.join(", ")
+        ));
+    }
+
+    let item = ItemProject {
+        name: name.to_string(),
+        display_name: display_name.to_string(),
+        mod_id: mod_id.to_string(),
+        max_stack_size: params
+            .get("max_stack_size")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(64) as u32,
+        rarity,
+        food: food_from_params(&params),
+        texture: Some(
+            params
+                .get("texture")
+                .and_then(|v| v.as_str())
+                .unwrap_or(name)
+                .to_string(),
+        ),
+    };
+
+    let item_json = match serde_json::to_string_pretty(&item) {
+        Ok(j) => j,
+        Err(e) => return ToolResult::error(format!("Failed to serialize item: {}", e)),
+    };
+
+    let item_json_path = project.join(format!("{}.item.json", name));
+    if let Err(e) = std::fs::write(&item_json_path, &item_json) {
+        return ToolResult::error(format!("Failed to write item JSON: {}", e));
+    }
+
+    ToolResult::json(&json!({
+        "status": "created",
+        "item_json_path": item_json_path.to_string_lossy(),
+        "created_files": [{
+            "path": item_json_path.to_string_lossy(),
+            "file_type": "item_json"
+        }]
+    }))
+}
+
+// ---------------------------------------------------------------------------
+// item_read
+// ---------------------------------------------------------------------------
+
+async fn handle_item_read(params: Value) -> ToolResult {
+    let path = match params.get("path").and_then(|v| v.as_str()) {
+        Some(p) => p,
+        None => return ToolResult::missing_param("path"),
+    };
+
+    let item_path = Path::new(path);
+    if !item_path.exists() {
+        return ToolResult::error(format!("Item JSON file does not exist: {}", path));
+    }
+    if !item_path.is_file() {
+        return ToolResult::error(format!("Path is not a file: {}", path));
+    }
+
+    let content = match std::fs::read_to_string(item_path) {
+        Ok(c) => c,
+        Err(e) => return ToolResult::error(format!("Failed to read item JSON: {}", e)),
+    };
+
+    let item_value: Value = match serde_json::from_str(&content) {
+        Ok(v) => v,
+        Err(e) => return ToolResult::error(format!("Failed to parse item JSON: {}", e)),
+    };
+
+    ToolResult::json(&item_value)
+}
+
+// ---------------------------------------------------------------------------
+// item_update
+// ---------------------------------------------------------------------------
+
+async fn handle_item_update(params: Value) -> ToolResult {
+    let path = match params.get("path").and_then(|v| v.as_str()) {
+        Some(p) => p,
+        None => return ToolResult::missing_param("path"),
+    };
+    let updates = match params.get("updates") {
+        Some(u) => u.clone(),
+        None => return ToolResult::missing_param("updates"),
+    };
+
+    let item_path = Path::new(path);
+    if !item_path.exists() {
+        return ToolResult::error(format!("Item JSON file does not exist: {}", path));
+    }
+
+    let content = match std::fs::read_to_string(item_path) {
+        Ok(c) => c,
+        Err(e) => return ToolResult::error(format!("Failed to read item JSON: {}", e)),
+    };
+
+    let mut item_value: Value = match serde_json::from_str(&content) {
+        Ok(v) => v,
+        Err(e) => return ToolResult::error(format!("Failed to parse item JSON: {}", e)),
+    };
+
+    // Merge updates into the existing value; food merges field by field
+    if let (Some(existing), Some(updates_obj)) = (item_value.as_object_mut(), updates.as_object())
+    {
+        for (key, value) in updates_obj {
+            if key == "food" && value.is_object() {
+                if let Some(en) = existing.get_mut(key).and_then(|f| f.as_object_mut()) {
+                    for (nk, nv) in value.as_object().into_iter().flatten() {
+                        en.insert(nk.clone(), nv.clone());
+                    }
+                    continue;
+                }
+            }
+            existing.insert(key.clone(), value.clone());
+        }
+    } else {
+        return ToolResult::error(
+            "Updates must be a JSON object with fields to merge into the item definition.",
+        );
+    }
+
+    // Refuse to write a definition the other item tools could not parse
+    if let Err(e) = serde_json::from_value::<ItemProject>(item_value.clone()) {
+        return ToolResult::error(format!("Updated item definition is invalid: {}", e));
+    }
+
+    let new_content = match serde_json::to_string_pretty(&item_value) {
+        Ok(s) => s,
+        Err(e) => return ToolResult::error(format!("Failed to serialize updated item: {}", e)),
+    };
+
+    if let Err(e) = std::fs::write(item_path, &new_content) {
+        return ToolResult::error(format!("Failed to write updated item JSON: {}", e));
+    }
+
+    ToolResult::json(&json!({
+        "status": "updated",
+        "path": path,
+        "item": item_value
+    }))
+}
+
+// ---------------------------------------------------------------------------
+// item_validate
+// ---------------------------------------------------------------------------
+
+async fn handle_item_validate(params: Value) -> ToolResult {
+    let project_path = match params.get("project_path").and_then(|v| v.as_str()) {
+        Some(p) => p,
+        None => return ToolResult::missing_param("project_path"),
+    };
+    let path = match params.get("path").and_then(|v| v.as_str()) {
+        Some(p) => p,
+        None => return ToolResult::missing_param("path"),
+    };
+
+    let project = Path::new(project_path);
+    let item = match read_item(Path::new(path)) {
+        Ok(i) => i,
+        Err(e) => return ToolResult::error(e),
+    };
+
+    let mut issues: Vec<Value> = Vec::new();
+
+    if !is_valid_block_name(&item.name) {
+        issues.push(json!({
+            "severity": "error",
+            "message": format!("Invalid item name '{}'.", item.name),
+            "suggestion": "Use lowercase a-z, 0-9, and underscores only, not starting with a digit."
+        }));
+    }
+
+    if is_vanilla_item(&item.name) {
+        issues.push(json!({
+            "severity": "error",
+            "message": format!("Item name '{}' conflicts with a vanilla Minecraft item.", item.name),
+            "suggestion": format!("Rename to 'custom_{}' or another unique name.", item.name)
+        }));
+    }
+
+    if item.display_name.trim().is_empty() {
+        issues.push(json!({
+            "severity": "warning",
+            "message": "Display name is empty. The item will show its translation key in game.",
+            "suggestion": "Set a display_name."
+        }));
+    }
+
+    if item.max_stack_size == 0 || item.max_stack_size > 64 {
+        issues.push(json!({
+            "severity": "error",
+            "message": format!("Max stack size {} is out of range.", item.max_stack_size),
+            "suggestion": "Use a value between 1 and 64."
+        }));
+    }
+
+    if !RARITIES.contains(&item.rarity.as_str()) {
+        issues.push(json!({
+            "severity": "error",
+            "message": format!("Unknown rarity '{}'.", item.rarity),
+            "suggestion": format!("Use one of: {}.", RARITIES.join(", "))
+        }));
+    }
+
+    if let Some(food) = &item.food {
+        if food.nutrition == 0 {
+            issues.push(json!({
+                "severity": "warning",
+                "message": "Food restores no hunger (nutrition is 0).",
+                "suggestion": "Set nutrition, e.g. 4 for an apple or 8 for steak."
+            }));
+        } else if food.nutrition > 20 {
+            issues.push(json!({
+                "severity": "warning",
+                "message": format!("Nutrition {} exceeds the full hunger bar (20).", food.nutrition),
+                "suggestion": "Anything above 20 is wasted; most foods restore 1-8."
+            }));
+        }
+        if food.saturation < 0.0 {
+            issues.push(json!({
+                "severity": "error",
+                "message": "Food saturation must not be negative.",
+                "suggestion": "Most foods use 0.1-1.2 (bread is 0.6)."
+            }));
+        }
+    }
+
+    // Check the texture file exists and is usable
+    let textures_dir = project
+        .join("src/main/resources/assets")
+        .join(&item.mod_id)
+        .join("textures/item");
+    match &item.texture {
+        None => issues.push(json!({
+            "severity": "warning",
+            "message": "No texture assigned. The item will render with the missing texture.",
+            "suggestion": "Set texture to a PNG name under textures/item."
+        })),
+        Some(name) => {
+            let tex_path = textures_dir.join(format!("{}.png", name));
+            if !tex_path.exists() {
+                issues.push(json!({
+                    "severity": "warning",
+                    "message": format!("Texture file not found: {}.png", name),
+                    "suggestion": "Import the texture file into the project assets."
+                }));
+            } else {
+                match super::resources::inspect_texture(&tex_path) {
+                    Ok(report) => issues.extend(report.issues.into_iter().map(|mut issue| {
+                        let message = issue["message"].as_str().unwrap_or("").to_string();
+                        issue["message"] = json!(format!("{}.png: {}", name, message));
+                        issue
+                    })),
+                    Err(e) => issues.push(json!({
+                        "severity": "error",
+                        "message": e,
+                        "suggestion": "Re-export the texture as a valid PNG."
+                    })),
+                }
+            }
+        }
+    }
+
+    // Check the generated item model, if any, still resolves
+    let item_model_path = project
+        .join("src/main/resources/assets")
+        .join(&item.mod_id)
+        .join("models/item")
+        .join(format!("{}.json", item.name));
+    if let Some(issue) = check_item_model_parent(project, &item_model_path) {
+        issues.push(issue);
+    }
+
+    let has_errors = issues
+        .iter()
+        .any(|i| i.get("severity").and_then(|s| s.as_str()) == Some("error"));
+
+    ToolResult::json(&json!({
+        "path": path,
+        "item_name": item.name,
+        "issues": issues,
+        "issue_count": issues.len(),
+        "valid": !has_errors
+    }))
+}
+
+// ---------------------------------------------------------------------------
+// item_generate_code
+// ---------------------------------------------------------------------------
+
+async fn handle_item_generate_code(params: Value) -> ToolResult {
+    let project_path = match params.get("project_path").and_then(|v| v.as_str()) {
+        Some(p) => p,
+        None => return ToolResult::missing_param("project_path"),
+    };
+    let path = match params.get("path").and_then(|v| v.as_str()) {
+        Some(p) => p,
+        None => return ToolResult::missing_param("path"),
+    };
+
+    let project = Path::new(project_path);
+    let item = match read_item(Path::new(path)) {
+        Ok(i) => i,
+        Err(e) => return ToolResult::error(e),
+    };
+
+    let class_name = to_pascal_case(&item.name);
+    let mut created_files: Vec<Value> = Vec::new();
+
+    // Same package layout as block_generate_code
+    let package_name = format!("com.{}", item.mod_id);
+
+    // 1. Generate Item Java class
+    let java_code = generate_item_class(&item, &class_name, &package_name);
+    let java_dir = project
+        .join("src/main/java")
+        .join(package_name.replace('.', "/"))
+        .join("item");
+
+    if let Err(e) = std::fs::create_dir_all(&java_dir) {
+        return ToolResult::error(format!("Failed to create directory: {}", e));
+    }
+
+    let java_path = java_dir.join(format!("{}.java", class_name));
+    if let Err(e) = std::fs::write(&java_path, &java_code) {
+        return ToolResult::error(format!("Failed to write Java file: {}", e));
+    }
+
+    created_files.push(json!({
+        "path": java_path.to_string_lossy(),
+        "file_type": "java_class"
+    }));
+
+    // 2. Generate Item model JSON
+    let assets_dir = project.join("src/main/resources/assets").join(&item.mod_id);
+    let item_models_dir = assets_dir.join("models/item");
+    if let Err(e) = std::fs::create_dir_all(&item_models_dir) {
+        return ToolResult::error(format!("Failed to create item models directory: {}", e));
+    }
+
+    let item_model_path = item_models_dir.join(format!("{}.json", item.name));
+    if let Err(e) = std::fs::write(&item_model_path, generate_item_model(&item)) {
+        return ToolResult::error(format!("Failed to write item model: {}", e));
+    }
+
+    created_files.push(json!({
+        "path": item_model_path.to_string_lossy(),
+        "file_type": "item_model"
+    }));
+
+    // 3. Display name in en_us.json
+    let key = lang_key(&item);
+    let lang_path = match super::resources::set_lang_key(
+        &assets_dir.join("lang"),
+        super::resources::SOURCE_LOCALE,
+        &key,
+        &item.display_name,
+    ) {
+        Ok((lang_path, _, _)) => lang_path,
+        Err(e) => return ToolResult::error(e),
+    };
+
+    created_files.push(json!({
+        "path": lang_path.to_string_lossy(),
+        "file_type": "lang"
+    }));
+
+    // 4. Registration snippet
+    let registration_snippet = format!(
+        r#"// Register item: {class_name}
+public static final Item {upper} = Registry.register(
+    Items.class,
+    "{name}",
+    new {class_name}()
+);"#,
+        class_name = class_name,
+        upper = item.name.to_uppercase(),
+        name = item.name,
+    );
+
+    ToolResult::json(&json!({
+        "status": "generated",
+        "created_files": created_files,
+        "item_class_path": java_path.to_string_lossy(),
+        "lang_key": key,
+        "registration_snippet": registration_snippet
+    }))
+}
@@ -1,6 +1,11 @@
+use crate::lsp::manager::{path_to_uri, uri_to_path};
 use crate::state::ProjectState;
+use crate::tools::{filesystem, paging};
 use crate::types::{ToolDefinition, ToolResult};
 use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 pub fn definitions() -> Vec<ToolDefinition> {
     vec![
@@ -17,21 +22,110 @@ pub fn definitions() -> Vec<ToolDefinition> {
         },
         ToolDefinition {
             name: "code_references".into(),
-            description: "Find all references to a symbol across the project (grep-based)".into(),
+            description: "Find all references to a symbol across the project (grep-based). Returns \
+                100 matches per page by default; pass next_cursor back as cursor for more."
+                .into(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
                     "symbol": { "type": "string", "description": "Symbol name to search for" },
-                    "path": { "type": "string", "description": "Directory to search in (defaults to project src/)" }
+                    "path": { "type": "string", "description": "Directory to search in (defaults to project src/)" },
+                    "project_path": { "type": "string", "description": "Project whose src/ to search when path is omitted (defaults to the current project)" },
+                    "limit": { "type": "integer", "description": "Maximum matches to return (default: 100, max: 1000)" },
+                    "cursor": { "type": "string", "description": "next_cursor from a previous call with the same symbol and path" }
                 },
                 "required": ["symbol"]
             }),
         },
         ToolDefinition {
-            name: "code_diagnostics".into(),
-            description: "Get environment violations and basic Java checks for the project".into(),
+            name: "code_find_references".into(),
+            description: "Find every usage of the symbol at a position, using the Java language server. \
+                Scope-aware, unlike the text search of code_references. Starts the language server \
+                on first use, which can take a while on large projects."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string", "description": "Absolute path to the Java file containing the symbol" },
+                    "line": { "type": "integer", "description": "1-based line of the symbol" },
+                    "column": { "type": "integer", "description": "1-based column of the symbol" },
+                    "project_path": { "type": "string", "description": "Project root the language server should load (defaults to the open project)" }
+                },
+                "required": ["path", "line", "column"]
+            }),
+        },
+        ToolDefinition {
+            name: "code_rename_symbol".into(),
+            description: "Rename the class, method, field, or variable at a position everywhere it is used, \
+                using the Java language server. Scope-aware: unrelated text that happens to match is left \
+                alone. Renaming a class also renames its file. Use dry_run to preview the edits."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string", "description": "Absolute path to the Java file containing the symbol" },
+                    "line": { "type": "integer", "description": "1-based line of the symbol" },
+                    "column": { "type": "integer", "description": "1-based column of the symbol" },
+                    "new_name": { "type": "string", "description": "New name for the symbol" },
+                    "dry_run": { "type": "boolean", "description": "Return the planned edits without writing (default false)" },
+                    "project_path": { "type": "string", "description": "Project root the language server should load (defaults to the open project)" }
+                },
+                "required": ["path", "line", "column", "new_name"]
+            }),
+        },
+        ToolDefinition {
+            name: "code_format".into(),
+            description: "Format a file, or a range of lines in it, with the Java language server and \
+                write the result back. Indentation, final newline and trailing whitespace follow the \
+                file's .editorconfig; other formatter settings the language server is configured \
+                with apply as usual. Returns whether anything changed."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string", "description": "Absolute path to the file to format" },
+                    "start_line": { "type": "integer", "description": "1-based first line of the range to format (formats the whole file if omitted)" },
+                    "start_column": { "type": "integer", "description": "1-based column the range starts at (default 1)" },
+                    "end_line": { "type": "integer", "description": "1-based last line of the range, inclusive (default start_line)" },
+                    "end_column": { "type": "integer", "description": "1-based column the range ends before (default: end of end_line)" },
+                    "project_path": { "type": "string", "description": "Project root the language server should load (defaults to the open project)" }
+                },
+                "required": ["path"]
+            }),
+        },
+        ToolDefinition {
+            name: "code_lsp_status".into(),
+            description: "Report whether the Java language server behind the code_* tools is running and \
+                initialized, which project root it has loaded, and its crash/restart history."
+                .into(),
             input_schema: json!({ "type": "object", "properties": {} }),
         },
+        ToolDefinition {
+            name: "code_lsp_restart".into(),
+            description: "Stop the Java language server and start a fresh one, for when code_* tools hang \
+                or return stale results. Waits until the new server is initialized."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_path": { "type": "string", "description": "Project root to load (defaults to the open project)" }
+                }
+            }),
+        },
+        ToolDefinition {
+            name: "code_diagnostics".into(),
+            description: "Get compiler errors and warnings from the Java language server, plus Alloy \
+                environment violations and style checks. Pass path for one file (opened with the \
+                language server and analysed fresh), or omit it for the whole project."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string", "description": "Absolute path to a single file to check" },
+                    "project_path": { "type": "string", "description": "Project root (defaults to the open project)" }
+                }
+            }),
+        },
     ]
 }
 
@@ -39,7 +133,12 @@ pub async fn execute(name: &str, params: Value, state: &ProjectState) -> ToolRes
     match name {
         "code_symbols" => code_symbols(params).await,
         "code_references" => code_references(params, state).await,
-        "code_diagnostics" => code_diagnostics(state).await,
+        "code_find_references" => code_find_references(params, state).await,
+        "code_rename_symbol" => code_rename_symbol(params, state).await,
+        "code_format" => code_format(params, state).await,
+        "code_diagnostics" => code_diagnostics(params, state).await,
+        "code_lsp_status" => ToolResult::json(&state.lsp.status().await),
+        "code_lsp_restart" => code_lsp_restart(params, state).await,
         _ => ToolResult::error(format!("Unknown code tool: {}", name)),
     }
 }
@@ -47,7 +146,7 @@ pub async fn execute(name: &str, params: Value, state: &ProjectState) -> ToolRes
 async fn code_symbols(params: Value) -> ToolResult {
     let path = match params.get("path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: path"),
+        None => return ToolResult::missing_param("path"),
     };
 
     let content = match std::fs::read_to_string(path) {
@@ -158,23 +257,36 @@ async fn code_symbols(params: Value) -> ToolResult {
 async fn code_references(params: Value, state: &ProjectState) -> ToolResult {
     let symbol = match params.get("symbol").and_then(|v| v.as_str()) {
         Some(s) => s.to_string(),
-        None => return ToolResult::error("Missing required parameter: symbol"),
+        None => return ToolResult::missing_param("symbol"),
     };
 
     let search_path = match params.get("path").and_then(|v| v.as_str()) {
         Some(p) => p.to_string(),
         None => {
-            match state.project_path() {
+            match state.resolve_project_path(&params) {
                 Ok(p) => format!("{}/src", p),
                 Err(_) => return ToolResult::error("No search path and no project open"),
             }
         }
     };
 
+    let limit = paging::limit(params.get("limit"), 100, 1000);
+    let resume = match paging::decode_cursor(&params) {
+        Ok(Some(cursor)) => match paging::FilePosition::from_cursor(&cursor) {
+            Ok(p) => Some(p),
+            Err(e) => return ToolResult::error(e),
+        },
+        Ok(None) => None,
+        Err(e) => return ToolResult::error(e),
+    };
+    let root = Path::new(&search_path);
     let mut results: Vec<Value> = Vec::new();
+    let mut last: Option<paging::FilePosition> = None;
+    let mut next: Option<paging::FilePosition> = None;
 
-    for entry in walkdir::WalkDir::new(&search_path)
+    'walk: for entry in walkdir::WalkDir::new(root)
         .max_depth(15)
+        .sort_by_file_name()
         .into_iter()
         .filter_entry(|e| {
             let name = e.file_name().to_string_lossy();
@@ -191,23 +303,31 @@ async fn code_references(params: Value, state: &ProjectState) -> ToolResult {
         if ext != "java" && ext != "kt" && ext != "gradle" && ext != "kts" {
             continue;
         }
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        if resume.as_ref().is_some_and(|r| r.skips_file(relative)) {
+            continue;
+        }
 
         if let Ok(content) = std::fs::read_to_string(path) {
             for (i, line) in content.lines().enumerate() {
-                if line.contains(&symbol) {
-                    results.push(json!({
-                        "file": path.to_string_lossy(),
-                        "line": i + 1,
-                        "content": line.trim(),
-                    }));
-                    if results.len() >= 100 {
-                        return ToolResult::json(&json!({
-                            "symbol": symbol,
-                            "results": results,
-                            "truncated": true
-                        }));
-                    }
+                if !line.contains(&symbol)
+                    || resume.as_ref().is_some_and(|r| r.covers(relative, i + 1))
+                {
+                    continue;
+                }
+                if results.len() >= limit {
+                    next = last.take();
+                    break 'walk;
                 }
+                results.push(json!({
+                    "file": path.to_string_lossy(),
+                    "line": i + 1,
+                    "content": line.trim(),
+                }));
+                last = Some(paging::FilePosition {
+                    file: relative.to_string_lossy().to_string(),
+                    line: i + 1,
+                });
             }
         }
     }
@@ -216,112 +336,660 @@ async fn code_references(params: Value, state: &ProjectState) -> ToolResult {
         "symbol": symbol,
         "results": results,
         "count": results.len(),
-        "truncated": false
+        "truncated": next.is_some(),
+        "next_cursor": next.map(|n| n.to_cursor())
     }))
 }
 
-async fn code_diagnostics(state: &ProjectState) -> ToolResult {
-    let project_path = match state.project_path() {
+/// A position in a file, as the LSP tools take it: 1-based line and column.
+struct Position {
+    path: PathBuf,
+    line: u64,
+    column: u64,
+}
+
+impl Position {
+    fn from_params(params: &Value) -> Result<Self, String> {
+        let path = params
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or("Missing required parameter: path")?;
+        let line = params
+            .get("line")
+            .and_then(|v| v.as_u64())
+            .filter(|l| *l >= 1)
+            .ok_or("Missing or invalid parameter: line (1-based)")?;
+        let column = params
+            .get("column")
+            .and_then(|v| v.as_u64())
+            .filter(|c| *c >= 1)
+            .ok_or("Missing or invalid parameter: column (1-based)")?;
+        Ok(Self {
+            path: PathBuf::from(path),
+            line,
+            column,
+        })
+    }
+
+    fn to_lsp(&self) -> Value {
+        json!({ "line": self.line - 1, "character": self.column - 1 })
+    }
+}
+
+/// The root the language server should load: `project_path` if given,
+/// otherwise the open project.
+fn lsp_root(params: &Value, state: &ProjectState) -> Result<String, String> {
+    state.resolve_project_path(params)
+}
+
+/// Convert LSP `Location`s (or `LocationLink`s) into `{ file, line, column, preview }`.
+fn locations_to_json(result: &Value) -> Vec<Value> {
+    let mut lines_by_file: HashMap<PathBuf, Vec<String>> = HashMap::new();
+    result
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|loc| {
+            let uri = loc.get("uri").or_else(|| loc.get("targetUri"))?.as_str()?;
+            let range = loc.get("range").or_else(|| loc.get("targetSelectionRange"))?;
+            let line = range.get("start")?.get("line")?.as_u64()?;
+            let character = range.get("start")?.get("character")?.as_u64()?;
+            let file = uri_to_path(uri);
+            let lines = lines_by_file.entry(file.clone()).or_insert_with(|| {
+                std::fs::read_to_string(&file)
+                    .map(|c| c.lines().map(String::from).collect())
+                    .unwrap_or_default()
+            });
+            let preview = lines.get(line as usize).map(|l| l.trim()).unwrap_or("");
+            Some(json!({
+                "file": file.to_string_lossy(),
+                "line": line + 1,
+                "column": character + 1,
+                "preview": preview,
+            }))
+        })
+        .collect()
+}
+
+async fn code_find_references(params: Value, state: &ProjectState) -> ToolResult {
+    let pos = match Position::from_params(&params) {
         Ok(p) => p,
         Err(e) => return ToolResult::error(e),
     };
+    let root = match lsp_root(&params, state) {
+        Ok(r) => r,
+        Err(e) => return ToolResult::error(e),
+    };
+
+    let uri = match state.lsp.open_document(&root, &pos.path).await {
+        Ok(uri) => uri,
+        Err(e) => return ToolResult::error(e),
+    };
+    let result = match state
+        .lsp
+        .request(
+            &root,
+            "textDocument/references",
+            json!({
+                "textDocument": { "uri": uri },
+                "position": pos.to_lsp(),
+                "context": { "includeDeclaration": false }
+            }),
+        )
+        .await
+    {
+        Ok(r) => r,
+        Err(e) => return ToolResult::error(e),
+    };
+
+    // A null result means no references, not a failure
+    let references = locations_to_json(&result);
+    ToolResult::json(&json!({
+        "file": pos.path.to_string_lossy(),
+        "line": pos.line,
+        "column": pos.column,
+        "references": references,
+        "count": references.len(),
+    }))
+}
+
+/// One LSP `TextEdit`, with its range already resolved to line/character.
+struct TextEdit {
+    start: (usize, usize),
+    end: (usize, usize),
+    new_text: String,
+}
+
+impl TextEdit {
+    fn from_lsp(edit: &Value) -> Option<Self> {
+        let point = |p: &Value| -> Option<(usize, usize)> {
+            Some((p.get("line")?.as_u64()? as usize, p.get("character")?.as_u64()? as usize))
+        };
+        let range = edit.get("range")?;
+        Some(Self {
+            start: point(range.get("start")?)?,
+            end: point(range.get("end")?)?,
+            new_text: edit.get("newText")?.as_str()?.to_string(),
+        })
+    }
+}
 
-    let mod_json_path = std::path::Path::new(&project_path).join("alloy.mod.json");
-    if !mod_json_path.exists() {
-        return ToolResult::json(&json!({
-            "diagnostics": [],
-            "message": "No alloy.mod.json found"
-        }));
+/// Byte offset of an LSP position. LSP characters count UTF-16 code units.
+fn lsp_offset(text: &str, (line, character): (usize, usize)) -> usize {
+    let mut offset = 0;
+    for (i, l) in text.split_inclusive('\n').enumerate() {
+        if i == line {
+            let mut units = 0;
+            for (byte, c) in l.char_indices() {
+                if units >= character || c == '\n' || c == '\r' {
+                    return offset + byte;
+                }
+                units += c.len_utf16();
+            }
+            return offset + l.trim_end_matches(['\n', '\r']).len();
+        }
+        offset += l.len();
     }
+    text.len()
+}
 
-    let mod_json = match std::fs::read_to_string(&mod_json_path) {
-        Ok(c) => c,
-        Err(e) => return ToolResult::error(format!("Failed to read alloy.mod.json: {}", e)),
-    };
+/// Apply edits to `text`. LSP edits never overlap and refer to the
+/// original text, so applying them back to front keeps offsets valid.
+fn apply_text_edits(text: &str, edits: &[TextEdit]) -> String {
+    let mut resolved: Vec<(usize, usize, &str)> = edits
+        .iter()
+        .map(|e| (lsp_offset(text, e.start), lsp_offset(text, e.end), e.new_text.as_str()))
+        .collect();
+    resolved.sort_by(|a, b| b.0.cmp(&a.0).then(b.1.cmp(&a.1)));
+    let mut out = text.to_string();
+    for (start, end, new_text) in resolved {
+        out.replace_range(start..end.max(start), new_text);
+    }
+    out
+}
 
-    let parsed: Value = match serde_json::from_str(&mod_json) {
-        Ok(v) => v,
-        Err(e) => return ToolResult::error(format!("Failed to parse: {}", e)),
+/// A step of an LSP `WorkspaceEdit`, in the order it must be applied.
+enum EditStep {
+    Text { path: PathBuf, edits: Vec<TextEdit> },
+    Create { path: PathBuf },
+    Rename { from: PathBuf, to: PathBuf },
+    Delete { path: PathBuf },
+}
+
+/// Flatten a `WorkspaceEdit` (either `documentChanges` or the older `changes` map).
+fn workspace_edit_steps(edit: &Value) -> Vec<EditStep> {
+    let text_step = |uri: &str, edits: &Value| EditStep::Text {
+        path: uri_to_path(uri),
+        edits: edits
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(TextEdit::from_lsp)
+            .collect(),
     };
 
-    let environment = parsed.get("environment").and_then(|e| e.as_str()).unwrap_or("both");
+    if let Some(changes) = edit.get("documentChanges").and_then(|c| c.as_array()) {
+        return changes
+            .iter()
+            .filter_map(|change| {
+                let uri_of = |key: &str| change.get(key).and_then(|u| u.as_str()).map(uri_to_path);
+                match change.get("kind").and_then(|k| k.as_str()) {
+                    Some("create") => Some(EditStep::Create { path: uri_of("uri")? }),
+                    Some("rename") => Some(EditStep::Rename {
+                        from: uri_of("oldUri")?,
+                        to: uri_of("newUri")?,
+                    }),
+                    Some("delete") => Some(EditStep::Delete { path: uri_of("uri")? }),
+                    _ => {
+                        let uri = change.get("textDocument")?.get("uri")?.as_str()?;
+                        Some(text_step(uri, change.get("edits")?))
+                    }
+                }
+            })
+            .collect();
+    }
 
-    let mut diagnostics: Vec<Value> = Vec::new();
+    edit.get("changes")
+        .and_then(|c| c.as_object())
+        .into_iter()
+        .flatten()
+        .map(|(uri, edits)| text_step(uri, edits))
+        .collect()
+}
 
-    if environment != "both" {
-        let forbidden: Vec<&str> = if environment == "server" {
-            vec!["net.alloymc.api.client", "net.alloymc.api.render", "net.alloymc.api.gui",
-                 "AlloyRenderer", "AlloyScreen", "AlloyHud", "GuiComponent"]
-        } else {
-            vec!["net.alloymc.api.server", "ServerCommandSource", "DedicatedServer"]
-        };
+async fn code_rename_symbol(params: Value, state: &ProjectState) -> ToolResult {
+    let pos = match Position::from_params(&params) {
+        Ok(p) => p,
+        Err(e) => return ToolResult::error(e),
+    };
+    let new_name = match params.get("new_name").and_then(|v| v.as_str()).map(str::trim) {
+        Some(n) if !n.is_empty() => n.to_string(),
+        _ => return ToolResult::missing_param("new_name"),
+    };
+    let dry_run = params.get("dry_run").and_then(|v| v.as_bool()).unwrap_or(false);
+    let root = match lsp_root(&params, state) {
+        Ok(r) => r,
+        Err(e) => return ToolResult::error(e),
+    };
 
-        let src_dir = std::path::Path::new(&project_path).join("src");
-        if src_dir.exists() {
-            for entry in walkdir::WalkDir::new(&src_dir).into_iter().flatten() {
-                let path = entry.path();
-                if !path.is_file() || path.extension().and_then(|e| e.to_str()) != Some("java") {
-                    continue;
+    let uri = match state.lsp.open_document(&root, &pos.path).await {
+        Ok(uri) => uri,
+        Err(e) => return ToolResult::error(e),
+    };
+    let edit = match state
+        .lsp
+        .request(
+            &root,
+            "textDocument/rename",
+            json!({
+                "textDocument": { "uri": uri },
+                "position": pos.to_lsp(),
+                "newName": new_name
+            }),
+        )
+        .await
+    {
+        Ok(Value::Null) => return ToolResult::error("Nothing to rename at that position"),
+        Ok(edit) => edit,
+        Err(e) => return ToolResult::error(e),
+    };
+
+    let mut files: Vec<Value> = Vec::new();
+    let mut renamed: Vec<Value> = Vec::new();
+    let mut total_edits = 0;
+
+    for step in workspace_edit_steps(&edit) {
+        match step {
+            EditStep::Text { path, edits } => {
+                let original = match std::fs::read_to_string(&path) {
+                    Ok(t) => t,
+                    Err(e) => return ToolResult::error(format!("Failed to read {}: {}", path.display(), e)),
+                };
+                total_edits += edits.len();
+                let mut entry = json!({
+                    "file": path.to_string_lossy(),
+                    "edits": edits.len(),
+                });
+                if dry_run {
+                    entry["changes"] = edits
+                        .iter()
+                        .map(|e| {
+                            let (start, end) = (lsp_offset(&original, e.start), lsp_offset(&original, e.end));
+                            json!({
+                                "line": e.start.0 + 1,
+                                "column": e.start.1 + 1,
+                                "old": original.get(start..end.max(start)).unwrap_or(""),
+                                "new": e.new_text,
+                            })
+                        })
+                        .collect();
+                } else if let Err(e) = std::fs::write(&path, apply_text_edits(&original, &edits)) {
+                    return ToolResult::error(format!("Failed to write {}: {}", path.display(), e));
+                }
+                files.push(entry);
+            }
+            EditStep::Rename { from, to } => {
+                if !dry_run {
+                    if to.exists() {
+                        return ToolResult::error(format!("Cannot rename {}: {} already exists", from.display(), to.display()));
+                    }
+                    if let Err(e) = std::fs::rename(&from, &to) {
+                        return ToolResult::error(format!("Failed to rename {}: {}", from.display(), e));
+                    }
+                    state.lsp.close_document(&path_to_uri(&from)).await;
+                }
+                renamed.push(json!({ "from": from.to_string_lossy(), "to": to.to_string_lossy() }));
+            }
+            EditStep::Create { path } => {
+                if !dry_run && !path.exists() {
+                    if let Some(parent) = path.parent() {
+                        let _ = std::fs::create_dir_all(parent);
+                    }
+                    if let Err(e) = std::fs::write(&path, "") {
+                        return ToolResult::error(format!("Failed to create {}: {}", path.display(), e));
+                    }
                 }
-                if let Ok(content) = std::fs::read_to_string(path) {
-                    for (line_num, line) in content.lines().enumerate() {
-                        let trimmed = line.trim();
-                        if trimmed.starts_with("import ") {
-                            for pattern in &forbidden {
-                                if trimmed.contains(pattern) {
-                                    diagnostics.push(json!({
-                                        "file": path.to_string_lossy(),
-                                        "line": line_num + 1,
-                                        "severity": "warning",
-                                        "message": format!("Forbidden import for {} mod: {}", environment, pattern),
-                                    }));
-                                    break;
-                                }
-                            }
-                        }
+                files.push(json!({ "file": path.to_string_lossy(), "created": true }));
+            }
+            EditStep::Delete { path } => {
+                if !dry_run {
+                    if let Err(e) = std::fs::remove_file(&path) {
+                        return ToolResult::error(format!("Failed to delete {}: {}", path.display(), e));
                     }
+                    state.lsp.close_document(&path_to_uri(&path)).await;
                 }
+                files.push(json!({ "file": path.to_string_lossy(), "deleted": true }));
             }
         }
     }
 
-    // Check for common issues
-    let src_dir = std::path::Path::new(&project_path).join("src");
-    if src_dir.exists() {
-        for entry in walkdir::WalkDir::new(&src_dir).into_iter().flatten() {
-            let path = entry.path();
-            if !path.is_file() || path.extension().and_then(|e| e.to_str()) != Some("java") {
+    ToolResult::json(&json!({
+        "new_name": new_name,
+        "dry_run": dry_run,
+        "files": files,
+        "renamed": renamed,
+        "file_count": files.len(),
+        "total_edits": total_edits,
+    }))
+}
+
+/// Whether an .editorconfig glob matches `path`, relative to the file's
+/// directory. Globs without a `/` match the file name at any depth; a
+/// leading `/` anchors the glob to the .editorconfig's directory.
+fn editorconfig_matches(glob: &str, path: &str) -> bool {
+    match glob.strip_prefix('/') {
+        Some(anchored) => filesystem::matches_path_glob(path, anchored),
+        None => filesystem::matches_glob(path, glob),
+    }
+}
+
+/// The LSP `FormattingOptions` for a file: 4-space indentation unless an
+/// .editorconfig between the file and the nearest `root = true` says
+/// otherwise.
+fn formatting_options(file: &Path) -> Value {
+    let mut configs: Vec<(PathBuf, String)> = Vec::new();
+    for dir in file.ancestors().skip(1) {
+        let Ok(content) = std::fs::read_to_string(dir.join(".editorconfig")) else {
+            continue;
+        };
+        let is_root = content
+            .lines()
+            .map(str::trim)
+            .take_while(|l| !l.starts_with('['))
+            .any(|l| l.replace(' ', "").eq_ignore_ascii_case("root=true"));
+        configs.push((dir.to_path_buf(), content));
+        if is_root {
+            break;
+        }
+    }
+
+    // Apply the outermost file first so nearer ones override it
+    let mut props: HashMap<String, String> = HashMap::new();
+    for (dir, content) in configs.iter().rev() {
+        let relative = file.strip_prefix(dir).unwrap_or(file).to_string_lossy().replace('\\', "/");
+        let mut active = false;
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
                 continue;
             }
-            if let Ok(content) = std::fs::read_to_string(path) {
-                for (line_num, line) in content.lines().enumerate() {
-                    let trimmed = line.trim();
-                    // Check for star imports
-                    if trimmed.starts_with("import ") && trimmed.contains(".*") {
-                        diagnostics.push(json!({
-                            "file": path.to_string_lossy(),
-                            "line": line_num + 1,
-                            "severity": "warning",
-                            "message": "Star import detected — use explicit imports",
-                        }));
-                    }
-                    // Check for direct Minecraft class usage
-                    if trimmed.starts_with("import net.minecraft.") {
-                        diagnostics.push(json!({
-                            "file": path.to_string_lossy(),
-                            "line": line_num + 1,
-                            "severity": "error",
-                            "message": "Direct Minecraft import — use Alloy API instead",
-                        }));
-                    }
-                }
+            if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                active = editorconfig_matches(section, &relative);
+            } else if let (true, Some((key, value))) = (active, line.split_once('=')) {
+                props.insert(key.trim().to_ascii_lowercase(), value.trim().to_ascii_lowercase());
             }
         }
     }
 
+    let insert_spaces = props.get("indent_style").is_none_or(|s| s != "tab");
+    let indent_size = props.get("indent_size").and_then(|s| s.parse::<u64>().ok());
+    let tab_width = props.get("tab_width").and_then(|s| s.parse::<u64>().ok());
+    let tab_size = if insert_spaces {
+        indent_size.or(tab_width)
+    } else {
+        tab_width.or(indent_size)
+    };
+    let mut options = json!({
+        "tabSize": tab_size.unwrap_or(4),
+        "insertSpaces": insert_spaces,
+    });
+    if let Some(v) = props.get("trim_trailing_whitespace") {
+        options["trimTrailingWhitespace"] = json!(v == "true");
+    }
+    if let Some(v) = props.get("insert_final_newline") {
+        options["insertFinalNewline"] = json!(v == "true");
+        options["trimFinalNewlines"] = json!(v == "true");
+    }
+    options
+}
+
+/// The `start_line`..`end_line` range to format, as an LSP `Range`, or `None`
+/// for the whole file.
+fn format_range(params: &Value) -> Result<Option<Value>, String> {
+    let get = |key: &str| params.get(key).and_then(|v| v.as_u64());
+    let Some(start_line) = get("start_line") else {
+        return Ok(None);
+    };
+    let end_line = get("end_line").unwrap_or(start_line);
+    if start_line < 1 || end_line < start_line {
+        return Err(
+            "Invalid range: lines are 1-based and end_line must not be before start_line".into(),
+        );
+    }
+    let start_column = get("start_column").unwrap_or(1).max(1);
+    // Without an end column the range runs to the start of the next line
+    let end = match get("end_column") {
+        Some(column) => json!({ "line": end_line - 1, "character": column.max(1) - 1 }),
+        None => json!({ "line": end_line, "character": 0 }),
+    };
+    Ok(Some(json!({
+        "start": { "line": start_line - 1, "character": start_column - 1 },
+        "end": end
+    })))
+}
+
+async fn code_format(params: Value, state: &ProjectState) -> ToolResult {
+    let path = match params.get("path").and_then(|v| v.as_str()) {
+        Some(p) => PathBuf::from(p),
+        None => return ToolResult::missing_param("path"),
+    };
+    let range = match format_range(&params) {
+        Ok(r) => r,
+        Err(e) => return ToolResult::error(e),
+    };
+    let root = match lsp_root(&params, state) {
+        Ok(r) => r,
+        Err(e) => return ToolResult::error(e),
+    };
+
+    let original = match std::fs::read_to_string(&path) {
+        Ok(t) => t,
+        Err(e) => return ToolResult::error(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    let uri = match state.lsp.open_document(&root, &path).await {
+        Ok(uri) => uri,
+        Err(e) => return ToolResult::error(e),
+    };
+
+    let options = formatting_options(&path);
+    let (method, request) = match &range {
+        Some(range) => (
+            "textDocument/rangeFormatting",
+            json!({ "textDocument": { "uri": uri }, "range": range, "options": options }),
+        ),
+        None => (
+            "textDocument/formatting",
+            json!({ "textDocument": { "uri": uri }, "options": options }),
+        ),
+    };
+    let edits: Vec<TextEdit> = match state.lsp.request(&root, method, request).await {
+        // A null result means there was nothing to change
+        Ok(result) => result
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(TextEdit::from_lsp)
+            .collect(),
+        Err(e) => return ToolResult::error(e),
+    };
+
+    let formatted = apply_text_edits(&original, &edits);
+    let changed = formatted != original;
+    if changed {
+        if let Err(e) = std::fs::write(&path, &formatted) {
+            return ToolResult::error(format!("Failed to write {}: {}", path.display(), e));
+        }
+    }
+
     ToolResult::json(&json!({
+        "file": path.to_string_lossy(),
+        "changed": changed,
+        "edits": edits.len(),
+        "range": range,
+        "options": options,
+    }))
+}
+
+async fn code_lsp_restart(params: Value, state: &ProjectState) -> ToolResult {
+    let root = match lsp_root(&params, state) {
+        Ok(r) => r,
+        Err(e) => return ToolResult::error(e),
+    };
+    if let Err(e) = state.lsp.restart(&root).await {
+        return ToolResult::error(e);
+    }
+    // Wait for the project import like any other call would
+    if let Err(e) = state.lsp.ensure(&root).await {
+        return ToolResult::error(e);
+    }
+    ToolResult::json(&state.lsp.status().await)
+}
+
+/// How long code_diagnostics waits for the server to analyse a file.
+const DIAGNOSTICS_WAIT: Duration = Duration::from_secs(5);
+
+/// Convert an LSP `Diagnostic` to `{ file, line, column, severity, message, source }`.
+fn lsp_diagnostic_to_json(file: &Path, diagnostic: &Value) -> Value {
+    let start = diagnostic.get("range").and_then(|r| r.get("start"));
+    let position = |key: &str| {
+        start
+            .and_then(|s| s.get(key))
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0)
+            + 1
+    };
+    let severity = match diagnostic.get("severity").and_then(|s| s.as_u64()) {
+        Some(1) => "error",
+        Some(2) => "warning",
+        Some(3) => "info",
+        _ => "hint",
+    };
+    json!({
+        "file": file.to_string_lossy(),
+        "line": position("line"),
+        "column": position("character"),
+        "severity": severity,
+        "message": diagnostic.get("message").and_then(|m| m.as_str()).unwrap_or(""),
+        "source": diagnostic.get("source").and_then(|s| s.as_str()).unwrap_or("java"),
+    })
+}
+
+async fn code_diagnostics(params: Value, state: &ProjectState) -> ToolResult {
+    let file = params.get("path").and_then(|v| v.as_str()).map(PathBuf::from);
+    let project_path = match lsp_root(&params, state) {
+        Ok(p) => p,
+        Err(e) => return ToolResult::error(e),
+    };
+
+    let mut diagnostics: Vec<Value> = Vec::new();
+
+    // Compiler problems. A missing or broken language server is reported
+    // alongside the Alloy checks rather than failing the whole call.
+    let lsp = match &file {
+        Some(file) => state
+            .lsp
+            .document_diagnostics(&project_path, file, DIAGNOSTICS_WAIT)
+            .await
+            .map(|d| d.iter().map(|d| lsp_diagnostic_to_json(file, d)).collect::<Vec<_>>()),
+        None => state.lsp.all_diagnostics(&project_path).await.map(|by_uri| {
+            let mut all: Vec<Value> = by_uri
+                .iter()
+                .flat_map(|(uri, list)| {
+                    let file = uri_to_path(uri);
+                    list.iter()
+                        .map(|d| lsp_diagnostic_to_json(&file, d))
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+            all.sort_by(|a, b| {
+                (a["file"].as_str(), a["line"].as_u64()).cmp(&(b["file"].as_str(), b["line"].as_u64()))
+            });
+            all
+        }),
+    };
+    let lsp_error = match lsp {
+        Ok(found) => {
+            diagnostics.extend(found);
+            None
+        }
+        Err(e) => Some(e),
+    };
+
+    let (environment, alloy) = alloy_checks(&project_path, file.as_deref());
+    diagnostics.extend(alloy);
+
+    let mut result = json!({
         "environment": environment,
         "diagnostics": diagnostics,
         "count": diagnostics.len()
-    }))
+    });
+    if let Some(e) = lsp_error {
+        result["lsp_error"] = json!(e);
+    }
+    ToolResult::json(&result)
+}
+
+/// Environment violations and Alloy style checks over the project's Java
+/// sources, or just `only` if given. Returns the declared environment too.
+fn alloy_checks(project_path: &str, only: Option<&Path>) -> (String, Vec<Value>) {
+    let mod_json_path = Path::new(project_path).join("alloy.mod.json");
+    let environment = std::fs::read_to_string(&mod_json_path)
+        .ok()
+        .and_then(|c| serde_json::from_str::<Value>(&c).ok())
+        .and_then(|v| v.get("environment").and_then(|e| e.as_str()).map(String::from))
+        .unwrap_or_else(|| "both".to_string());
+
+    let forbidden: Vec<&str> = match environment.as_str() {
+        "server" => vec!["net.alloymc.api.client", "net.alloymc.api.render", "net.alloymc.api.gui",
+                         "AlloyRenderer", "AlloyScreen", "AlloyHud", "GuiComponent"],
+        "client" => vec!["net.alloymc.api.server", "ServerCommandSource", "DedicatedServer"],
+        _ => Vec::new(),
+    };
+
+    let files: Vec<PathBuf> = match only {
+        Some(file) => vec![file.to_path_buf()],
+        None => walkdir::WalkDir::new(Path::new(project_path).join("src"))
+            .into_iter()
+            .flatten()
+            .map(|e| e.into_path())
+            .collect(),
+    };
+
+    let mut diagnostics: Vec<Value> = Vec::new();
+    for path in files {
+        if !path.is_file() || path.extension().and_then(|e| e.to_str()) != Some("java") {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let mut push = |line_num: usize, severity: &str, message: String| {
+            diagnostics.push(json!({
+                "file": path.to_string_lossy(),
+                "line": line_num + 1,
+                "column": 1,
+                "severity": severity,
+                "message": message,
+                "source": "alloy",
+            }));
+        };
+        for (line_num, line) in content.lines().enumerate() {
+            let trimmed = line.trim();
+            if !trimmed.starts_with("import ") {
+                continue;
+            }
+            if let Some(pattern) = forbidden.iter().find(|p| trimmed.contains(*p)) {
+                push(line_num, "warning", format!("Forbidden import for {} mod: {}", environment, pattern));
+            }
+            // Check for star imports
+            if trimmed.contains(".*") {
+                push(line_num, "warning", "Star import detected — use explicit imports".to_string());
+            }
+            // Check for direct Minecraft class usage
+            if trimmed.starts_with("import net.minecraft.") {
+                push(line_num, "error", "Direct Minecraft import — use Alloy API instead".to_string());
+            }
+        }
+    }
+
+    (environment, diagnostics)
 }
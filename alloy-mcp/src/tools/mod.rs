@@ -5,14 +5,17 @@ pub mod editor;
 pub mod filesystem;
 pub mod git;
 pub mod gui;
+pub mod item;
 pub mod lsp;
 pub mod modpack;
+pub(crate) mod paging;
 pub mod project;
 pub mod resources;
+pub mod server;
 pub mod terminal;
 
 use crate::state::ProjectState;
-use crate::types::{ToolDefinition, ToolResult};
+use crate::types::{ProgressSink, ToolDefinition, ToolResult};
 use serde_json::Value;
 
 /// Central tool registry. Aggregates definitions and dispatch from all tool modules.
@@ -29,21 +32,30 @@ impl ToolRegistry {
         defs.extend(build::definitions());
         defs.extend(terminal::definitions());
         defs.extend(block::definitions());
+        defs.extend(item::definitions());
         defs.extend(gui::definitions());
         defs.extend(animation::definitions());
         defs.extend(modpack::definitions());
         defs.extend(lsp::definitions());
+        defs.extend(resources::definitions());
+        defs.extend(server::definitions());
         defs
     }
 
-    /// Execute a tool by name with JSON parameters.
-    pub async fn execute(name: &str, params: Value, state: &ProjectState) -> ToolResult {
+    /// Execute a tool by name with JSON parameters. Tools that can report
+    /// partial results send them to `progress` when one is given.
+    pub async fn execute(
+        name: &str,
+        params: Value,
+        state: &ProjectState,
+        progress: Option<&ProgressSink>,
+    ) -> ToolResult {
         // Route to the correct module based on tool name prefix
         if name.starts_with("project_") {
             return project::execute(name, params, state).await;
         }
         if name.starts_with("fs_") {
-            return filesystem::execute(name, params, state).await;
+            return filesystem::execute(name, params, state, progress).await;
         }
         if name.starts_with("git_") {
             return git::execute(name, params, state).await;
@@ -60,6 +72,9 @@ impl ToolRegistry {
         if name.starts_with("block_") {
             return block::execute(name, params, state).await;
         }
+        if name.starts_with("item_") {
+            return item::execute(name, params, state).await;
+        }
         if name.starts_with("gui_") {
             return gui::execute(name, params).await;
         }
@@ -72,6 +87,12 @@ impl ToolRegistry {
         if name.starts_with("code_") {
             return lsp::execute(name, params, state).await;
         }
+        if name.starts_with("resources_") || name.starts_with("resource_") {
+            return resources::execute(name, params, state).await;
+        }
+        if name.starts_with("server_") {
+            return server::execute(name, params, state).await;
+        }
 
         ToolResult::error(format!("Unknown tool: {}", name))
     }
@@ -1,8 +1,9 @@
 use crate::state::ProjectState;
-use crate::types::{ToolDefinition, ToolResult};
+use crate::types::{ErrorCode, ToolDefinition, ToolResult};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -23,6 +24,9 @@ struct ModpackMod {
     description: Option<String>,
     #[serde(default)]
     dependencies: Vec<ModDependency>,
+    /// Minecraft versions the JAR declares support for, e.g. `">=1.21 <1.22"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    minecraft_versions: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -34,6 +38,33 @@ struct ModpackManifest {
     mods: Vec<ModpackMod>,
 }
 
+const LOCK_FILE: &str = "alloy.pack.lock";
+
+/// Bump when the lockfile's shape changes.
+const LOCK_VERSION: u32 = 1;
+
+/// `alloy.pack.lock`: the exact file every mod resolved to when the pack was
+/// locked, so anyone installing it can check they have the same JARs.
+#[derive(Debug, Serialize, Deserialize)]
+struct PackLock {
+    version: u32,
+    #[serde(default)]
+    mods: Vec<LockedMod>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LockedMod {
+    id: String,
+    /// Concrete version the constraint resolved to
+    version: String,
+    /// The manifest's version constraint at lock time
+    constraint: String,
+    source: String,
+    /// JAR path, relative to the project when it lies inside it
+    file: String,
+    sha256: String,
+}
+
 impl Default for ModpackManifest {
     fn default() -> Self {
         Self {
@@ -73,12 +104,13 @@ pub fn definitions() -> Vec<ToolDefinition> {
         },
         ToolDefinition {
             name: "modpack_add_mod".into(),
-            description: "Add a mod to the modpack from a JAR file path".into(),
+            description: "Add a mod to the modpack from a JAR file path. Refuses JARs whose fabric.mod.json doesn't support the pack's Minecraft version unless force is set".into(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
                     "project_path": { "type": "string", "description": "Path to the modpack project" },
-                    "jar_path": { "type": "string", "description": "Path to the mod JAR file" }
+                    "jar_path": { "type": "string", "description": "Path to the mod JAR file" },
+                    "force": { "type": "boolean", "description": "Add the mod even if it doesn't declare support for the pack's Minecraft version (default false)" }
                 },
                 "required": ["project_path", "jar_path"]
             }),
@@ -97,7 +129,9 @@ pub fn definitions() -> Vec<ToolDefinition> {
         },
         ToolDefinition {
             name: "modpack_check_conflicts".into(),
-            description: "Detect dependency, version, and environment conflicts in the modpack".into(),
+            description: "Detect dependency, version, and environment conflicts in the modpack. \
+                Each JAR's declared environment (fabric.mod.json, quilt.mod.json or alloy.mod.json) \
+                overrides the manifest's; corrections are saved and reported under notes.".into(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
@@ -118,16 +152,61 @@ pub fn definitions() -> Vec<ToolDefinition> {
                 "required": ["project_path", "output_path"]
             }),
         },
+        ToolDefinition {
+            name: "modpack_lock".into(),
+            description: "Resolve every mod in the manifest to a concrete version and record it \
+                with its JAR's SHA-256 in alloy.pack.lock. Versions come from the JAR's \
+                fabric.mod.json, quilt.mod.json or alloy.mod.json, falling back to an exact \
+                manifest version. Fails without writing if any mod cannot be resolved or its \
+                version does not satisfy the manifest constraint.".into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_path": { "type": "string", "description": "Path to the modpack project" }
+                },
+                "required": ["project_path"]
+            }),
+        },
+        ToolDefinition {
+            name: "modpack_verify".into(),
+            description: "Check the pack's installed JARs against alloy.pack.lock. Reports JARs \
+                that are missing or whose hash differs, manifest mods missing from the lock, and \
+                locked mods no longer in the manifest.".into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_path": { "type": "string", "description": "Path to the modpack project" }
+                },
+                "required": ["project_path"]
+            }),
+        },
+        ToolDefinition {
+            name: "modpack_diff".into(),
+            description: "Compare two modpacks, each a project directory or an exported .alloypack \
+                file. Returns pack metadata changes, mods added, removed, version-changed and \
+                enabled-toggled (keyed by mod id, with before/after values), and config files \
+                added, removed or changed. TOML and JSON configs are compared key by key.".into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "base_path": { "type": "string", "description": "The pack to compare from: a modpack project directory or .alloypack file" },
+                    "target_path": { "type": "string", "description": "The pack to compare to: a modpack project directory or .alloypack file" }
+                },
+                "required": ["base_path", "target_path"]
+            }),
+        },
         ToolDefinition {
             name: "modpack_config".into(),
-            description: "Read or write a mod's config file in the modpack".into(),
+            description: "Read or write a mod's config file in the modpack. Writes are parsed as \
+                the declared format first and rejected with the line and column of any syntax \
+                error; validate_only runs the same check without writing.".into(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
                     "project_path": { "type": "string", "description": "Path to the modpack project" },
                     "mod_id": { "type": "string", "description": "Mod ID" },
-                    "action": { "type": "string", "enum": ["read", "write"], "description": "Read or write config" },
-                    "content": { "type": "string", "description": "Config content to write (for write action)" },
+                    "action": { "type": "string", "enum": ["read", "write", "validate_only"], "description": "Read, write, or only validate config" },
+                    "content": { "type": "string", "description": "Config content to write or validate (for write and validate_only)" },
                     "format": { "type": "string", "enum": ["toml", "json"], "description": "Config format (default: toml)" }
                 },
                 "required": ["project_path", "mod_id", "action"]
@@ -144,6 +223,9 @@ pub async fn execute(name: &str, params: Value, _state: &ProjectState) -> ToolRe
         "modpack_remove_mod" => modpack_remove_mod(params).await,
         "modpack_check_conflicts" => modpack_check_conflicts(params).await,
         "modpack_export" => modpack_export(params).await,
+        "modpack_diff" => modpack_diff(params).await,
+        "modpack_lock" => modpack_lock(params).await,
+        "modpack_verify" => modpack_verify(params).await,
         "modpack_config" => modpack_config(params).await,
         _ => ToolResult::error(format!("Unknown modpack tool: {}", name)),
     }
@@ -158,7 +240,10 @@ fn load_manifest(project_path: &str) -> Result<ModpackManifest, String> {
 
     let content = std::fs::read_to_string(&manifest_path)
         .map_err(|e| format!("Failed to read manifest: {}", e))?;
+    parse_manifest(&content)
+}
 
+fn parse_manifest(content: &str) -> Result<ModpackManifest, String> {
     let parsed: toml::Value = content
         .parse()
         .map_err(|e| format!("Failed to parse TOML: {}", e))?;
@@ -189,6 +274,7 @@ fn load_manifest(project_path: &str) -> Result<ModpackManifest, String> {
                 enabled: m.get("enabled").and_then(|e| e.as_bool()).unwrap_or(true),
                 description: m.get("description").and_then(|d| d.as_str()).map(String::from),
                 dependencies,
+                minecraft_versions: m.get("minecraft_versions").and_then(|v| v.as_str()).map(String::from),
             })
         }).collect()
     }).unwrap_or_default();
@@ -224,6 +310,9 @@ fn save_manifest(project_path: &str, manifest: &ModpackManifest) -> Result<(), S
             if let Some(ref desc) = m.description {
                 content.push_str(&format!("description = \"{}\"\n", desc));
             }
+            if let Some(ref range) = m.minecraft_versions {
+                content.push_str(&format!("minecraft_versions = \"{}\"\n", range));
+            }
             for dep in &m.dependencies {
                 content.push_str(&format!(
                     "[[mods.dependencies]]\nmod_id = \"{}\"\nversion_constraint = \"{}\"\n",
@@ -239,7 +328,7 @@ fn save_manifest(project_path: &str, manifest: &ModpackManifest) -> Result<(), S
 async fn modpack_load(params: Value) -> ToolResult {
     let project_path = match params.get("project_path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: project_path"),
+        None => return ToolResult::missing_param("project_path"),
     };
 
     match load_manifest(project_path) {
@@ -251,11 +340,11 @@ async fn modpack_load(params: Value) -> ToolResult {
 async fn modpack_save(params: Value) -> ToolResult {
     let project_path = match params.get("project_path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: project_path"),
+        None => return ToolResult::missing_param("project_path"),
     };
     let manifest_value = match params.get("manifest") {
         Some(m) => m,
-        None => return ToolResult::error("Missing required parameter: manifest"),
+        None => return ToolResult::missing_param("manifest"),
     };
 
     let manifest: ModpackManifest = match serde_json::from_value(manifest_value.clone()) {
@@ -272,11 +361,11 @@ async fn modpack_save(params: Value) -> ToolResult {
 async fn modpack_add_mod(params: Value) -> ToolResult {
     let project_path = match params.get("project_path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: project_path"),
+        None => return ToolResult::missing_param("project_path"),
     };
     let jar_path = match params.get("jar_path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: jar_path"),
+        None => return ToolResult::missing_param("jar_path"),
     };
 
     let jar = Path::new(jar_path);
@@ -285,6 +374,28 @@ async fn modpack_add_mod(params: Value) -> ToolResult {
     }
 
     let file_name = jar.file_stem().and_then(|s| s.to_str()).unwrap_or("unknown");
+    let force = params.get("force").and_then(|v| v.as_bool()).unwrap_or(false);
+
+    let mut manifest = load_manifest(project_path).unwrap_or_default();
+
+    // Check before copying so a refused JAR leaves the pack untouched
+    let minecraft_versions = jar_minecraft_versions(jar);
+    let mut warning = None;
+    if let Some(ref range) = minecraft_versions {
+        if !satisfies_version(&manifest.minecraft_version, range) {
+            let message = format!(
+                "\"{}\" supports Minecraft {} but the pack targets {}",
+                file_name, range, manifest.minecraft_version
+            );
+            if !force {
+                return ToolResult::error_with(
+                    ErrorCode::Conflict,
+                    format!("{}; pass force to add it anyway", message),
+                );
+            }
+            warning = Some(message);
+        }
+    }
 
     let mods_dir = Path::new(project_path).join("mods");
     if let Err(e) = std::fs::create_dir_all(&mods_dir) {
@@ -299,36 +410,46 @@ async fn modpack_add_mod(params: Value) -> ToolResult {
     let id: String = file_name.to_lowercase().replace(' ', "-")
         .chars().filter(|c| c.is_alphanumeric() || *c == '-').collect();
 
+    // Trust the side the JAR declares over guessing from the file name
+    let environment = jar_environment(&dest).unwrap_or_else(|| "both".to_string());
+
     let new_mod = ModpackMod {
         id: id.clone(),
         name: file_name.to_string(),
         version: "*".to_string(),
-        environment: "both".to_string(),
+        environment,
         source: "jar".to_string(),
         source_path: Some(dest.to_string_lossy().to_string()),
         enabled: true,
         description: None,
         dependencies: Vec::new(),
+        minecraft_versions,
     };
 
     // Update manifest
-    let mut manifest = load_manifest(project_path).unwrap_or_default();
     manifest.mods.push(new_mod.clone());
     if let Err(e) = save_manifest(project_path, &manifest) {
         return ToolResult::error(e);
     }
 
-    ToolResult::json(&new_mod)
+    match warning {
+        Some(warning) => {
+            let mut result = json!(new_mod);
+            result["warning"] = json!(warning);
+            ToolResult::json(&result)
+        }
+        None => ToolResult::json(&new_mod),
+    }
 }
 
 async fn modpack_remove_mod(params: Value) -> ToolResult {
     let project_path = match params.get("project_path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: project_path"),
+        None => return ToolResult::missing_param("project_path"),
     };
     let mod_id = match params.get("mod_id").and_then(|v| v.as_str()) {
         Some(id) => id,
-        None => return ToolResult::error("Missing required parameter: mod_id"),
+        None => return ToolResult::missing_param("mod_id"),
     };
 
     let mut manifest = match load_manifest(project_path) {
@@ -359,46 +480,176 @@ async fn modpack_remove_mod(params: Value) -> ToolResult {
     }
 }
 
+/// The first of fabric.mod.json, quilt.mod.json or alloy.mod.json found in a
+/// mod JAR, with its file name.
+fn jar_mod_json(jar: &Path) -> Option<(&'static str, Value)> {
+    let file = std::fs::File::open(jar).ok()?;
+    let mut archive = zip::ZipArchive::new(file).ok()?;
+    ["fabric.mod.json", "quilt.mod.json", "alloy.mod.json"]
+        .into_iter()
+        .find_map(|name| {
+            let entry = archive.by_name(name).ok()?;
+            serde_json::from_reader(entry).ok().map(|json| (name, json))
+        })
+}
+
+/// The environment a mod JAR declares in its fabric.mod.json, quilt.mod.json
+/// or alloy.mod.json, normalized to "client", "server" or "both". `None` when
+/// the JAR can't be read or declares nothing.
+fn jar_environment(jar: &Path) -> Option<String> {
+    let (name, json) = jar_mod_json(jar)?;
+    let pointer = match name {
+        "quilt.mod.json" => "/minecraft/environment",
+        _ => "/environment",
+    };
+    let declared = json.pointer(pointer).and_then(|e| e.as_str())?.to_string();
+
+    match declared.as_str() {
+        "client" => Some("client".to_string()),
+        "server" | "dedicated_server" => Some("server".to_string()),
+        "*" | "both" => Some("both".to_string()),
+        _ => None,
+    }
+}
+
+/// The Minecraft versions a mod JAR supports, from `depends.minecraft` in its
+/// fabric.mod.json. A list of alternatives is joined with `||`.
+fn jar_minecraft_versions(jar: &Path) -> Option<String> {
+    let (name, json) = jar_mod_json(jar)?;
+    if name != "fabric.mod.json" {
+        return None;
+    }
+    let ranges: Vec<&str> = match json.pointer("/depends/minecraft")? {
+        Value::String(range) => vec![range.as_str()],
+        Value::Array(ranges) => ranges.iter().filter_map(|r| r.as_str()).collect(),
+        _ => return None,
+    };
+    (!ranges.is_empty()).then(|| ranges.join(" || "))
+}
+
+/// The version a mod JAR declares, or `None` when it can't be read or uses an
+/// unexpanded build placeholder.
+fn jar_version(jar: &Path) -> Option<String> {
+    let (name, json) = jar_mod_json(jar)?;
+    let pointer = match name {
+        "quilt.mod.json" => "/quilt_loader/version",
+        _ => "/version",
+    };
+    let version = json.pointer(pointer).and_then(|v| v.as_str())?;
+    (!version.is_empty() && !version.contains("${")).then(|| version.to_string())
+}
+
+fn hash_file(path: &Path) -> std::io::Result<String> {
+    Ok(format!("{:x}", Sha256::digest(std::fs::read(path)?)))
+}
+
+/// Whether `version` meets `constraint`. Accepts `*`, exact versions, `x`
+/// wildcards (`1.21.x`), `<`, `<=`, `>`, `>=`, `=`, `^` and `~`; comparators
+/// separated by spaces must all hold, and `||` separates alternatives, as in
+/// Fabric's version ranges.
 fn satisfies_version(version: &str, constraint: &str) -> bool {
     let constraint = constraint.trim();
     if constraint == "*" || constraint.is_empty() { return true; }
 
-    let parse_ver = |s: &str| -> Option<(u64, u64, u64)> {
-        let parts: Vec<&str> = s.trim().split('.').collect();
-        match parts.len() {
-            3.. => Some((parts[0].parse().ok()?, parts[1].parse().ok()?, parts[2].parse().ok()?)),
-            2 => Some((parts[0].parse().ok()?, parts[1].parse().ok()?, 0)),
-            1 => Some((parts[0].parse().ok()?, 0, 0)),
-            _ => None,
-        }
-    };
+    let ver = match parse_version(version) { Some(v) => v, None => return false };
 
-    let ver = match parse_ver(version) { Some(v) => v, None => return false };
+    constraint.split("||").any(|alternative| {
+        comparators(alternative).iter().all(|c| satisfies_comparator(ver, c))
+    })
+}
 
-    if constraint.starts_with(">=") {
-        return parse_ver(&constraint[2..]).map_or(false, |min| ver >= min);
+/// `major.minor.patch` of a version, ignoring pre-release and build suffixes.
+fn parse_version(s: &str) -> Option<(u64, u64, u64)> {
+    let core = s.trim().split(['-', '+']).next()?;
+    let parts: Vec<&str> = core.split('.').collect();
+    match parts.len() {
+        3.. => Some((parts[0].parse().ok()?, parts[1].parse().ok()?, parts[2].parse().ok()?)),
+        2 => Some((parts[0].parse().ok()?, parts[1].parse().ok()?, 0)),
+        1 => Some((parts[0].parse().ok()?, 0, 0)),
+        _ => None,
     }
-    if constraint.starts_with('^') {
-        return parse_ver(&constraint[1..]).map_or(false, |base| ver >= base && ver.0 == base.0);
+}
+
+/// Split a range into comparators, keeping an operator written apart from its
+/// version (`>= 1.20`) attached to it.
+fn comparators(range: &str) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    let mut pending = String::new();
+    for token in range.split_whitespace() {
+        pending.push_str(token);
+        if !token.chars().all(|c| "<>=^~".contains(c)) {
+            out.push(std::mem::take(&mut pending));
+        }
     }
-    if constraint.starts_with('~') {
-        return parse_ver(&constraint[1..]).map_or(false, |base| ver >= base && ver.0 == base.0 && ver.1 == base.1);
+    out
+}
+
+fn satisfies_comparator(ver: (u64, u64, u64), comparator: &str) -> bool {
+    if comparator == "*" { return true; }
+
+    let (op, bound) = ["<=", ">=", "<", ">", "=", "^", "~"]
+        .into_iter()
+        .find_map(|op| comparator.strip_prefix(op).map(|rest| (op, rest)))
+        .unwrap_or(("", comparator));
+
+    // `1.21.x` matches every version sharing the components before the wildcard
+    let parts: Vec<&str> = bound.split('.').collect();
+    if let Some(wild) = parts.iter().position(|p| matches!(*p, "x" | "X" | "*")) {
+        let actual = [ver.0, ver.1, ver.2];
+        return parts[..wild]
+            .iter()
+            .zip(actual)
+            .all(|(p, a)| p.parse::<u64>().is_ok_and(|p| p == a));
     }
 
-    parse_ver(constraint).map_or(true, |exact| ver == exact)
+    let Some(bound) = parse_version(bound) else {
+        // An unparseable exact version can't be checked, so don't flag it
+        return op.is_empty() || op == "=";
+    };
+    match op {
+        "<=" => ver <= bound,
+        ">=" => ver >= bound,
+        "<" => ver < bound,
+        ">" => ver > bound,
+        "^" => ver >= bound && ver.0 == bound.0,
+        "~" => ver >= bound && ver.0 == bound.0 && ver.1 == bound.1,
+        _ => ver == bound,
+    }
 }
 
 async fn modpack_check_conflicts(params: Value) -> ToolResult {
     let project_path = match params.get("project_path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: project_path"),
+        None => return ToolResult::missing_param("project_path"),
     };
 
-    let manifest = match load_manifest(project_path) {
+    let mut manifest = match load_manifest(project_path) {
         Ok(m) => m,
         Err(e) => return ToolResult::error(e),
     };
 
+    // Correct environments from what each JAR declares before checking
+    let mut notes: Vec<Value> = Vec::new();
+    for m in manifest.mods.iter_mut() {
+        let Some(declared) = m.source_path.as_deref().and_then(|p| jar_environment(Path::new(p))) else {
+            continue;
+        };
+        if declared != m.environment {
+            notes.push(json!({
+                "kind": "environment_corrected",
+                "mod_id": m.id,
+                "details": format!("\"{}\" was listed as \"{}\" but its JAR declares \"{}\"", m.name, m.environment, declared),
+                "suggestion": null
+            }));
+            m.environment = declared;
+        }
+    }
+    if !notes.is_empty() {
+        if let Err(e) = save_manifest(project_path, &manifest) {
+            return ToolResult::error(e);
+        }
+    }
+
     let mut conflicts: Vec<Value> = Vec::new();
     let enabled_mods: Vec<&ModpackMod> = manifest.mods.iter().filter(|m| m.enabled).collect();
     let mod_map: HashMap<&str, &ModpackMod> = enabled_mods.iter().map(|m| (m.id.as_str(), *m)).collect();
@@ -445,6 +696,22 @@ async fn modpack_check_conflicts(params: Value) -> ToolResult {
         }
     }
 
+    // Mods that don't support the pack's Minecraft version
+    for m in &enabled_mods {
+        let declared = m.source_path.as_deref().and_then(|p| jar_minecraft_versions(Path::new(p)));
+        let Some(range) = declared.as_deref().or(m.minecraft_versions.as_deref()) else {
+            continue;
+        };
+        if !satisfies_version(&manifest.minecraft_version, range) {
+            conflicts.push(json!({
+                "kind": "minecraft_version",
+                "mod_id": m.id,
+                "details": format!("\"{}\" supports Minecraft {} but the pack targets {}", m.name, range, manifest.minecraft_version),
+                "suggestion": format!("Use a build of \"{}\" for Minecraft {} or change the pack's minecraft_version", m.name, manifest.minecraft_version)
+            }));
+        }
+    }
+
     // Environment conflicts
     for m in &enabled_mods {
         for dep in &m.dependencies {
@@ -480,7 +747,7 @@ async fn modpack_check_conflicts(params: Value) -> ToolResult {
         }));
     }
 
-    ToolResult::json(&json!({ "conflicts": conflicts, "count": conflicts.len() }))
+    ToolResult::json(&json!({ "conflicts": conflicts, "count": conflicts.len(), "notes": notes }))
 }
 
 async fn modpack_export(params: Value) -> ToolResult {
@@ -488,11 +755,11 @@ async fn modpack_export(params: Value) -> ToolResult {
 
     let project_path = match params.get("project_path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: project_path"),
+        None => return ToolResult::missing_param("project_path"),
     };
     let output_path = match params.get("output_path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: output_path"),
+        None => return ToolResult::missing_param("output_path"),
     };
 
     let project = Path::new(project_path);
@@ -519,6 +786,12 @@ async fn modpack_export(params: Value) -> ToolResult {
         }
     }
 
+    // Ship the lockfile so installs can be verified
+    if let Ok(content) = std::fs::read(project.join(LOCK_FILE)) {
+        let _ = zip.start_file(LOCK_FILE, options);
+        let _ = zip.write_all(&content);
+    }
+
     // Write mod JARs
     let mut included = 0;
     for m in &manifest.mods {
@@ -576,18 +849,372 @@ async fn modpack_export(params: Value) -> ToolResult {
     }))
 }
 
+fn load_lock(project_path: &str) -> Result<PackLock, String> {
+    let lock_path = Path::new(project_path).join(LOCK_FILE);
+    if !lock_path.exists() {
+        return Err(format!("No {} found; run modpack_lock first", LOCK_FILE));
+    }
+    let content = std::fs::read_to_string(&lock_path)
+        .map_err(|e| format!("Failed to read {}: {}", LOCK_FILE, e))?;
+    toml::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", LOCK_FILE, e))
+}
+
+/// Resolve one manifest entry to its locked form.
+fn lock_mod(project: &Path, m: &ModpackMod) -> Result<LockedMod, String> {
+    let Some(source_path) = m.source_path.as_deref() else {
+        return Err(format!(
+            "'{}' has no source_path; only JAR-sourced mods can be locked so far",
+            m.id
+        ));
+    };
+    let jar = project.join(source_path);
+    let sha256 = hash_file(&jar)
+        .map_err(|e| format!("'{}': failed to read {}: {}", m.id, jar.display(), e))?;
+
+    let manifest_exact = (!m.version.contains(['*', '^', '~', '>', '<'])).then(|| m.version.clone());
+    let Some(version) = jar_version(&jar).or(manifest_exact) else {
+        return Err(format!(
+            "'{}': {} declares no version and the manifest only has the constraint \"{}\"",
+            m.id,
+            jar.display(),
+            m.version
+        ));
+    };
+    if version != m.version && !satisfies_version(&version, &m.version) {
+        return Err(format!(
+            "'{}': resolved version {} does not satisfy the manifest constraint \"{}\"",
+            m.id, version, m.version
+        ));
+    }
+
+    let file = jar.strip_prefix(project).unwrap_or(&jar).to_string_lossy().replace('\\', "/");
+    Ok(LockedMod {
+        id: m.id.clone(),
+        version,
+        constraint: m.version.clone(),
+        source: m.source.clone(),
+        file,
+        sha256,
+    })
+}
+
+async fn modpack_lock(params: Value) -> ToolResult {
+    let project_path = match params.get("project_path").and_then(|v| v.as_str()) {
+        Some(p) => p,
+        None => return ToolResult::missing_param("project_path"),
+    };
+    let project = Path::new(project_path);
+    let manifest = match load_manifest(project_path) {
+        Ok(m) => m,
+        Err(e) => return ToolResult::error(e),
+    };
+
+    let (locked, failed): (Vec<_>, Vec<_>) = manifest
+        .mods
+        .iter()
+        .map(|m| lock_mod(project, m))
+        .partition(|r| r.is_ok());
+    if !failed.is_empty() {
+        let errors: Vec<String> = failed.into_iter().filter_map(Result::err).collect();
+        return ToolResult::error(format!(
+            "Could not lock the pack; {} not written:\n{}",
+            LOCK_FILE,
+            errors.join("\n")
+        ));
+    }
+
+    let lock = PackLock {
+        version: LOCK_VERSION,
+        mods: locked.into_iter().filter_map(Result::ok).collect(),
+    };
+    let body = match toml::to_string_pretty(&lock) {
+        Ok(b) => b,
+        Err(e) => return ToolResult::error(format!("Failed to serialize {}: {}", LOCK_FILE, e)),
+    };
+    let content = format!("# Generated by modpack_lock. Do not edit by hand.\n\n{}", body);
+    if let Err(e) = std::fs::write(project.join(LOCK_FILE), content) {
+        return ToolResult::error(format!("Failed to write {}: {}", LOCK_FILE, e));
+    }
+
+    ToolResult::json(&json!({
+        "lock_path": project.join(LOCK_FILE).to_string_lossy(),
+        "mods": lock.mods,
+        "count": lock.mods.len(),
+    }))
+}
+
+async fn modpack_verify(params: Value) -> ToolResult {
+    let project_path = match params.get("project_path").and_then(|v| v.as_str()) {
+        Some(p) => p,
+        None => return ToolResult::missing_param("project_path"),
+    };
+    let project = Path::new(project_path);
+    let (manifest, lock) = match (load_manifest(project_path), load_lock(project_path)) {
+        (Ok(m), Ok(l)) => (m, l),
+        (Err(e), _) | (_, Err(e)) => return ToolResult::error(e),
+    };
+
+    let mut problems: Vec<Value> = Vec::new();
+    let mut verified = 0;
+    for locked in &lock.mods {
+        let jar = project.join(&locked.file);
+        match hash_file(&jar) {
+            Err(_) => problems.push(json!({
+                "kind": "missing",
+                "mod_id": locked.id,
+                "file": locked.file,
+                "details": format!("{} is not installed", locked.file)
+            })),
+            Ok(actual) if actual != locked.sha256 => problems.push(json!({
+                "kind": "hash_mismatch",
+                "mod_id": locked.id,
+                "file": locked.file,
+                "expected": locked.sha256,
+                "actual": actual,
+                "details": format!("{} differs from the locked {} {}", locked.file, locked.id, locked.version)
+            })),
+            Ok(_) => verified += 1,
+        }
+        if !manifest.mods.iter().any(|m| m.id == locked.id) {
+            problems.push(json!({
+                "kind": "not_in_manifest",
+                "mod_id": locked.id,
+                "details": format!("'{}' is locked but no longer in alloy.pack.toml", locked.id)
+            }));
+        }
+    }
+    for m in &manifest.mods {
+        if !lock.mods.iter().any(|l| l.id == m.id) {
+            problems.push(json!({
+                "kind": "not_locked",
+                "mod_id": m.id,
+                "details": format!("'{}' is in alloy.pack.toml but not in {}", m.id, LOCK_FILE),
+                "suggestion": "Run modpack_lock to update the lockfile"
+            }));
+        }
+    }
+
+    ToolResult::json(&json!({
+        "ok": problems.is_empty(),
+        "verified": verified,
+        "problems": problems,
+    }))
+}
+
+/// A pack's manifest and config files, read from a project or an archive.
+struct PackSnapshot {
+    manifest: ModpackManifest,
+    /// Config file contents keyed by path relative to `config/`
+    configs: BTreeMap<String, String>,
+}
+
+/// Read a modpack project directory or an exported .alloypack file.
+fn load_snapshot(path: &str) -> Result<PackSnapshot, String> {
+    let root = Path::new(path);
+    if root.is_dir() {
+        if !root.join("alloy.pack.toml").exists() {
+            return Err(format!("{} has no alloy.pack.toml", path));
+        }
+        let manifest = load_manifest(path)?;
+        let config_dir = root.join("config");
+        let configs = walkdir::WalkDir::new(&config_dir)
+            .into_iter()
+            .flatten()
+            .filter(|e| e.file_type().is_file())
+            .filter_map(|e| {
+                let rel = e.path().strip_prefix(&config_dir).ok()?;
+                let content = std::fs::read(e.path()).ok()?;
+                Some((
+                    rel.to_string_lossy().replace('\\', "/"),
+                    String::from_utf8_lossy(&content).into_owned(),
+                ))
+            })
+            .collect();
+        return Ok(PackSnapshot { manifest, configs });
+    }
+
+    use std::io::Read;
+
+    let file = std::fs::File::open(root).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| format!("{} is not a modpack directory or .alloypack file: {}", path, e))?;
+    let mut manifest = None;
+    let mut configs = BTreeMap::new();
+    for i in 0..archive.len() {
+        let mut entry = archive
+            .by_index(i)
+            .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        if !entry.is_file() {
+            continue;
+        }
+        let name = entry.name().to_string();
+        if name != "alloy.pack.toml" && !name.starts_with("config/") {
+            continue;
+        }
+        let mut content = Vec::new();
+        entry
+            .read_to_end(&mut content)
+            .map_err(|e| format!("Failed to read {} from {}: {}", name, path, e))?;
+        let content = String::from_utf8_lossy(&content).into_owned();
+        match name.strip_prefix("config/") {
+            Some(rel) => {
+                configs.insert(rel.to_string(), content);
+            }
+            None => manifest = Some(parse_manifest(&content)?),
+        }
+    }
+    let manifest = manifest.ok_or_else(|| format!("{} has no alloy.pack.toml", path))?;
+    Ok(PackSnapshot { manifest, configs })
+}
+
+/// Flatten a TOML or JSON config into dotted keys, or `None` when the file is
+/// neither or does not parse.
+fn flatten_config(file: &str, content: &str) -> Option<BTreeMap<String, Value>> {
+    let value = if file.ends_with(".toml") {
+        serde_json::to_value(content.parse::<toml::Table>().ok()?).ok()?
+    } else if file.ends_with(".json") {
+        serde_json::from_str(content).ok()?
+    } else {
+        return None;
+    };
+    fn walk(prefix: String, value: Value, out: &mut BTreeMap<String, Value>) {
+        match value {
+            Value::Object(map) if !map.is_empty() => {
+                for (key, value) in map {
+                    let key = if prefix.is_empty() { key } else { format!("{}.{}", prefix, key) };
+                    walk(key, value, out);
+                }
+            }
+            other => {
+                out.insert(prefix, other);
+            }
+        }
+    }
+    let mut keys = BTreeMap::new();
+    walk(String::new(), value, &mut keys);
+    Some(keys)
+}
+
+/// Describe how one config file changed, key by key where possible.
+fn diff_config(file: &str, before: &str, after: &str) -> Value {
+    let (Some(old), Some(new)) = (flatten_config(file, before), flatten_config(file, after)) else {
+        return json!({ "file": file, "note": "Not parseable as TOML or JSON; contents differ" });
+    };
+    let added: BTreeMap<_, _> = new.iter().filter(|(k, _)| !old.contains_key(*k)).collect();
+    let removed: BTreeMap<_, _> = old.iter().filter(|(k, _)| !new.contains_key(*k)).collect();
+    let changed: BTreeMap<_, _> = old
+        .iter()
+        .filter_map(|(k, before)| {
+            let after = new.get(k).filter(|after| *after != before)?;
+            Some((k, json!({ "before": before, "after": after })))
+        })
+        .collect();
+    json!({ "file": file, "added": added, "removed": removed, "changed": changed })
+}
+
+async fn modpack_diff(params: Value) -> ToolResult {
+    let base_path = match params.get("base_path").and_then(|v| v.as_str()) {
+        Some(p) => p,
+        None => return ToolResult::missing_param("base_path"),
+    };
+    let target_path = match params.get("target_path").and_then(|v| v.as_str()) {
+        Some(p) => p,
+        None => return ToolResult::missing_param("target_path"),
+    };
+    let (base, target) = match (load_snapshot(base_path), load_snapshot(target_path)) {
+        (Ok(b), Ok(t)) => (b, t),
+        (Err(e), _) | (_, Err(e)) => return ToolResult::error(e),
+    };
+
+    let (b, t) = (&base.manifest, &target.manifest);
+    let mut pack = serde_json::Map::new();
+    for (field, before, after) in [
+        ("name", &b.name, &t.name),
+        ("version", &b.version, &t.version),
+        ("minecraft_version", &b.minecraft_version, &t.minecraft_version),
+        ("alloy_version", &b.alloy_version, &t.alloy_version),
+    ] {
+        if before != after {
+            pack.insert(field.to_string(), json!({ "before": before, "after": after }));
+        }
+    }
+
+    let old_mods: BTreeMap<&str, &ModpackMod> = b.mods.iter().map(|m| (m.id.as_str(), m)).collect();
+    let new_mods: BTreeMap<&str, &ModpackMod> = t.mods.iter().map(|m| (m.id.as_str(), m)).collect();
+    let summary = |m: &ModpackMod| json!({ "id": m.id, "name": m.name, "version": m.version, "enabled": m.enabled });
+    let added: Vec<Value> = new_mods
+        .iter()
+        .filter(|(id, _)| !old_mods.contains_key(*id))
+        .map(|(_, m)| summary(m))
+        .collect();
+    let removed: Vec<Value> = old_mods
+        .iter()
+        .filter(|(id, _)| !new_mods.contains_key(*id))
+        .map(|(_, m)| summary(m))
+        .collect();
+    let mut version_changed = Vec::new();
+    let mut enabled_changed = Vec::new();
+    for (id, old) in &old_mods {
+        let Some(new) = new_mods.get(id) else { continue };
+        if old.version != new.version {
+            version_changed.push(json!({ "id": id, "name": new.name, "before": old.version, "after": new.version }));
+        }
+        if old.enabled != new.enabled {
+            enabled_changed.push(json!({ "id": id, "name": new.name, "before": old.enabled, "after": new.enabled }));
+        }
+    }
+
+    let configs_added: Vec<&String> = target.configs.keys().filter(|f| !base.configs.contains_key(*f)).collect();
+    let configs_removed: Vec<&String> = base.configs.keys().filter(|f| !target.configs.contains_key(*f)).collect();
+    let configs_changed: Vec<Value> = base
+        .configs
+        .iter()
+        .filter_map(|(file, before)| {
+            let after = target.configs.get(file).filter(|after| *after != before)?;
+            Some(diff_config(file, before, after))
+        })
+        .collect();
+
+    let identical = pack.is_empty()
+        && added.is_empty()
+        && removed.is_empty()
+        && version_changed.is_empty()
+        && enabled_changed.is_empty()
+        && configs_added.is_empty()
+        && configs_removed.is_empty()
+        && configs_changed.is_empty();
+
+    ToolResult::json(&json!({
+        "base": base_path,
+        "target": target_path,
+        "identical": identical,
+        "pack": pack,
+        "mods": {
+            "added": added,
+            "removed": removed,
+            "version_changed": version_changed,
+            "enabled_changed": enabled_changed,
+        },
+        "configs": {
+            "added": configs_added,
+            "removed": configs_removed,
+            "changed": configs_changed,
+        },
+    }))
+}
+
 async fn modpack_config(params: Value) -> ToolResult {
     let project_path = match params.get("project_path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: project_path"),
+        None => return ToolResult::missing_param("project_path"),
     };
     let mod_id = match params.get("mod_id").and_then(|v| v.as_str()) {
         Some(id) => id,
-        None => return ToolResult::error("Missing required parameter: mod_id"),
+        None => return ToolResult::missing_param("mod_id"),
     };
     let action = match params.get("action").and_then(|v| v.as_str()) {
         Some(a) => a,
-        None => return ToolResult::error("Missing required parameter: action"),
+        None => return ToolResult::missing_param("action"),
     };
 
     let config_dir = Path::new(project_path).join("config");
@@ -610,13 +1237,20 @@ async fn modpack_config(params: Value) -> ToolResult {
             }
             ToolResult::text(format!("# Configuration for {}\n# Edit values below and save\n", mod_id))
         }
-        "write" => {
+        "write" | "validate_only" => {
             let content = match params.get("content").and_then(|v| v.as_str()) {
                 Some(c) => c,
-                None => return ToolResult::error("Missing 'content' for write action"),
+                None => return ToolResult::error(format!("Missing 'content' for {} action", action)),
             };
             let format = params.get("format").and_then(|v| v.as_str()).unwrap_or("toml");
 
+            if let Err(e) = validate_config(content, format) {
+                return ToolResult::error(format!("Invalid {} config for '{}': {}", format, mod_id, e));
+            }
+            if action == "validate_only" {
+                return ToolResult::text(format!("Config for '{}' is valid {}", mod_id, format));
+            }
+
             if let Err(e) = std::fs::create_dir_all(&config_dir) {
                 return ToolResult::error(format!("Failed to create config directory: {}", e));
             }
@@ -629,6 +1263,40 @@ async fn modpack_config(params: Value) -> ToolResult {
                 Err(e) => ToolResult::error(format!("Failed to write config: {}", e)),
             }
         }
-        _ => ToolResult::error(format!("Unknown action: {}. Use 'read' or 'write'", action)),
+        _ => ToolResult::error(format!("Unknown action: {}. Use 'read', 'write' or 'validate_only'", action)),
+    }
+}
+
+/// Parse config `content` as `format`, reporting where the first syntax
+/// error is.
+fn validate_config(content: &str, format: &str) -> Result<(), String> {
+    match format {
+        "json" => serde_json::from_str::<Value>(content)
+            .map(|_| ())
+            .map_err(|e| {
+                // Display appends " at line L column C"; report the position up front instead
+                let message = e.to_string();
+                let message = message.split(" at line ").next().unwrap_or(&message);
+                format!("line {}, column {}: {}", e.line(), e.column(), message)
+            }),
+        "toml" => content.parse::<toml::Table>().map(|_| ()).map_err(|e| {
+            let message = e.message().to_string();
+            match e.span() {
+                Some(span) => {
+                    let (line, column) = line_column(content, span.start);
+                    format!("line {}, column {}: {}", line, column, message)
+                }
+                None => message,
+            }
+        }),
+        other => Err(format!("unsupported format '{}', expected 'toml' or 'json'", other)),
     }
 }
+
+/// 1-based line and column of byte `offset` in `text`.
+fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = text.get(..offset).unwrap_or(text);
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+    (line, column)
+}
@@ -0,0 +1,80 @@
+//! Cursor-based pagination for tools whose results can outgrow one response.
+//!
+//! Tools take an optional `limit` and `cursor` and return `next_cursor` when
+//! more results remain. Cursors are opaque to callers: base64 of a small JSON
+//! object describing where the next page starts.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde_json::{json, Value};
+use std::cmp::Ordering;
+use std::path::Path;
+
+/// Read a page size parameter, defaulting to `default` and capped at `max`.
+pub(crate) fn limit(value: Option<&Value>, default: usize, max: usize) -> usize {
+    value
+        .and_then(|v| v.as_u64())
+        .map(|l| (l as usize).clamp(1, max))
+        .unwrap_or(default)
+}
+
+/// Encode a resume position as a cursor string.
+pub(crate) fn encode_cursor(position: &Value) -> String {
+    URL_SAFE_NO_PAD.encode(position.to_string())
+}
+
+/// Decode the `cursor` parameter, if one was given.
+pub(crate) fn decode_cursor(params: &Value) -> Result<Option<Value>, String> {
+    let Some(cursor) = params.get("cursor").and_then(|v| v.as_str()) else {
+        return Ok(None);
+    };
+    URL_SAFE_NO_PAD
+        .decode(cursor)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .map(Some)
+        .ok_or_else(|| "Invalid cursor: pass next_cursor from a previous call unchanged".to_string())
+}
+
+/// The last match a file walk returned. Walks sorted by file name visit
+/// files in the same order `Path` comparison sorts them, so a later call can
+/// skip everything up to and including this position.
+pub(crate) struct FilePosition {
+    /// Path relative to the walk root
+    pub file: String,
+    /// 1-based line number
+    pub line: usize,
+}
+
+impl FilePosition {
+    pub fn from_cursor(cursor: &Value) -> Result<Self, String> {
+        match (
+            cursor.get("file").and_then(|f| f.as_str()),
+            cursor.get("line").and_then(|l| l.as_u64()),
+        ) {
+            (Some(file), Some(line)) => Ok(Self {
+                file: file.to_string(),
+                line: line as usize,
+            }),
+            _ => Err("Invalid cursor: not a file search cursor".to_string()),
+        }
+    }
+
+    pub fn to_cursor(&self) -> String {
+        encode_cursor(&json!({ "file": self.file, "line": self.line }))
+    }
+
+    /// Whether `file` comes entirely before this position and can be skipped.
+    pub fn skips_file(&self, file: &Path) -> bool {
+        file < Path::new(&self.file)
+    }
+
+    /// Whether a match at `file:line` has already been returned.
+    pub fn covers(&self, file: &Path, line: usize) -> bool {
+        match file.cmp(Path::new(&self.file)) {
+            Ordering::Less => true,
+            Ordering::Equal => line <= self.line,
+            Ordering::Greater => false,
+        }
+    }
+}
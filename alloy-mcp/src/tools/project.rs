@@ -1,15 +1,27 @@
-use crate::state::{load_from_shared_file, write_current_project_file, ProjectInfo, ProjectState, ProjectType};
-use crate::types::{ToolDefinition, ToolResult};
+use crate::state::{
+    detect_loader, load_from_shared_file, write_current_project_file, ProjectInfo, ProjectState,
+    ProjectType,
+};
+use crate::types::{ErrorCode, ToolDefinition, ToolResult};
+use alloy_common::generated::{self, GeneratedManifest};
+use alloy_common::mod_id::is_valid_mod_id;
+use alloy_common::scaffold::{
+    scaffold_loom_project, scaffold_mod_project, scaffold_modpack_project, ScaffoldArgs,
+    FABRIC_LOADER_VERSION, QUILT_LOADER_VERSION,
+};
 use serde_json::{json, Value};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Return tool definitions for all project tools.
 pub fn definitions() -> Vec<ToolDefinition> {
     vec![
         ToolDefinition {
             name: "project_create".into(),
-            description: "Create a new Alloy mod or modpack project with full scaffolding. \
-                Generates manifest, build files, source directories, and entry point class."
+            description: "Create a new mod or modpack project with full scaffolding, from the \
+                same templates as the IDE's New Project dialog. Mods target Alloy, or Fabric or \
+                Quilt with a Loom build, Gradle wrapper, mod metadata and mixins config. Returns \
+                every created file and main_class, the entry class to open first. The new \
+                project becomes the current one."
                 .into(),
             input_schema: json!({
                 "type": "object",
@@ -35,6 +47,19 @@ pub fn definitions() -> Vec<ToolDefinition> {
                         "type": "string",
                         "enum": ["client", "server", "both"],
                         "description": "Target environment for the mod (default: 'both'). Ignored for modpacks."
+                    },
+                    "loader": {
+                        "type": "string",
+                        "enum": ["alloy", "fabric", "quilt"],
+                        "description": "Mod loader to scaffold for (default: 'alloy'). Ignored for modpacks."
+                    },
+                    "package": {
+                        "type": "string",
+                        "description": "Java package the entry class goes under, followed by the mod_id (default: 'com.example')"
+                    },
+                    "minecraft_version": {
+                        "type": "string",
+                        "description": "Minecraft version a Fabric or Quilt mod targets; also picks the Java release (default: 1.21.11)"
                     }
                 },
                 "required": ["path", "name", "mod_id", "project_type"]
@@ -42,8 +67,10 @@ pub fn definitions() -> Vec<ToolDefinition> {
         },
         ToolDefinition {
             name: "project_open".into(),
-            description: "Set the active project directory. Reads the project manifest and \
-                updates IDE state so all other tools operate relative to this project."
+            description: "Open a project and make it the current one. Reads the project manifest and \
+                updates IDE state so other tools operate relative to this project by default. \
+                Previously opened projects stay open; target them with a tool's `project_path` \
+                parameter, or close them with project_close."
                 .into(),
             input_schema: json!({
                 "type": "object",
@@ -58,14 +85,29 @@ pub fn definitions() -> Vec<ToolDefinition> {
         },
         ToolDefinition {
             name: "project_info".into(),
-            description: "Get information about the currently open project, including name, \
-                path, type, and environment."
+            description: "Get information about the current project, including name, \
+                path, type, and environment, plus every other open project."
                 .into(),
             input_schema: json!({
                 "type": "object",
                 "properties": {}
             }),
         },
+        ToolDefinition {
+            name: "project_close".into(),
+            description: "Close an open project. If it was the current project, another open \
+                project (if any) becomes current."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Path of the project to close. Defaults to the current project."
+                    }
+                }
+            }),
+        },
         ToolDefinition {
             name: "project_list_recent".into(),
             description: "List recently opened projects from the IDE history file at \
@@ -76,6 +118,62 @@ pub fn definitions() -> Vec<ToolDefinition> {
                 "properties": {}
             }),
         },
+        ToolDefinition {
+            name: "project_generate_verify_task".into(),
+            description: "Add a single local 'verify everything' entry point to a mod project \
+                that validates every .block.json (via `alloy-mcp --validate-all-blocks`), then \
+                runs the Gradle build and tests. Generates either a `verifyAll` Gradle task in \
+                build.gradle.kts or a verify.sh script, and returns how to invoke it. Safe to \
+                call repeatedly: an existing task or script is left untouched."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_path": {
+                        "type": "string",
+                        "description": "Absolute path to the mod project. Defaults to the current project."
+                    },
+                    "kind": {
+                        "type": "string",
+                        "enum": ["gradle", "script"],
+                        "description": "'gradle' appends a verifyAll task to build.gradle.kts, 'script' writes verify.sh. Default: 'gradle'."
+                    }
+                }
+            }),
+        },
+        ToolDefinition {
+            name: "project_rename_mod_id".into(),
+            description: "Change a mod's id across the project: renames assets/<old_id> and \
+                data/<old_id> under every source set's resources, rewrites \"<old_id>:\" and \
+                \"#<old_id>:\" references in JSON files, renames <type>.<old_id>.* lang keys, \
+                updates the id in alloy.mod.json, fabric.mod.json and .block.json/.item.json \
+                definitions, and moves the matching paths in .alloy/generated.json. Java sources \
+                and package names are left alone. Every change is reported; with dry_run \
+                nothing is written."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_path": {
+                        "type": "string",
+                        "description": "Absolute path to the mod project. Defaults to the current project."
+                    },
+                    "old_id": {
+                        "type": "string",
+                        "description": "The mod id currently used by the project"
+                    },
+                    "new_id": {
+                        "type": "string",
+                        "description": "The new mod id. Lowercase letters, digits and '_', starting with a letter."
+                    },
+                    "dry_run": {
+                        "type": "boolean",
+                        "description": "If true, list the planned changes without making them. Default: false."
+                    }
+                },
+                "required": ["old_id", "new_id"]
+            }),
+        },
     ]
 }
 
@@ -85,7 +183,10 @@ pub async fn execute(name: &str, params: Value, state: &ProjectState) -> ToolRes
         "project_create" => handle_create(params, state).await,
         "project_open" => handle_open(params, state).await,
         "project_info" => handle_info(state).await,
+        "project_close" => handle_close(params, state).await,
         "project_list_recent" => handle_list_recent().await,
+        "project_generate_verify_task" => handle_generate_verify_task(params, state).await,
+        "project_rename_mod_id" => handle_rename_mod_id(params, state).await,
         _ => ToolResult::error(format!("Unknown project tool: {}", name)),
     }
 }
@@ -97,272 +198,141 @@ pub async fn execute(name: &str, params: Value, state: &ProjectState) -> ToolRes
 async fn handle_create(params: Value, state: &ProjectState) -> ToolResult {
     let path = match params.get("path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: path"),
+        None => return ToolResult::missing_param("path"),
     };
     let name = match params.get("name").and_then(|v| v.as_str()) {
         Some(n) => n,
-        None => return ToolResult::error("Missing required parameter: name"),
+        None => return ToolResult::missing_param("name"),
     };
     let mod_id = match params.get("mod_id").and_then(|v| v.as_str()) {
         Some(id) => id,
-        None => return ToolResult::error("Missing required parameter: mod_id"),
+        None => return ToolResult::missing_param("mod_id"),
     };
     let project_type = match params.get("project_type").and_then(|v| v.as_str()) {
         Some(t) => t,
-        None => return ToolResult::error("Missing required parameter: project_type"),
+        None => return ToolResult::missing_param("project_type"),
     };
     let environment = params
         .get("environment")
         .and_then(|v| v.as_str())
         .unwrap_or("both");
+    let loader = params.get("loader").and_then(|v| v.as_str()).unwrap_or("alloy");
+    let package = params.get("package").and_then(|v| v.as_str());
+    let minecraft_version = params.get("minecraft_version").and_then(|v| v.as_str());
 
     let project_dir = Path::new(path);
 
     if project_dir.exists() {
-        return ToolResult::error(format!(
-            "Directory already exists: {}. Choose a different path or delete it first.",
-            path
-        ));
+        return ToolResult::error_with(
+            ErrorCode::Conflict,
+            format!(
+                "Directory already exists: {}. Choose a different path or delete it first.",
+                path
+            ),
+        );
     }
-
-    match project_type {
-        "mod" => create_mod_project(project_dir, name, mod_id, environment, state).await,
-        "modpack" => create_modpack_project(project_dir, name, mod_id, state).await,
-        other => ToolResult::error(format!(
-            "Invalid project_type '{}'. Must be 'mod' or 'modpack'.",
-            other
-        )),
+    if !matches!(project_type, "mod" | "modpack") {
+        return ToolResult::error_with(
+            ErrorCode::InvalidParams,
+            format!("Invalid project_type '{}'. Must be 'mod' or 'modpack'.", project_type),
+        );
     }
-}
-
-async fn create_mod_project(
-    project_dir: &Path,
-    name: &str,
-    mod_id: &str,
-    environment: &str,
-    state: &ProjectState,
-) -> ToolResult {
-    let mut created_files: Vec<String> = Vec::new();
-
-    // Build the package path from the mod_id: net.alloymc.<mod_id>
-    let package = format!("net.alloymc.{}", mod_id);
-    let package_path = package.replace('.', "/");
-    let entry_point = format!("{}.ModInit", package);
-
-    // Create directory structure
-    let src_java_dir = project_dir.join("src/main/java").join(&package_path);
-    let src_resources_dir = project_dir.join("src/main/resources");
-
-    if let Err(e) = std::fs::create_dir_all(&src_java_dir) {
-        return ToolResult::error(format!("Failed to create source directories: {}", e));
+    if project_type == "mod" && !matches!(loader, "alloy" | "fabric" | "quilt") {
+        return ToolResult::error_with(
+            ErrorCode::InvalidParams,
+            format!("Invalid loader '{}'. Must be 'alloy', 'fabric' or 'quilt'.", loader),
+        );
     }
-    if let Err(e) = std::fs::create_dir_all(&src_resources_dir) {
-        return ToolResult::error(format!("Failed to create resources directory: {}", e));
+    if !is_valid_mod_id(mod_id) {
+        return ToolResult::error_with(
+            ErrorCode::InvalidParams,
+            format!(
+                "Invalid mod_id '{}': use lowercase letters, digits, and underscores, \
+                 starting with a letter",
+                mod_id
+            ),
+        );
     }
-
-    // 1. alloy.mod.json
-    let mod_json = json!({
-        "name": name,
-        "mod_id": mod_id,
-        "version": "0.1.0",
-        "environment": environment,
-        "entry_point": entry_point,
-        "description": "",
-        "authors": [],
-        "license": "MIT"
-    });
-    let mod_json_path = project_dir.join("alloy.mod.json");
-    match std::fs::write(&mod_json_path, serde_json::to_string_pretty(&mod_json).unwrap()) {
-        Ok(_) => created_files.push(mod_json_path.display().to_string()),
-        Err(e) => {
-            return ToolResult::error(format!("Failed to write alloy.mod.json: {}", e));
-        }
-    }
-
-    // 2. build.gradle.kts
-    let build_gradle = format!(
-        r#"plugins {{
-    java
-}}
-
-group = "{package}"
-version = "0.1.0"
-
-java {{
-    sourceCompatibility = JavaVersion.VERSION_21
-    targetCompatibility = JavaVersion.VERSION_21
-}}
-
-repositories {{
-    mavenCentral()
-    maven {{
-        name = "alloy"
-        url = uri("https://maven.alloymc.net/releases")
-    }}
-}}
-
-dependencies {{
-    compileOnly("net.alloymc:alloy-api:+")
-}}
-
-tasks.withType<JavaCompile> {{
-    options.release.set(21)
-}}
-"#
-    );
-    let build_gradle_path = project_dir.join("build.gradle.kts");
-    match std::fs::write(&build_gradle_path, &build_gradle) {
-        Ok(_) => created_files.push(build_gradle_path.display().to_string()),
-        Err(e) => {
-            return ToolResult::error(format!("Failed to write build.gradle.kts: {}", e));
-        }
-    }
-
-    // 3. settings.gradle.kts
-    let settings_gradle = format!("rootProject.name = \"{}\"\n", mod_id);
-    let settings_gradle_path = project_dir.join("settings.gradle.kts");
-    match std::fs::write(&settings_gradle_path, &settings_gradle) {
-        Ok(_) => created_files.push(settings_gradle_path.display().to_string()),
-        Err(e) => {
-            return ToolResult::error(format!("Failed to write settings.gradle.kts: {}", e));
-        }
+    if let Some(package) = package.filter(|p| !is_valid_package(p)) {
+        return ToolResult::error_with(
+            ErrorCode::InvalidParams,
+            format!(
+                "Invalid package '{}': use dot-separated Java identifiers, e.g. com.example",
+                package
+            ),
+        );
     }
 
-    // 4. ModInit.java — entry point class
-    let mod_init_java = format!(
-        r#"package {package};
-
-import net.alloymc.api.ModInitializer;
-
-/**
- * Entry point for the {name} mod.
- */
-public class ModInit implements ModInitializer {{
-
-    @Override
-    public void onInitialize() {{
-        System.out.println("[{name}] Mod initialized!");
-    }}
-}}
-"#
-    );
-    let mod_init_path = src_java_dir.join("ModInit.java");
-    match std::fs::write(&mod_init_path, &mod_init_java) {
-        Ok(_) => created_files.push(mod_init_path.display().to_string()),
-        Err(e) => {
-            return ToolResult::error(format!("Failed to write ModInit.java: {}", e));
-        }
+    if let Err(e) = std::fs::create_dir_all(project_dir) {
+        return ToolResult::error_with(
+            ErrorCode::from(&e),
+            format!("Failed to create project directory: {}", e),
+        );
     }
 
-    // 5. .gitignore
-    let gitignore = "build/\n.gradle/\n*.class\n*.jar\nout/\n";
-    let gitignore_path = project_dir.join(".gitignore");
-    match std::fs::write(&gitignore_path, gitignore) {
-        Ok(_) => created_files.push(gitignore_path.display().to_string()),
+    let args = ScaffoldArgs {
+        name: name.to_string(),
+        environment: environment.to_string(),
+        mod_id: Some(mod_id.to_string()),
+        package_name: package.map(String::from),
+        minecraft_version: minecraft_version.map(String::from),
+    };
+    let scaffold = match (project_type, loader) {
+        ("modpack", _) => scaffold_modpack_project(project_dir, &args),
+        (_, "alloy") => scaffold_mod_project(project_dir, &args),
+        (_, loader) => scaffold_loom_project(project_dir, &args, loader),
+    };
+    let scaffold = match scaffold {
+        Ok(s) => s,
         Err(e) => {
-            return ToolResult::error(format!("Failed to write .gitignore: {}", e));
+            // Don't leave a half-written project behind to block a retry
+            let _ = std::fs::remove_dir_all(project_dir);
+            return ToolResult::error(e);
         }
-    }
+    };
 
-    // Update state to point at the new project
-    {
-        let info = ProjectInfo {
-            name: name.to_string(),
-            path: project_dir.display().to_string(),
-            project_type: ProjectType::Mod,
-            environment: Some(environment.to_string()),
-        };
-        if let Ok(mut current) = state.current_project.lock() {
-            *current = Some(info.clone());
-        }
+    let is_mod = project_type == "mod";
+    let info = ProjectInfo {
+        name: name.to_string(),
+        path: project_dir.display().to_string(),
+        project_type: if is_mod { ProjectType::Mod } else { ProjectType::Modpack },
+        environment: is_mod.then(|| environment.to_string()),
+        loader: is_mod.then(|| loader.to_string()),
+        loader_version: match (is_mod, loader) {
+            (true, "fabric") => Some(FABRIC_LOADER_VERSION.to_string()),
+            (true, "quilt") => Some(QUILT_LOADER_VERSION.to_string()),
+            _ => None,
+        },
+    };
+    state.set_current(info.clone());
+    if state.sync_with_ide {
         write_current_project_file(&info);
     }
+    append_recent_project(&info);
 
     ToolResult::json(&json!({
         "status": "created",
-        "project_type": "mod",
+        "project_type": project_type,
         "name": name,
         "mod_id": mod_id,
-        "environment": environment,
-        "path": project_dir.display().to_string(),
-        "created_files": created_files
+        "environment": info.environment,
+        "loader": info.loader,
+        "loader_version": info.loader_version,
+        "path": info.path,
+        "created_files": scaffold.created_files,
+        "main_class": scaffold.main_class
     }))
 }
 
-async fn create_modpack_project(
-    project_dir: &Path,
-    name: &str,
-    mod_id: &str,
-    state: &ProjectState,
-) -> ToolResult {
-    let mut created_files: Vec<String> = Vec::new();
-
-    // Create directories
-    let mods_dir = project_dir.join("mods");
-    if let Err(e) = std::fs::create_dir_all(&mods_dir) {
-        return ToolResult::error(format!("Failed to create project directories: {}", e));
-    }
-
-    // 1. alloy.pack.toml
-    let pack_toml = format!(
-        r#"[pack]
-name = "{name}"
-pack_id = "{mod_id}"
-version = "0.1.0"
-description = ""
-authors = []
-
-[minecraft]
-version = "1.21"
-
-[alloy]
-version = "*"
-
-[mods]
-# Add mods here:
-# my_mod = {{ source = "local", path = "mods/my_mod.jar" }}
-"#
-    );
-    let pack_toml_path = project_dir.join("alloy.pack.toml");
-    match std::fs::write(&pack_toml_path, &pack_toml) {
-        Ok(_) => created_files.push(pack_toml_path.display().to_string()),
-        Err(e) => {
-            return ToolResult::error(format!("Failed to write alloy.pack.toml: {}", e));
-        }
-    }
-
-    // 2. .gitignore
-    let gitignore = "mods/*.jar\n";
-    let gitignore_path = project_dir.join(".gitignore");
-    match std::fs::write(&gitignore_path, gitignore) {
-        Ok(_) => created_files.push(gitignore_path.display().to_string()),
-        Err(e) => {
-            return ToolResult::error(format!("Failed to write .gitignore: {}", e));
-        }
-    }
-
-    // Update state
-    {
-        let info = ProjectInfo {
-            name: name.to_string(),
-            path: project_dir.display().to_string(),
-            project_type: ProjectType::Modpack,
-            environment: None,
-        };
-        if let Ok(mut current) = state.current_project.lock() {
-            *current = Some(info.clone());
-        }
-        write_current_project_file(&info);
-    }
-
-    ToolResult::json(&json!({
-        "status": "created",
-        "project_type": "modpack",
-        "name": name,
-        "pack_id": mod_id,
-        "path": project_dir.display().to_string(),
-        "created_files": created_files
-    }))
+/// Whether `package` is a dot-separated list of Java identifiers.
+fn is_valid_package(package: &str) -> bool {
+    package.split('.').all(|part| {
+        let mut chars = part.chars();
+        chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
 }
 
 // ---------------------------------------------------------------------------
@@ -372,7 +342,7 @@ version = "*"
 async fn handle_open(params: Value, state: &ProjectState) -> ToolResult {
     let path = match params.get("path").and_then(|v| v.as_str()) {
         Some(p) => p,
-        None => return ToolResult::error("Missing required parameter: path"),
+        None => return ToolResult::missing_param("path"),
     };
 
     let project_dir = Path::new(path);
@@ -383,10 +353,18 @@ async fn handle_open(params: Value, state: &ProjectState) -> ToolResult {
         return ToolResult::error(format!("Path is not a directory: {}", path));
     }
 
-    // Detect project type by reading manifests
+    // Detect project type by reading manifests, Alloy's first
     let mod_json_path = project_dir.join("alloy.mod.json");
     let pack_toml_path = project_dir.join("alloy.pack.toml");
 
+    let dir_name = project_dir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("Unknown")
+        .to_string();
+    let mut loader = None;
+    let mut loader_version = None;
+
     let (project_type, environment, name) = if mod_json_path.exists() {
         match std::fs::read_to_string(&mod_json_path) {
             Ok(content) => {
@@ -400,6 +378,7 @@ async fn handle_open(params: Value, state: &ProjectState) -> ToolResult {
                     .and_then(|e| e.as_str())
                     .unwrap_or("Unknown")
                     .to_string();
+                loader = Some("alloy".to_string());
                 (ProjectType::Mod, env, n)
             }
             Err(e) => {
@@ -422,14 +401,13 @@ async fn handle_open(params: Value, state: &ProjectState) -> ToolResult {
                 return ToolResult::error(format!("Failed to read alloy.pack.toml: {}", e));
             }
         }
+    } else if let Some(detected) = detect_loader(project_dir) {
+        loader = Some(detected.loader.to_string());
+        loader_version = detected.version;
+        (ProjectType::Mod, detected.environment, dir_name)
     } else {
         // No recognized manifest — open as unknown
-        let n = project_dir
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("Unknown")
-            .to_string();
-        (ProjectType::Unknown, None, n)
+        (ProjectType::Unknown, None, dir_name)
     };
 
     let info = ProjectInfo {
@@ -437,15 +415,17 @@ async fn handle_open(params: Value, state: &ProjectState) -> ToolResult {
         path: path.to_string(),
         project_type: project_type.clone(),
         environment: environment.clone(),
+        loader: loader.clone(),
+        loader_version: loader_version.clone(),
     };
 
     // Update state
-    if let Ok(mut current) = state.current_project.lock() {
-        *current = Some(info.clone());
-    }
+    state.set_current(info.clone());
 
     // Write shared file for IDE sync
-    write_current_project_file(&info);
+    if state.sync_with_ide {
+        write_current_project_file(&info);
+    }
 
     // Append to recent projects
     append_recent_project(&info);
@@ -455,7 +435,10 @@ async fn handle_open(params: Value, state: &ProjectState) -> ToolResult {
         "name": name,
         "path": path,
         "project_type": project_type,
-        "environment": environment
+        "environment": environment,
+        "loader": loader,
+        "loader_version": loader_version,
+        "open_projects": open_projects_json(state)
     }))
 }
 
@@ -523,13 +506,17 @@ async fn handle_info(state: &ProjectState) -> ToolResult {
     // Always re-read the shared file to pick up IDE-side project changes.
     // The IDE writes to ~/.alloy-ide/current-project.json whenever the user
     // opens a different project, so we must check it on every call rather than
-    // relying on potentially stale in-memory state.
-    let info = match load_from_shared_file() {
+    // relying on potentially stale in-memory state. HTTP sessions keep their
+    // own project and skip this.
+    let shared = if state.sync_with_ide {
+        load_from_shared_file()
+    } else {
+        None
+    };
+    let info = match shared {
         Some(info) => {
             // Update in-memory state so other tools stay in sync
-            if let Ok(mut current) = state.current_project.lock() {
-                *current = Some(info.clone());
-            }
+            state.set_current(info.clone());
             info
         }
         None => {
@@ -552,7 +539,59 @@ async fn handle_info(state: &ProjectState) -> ToolResult {
         "name": info.name,
         "path": info.path,
         "project_type": info.project_type,
-        "environment": info.environment
+        "environment": info.environment,
+        "open_projects": open_projects_json(state)
+    }))
+}
+
+/// Summaries of all open projects, for tool responses.
+fn open_projects_json(state: &ProjectState) -> Vec<Value> {
+    let current = state.project_path().ok();
+    state
+        .open_projects()
+        .into_iter()
+        .map(|p| {
+            json!({
+                "name": p.name,
+                "path": p.path,
+                "project_type": p.project_type,
+                "current": current.as_deref() == Some(p.path.as_str())
+            })
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// project_close
+// ---------------------------------------------------------------------------
+
+async fn handle_close(params: Value, state: &ProjectState) -> ToolResult {
+    let path = match params.get("path").and_then(|v| v.as_str()) {
+        Some(p) => p.to_string(),
+        None => match state.project_path() {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(e),
+        },
+    };
+
+    let closed = match state.close_project(&path) {
+        Some(info) => info,
+        None => return ToolResult::error(format!("Project is not open: {}", path)),
+    };
+
+    // Point the IDE at whichever project is now current
+    let current = state.current_project.lock().ok().and_then(|c| c.clone());
+    if state.sync_with_ide {
+        if let Some(info) = &current {
+            write_current_project_file(info);
+        }
+    }
+
+    ToolResult::json(&json!({
+        "status": "closed",
+        "path": closed.path,
+        "current_project": current.map(|c| c.path),
+        "open_projects": open_projects_json(state)
     }))
 }
 
@@ -606,3 +645,339 @@ async fn handle_list_recent() -> ToolResult {
         Err(e) => ToolResult::error(format!("Failed to read recent projects file: {}", e)),
     }
 }
+
+// ---------------------------------------------------------------------------
+// project_generate_verify_task
+// ---------------------------------------------------------------------------
+
+const VERIFY_GRADLE_TASK: &str = r#"
+// Generated by Alloy: one-step local verification (block validation, build, tests).
+// Set ALLOY_MCP to the alloy-mcp binary if it is not on PATH.
+tasks.register<Exec>("validateBlocks") {
+    group = "verification"
+    description = "Validates every .block.json definition with alloy-mcp."
+    commandLine(System.getenv("ALLOY_MCP") ?: "alloy-mcp", "--project", rootDir.absolutePath, "--validate-all-blocks")
+}
+
+tasks.register("verifyAll") {
+    group = "verification"
+    description = "Validates blocks, then builds and tests the mod."
+    dependsOn("validateBlocks", "build", "test")
+}
+
+tasks.named("build") { mustRunAfter("validateBlocks") }
+"#;
+
+const VERIFY_SCRIPT: &str = r#"#!/bin/sh
+# Generated by Alloy: one-step local verification (block validation, build, tests).
+# Set ALLOY_MCP to the alloy-mcp binary if it is not on PATH.
+set -e
+cd "$(dirname "$0")"
+"${ALLOY_MCP:-alloy-mcp}" --project "$(pwd)" --validate-all-blocks
+./gradlew build test
+"#;
+
+async fn handle_generate_verify_task(params: Value, state: &ProjectState) -> ToolResult {
+    let project_path = match state.resolve_project_path(&params) {
+        Ok(p) => p,
+        Err(e) => return ToolResult::error(e),
+    };
+    let kind = params
+        .get("kind")
+        .and_then(|v| v.as_str())
+        .unwrap_or("gradle");
+
+    let project_dir = Path::new(&project_path);
+    if !project_dir.is_dir() {
+        return ToolResult::error(format!("Project directory does not exist: {}", project_path));
+    }
+
+    match kind {
+        "gradle" => {
+            let build_gradle_path = project_dir.join("build.gradle.kts");
+            let content = match std::fs::read_to_string(&build_gradle_path) {
+                Ok(c) => c,
+                Err(e) => {
+                    return ToolResult::error(format!(
+                        "Failed to read {}: {}. Use kind 'script' for projects without a Kotlin DSL build.",
+                        build_gradle_path.display(),
+                        e
+                    ))
+                }
+            };
+
+            let already_present = content.contains("tasks.register(\"verifyAll\")");
+            if !already_present {
+                let mut updated = content;
+                if !updated.ends_with('\n') {
+                    updated.push('\n');
+                }
+                updated.push_str(VERIFY_GRADLE_TASK);
+                if let Err(e) = std::fs::write(&build_gradle_path, updated) {
+                    return ToolResult::error(format!("Failed to write build.gradle.kts: {}", e));
+                }
+            }
+
+            ToolResult::json(&json!({
+                "status": if already_present { "unchanged" } else { "created" },
+                "kind": "gradle",
+                "path": build_gradle_path.display().to_string(),
+                "invoke": "./gradlew verifyAll"
+            }))
+        }
+        "script" => {
+            let script_path = project_dir.join("verify.sh");
+            let already_present = script_path.exists();
+            if !already_present {
+                if let Err(e) = std::fs::write(&script_path, VERIFY_SCRIPT) {
+                    return ToolResult::error(format!("Failed to write verify.sh: {}", e));
+                }
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs::PermissionsExt;
+                    let _ = std::fs::set_permissions(&script_path, std::fs::Permissions::from_mode(0o755));
+                }
+            }
+
+            ToolResult::json(&json!({
+                "status": if already_present { "unchanged" } else { "created" },
+                "kind": "script",
+                "path": script_path.display().to_string(),
+                "invoke": "./verify.sh"
+            }))
+        }
+        other => ToolResult::error(format!(
+            "Invalid kind '{}'. Must be 'gradle' or 'script'.",
+            other
+        )),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// project_rename_mod_id
+// ---------------------------------------------------------------------------
+
+/// A JSON file whose content changes under the rename.
+struct PlannedRewrite {
+    path: PathBuf,
+    original: String,
+    content: String,
+    changes: Vec<Value>,
+}
+
+/// Rewrite the parts of one JSON file that mention `old_id`. Returns the new
+/// content and a description of each kind of change, or `None` if the file
+/// doesn't need touching.
+fn rewrite_mod_id_json(
+    path: &Path,
+    content: &str,
+    old_id: &str,
+    new_id: &str,
+) -> Option<(String, Vec<Value>)> {
+    let escaped = regex::escape(old_id);
+    let mut updated = content.to_string();
+    let mut changes = Vec::new();
+
+    // Namespaced references: "old:block/x" and tag references "#old:logs"
+    let references = regex::Regex::new(&format!(r##"(["#]){}:"##, escaped)).ok()?;
+    let count = references.find_iter(&updated).count();
+    if count > 0 {
+        updated = references
+            .replace_all(&updated, format!("${{1}}{}:", new_id).as_str())
+            .into_owned();
+        changes.push(json!({ "kind": "references", "count": count }));
+    }
+
+    // Lang keys such as "block.old.ruby_ore"
+    let in_lang_dir = path
+        .parent()
+        .and_then(|p| p.file_name())
+        .is_some_and(|n| n == "lang");
+    if in_lang_dir {
+        let keys = regex::Regex::new(&format!(r#""([A-Za-z_]+)\.{}\."#, escaped)).ok()?;
+        let count = keys.find_iter(&updated).count();
+        if count > 0 {
+            updated = keys
+                .replace_all(&updated, format!("\"${{1}}.{}.", new_id).as_str())
+                .into_owned();
+            changes.push(json!({ "kind": "lang_keys", "count": count }));
+        }
+    }
+
+    // The id field of manifests and block/item definitions
+    let file_name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    let id_field = match file_name.as_ref() {
+        "fabric.mod.json" | "quilt.mod.json" => Some("id"),
+        "alloy.mod.json" => Some("mod_id"),
+        name if name.ends_with(".block.json") || name.ends_with(".item.json") => Some("mod_id"),
+        _ => None,
+    };
+    if let Some(field) = id_field {
+        let declared = serde_json::from_str::<Value>(content)
+            .ok()
+            .and_then(|v| v.get(field).and_then(|id| id.as_str()).map(String::from));
+        if declared.as_deref() == Some(old_id) {
+            // Edit the text rather than re-serializing, to keep the file's formatting
+            let field_re =
+                regex::Regex::new(&format!(r#"("{}"\s*:\s*"){}""#, field, escaped)).ok()?;
+            updated = field_re
+                .replacen(&updated, 1, format!("${{1}}{}\"", new_id).as_str())
+                .into_owned();
+            changes.push(json!({ "kind": "id_field", "field": field }));
+        }
+    }
+
+    (!changes.is_empty()).then_some((updated, changes))
+}
+
+async fn handle_rename_mod_id(params: Value, state: &ProjectState) -> ToolResult {
+    let project_path = match state.resolve_project_path(&params) {
+        Ok(p) => p,
+        Err(e) => return ToolResult::error(e),
+    };
+    let old_id = match params.get("old_id").and_then(|v| v.as_str()) {
+        Some(id) => id,
+        None => return ToolResult::missing_param("old_id"),
+    };
+    let new_id = match params.get("new_id").and_then(|v| v.as_str()) {
+        Some(id) => id,
+        None => return ToolResult::missing_param("new_id"),
+    };
+    let dry_run = params
+        .get("dry_run")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    if !is_valid_mod_id(new_id) {
+        return ToolResult::error(format!(
+            "Invalid new_id '{}': use lowercase letters, digits, and underscores, \
+             starting with a letter",
+            new_id
+        ));
+    }
+    if old_id == new_id {
+        return ToolResult::error("old_id and new_id are the same");
+    }
+
+    let project_dir = Path::new(&project_path);
+    if !project_dir.is_dir() {
+        return ToolResult::error(format!("Project directory does not exist: {}", project_path));
+    }
+    let relative = |p: &Path| p.strip_prefix(project_dir).unwrap_or(p).display().to_string();
+
+    // assets/<old_id> and data/<old_id> in every source set (main, client, ...)
+    let mut directory_moves: Vec<(PathBuf, PathBuf)> = Vec::new();
+    if let Ok(source_sets) = std::fs::read_dir(project_dir.join("src")) {
+        let mut source_sets: Vec<PathBuf> = source_sets.flatten().map(|e| e.path()).collect();
+        source_sets.sort();
+        for source_set in source_sets {
+            for kind in ["assets", "data"] {
+                let parent = source_set.join("resources").join(kind);
+                let from = parent.join(old_id);
+                if !from.is_dir() {
+                    continue;
+                }
+                let to = parent.join(new_id);
+                if to.exists() {
+                    return ToolResult::error(format!(
+                        "{} already exists; merge or remove it before renaming",
+                        relative(&to)
+                    ));
+                }
+                directory_moves.push((from, to));
+            }
+        }
+    }
+
+    let mut rewrites: Vec<PlannedRewrite> = Vec::new();
+    let max_depth = state.fs_limits.max_walk_depth;
+    for path in crate::tools::filesystem::search_files(project_dir, max_depth, false) {
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let Ok(original) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        if let Some((content, changes)) = rewrite_mod_id_json(&path, &original, old_id, new_id) {
+            rewrites.push(PlannedRewrite { path, original, content, changes });
+        }
+    }
+
+    // The generated-files manifest has to follow the moved files, or the next
+    // regeneration treats them as foreign and the rewritten ones as hand-edited
+    let mut manifest = match GeneratedManifest::load(project_dir) {
+        Ok(m) => m,
+        Err(e) => return ToolResult::error(format!("{}; fix or delete it and retry", e)),
+    };
+    let mut manifest_changes = 0;
+    for rewrite in &rewrites {
+        let path = generated::relative_path(project_dir, &rewrite.path);
+        if manifest.rehash(&path, &rewrite.original, &rewrite.content) {
+            manifest_changes += 1;
+        }
+    }
+    for (from, to) in &directory_moves {
+        manifest_changes += manifest.move_dir(
+            &generated::relative_path(project_dir, from),
+            &generated::relative_path(project_dir, to),
+        );
+    }
+
+    // File contents first, while every path is still where the walk found it
+    if !dry_run {
+        for rewrite in &rewrites {
+            if let Err(e) = std::fs::write(&rewrite.path, &rewrite.content) {
+                return ToolResult::error(format!(
+                    "Failed to write {}: {}",
+                    rewrite.path.display(),
+                    e
+                ));
+            }
+        }
+        for (from, to) in &directory_moves {
+            if let Err(e) = std::fs::rename(from, to) {
+                return ToolResult::error(format!(
+                    "Failed to rename {} to {}: {}. File contents were already rewritten.",
+                    relative(from),
+                    relative(to),
+                    e
+                ));
+            }
+        }
+        if manifest_changes > 0 {
+            if let Err(e) = manifest.save(project_dir) {
+                return ToolResult::error(format!("{}. Files were already renamed.", e));
+            }
+        }
+    }
+
+    let directories: Vec<Value> = directory_moves
+        .iter()
+        .map(|(from, to)| json!({ "from": relative(from), "to": relative(to) }))
+        .collect();
+    let files: Vec<Value> = rewrites
+        .iter()
+        .map(|r| json!({ "path": relative(&r.path), "changes": r.changes }))
+        .collect();
+
+    let status = if dry_run {
+        "dry_run"
+    } else if directories.is_empty() && files.is_empty() {
+        "unchanged"
+    } else {
+        "renamed"
+    };
+
+    ToolResult::json(&json!({
+        "status": status,
+        "old_id": old_id,
+        "new_id": new_id,
+        "dry_run": dry_run,
+        "renamed_directories": directories,
+        // Paths are as they were before the directory renames
+        "rewritten_files": files,
+        "generated_manifest_updates": manifest_changes,
+        "note": "Java sources, package names and Identifier strings in code were not changed; \
+            search them with fs_search for the old id"
+    }))
+}
@@ -1,5 +1,178 @@
 use crate::state::{load_from_shared_file, ProjectState};
-use serde_json::json;
+use crate::types::{ToolDefinition, ToolResult};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Return tool definitions for all resource (asset) tools.
+pub fn definitions() -> Vec<ToolDefinition> {
+    vec![
+        ToolDefinition {
+            name: "resource_validate_texture".into(),
+            description: "Check a texture PNG's dimensions without decoding it. Flags textures \
+                that are not square or whose size is not a power of two, both of which render \
+                garbled in game. Vertical strips of N×(N*frames) are reported as animation \
+                frames and checked for a matching .png.mcmeta."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Absolute path to the .png file"
+                    }
+                },
+                "required": ["path"]
+            }),
+        },
+        ToolDefinition {
+            name: "resource_validate_model".into(),
+            description: "Check a block or item model JSON for broken references. Resolves its \
+                parent and every texture it uses, following #variable indirection through the parent \
+                chain and mapping namespace:path ids into the assets tree, and reports missing \
+                parent models, missing texture files and undefined texture variables. References \
+                into the minecraft namespace are assumed to exist. Given project_path instead of \
+                path, every model under src/main/resources/assets/*/models is checked."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Absolute path to one model .json file"
+                    },
+                    "project_path": {
+                        "type": "string",
+                        "description": "Project root. Without path, validates every model in the project; defaults to the current project."
+                    }
+                }
+            }),
+        },
+        ToolDefinition {
+            name: "resource_preview_texture".into(),
+            description: "Return a texture PNG as an image so it can be looked at directly. Small \
+                textures are upscaled with nearest-neighbour filtering (pixels stay crisp) until \
+                their longest side is at least 256px."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Absolute path to the .png file"
+                    }
+                },
+                "required": ["path"]
+            }),
+        },
+        ToolDefinition {
+            name: "resource_create_animation_mcmeta".into(),
+            description: "Write the .png.mcmeta that makes a texture animated. The texture must be a \
+                vertical strip whose height is a multiple of its width; each width×width square is \
+                one frame. Frames play top to bottom unless an explicit frames order is given. \
+                Returns the path of the created file."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Absolute path to the animated .png texture"
+                    },
+                    "frametime": {
+                        "type": "integer",
+                        "description": "Ticks each frame is shown (20 ticks = 1 second). Default: 1"
+                    },
+                    "frames": {
+                        "type": "array",
+                        "description": "Optional frame order. Each entry is a frame index, or { \"index\": n, \"time\": ticks } to override the frametime for that frame.",
+                        "items": {}
+                    },
+                    "interpolate": {
+                        "type": "boolean",
+                        "description": "Blend between frames for smoother animation. Default: false"
+                    },
+                    "overwrite": {
+                        "type": "boolean",
+                        "description": "Replace an existing .mcmeta file. Default: false"
+                    }
+                },
+                "required": ["path"]
+            }),
+        },
+        ToolDefinition {
+            name: "resource_lang".into(),
+            description: "Manage translation keys in assets/<mod_id>/lang/*.json without hand-editing JSON. \
+                Actions: 'set' writes a key into one locale (en_us by default), 'remove' deletes a key \
+                from every locale (or just one), 'sync' reports keys present in en_us.json but missing \
+                from the other locale files. Keys are kept sorted and the file's indentation is preserved."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "enum": ["set", "remove", "sync"],
+                        "description": "What to do"
+                    },
+                    "key": {
+                        "type": "string",
+                        "description": "Translation key, e.g. 'block.mymod.ruby_ore' (set/remove)"
+                    },
+                    "value": {
+                        "type": "string",
+                        "description": "Translated text (set)"
+                    },
+                    "locale": {
+                        "type": "string",
+                        "description": "Locale file to act on, e.g. 'de_de'. Default for set: en_us. For remove and sync: every locale."
+                    },
+                    "mod_id": {
+                        "type": "string",
+                        "description": "Asset namespace (defaults to mod_id in alloy.mod.json)"
+                    },
+                    "project_path": {
+                        "type": "string",
+                        "description": "Project root (defaults to the open project)"
+                    }
+                },
+                "required": ["action"]
+            }),
+        },
+        ToolDefinition {
+            name: "resources_find_duplicate_textures".into(),
+            description: "Find textures with identical pixel content under \
+                src/main/resources/assets/*/textures. Hashes decoded pixel data (so re-encoded \
+                copies of the same image are still detected), groups duplicates, suggests a \
+                canonical texture for each group, lists the models that reference the redundant \
+                copies, and reports the bytes that consolidation would save."
+                .into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_path": {
+                        "type": "string",
+                        "description": "Absolute path to the Alloy project directory. Defaults to the current project."
+                    }
+                }
+            }),
+        },
+    ]
+}
+
+/// Dispatch execution to the appropriate resource tool handler.
+pub async fn execute(name: &str, params: Value, state: &ProjectState) -> ToolResult {
+    match name {
+        "resources_find_duplicate_textures" => handle_find_duplicate_textures(params, state).await,
+        "resource_validate_texture" => handle_validate_texture(params).await,
+        "resource_validate_model" => handle_validate_model(params, state).await,
+        "resource_preview_texture" => handle_preview_texture(params).await,
+        "resource_create_animation_mcmeta" => handle_create_animation_mcmeta(params).await,
+        "resource_lang" => handle_lang(params, state).await,
+        _ => ToolResult::error(format!("Unknown resource tool: {}", name)),
+    }
+}
 
 /// Read an MCP resource by URI.
 pub async fn read_resource(uri: &str, state: &ProjectState) -> String {
@@ -23,12 +196,10 @@ fn resource_project(state: &ProjectState) -> String {
     // Fall back to shared file if in-memory state is empty
     let info = match in_memory {
         Some(info) => info,
-        None => match load_from_shared_file() {
+        None => match load_from_shared_file().filter(|_| state.sync_with_ide) {
             Some(loaded) => {
                 // Update in-memory state for future calls
-                if let Ok(mut guard) = state.current_project.lock() {
-                    *guard = Some(loaded.clone());
-                }
+                state.set_current(loaded.clone());
                 loaded
             }
             None => {
@@ -178,3 +349,916 @@ fn resource_diagnostics(state: &ProjectState) -> String {
 fn resource_api_reference() -> String {
     include_str!("../../resources/alloy_api_reference.md").to_string()
 }
+
+// ---------------------------------------------------------------------------
+// Resource tool helpers
+// ---------------------------------------------------------------------------
+
+/// Resolve the project root from `project_path`, falling back to the open project.
+fn resolve_project(params: &Value, state: &ProjectState) -> Result<String, String> {
+    state.resolve_project_path(params)
+}
+
+/// Collect every `.png` under `assets/<namespace>/textures` as
+/// `(resource_id, path)` pairs, where `resource_id` is the `namespace:path`
+/// form models use to reference the texture.
+fn collect_textures(assets_dir: &Path) -> Vec<(String, PathBuf)> {
+    let mut textures = Vec::new();
+    let Ok(namespaces) = std::fs::read_dir(assets_dir) else {
+        return textures;
+    };
+    for namespace in namespaces.flatten() {
+        let ns = namespace.file_name().to_string_lossy().to_string();
+        let textures_dir = namespace.path().join("textures");
+        if !textures_dir.is_dir() {
+            continue;
+        }
+        for entry in walkdir::WalkDir::new(&textures_dir)
+            .follow_links(false)
+            .into_iter()
+            .flatten()
+        {
+            let path = entry.path();
+            if !entry.file_type().is_file()
+                || path.extension().and_then(|e| e.to_str()) != Some("png")
+            {
+                continue;
+            }
+            let without_ext = path.with_extension("");
+            let Ok(relative) = without_ext.strip_prefix(&textures_dir) else {
+                continue;
+            };
+            let id = format!("{}:{}", ns, relative.to_string_lossy().replace('\\', "/"));
+            textures.push((id, path.to_path_buf()));
+        }
+    }
+    textures
+}
+
+/// Hash the decoded RGBA pixels of a PNG, fed row by row into SHA-256 so
+/// large textures never need a second contiguous copy.
+fn hash_texture_pixels(path: &Path) -> Result<String, String> {
+    let img = image::open(path).map_err(|e| e.to_string())?.into_rgba8();
+    let (width, height) = img.dimensions();
+    let mut hasher = Sha256::new();
+    hasher.update(width.to_le_bytes());
+    hasher.update(height.to_le_bytes());
+    for row in img.rows() {
+        for pixel in row {
+            hasher.update(pixel.0);
+        }
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+// ---------------------------------------------------------------------------
+// resources_find_duplicate_textures
+// ---------------------------------------------------------------------------
+
+async fn handle_find_duplicate_textures(params: Value, state: &ProjectState) -> ToolResult {
+    let project_path = match resolve_project(&params, state) {
+        Ok(p) => p,
+        Err(e) => return ToolResult::error(e),
+    };
+    let assets_dir = Path::new(&project_path).join("src/main/resources/assets");
+    if !assets_dir.is_dir() {
+        return ToolResult::error(format!(
+            "Assets directory does not exist: {}",
+            assets_dir.display()
+        ));
+    }
+
+    let textures = collect_textures(&assets_dir);
+    let mut by_hash: BTreeMap<String, Vec<(String, PathBuf)>> = BTreeMap::new();
+    let mut unreadable: Vec<Value> = Vec::new();
+    for (id, path) in &textures {
+        match hash_texture_pixels(path) {
+            Ok(hash) => by_hash.entry(hash).or_default().push((id.clone(), path.clone())),
+            Err(e) => unreadable.push(json!({ "path": path.to_string_lossy(), "error": e })),
+        }
+    }
+
+    // Read every model once so references can be matched against each group
+    let models: Vec<(PathBuf, String)> = walkdir::WalkDir::new(&assets_dir)
+        .follow_links(false)
+        .into_iter()
+        .flatten()
+        .filter(|e| {
+            e.file_type().is_file()
+                && e.path().extension().and_then(|x| x.to_str()) == Some("json")
+                && e.path().components().any(|c| c.as_os_str() == "models")
+        })
+        .filter_map(|e| {
+            std::fs::read_to_string(e.path())
+                .ok()
+                .map(|c| (e.path().to_path_buf(), c))
+        })
+        .collect();
+
+    let mut groups: Vec<Value> = Vec::new();
+    let mut wasted_bytes: u64 = 0;
+    for (hash, mut members) in by_hash {
+        if members.len() < 2 {
+            continue;
+        }
+        // Prefer the shortest id as canonical, breaking ties alphabetically
+        members.sort_by(|a, b| a.0.len().cmp(&b.0.len()).then_with(|| a.0.cmp(&b.0)));
+        let (canonical_id, canonical_path) = &members[0];
+
+        let mut duplicates: Vec<Value> = Vec::new();
+        for (id, path) in &members[1..] {
+            let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+            wasted_bytes += size;
+
+            // Models may omit the "minecraft:" namespace, but never a mod namespace
+            let needle = format!("\"{}\"", id);
+            let referenced_by: Vec<String> = models
+                .iter()
+                .filter(|(_, content)| content.contains(&needle))
+                .map(|(p, _)| p.to_string_lossy().to_string())
+                .collect();
+
+            duplicates.push(json!({
+                "id": id,
+                "path": path.to_string_lossy(),
+                "bytes": size,
+                "referenced_by": referenced_by
+            }));
+        }
+
+        groups.push(json!({
+            "hash": hash,
+            "canonical": {
+                "id": canonical_id,
+                "path": canonical_path.to_string_lossy()
+            },
+            "duplicates": duplicates,
+            "suggestion": format!(
+                "Point the listed models at \"{}\" and delete the duplicate files.",
+                canonical_id
+            )
+        }));
+    }
+
+    ToolResult::json(&json!({
+        "project_path": project_path,
+        "textures_scanned": textures.len(),
+        "duplicate_groups": groups,
+        "group_count": groups.len(),
+        "wasted_bytes": wasted_bytes,
+        "unreadable": unreadable
+    }))
+}
+
+// ---------------------------------------------------------------------------
+// resource_validate_texture
+// ---------------------------------------------------------------------------
+
+/// What `inspect_texture` found: the PNG's size, its frame count when it is
+/// an animation strip, and any issues in block_validate's issue format.
+pub(crate) struct TextureReport {
+    pub width: u32,
+    pub height: u32,
+    pub frames: Option<u32>,
+    pub issues: Vec<Value>,
+}
+
+/// Read a PNG's dimensions from its header and check them.
+pub(crate) fn inspect_texture(path: &Path) -> Result<TextureReport, String> {
+    let (width, height) = image::image_dimensions(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+    let mut issues: Vec<Value> = Vec::new();
+    let mut frames = None;
+
+    if width == 0 || height == 0 {
+        issues.push(json!({
+            "severity": "error",
+            "message": "Texture is empty (zero width or height).",
+            "suggestion": "Re-export the texture."
+        }));
+    } else if width != height {
+        let mcmeta = PathBuf::from(format!("{}.mcmeta", path.display()));
+        if height > width && height % width == 0 {
+            // Vertical strip: one frame per width×width square
+            frames = Some(height / width);
+            if !mcmeta.exists() {
+                issues.push(json!({
+                    "severity": "warning",
+                    "message": format!(
+                        "Texture is {}x{}, an animation strip of {} frames, but has no .mcmeta file.",
+                        width, height, height / width
+                    ),
+                    "suggestion": format!(
+                        "Add {} with an \"animation\" section, or crop the texture to {}x{}.",
+                        mcmeta.file_name().unwrap_or_default().to_string_lossy(), width, width
+                    )
+                }));
+            }
+        } else {
+            issues.push(json!({
+                "severity": "error",
+                "message": format!("Texture is {}x{}, but block and item textures must be square.", width, height),
+                "suggestion": "Resize the texture to a square such as 16x16."
+            }));
+        }
+    }
+
+    if width > 0 && !width.is_power_of_two() {
+        issues.push(json!({
+            "severity": "warning",
+            "message": format!("Texture width {} is not a power of two, which breaks mipmapping.", width),
+            "suggestion": format!("Resize to {0}x{0}.", nearest_power_of_two(width))
+        }));
+    }
+
+    Ok(TextureReport {
+        width,
+        height,
+        frames,
+        issues,
+    })
+}
+
+fn nearest_power_of_two(n: u32) -> u32 {
+    let up = n.next_power_of_two();
+    let down = up / 2;
+    if down > 0 && n - down < up - n {
+        down
+    } else {
+        up
+    }
+}
+
+async fn handle_validate_texture(params: Value) -> ToolResult {
+    let path = match params.get("path").and_then(|v| v.as_str()) {
+        Some(p) => p,
+        None => return ToolResult::missing_param("path"),
+    };
+
+    let report = match inspect_texture(Path::new(path)) {
+        Ok(r) => r,
+        Err(e) => return ToolResult::error(e),
+    };
+
+    let valid = !report
+        .issues
+        .iter()
+        .any(|i| i.get("severity").and_then(|s| s.as_str()) == Some("error"));
+    ToolResult::json(&json!({
+        "path": path,
+        "width": report.width,
+        "height": report.height,
+        "animation_frames": report.frames,
+        "issues": report.issues,
+        "valid": valid
+    }))
+}
+
+// ---------------------------------------------------------------------------
+// resource_validate_model
+// ---------------------------------------------------------------------------
+
+/// Texture variables resolve through at most this many `#` hops; more means
+/// the variables refer to each other in a loop.
+const MAX_VARIABLE_HOPS: usize = 16;
+
+/// Where a `namespace:path` reference points in an assets tree, or `None` for
+/// builtin and vanilla references, which can't be checked on disk.
+fn resolve_asset(assets_dir: &Path, reference: &str, kind: &str, ext: &str) -> Option<PathBuf> {
+    if reference.starts_with("builtin/") {
+        return None;
+    }
+    let (namespace, path) = reference.split_once(':').unwrap_or(("minecraft", reference));
+    if namespace == "minecraft" {
+        return None;
+    }
+    Some(assets_dir.join(namespace).join(kind).join(format!("{}.{}", path, ext)))
+}
+
+fn read_model(path: &Path) -> Result<Value, String> {
+    let content =
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to read model: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Model is not valid JSON: {}", e))
+}
+
+/// The `textures` map of a model, as variable name to reference.
+fn model_textures(model: &Value) -> impl Iterator<Item = (&String, &str)> {
+    model
+        .get("textures")
+        .and_then(|t| t.as_object())
+        .into_iter()
+        .flatten()
+        .filter_map(|(k, v)| v.as_str().map(|v| (k, v)))
+}
+
+/// Check one model's parent and texture references against `assets_dir`.
+fn validate_model(assets_dir: &Path, model_path: &Path) -> Vec<Value> {
+    let model = match read_model(model_path) {
+        Ok(m) => m,
+        Err(e) => {
+            return vec![json!({
+                "severity": "error",
+                "message": e,
+                "suggestion": "Fix the JSON syntax or regenerate the model."
+            })]
+        }
+    };
+    let mut issues: Vec<Value> = Vec::new();
+
+    // Gather texture variables up the parent chain; the nearest model's
+    // value wins. Undefined variables are only reported when every model in
+    // the chain could be read, since a vanilla parent may define them.
+    let mut variables: BTreeMap<String, String> = model_textures(&model)
+        .map(|(k, v)| (k.clone(), v.to_string()))
+        .collect();
+    let mut chain_known = true;
+    let mut visited: Vec<PathBuf> = vec![model_path.to_path_buf()];
+    let mut current = model.clone();
+    while let Some(parent) = current.get("parent").and_then(|p| p.as_str()).map(String::from) {
+        let Some(parent_path) = resolve_asset(assets_dir, &parent, "models", "json") else {
+            chain_known = false;
+            break;
+        };
+        if !parent_path.is_file() {
+            // Only the direct parent is this model's problem; a gap further up
+            // is reported against the model that references it
+            if visited.len() == 1 {
+                issues.push(json!({
+                    "severity": "error",
+                    "message": format!(
+                        "Parent model \"{}\" not found at {}. The model will render as the missing-model cube.",
+                        parent,
+                        parent_path.display()
+                    ),
+                    "suggestion": "Create the parent model or point \"parent\" at an existing one."
+                }));
+            }
+            chain_known = false;
+            break;
+        }
+        if visited.contains(&parent_path) {
+            issues.push(json!({
+                "severity": "error",
+                "message": format!("Parent chain loops back to \"{}\".", parent),
+                "suggestion": "Break the loop so the chain ends at a vanilla or builtin model."
+            }));
+            chain_known = false;
+            break;
+        }
+        current = match read_model(&parent_path) {
+            Ok(m) => m,
+            Err(_) => {
+                chain_known = false;
+                break;
+            }
+        };
+        for (k, v) in model_textures(&current) {
+            variables.entry(k.clone()).or_insert_with(|| v.to_string());
+        }
+        visited.push(parent_path);
+    }
+
+    // Every reference this model makes: its own texture values and the
+    // textures its element faces use
+    let mut references: Vec<(String, String)> = model_textures(&model)
+        .map(|(k, v)| (format!("texture \"{}\"", k), v.to_string()))
+        .collect();
+    for (i, element) in model
+        .get("elements")
+        .and_then(|e| e.as_array())
+        .into_iter()
+        .flatten()
+        .enumerate()
+    {
+        let faces = element.get("faces").and_then(|f| f.as_object());
+        for (face, spec) in faces.into_iter().flatten() {
+            if let Some(texture) = spec.get("texture").and_then(|t| t.as_str()) {
+                references.push((format!("element {} {} face", i, face), texture.to_string()));
+            }
+        }
+    }
+
+    let mut checked: Vec<String> = Vec::new();
+    for (used_by, reference) in references {
+        let mut texture = reference.clone();
+        let mut hops = 0;
+        while let Some(name) = texture.strip_prefix('#') {
+            hops += 1;
+            match variables.get(name) {
+                Some(next) if hops <= MAX_VARIABLE_HOPS => texture = next.clone(),
+                Some(_) => {
+                    issues.push(json!({
+                        "severity": "error",
+                        "message": format!("Texture variable \"{}\" used by {} refers to itself in a loop.", reference, used_by),
+                        "suggestion": "Point one of the variables at a texture id."
+                    }));
+                    break;
+                }
+                None => {
+                    if chain_known {
+                        issues.push(json!({
+                            "severity": "error",
+                            "message": format!("Texture variable \"{}\" used by {} is never defined.", texture, used_by),
+                            "suggestion": format!("Add \"{}\" to the model's textures.", name)
+                        }));
+                    }
+                    break;
+                }
+            }
+        }
+        if texture.starts_with('#') || checked.contains(&texture) {
+            continue;
+        }
+        if let Some(path) = resolve_asset(assets_dir, &texture, "textures", "png") {
+            if !path.is_file() {
+                issues.push(json!({
+                    "severity": "error",
+                    "message": format!(
+                        "Texture \"{}\" used by {} not found at {}. It will render as the missing texture.",
+                        texture,
+                        used_by,
+                        path.display()
+                    ),
+                    "suggestion": "Add the texture file or fix the texture id."
+                }));
+            }
+        }
+        checked.push(texture);
+    }
+
+    issues
+}
+
+/// The `assets` directory a model file lives under.
+fn assets_dir_of(model_path: &Path) -> Option<PathBuf> {
+    model_path
+        .ancestors()
+        .find(|dir| dir.file_name().is_some_and(|n| n == "assets"))
+        .map(Path::to_path_buf)
+}
+
+async fn handle_validate_model(params: Value, state: &ProjectState) -> ToolResult {
+    if let Some(path) = params.get("path").and_then(|v| v.as_str()) {
+        let model_path = Path::new(path);
+        if !model_path.is_file() {
+            return ToolResult::error(format!("Model file does not exist: {}", path));
+        }
+        let assets_dir = match params.get("project_path").and_then(|v| v.as_str()) {
+            Some(project) => Path::new(project).join("src/main/resources/assets"),
+            None => match assets_dir_of(model_path) {
+                Some(dir) => dir,
+                None => {
+                    return ToolResult::error(format!(
+                        "{} is not inside an assets directory; pass project_path to say where references resolve",
+                        path
+                    ))
+                }
+            },
+        };
+        let issues = validate_model(&assets_dir, model_path);
+        return ToolResult::json(&json!({
+            "path": path,
+            "issues": issues,
+            "issue_count": issues.len(),
+            "valid": !issues.iter().any(|i| i["severity"] == "error")
+        }));
+    }
+
+    let project_path = match resolve_project(&params, state) {
+        Ok(p) => p,
+        Err(e) => return ToolResult::error(e),
+    };
+    let assets_dir = Path::new(&project_path).join("src/main/resources/assets");
+    if !assets_dir.is_dir() {
+        return ToolResult::error(format!(
+            "Assets directory does not exist: {}",
+            assets_dir.display()
+        ));
+    }
+
+    let mut models: Vec<PathBuf> = walkdir::WalkDir::new(&assets_dir)
+        .follow_links(false)
+        .into_iter()
+        .flatten()
+        .filter(|e| {
+            // Only assets/<namespace>/models/**
+            let in_models = e
+                .path()
+                .strip_prefix(&assets_dir)
+                .is_ok_and(|rel| rel.components().nth(1).is_some_and(|c| c.as_os_str() == "models"));
+            e.file_type().is_file()
+                && e.path().extension().and_then(|x| x.to_str()) == Some("json")
+                && in_models
+        })
+        .map(|e| e.path().to_path_buf())
+        .collect();
+    models.sort();
+
+    let mut issues: Vec<Value> = Vec::new();
+    for model in &models {
+        for mut issue in validate_model(&assets_dir, model) {
+            issue["model"] = json!(model.to_string_lossy());
+            issues.push(issue);
+        }
+    }
+
+    ToolResult::json(&json!({
+        "project_path": project_path,
+        "models_checked": models.len(),
+        "issues": issues,
+        "issue_count": issues.len(),
+        "valid": !issues.iter().any(|i| i["severity"] == "error")
+    }))
+}
+
+// ---------------------------------------------------------------------------
+// resource_preview_texture
+// ---------------------------------------------------------------------------
+
+/// Previews are upscaled until their longest side reaches this many pixels.
+const PREVIEW_MIN_SIZE: u32 = 256;
+
+async fn handle_preview_texture(params: Value) -> ToolResult {
+    let path = match params.get("path").and_then(|v| v.as_str()) {
+        Some(p) => p,
+        None => return ToolResult::missing_param("path"),
+    };
+
+    let img = match image::open(path) {
+        Ok(img) => img,
+        Err(e) => return ToolResult::error(format!("Failed to read {}: {}", path, e)),
+    };
+    let (width, height) = (img.width(), img.height());
+    let longest = width.max(height).max(1);
+    let scale = PREVIEW_MIN_SIZE.div_ceil(longest).clamp(1, 16);
+    let preview = if scale > 1 {
+        img.resize_exact(width * scale, height * scale, image::imageops::FilterType::Nearest)
+    } else {
+        img
+    };
+
+    let mut png = Vec::new();
+    if let Err(e) = preview.write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png) {
+        return ToolResult::error(format!("Failed to encode preview: {}", e));
+    }
+
+    ToolResult::image(&png, "image/png").with_text(format!(
+        "{} ({}x{}, shown at {}x)",
+        path, width, height, scale
+    ))
+}
+
+// ---------------------------------------------------------------------------
+// resource_create_animation_mcmeta
+// ---------------------------------------------------------------------------
+
+async fn handle_create_animation_mcmeta(params: Value) -> ToolResult {
+    let path = match params.get("path").and_then(|v| v.as_str()) {
+        Some(p) => Path::new(p),
+        None => return ToolResult::missing_param("path"),
+    };
+    let frametime = match params.get("frametime") {
+        None | Some(Value::Null) => 1,
+        Some(v) => match v.as_u64() {
+            Some(t) if t >= 1 => t,
+            _ => return ToolResult::error("frametime must be a positive number of ticks"),
+        },
+    };
+    let interpolate = params
+        .get("interpolate")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let overwrite = params
+        .get("overwrite")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    let (width, height) = match image::image_dimensions(path) {
+        Ok(dims) => dims,
+        Err(e) => return ToolResult::error(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    if width == 0 || height % width != 0 {
+        return ToolResult::error(format!(
+            "Texture is {}x{}: an animated texture's height must be a multiple of its width.",
+            width, height
+        ));
+    }
+    let frame_count = height / width;
+    if frame_count < 2 {
+        return ToolResult::error(format!(
+            "Texture is {}x{}, a single frame. Stack the frames vertically first.",
+            width, height
+        ));
+    }
+
+    // Validate an explicit frame order against the frames the strip has
+    let frames: Option<Vec<Value>> = match params.get("frames") {
+        None | Some(Value::Null) => None,
+        Some(Value::Array(entries)) => {
+            let mut frames = Vec::with_capacity(entries.len());
+            for entry in entries {
+                let (index, time) = match entry {
+                    Value::Number(n) => (n.as_u64(), None),
+                    Value::Object(o) => (
+                        o.get("index").and_then(|i| i.as_u64()),
+                        o.get("time").map(|t| t.as_u64().filter(|t| *t >= 1)),
+                    ),
+                    _ => (None, None),
+                };
+                let Some(index) = index else {
+                    return ToolResult::error(format!("Invalid frame entry: {}", entry));
+                };
+                if index >= frame_count as u64 {
+                    return ToolResult::error(format!(
+                        "Frame index {} is out of range: the texture has {} frames (0-{}).",
+                        index,
+                        frame_count,
+                        frame_count - 1
+                    ));
+                }
+                frames.push(match time {
+                    None => json!(index),
+                    Some(Some(time)) => json!({ "index": index, "time": time }),
+                    Some(None) => {
+                        return ToolResult::error(format!("Frame {} has an invalid time", index))
+                    }
+                });
+            }
+            if frames.is_empty() {
+                return ToolResult::error("frames must not be empty");
+            }
+            Some(frames)
+        }
+        Some(_) => return ToolResult::error("frames must be an array"),
+    };
+
+    let mut animation = json!({ "frametime": frametime });
+    if interpolate {
+        animation["interpolate"] = json!(true);
+    }
+    if let Some(frames) = &frames {
+        animation["frames"] = json!(frames);
+    }
+
+    let mcmeta_path = PathBuf::from(format!("{}.mcmeta", path.display()));
+    if mcmeta_path.exists() && !overwrite {
+        return ToolResult::error(format!(
+            "{} already exists. Pass overwrite: true to replace it.",
+            mcmeta_path.display()
+        ));
+    }
+    let content = match serde_json::to_string_pretty(&json!({ "animation": animation })) {
+        Ok(c) => c,
+        Err(e) => return ToolResult::error(format!("Serialization error: {}", e)),
+    };
+    if let Err(e) = std::fs::write(&mcmeta_path, content + "\n") {
+        return ToolResult::error(format!("Failed to write {}: {}", mcmeta_path.display(), e));
+    }
+
+    ToolResult::json(&json!({
+        "path": mcmeta_path.to_string_lossy(),
+        "texture": path.to_string_lossy(),
+        "frame_count": frame_count,
+        "frame_size": width,
+        "animation": animation
+    }))
+}
+
+// ---------------------------------------------------------------------------
+// resource_lang
+// ---------------------------------------------------------------------------
+
+/// The locale every other lang file is checked against.
+pub(crate) const SOURCE_LOCALE: &str = "en_us";
+
+/// `mod_id` from the params, falling back to the project's alloy.mod.json.
+fn resolve_mod_id(params: &Value, project: &Path) -> Result<String, String> {
+    if let Some(id) = params.get("mod_id").and_then(|v| v.as_str()) {
+        return Ok(id.to_string());
+    }
+    std::fs::read_to_string(project.join("alloy.mod.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        .and_then(|v| v.get("mod_id").and_then(|id| id.as_str()).map(String::from))
+        .ok_or_else(|| "No mod_id given and none found in alloy.mod.json".to_string())
+}
+
+/// A parsed lang file and the indentation it was written with.
+struct LangFile {
+    path: PathBuf,
+    entries: serde_json::Map<String, Value>,
+    indent: String,
+}
+
+fn read_lang(path: &Path) -> Result<LangFile, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let entries = match serde_json::from_str::<Value>(&content) {
+        Ok(Value::Object(map)) => map,
+        Ok(_) => return Err(format!("{} is not a JSON object", path.display())),
+        Err(e) => return Err(format!("{} is not valid JSON: {}", path.display(), e)),
+    };
+    // Reuse the indentation of the first indented line
+    let indent = content
+        .lines()
+        .skip(1)
+        .map(|line| &line[..line.len() - line.trim_start().len()])
+        .find(|ws| !ws.is_empty())
+        .unwrap_or("  ")
+        .to_string();
+    Ok(LangFile {
+        path: path.to_path_buf(),
+        entries,
+        indent,
+    })
+}
+
+/// Write the file back with sorted keys (serde_json maps are ordered) and
+/// its original indentation.
+fn write_lang(lang: &LangFile) -> Result<(), String> {
+    use serde::Serialize;
+
+    let mut out = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(lang.indent.as_bytes());
+    let mut ser = serde_json::Serializer::with_formatter(&mut out, formatter);
+    lang.entries
+        .serialize(&mut ser)
+        .map_err(|e| format!("Serialization error: {}", e))?;
+    out.push(b'\n');
+    std::fs::write(&lang.path, out)
+        .map_err(|e| format!("Failed to write {}: {}", lang.path.display(), e))
+}
+
+/// Every `<locale>.json` in the lang directory, sorted by locale.
+fn list_locales(lang_dir: &Path) -> Vec<(String, PathBuf)> {
+    let mut locales: Vec<(String, PathBuf)> = std::fs::read_dir(lang_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("json"))
+        .filter_map(|p| Some((p.file_stem()?.to_str()?.to_string(), p)))
+        .collect();
+    locales.sort();
+    locales
+}
+
+/// Set `key` in `<lang_dir>/<locale>.json`, creating the file if needed.
+/// Returns the file path, the value it replaced, and the file's key count.
+pub(crate) fn set_lang_key(
+    lang_dir: &Path,
+    locale: &str,
+    key: &str,
+    value: &str,
+) -> Result<(PathBuf, Option<Value>, usize), String> {
+    let path = lang_dir.join(format!("{}.json", locale));
+    let mut lang = if path.exists() {
+        read_lang(&path)?
+    } else {
+        std::fs::create_dir_all(lang_dir)
+            .map_err(|e| format!("Failed to create {}: {}", lang_dir.display(), e))?;
+        LangFile {
+            path: path.clone(),
+            entries: serde_json::Map::new(),
+            indent: "  ".to_string(),
+        }
+    };
+    let previous = lang.entries.insert(key.to_string(), json!(value));
+    write_lang(&lang)?;
+    Ok((path, previous, lang.entries.len()))
+}
+
+async fn handle_lang(params: Value, state: &ProjectState) -> ToolResult {
+    let action = match params.get("action").and_then(|v| v.as_str()) {
+        Some(a) => a,
+        None => return ToolResult::missing_param("action"),
+    };
+    let project_path = match resolve_project(&params, state) {
+        Ok(p) => p,
+        Err(e) => return ToolResult::error(e),
+    };
+    let project = Path::new(&project_path);
+    let mod_id = match resolve_mod_id(&params, project) {
+        Ok(id) => id,
+        Err(e) => return ToolResult::error(e),
+    };
+    let lang_dir = project
+        .join("src/main/resources/assets")
+        .join(&mod_id)
+        .join("lang");
+    let locale = params.get("locale").and_then(|v| v.as_str());
+    let key = params.get("key").and_then(|v| v.as_str());
+
+    match action {
+        "set" => {
+            let Some(key) = key else {
+                return ToolResult::missing_param("key");
+            };
+            let Some(value) = params.get("value").and_then(|v| v.as_str()) else {
+                return ToolResult::missing_param("value");
+            };
+            let locale = locale.unwrap_or(SOURCE_LOCALE);
+            let (path, previous, key_count) = match set_lang_key(&lang_dir, locale, key, value) {
+                Ok(result) => result,
+                Err(e) => return ToolResult::error(e),
+            };
+            ToolResult::json(&json!({
+                "path": path.to_string_lossy(),
+                "locale": locale,
+                "key": key,
+                "value": value,
+                "previous": previous,
+                "key_count": key_count
+            }))
+        }
+        "remove" => {
+            let Some(key) = key else {
+                return ToolResult::missing_param("key");
+            };
+            let targets: Vec<(String, PathBuf)> = match locale {
+                Some(l) => vec![(l.to_string(), lang_dir.join(format!("{}.json", l)))],
+                None => list_locales(&lang_dir),
+            };
+            let mut removed_from = Vec::new();
+            for (locale, path) in targets {
+                if !path.exists() {
+                    continue;
+                }
+                let mut lang = match read_lang(&path) {
+                    Ok(lang) => lang,
+                    Err(e) => return ToolResult::error(e),
+                };
+                if lang.entries.remove(key).is_some() {
+                    if let Err(e) = write_lang(&lang) {
+                        return ToolResult::error(e);
+                    }
+                    removed_from.push(locale);
+                }
+            }
+            ToolResult::json(&json!({
+                "key": key,
+                "removed_from": removed_from,
+                "removed": !removed_from.is_empty()
+            }))
+        }
+        "sync" => {
+            let locales = list_locales(&lang_dir);
+            let Some((_, source_path)) = locales.iter().find(|(l, _)| l == SOURCE_LOCALE) else {
+                return ToolResult::error(format!(
+                    "No {}.json in {}",
+                    SOURCE_LOCALE,
+                    lang_dir.display()
+                ));
+            };
+            let source = match read_lang(source_path) {
+                Ok(lang) => lang,
+                Err(e) => return ToolResult::error(e),
+            };
+
+            let mut report = Vec::new();
+            for (name, path) in locales.iter().filter(|(l, _)| l != SOURCE_LOCALE) {
+                if locale.is_some_and(|only| only != name) {
+                    continue;
+                }
+                let lang = match read_lang(path) {
+                    Ok(lang) => lang,
+                    Err(e) => {
+                        report.push(json!({ "locale": name, "error": e }));
+                        continue;
+                    }
+                };
+                let missing: Vec<&String> = source
+                    .entries
+                    .keys()
+                    .filter(|k| !lang.entries.contains_key(*k))
+                    .collect();
+                let extra: Vec<&String> = lang
+                    .entries
+                    .keys()
+                    .filter(|k| !source.entries.contains_key(*k))
+                    .collect();
+                report.push(json!({
+                    "locale": name,
+                    "missing": missing,
+                    "missing_count": missing.len(),
+                    "extra": extra
+                }));
+            }
+            let in_sync = report
+                .iter()
+                .all(|r| r["missing_count"] == json!(0) && r.get("error").is_none());
+            ToolResult::json(&json!({
+                "lang_dir": lang_dir.to_string_lossy(),
+                "source_locale": SOURCE_LOCALE,
+                "source_key_count": source.entries.len(),
+                "locales": report,
+                "in_sync": in_sync
+            }))
+        }
+        other => ToolResult::error(format!(
+            "Unknown action '{}'. Expected set, remove, or sync.",
+            other
+        )),
+    }
+}
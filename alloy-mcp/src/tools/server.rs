@@ -0,0 +1,48 @@
+use crate::state::ProjectState;
+use crate::tools::ToolRegistry;
+use crate::types::{ToolDefinition, ToolResult};
+use serde_json::{json, Value};
+
+/// Return tool definitions for server introspection tools.
+pub fn definitions() -> Vec<ToolDefinition> {
+    vec![ToolDefinition {
+        name: "server_info".into(),
+        description: "Report the MCP server's effective configuration: project root, open projects, write and \
+            sandbox restrictions, command policy, number of registered tools, and server \
+            version. Use this to diagnose why a tool call was refused."
+            .into(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {}
+        }),
+    }]
+}
+
+/// Dispatch execution to the appropriate server tool handler.
+pub async fn execute(name: &str, _params: Value, state: &ProjectState) -> ToolResult {
+    match name {
+        "server_info" => handle_server_info(state).await,
+        _ => ToolResult::error(format!("Unknown server tool: {}", name)),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// server_info
+// ---------------------------------------------------------------------------
+
+async fn handle_server_info(state: &ProjectState) -> ToolResult {
+    let project_root = state.project_path().ok();
+    let open_projects: Vec<String> = state.open_projects().into_iter().map(|p| p.path).collect();
+
+    // The server currently runs without write or command restrictions; these
+    // fields report that explicitly so clients can rely on their presence.
+    ToolResult::json(&json!({
+        "project_root": project_root,
+        "open_projects": open_projects,
+        "read_only": false,
+        "sandbox_enabled": false,
+        "command_policy": "unrestricted",
+        "tool_count": ToolRegistry::definitions().len(),
+        "version": env!("CARGO_PKG_VERSION")
+    }))
+}
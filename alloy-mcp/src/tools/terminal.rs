@@ -1,4 +1,4 @@
-use crate::state::ProjectState;
+use crate::state::{ProjectState, DEFAULT_TERMINAL_SESSION};
 use crate::types::{ToolDefinition, ToolResult};
 use serde_json::{json, Value};
 
@@ -12,15 +12,22 @@ pub fn definitions() -> Vec<ToolDefinition> {
                 "properties": {
                     "command": { "type": "string", "description": "Shell command to execute" },
                     "cwd": { "type": "string", "description": "Working directory (defaults to project root)" },
-                    "timeout_secs": { "type": "integer", "description": "Timeout in seconds (default: 60)" }
+                    "project_path": { "type": "string", "description": "Project whose root to use when cwd is omitted (defaults to the current project)" },
+                    "timeout_secs": { "type": "integer", "description": "Timeout in seconds (default: 60)" },
+                    "session_id": { "type": "string", "description": "Terminal session to record the output under (default: \"default\")" }
                 },
                 "required": ["command"]
             }),
         },
         ToolDefinition {
             name: "terminal_get_output".into(),
-            description: "Get recent terminal command history and output".into(),
-            input_schema: json!({ "type": "object", "properties": {} }),
+            description: "Get recent command history and output for one terminal session".into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "session_id": { "type": "string", "description": "Terminal session to read (default: \"default\")" }
+                }
+            }),
         },
     ]
 }
@@ -28,7 +35,7 @@ pub fn definitions() -> Vec<ToolDefinition> {
 pub async fn execute(name: &str, params: Value, state: &ProjectState) -> ToolResult {
     match name {
         "terminal_execute" => terminal_execute(params, state).await,
-        "terminal_get_output" => terminal_get_output(state).await,
+        "terminal_get_output" => terminal_get_output(params, state).await,
         _ => ToolResult::error(format!("Unknown terminal tool: {}", name)),
     }
 }
@@ -36,14 +43,21 @@ pub async fn execute(name: &str, params: Value, state: &ProjectState) -> ToolRes
 async fn terminal_execute(params: Value, state: &ProjectState) -> ToolResult {
     let command = match params.get("command").and_then(|v| v.as_str()) {
         Some(c) => c,
-        None => return ToolResult::error("Missing required parameter: command"),
+        None => return ToolResult::missing_param("command"),
     };
 
     let cwd = match params.get("cwd").and_then(|v| v.as_str()) {
         Some(c) => c.to_string(),
-        None => state.project_path().unwrap_or_else(|_| ".".to_string()),
+        None => state
+            .resolve_project_path(&params)
+            .unwrap_or_else(|_| ".".to_string()),
     };
 
+    let session_id = params
+        .get("session_id")
+        .and_then(|v| v.as_str())
+        .unwrap_or(DEFAULT_TERMINAL_SESSION);
+
     let timeout_secs = params
         .get("timeout_secs")
         .and_then(|v| v.as_u64())
@@ -69,18 +83,14 @@ async fn terminal_execute(params: Value, state: &ProjectState) -> ToolResult {
         Err(_) => return ToolResult::error(format!("Command timed out after {}s", timeout_secs)),
     };
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
+    // Mask secrets before anything reaches the history or the client
+    let command = state.redactor.redact(command);
+    let stdout = state.redactor.redact(&String::from_utf8_lossy(&output.stdout));
+    let stderr = state.redactor.redact(&String::from_utf8_lossy(&output.stderr));
     let exit_code = output.status.code().unwrap_or(-1);
 
-    // Store in terminal history
-    if let Ok(mut history) = state.terminal_output.lock() {
-        history.push(format!("$ {}\n{}{}", command, stdout, stderr));
-        if history.len() > 50 {
-            let drain_to = history.len() - 50;
-            history.drain(..drain_to);
-        }
-    }
+    // Store in the session's terminal history
+    state.record_terminal_output(session_id, format!("$ {}\n{}{}", command, stdout, stderr));
 
     if output.status.success() {
         ToolResult::text(format!("{}{}", stdout, stderr))
@@ -92,10 +102,30 @@ async fn terminal_execute(params: Value, state: &ProjectState) -> ToolResult {
     }
 }
 
-async fn terminal_get_output(state: &ProjectState) -> ToolResult {
-    let history = state.terminal_output.lock().ok();
-    match history.as_deref() {
+async fn terminal_get_output(params: Value, state: &ProjectState) -> ToolResult {
+    let session_id = params
+        .get("session_id")
+        .and_then(|v| v.as_str())
+        .unwrap_or(DEFAULT_TERMINAL_SESSION);
+
+    let sessions = match state.terminal_output.lock() {
+        Ok(s) => s,
+        Err(e) => return ToolResult::error(format!("Failed to read terminal output: {}", e)),
+    };
+    match sessions.get(session_id) {
         Some(lines) if !lines.is_empty() => ToolResult::text(lines.join("\n---\n")),
-        _ => ToolResult::text("No terminal output"),
+        _ => {
+            let mut known: Vec<&str> = sessions.keys().map(String::as_str).collect();
+            known.sort();
+            if known.is_empty() {
+                ToolResult::text("No terminal output")
+            } else {
+                ToolResult::text(format!(
+                    "No terminal output for session '{}'. Sessions with output: {}",
+                    session_id,
+                    known.join(", ")
+                ))
+            }
+        }
     }
 }
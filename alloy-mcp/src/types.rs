@@ -1,4 +1,6 @@
+use base64::Engine;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 /// A single tool definition for the MCP server.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +16,50 @@ pub struct ToolDefinition {
 pub enum ContentBlock {
     #[serde(rename = "text")]
     Text { text: String },
+    #[serde(rename = "image")]
+    Image { source: ImageSource },
+}
+
+/// Inline image data carried by an image content block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageSource {
+    /// Always "base64"
+    #[serde(rename = "type")]
+    pub source_type: String,
+    pub media_type: String,
+    pub data: String,
+}
+
+/// What kind of failure an error result is, so callers can branch on it
+/// instead of matching the message text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// A file, directory, definition or git object does not exist
+    NotFound,
+    /// A parameter is missing, malformed or points at the wrong kind of thing
+    InvalidParams,
+    /// The OS refused access to a path
+    PermissionDenied,
+    /// Reading or writing failed for any other reason
+    Io,
+    /// The request clashes with existing state, e.g. the target already exists
+    /// or a merge conflicted
+    Conflict,
+    /// The request is understood but can't be served here, e.g. a required
+    /// program is missing or a configured limit is exceeded
+    Unsupported,
+}
+
+impl From<&std::io::Error> for ErrorCode {
+    fn from(e: &std::io::Error) -> Self {
+        match e.kind() {
+            std::io::ErrorKind::NotFound => ErrorCode::NotFound,
+            std::io::ErrorKind::PermissionDenied => ErrorCode::PermissionDenied,
+            std::io::ErrorKind::AlreadyExists => ErrorCode::Conflict,
+            _ => ErrorCode::Io,
+        }
+    }
 }
 
 /// Result returned by a tool execution.
@@ -22,6 +68,9 @@ pub struct ToolResult {
     pub content: Vec<ContentBlock>,
     #[serde(default)]
     pub is_error: bool,
+    /// Set on errors whose kind is known; sent to clients as structured content
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<ErrorCode>,
 }
 
 impl ToolResult {
@@ -29,6 +78,7 @@ impl ToolResult {
         Self {
             content: vec![ContentBlock::Text { text: text.into() }],
             is_error: false,
+            error_code: None,
         }
     }
 
@@ -36,9 +86,47 @@ impl ToolResult {
         Self {
             content: vec![ContentBlock::Text { text: text.into() }],
             is_error: true,
+            error_code: None,
         }
     }
 
+    /// An error with a machine-readable `code` alongside the message.
+    pub fn error_with(code: ErrorCode, text: impl Into<String>) -> Self {
+        Self {
+            error_code: Some(code),
+            ..Self::error(text)
+        }
+    }
+
+    /// The error for a required parameter the caller left out.
+    pub fn missing_param(name: &str) -> Self {
+        Self::error_with(
+            ErrorCode::InvalidParams,
+            format!("Missing required parameter: {}", name),
+        )
+    }
+
+    /// An image result, e.g. `ToolResult::image(&png_bytes, "image/png")`.
+    pub fn image(bytes: &[u8], mime: impl Into<String>) -> Self {
+        Self {
+            content: vec![ContentBlock::Image {
+                source: ImageSource {
+                    source_type: "base64".to_string(),
+                    media_type: mime.into(),
+                    data: base64::engine::general_purpose::STANDARD.encode(bytes),
+                },
+            }],
+            is_error: false,
+            error_code: None,
+        }
+    }
+
+    /// Append a caption or other text block after the existing content.
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.content.push(ContentBlock::Text { text: text.into() });
+        self
+    }
+
     pub fn json<T: Serialize>(value: &T) -> Self {
         match serde_json::to_string_pretty(value) {
             Ok(s) => Self::text(s),
@@ -46,3 +134,65 @@ impl ToolResult {
         }
     }
 }
+
+/// Read a required string parameter of `tool`. The error names the tool and
+/// tells an absent parameter apart from one of the wrong JSON type.
+pub fn require_str<'a>(params: &'a Value, key: &str, tool: &str) -> Result<&'a str, ToolResult> {
+    match params.get(key) {
+        Some(Value::String(s)) => Ok(s),
+        None | Some(Value::Null) => Err(ToolResult::error_with(
+            ErrorCode::InvalidParams,
+            format!("tool '{}': missing required string parameter '{}'", tool, key),
+        )),
+        Some(other) => Err(ToolResult::error_with(
+            ErrorCode::InvalidParams,
+            format!(
+                "tool '{}': parameter '{}' must be a string, got {}",
+                tool,
+                key,
+                json_type(other)
+            ),
+        )),
+    }
+}
+
+/// `require_str`, rejecting the empty string as well.
+pub fn require_non_empty_str<'a>(params: &'a Value, key: &str, tool: &str) -> Result<&'a str, ToolResult> {
+    match require_str(params, key, tool)? {
+        "" => Err(ToolResult::error_with(
+            ErrorCode::InvalidParams,
+            format!("tool '{}': parameter '{}' must not be empty", tool, key),
+        )),
+        value => Ok(value),
+    }
+}
+
+/// How a JSON value's type reads in an error message.
+pub fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+/// Partial results a running tool sends ahead of its final result. The server
+/// forwards each update to the client as an MCP progress notification; tools
+/// only get a sink when the caller sent a progress token.
+#[derive(Clone)]
+pub struct ProgressSink(tokio::sync::mpsc::UnboundedSender<(f64, serde_json::Value)>);
+
+impl ProgressSink {
+    pub fn channel() -> (Self, tokio::sync::mpsc::UnboundedReceiver<(f64, serde_json::Value)>) {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        (Self(tx), rx)
+    }
+
+    /// Report `update` with a progress value that grows with each call.
+    pub fn send(&self, progress: f64, update: serde_json::Value) {
+        let _ = self.0.send((progress, update));
+    }
+}